@@ -9,7 +9,9 @@ use std::time::{SystemTime, Duration};
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DesignModule {
     pub id: String,
+    // 資料夾代稱（ASCII）；顯示用名稱見 display_name
     pub name: String,
+    pub display_name: String,
     pub description: String,
     pub asset_count: usize,
     pub last_updated: String,
@@ -22,6 +24,13 @@ pub struct AssetList {
     pub screenshots: Vec<String>,
     pub html: Vec<String>,
     pub css: Vec<String>,
+    pub fonts: Vec<String>,
+    // 專案自訂的資產類別（project.json 的 asset_categories）
+    #[serde(default)]
+    pub custom: std::collections::BTreeMap<String, Vec<String>>,
+    // 各斷點的截圖（screenshots/<breakpoint>/）
+    #[serde(default)]
+    pub screenshot_variants: std::collections::BTreeMap<String, Vec<String>>,
 }
 
 // 批量生成結果摘要
@@ -30,6 +39,27 @@ pub struct BulkGenerationResult {
     pub total: usize,
     pub success: Vec<String>,
     pub failed: Vec<String>,
+    // 資產複製統計（"if-changed" 策略會略過內容相同的檔案）
+    pub files_copied: usize,
+    pub files_skipped: usize,
+    // only_changed 時因內容指紋未變而略過的模組
+    pub skipped_modules: Vec<String>,
+    // 生成工作 ID（generation-progress 事件與 cancel_generation 使用）
+    pub job_id: String,
+    // 是否被 cancel_generation 中止；未處理的模組列於 failed
+    pub cancelled: bool,
+    // validate 時的生成後檢查警告（HTML 解析、CSS 語法、缺漏的引用檔案），格式為 "<模組>: <檔案>: <訊息>"
+    pub warnings: Vec<String>,
+    // 指定 archive_format 時各模組的封存檔（output/<module>.zip 等）
+    pub archives: Vec<String>,
+}
+
+// 批量上傳結果摘要
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BulkUploadResult {
+    pub total: usize,
+    pub success: Vec<String>,
+    pub failed: Vec<String>,
 }
 
 // 導出整包結果
@@ -37,11 +67,24 @@ pub struct BulkGenerationResult {
 pub struct UnifiedPackageResult {
     pub output_dir: String,
     pub zip_path: Option<String>,
+    // 依 archive_format 產生的封存檔（zip 或 tar.gz）
+    pub archive_path: Option<String>,
     pub modules_count: usize,
+    // 生成失敗的模組（訊息以模組名稱開頭），其餘模組仍會輸出
+    pub failed: Vec<String>,
+    // 封存檔分割時的所有分段（未分割時只有 archive_path）
+    pub archive_parts: Vec<String>,
+    // 超過 archive_max_screenshot_kb 而未封存的截圖（相對輸出包）
+    pub archive_excluded: Vec<String>,
+    // validate 時的生成後檢查警告
+    pub warnings: Vec<String>,
+    pub job_id: String,
+    // 取消時 output_dir 內未完成的輸出已移除
+    pub cancelled: bool,
 }
 
 // 專案結構（Phase 1：僅 Default）
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ProjectConfig {
     pub name: String,
     pub slug: String,
@@ -54,6 +97,72 @@ pub struct ProjectConfig {
     pub overwrite_strategy_default: Option<String>,
     pub mermaid_theme: Option<String>,
     pub mermaid_layout_direction: Option<String>,
+    // 截圖擷取的斷點（未設定時使用 desktop/tablet/mobile 預設值）
+    pub screenshot_breakpoints: Option<Vec<Breakpoint>>,
+    // 截圖擷取工具："playwright"（預設，透過 npx）或 "chrome"
+    pub screenshot_backend: Option<String>,
+    pub chrome_path: Option<String>,
+    // 輸出 HTML 的資產路徑改寫："relative" 改為相對路徑，其他值視為 base URL 前綴（如 CDN）
+    pub asset_base_url: Option<String>,
+    // 字型子集化的 unicode 範圍（例如 "U+0000-00FF,U+4E00-9FFF"）；未設定時使用 HTML 文案中的字元
+    pub font_unicode_range: Option<String>,
+    // 圖片壓縮：JPEG 品質（1-100，預設 82）與視為過大的門檻（KB，預設 500）
+    pub image_quality: Option<u8>,
+    pub image_min_size_kb: Option<u64>,
+    // RTL 版面（阿拉伯文/希伯來文等）：額外輸出 dir="rtl" 的 HTML，CSS 改用邏輯屬性
+    pub rtl: Option<bool>,
+    // 內建 screenshots/html/css/fonts 以外的資產類別（例如 icons、js）
+    pub asset_categories: Option<Vec<String>>,
+    // 掃描/複製/打包時忽略的檔名 glob；未設定時使用 DEFAULT_IGNORE_PATTERNS
+    pub ignore_patterns: Option<Vec<String>>,
+    // 同步至既有 repo 的目標路徑：模組名稱 → 相對 repo 根目錄的路徑；未列出者使用 repo_target_default（<module> 會替換為模組名稱）
+    pub repo_targets: Option<HashMap<String, String>>,
+    pub repo_target_default: Option<String>,
+    // 輸出包根目錄附帶的 repo 初始檔案："gitignore"、"editorconfig"、"package_json"；未設定時全部輸出，空陣列表示不輸出
+    pub scaffold_files: Option<Vec<String>>,
+    // 生成檔案的表頭註解（版權/客戶聲明），可用 {{year}}、{{project}}、{{brand.<key>}}
+    pub file_header: Option<String>,
+    // 品牌變數：HTML/MD 中的 {{brand.<key>}} 會被替換，CSS 另輸出 --brand-<key> 自訂屬性
+    pub branding: Option<HashMap<String, String>>,
+    // 回收桶保留天數（預設 30），逾期項目會在刪除/列出時自動清除
+    pub trash_retention_days: Option<u32>,
+    // 生成前/後執行的指令（經由系統 shell）；輸出包路徑附加為最後一個參數，stdin 為 JSON 情境
+    pub pre_generate_hook: Option<String>,
+    // 於校驗碼與封存檔寫入之後執行，只應讀取或上傳輸出包，不可再修改其中的檔案
+    pub post_generate_hook: Option<String>,
+    // 外部生成器外掛（可執行檔或 WASM 模組），見 run_plugin
+    pub plugins: Option<Vec<GeneratorPlugin>>,
+    // zip 壓縮等級：0 為不壓縮（stored），1-9 為 deflate 等級；未設定時使用預設等級（6）
+    pub zip_compression_level: Option<i64>,
+    // 樣式表語法："css"（預設）、"scss"、"less"；生成時可逐次覆寫
+    pub stylesheet_dialect: Option<String>,
+    // 批量生成同時處理的模組數；未設定時依 CPU 核心數（最多 4）
+    pub generation_concurrency: Option<usize>,
+    // 說明文件（ai-spec.md）的語言："zh-TW"（預設）、"en"、"ja"
+    pub spec_language: Option<String>,
+    // 生成輸出的根目錄（建議為絕對路徑，相對路徑以工作目錄為準）；未設定時為 ./output
+    pub output_root: Option<String>,
+    // 封存檔分割大小（MB）：超過時分成可各自解壓的 <名稱>.partN.zip；未設定時不分割
+    pub archive_split_mb: Option<u64>,
+    // 封存時排除大於此大小（KB）的截圖（仍保留於輸出資料夾），排除清單回報於生成結果
+    pub archive_max_screenshot_kb: Option<u64>,
+    // 品牌區塊：注入每個生成的 index.html 與 Mermaid 預覽頁的頁首（logo、專案名稱）與頁尾文字
+    pub brand_block: Option<BrandBlock>,
+    // 生成 HTML/CSS 的格式："default"（模板原樣）、"minify"（壓縮，便於直接上線）、"pretty"（統一縮排，便於手動編輯）；生成時可逐次覆寫
+    pub output_formatting: Option<String>,
+    // 已命名的生成選項組合，generate_* 可以 profile 名稱套用，見 save_generation_profile
+    pub generation_profiles: Option<Vec<GenerationProfile>>,
+    // 批量/整包生成完成時 POST JSON 通知的網址（含 text 欄位，可直接使用 Slack incoming webhook）
+    pub webhook_url: Option<String>,
+    // mermaid-cli（mmdc）執行檔路徑；未設定時透過 npx 執行 @mermaid-js/mermaid-cli
+    pub mermaid_cli_path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Breakpoint {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -114,6 +223,18 @@ impl SitemapCache {
     }
 }
 
+// 供檔案監看器使用：design-assets 被外部修改後清除快取；module_name 為 None 時全部清除
+pub(crate) fn invalidate_sitemap_cache(module_name: Option<&str>) {
+    let mut cache = SITEMAP_CACHE.lock().unwrap();
+    match module_name {
+        Some(m) => {
+            cache.invalidate_module(m);
+            cache.design_modules = None;
+        }
+        None => cache.invalidate_all(),
+    }
+}
+
 lazy_static::lazy_static! {
     static ref SITEMAP_CACHE: Arc<Mutex<SitemapCache>> = Arc::new(Mutex::new(SitemapCache::new()));
 }
@@ -147,6 +268,7 @@ pub struct PageInfo {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PageNode {
     pub slug: String,
+    pub display_name: String,
     pub path: String,
     pub title: Option<String>,
     pub status: Option<String>,
@@ -180,9 +302,11 @@ fn load_order(module_dir: &std::path::Path) -> OrderFile {
     OrderFile::default()
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 struct PageMeta {
     slug: Option<String>,
+    // 顯示名稱（可為任何語言），與 ASCII 資料夾代稱分開
+    display_name: Option<String>,
     title: Option<String>,
     path: Option<String>,
     status: Option<String>,
@@ -195,6 +319,19 @@ struct PageMeta {
     class: Option<String>,
     mermaid_id: Option<String>,
     links: Option<Vec<LinkMeta>>,
+    nav: Option<Vec<NavPlacement>>,
+    access: Option<PageAccess>,
+    feature_flag: Option<String>,
+    overlays: Option<Vec<PageOverlay>>,
+    empty_state: Option<String>,
+    error_state: Option<String>,
+    focus_order: Option<Vec<String>>,
+    loading_skeleton: Option<bool>,
+    seo: Option<PageSeo>,
+    events: Option<Vec<TrackingEvent>>,
+    props: Option<Vec<ComponentProp>>,
+    // 翻譯欄位，key 為 "<欄位>.<語言>"（例如 "title.en"）；空字串為待翻譯的 stub
+    translations: Option<std::collections::BTreeMap<String, String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -204,12 +341,96 @@ struct LinkMeta {
 }
 
 fn read_page_meta(path: &std::path::Path) -> PageMeta {
-    use std::fs;
-    let p = path.join("page.json");
-    if let Ok(txt) = fs::read_to_string(&p) {
-        if let Ok(v) = serde_json::from_str::<PageMeta>(&txt) { return v; }
+    read_json_with_recovery::<PageMeta>(&path.join("page.json")).unwrap_or_default()
+}
+
+fn write_page_meta(path: &std::path::Path, meta: &PageMeta) -> Result<(), String> {
+    let s = serde_json::to_string_pretty(meta).map_err(|e| e.to_string())?;
+    backup_json_file(&path.join("page.json"));
+    std::fs::write(path.join("page.json"), s).map_err(|e| format!("寫入 page.json 失敗: {}", e))
+}
+
+// 頁面或子頁的資料夾：parent_slug 為 None 時為頁面，否則為 parent 下的子頁
+fn resolve_page_dir(module_name: &str, parent_slug: Option<&str>, slug: &str) -> PathBuf {
+    let pages = PathBuf::from("design-assets").join(module_name).join("pages");
+    match parent_slug {
+        Some(parent) => pages.join(parent).join("subpages").join(slug),
+        None => pages.join(slug),
+    }
+}
+
+// 全專案頁面/子頁清單（依 _order.json 排序）
+#[derive(Debug, Clone)]
+struct PageEntry {
+    module: String,
+    page: String,
+    subpage: Option<String>,
+    meta: PageMeta,
+    // 模組資料夾；整包導出時位於外部設計資產根目錄，其餘為 design-assets/<module>
+    module_dir: PathBuf,
+}
+
+impl PageEntry {
+    fn route_path(&self) -> String {
+        match &self.subpage {
+            Some(sub) => format!("/{}/{}/{}", self.module, self.page, sub),
+            None => format!("/{}/{}", self.module, self.page),
+        }
+    }
+}
+
+fn list_dir_names(dir: &std::path::Path) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    let ignore = IgnoreRules::load();
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
+            let p = entry.path();
+            if p.is_dir() {
+                if let Some(name) = p.file_name().and_then(|n| n.to_str()) {
+                    out.push(name.to_string());
+                }
+            }
+        }
+    }
+    out.sort_by_key(|a| a.to_lowercase());
+    out
+}
+
+fn collect_module_page_entries(module_name: &str) -> Vec<PageEntry> {
+    collect_page_entries_in(&PathBuf::from("design-assets").join(module_name), module_name)
+}
+
+// 讀取指定模組資料夾的頁面樹（可位於工作區以外）
+fn collect_page_entries_in(module_dir: &std::path::Path, module_name: &str) -> Vec<PageEntry> {
+    let pages_dir = module_dir.join("pages");
+    let order = load_order(module_dir);
+    let mut out: Vec<PageEntry> = Vec::new();
+    let mut page_slugs = list_dir_names(&pages_dir);
+    if !order.pages.is_empty() {
+        page_slugs.sort_by_key(|s| order.pages.iter().position(|x| x == s).unwrap_or(usize::MAX));
+    }
+    for pslug in page_slugs {
+        let pdir = pages_dir.join(&pslug);
+        out.push(PageEntry { module: module_name.to_string(), page: pslug.clone(), subpage: None, meta: read_page_meta(&pdir), module_dir: module_dir.to_path_buf() });
+        let sp_dir = pdir.join("subpages");
+        let mut sub_slugs = list_dir_names(&sp_dir);
+        if let Some(subo) = order.subpages.get(&pslug) {
+            sub_slugs.sort_by_key(|s| subo.iter().position(|x| x == s).unwrap_or(usize::MAX));
+        }
+        for sslug in sub_slugs {
+            let smeta = read_page_meta(&sp_dir.join(&sslug));
+            out.push(PageEntry { module: module_name.to_string(), page: pslug.clone(), subpage: Some(sslug), meta: smeta, module_dir: module_dir.to_path_buf() });
+        }
     }
-    PageMeta { slug: None, title: None, path: None, status: None, route: None, notes: None, domain: None, area: None, component: None, action: None, class: None, mermaid_id: None, links: None }
+    out
+}
+
+fn collect_page_entries() -> Vec<PageEntry> {
+    list_dir_names(&PathBuf::from("design-assets"))
+        .iter()
+        .flat_map(|m| collect_module_page_entries(m))
+        .collect()
 }
 
 fn save_order(module_dir: &std::path::Path, mut of: OrderFile) -> Result<(), Box<dyn std::error::Error>> {
@@ -233,22 +454,28 @@ pub async fn create_design_module(
 ) -> Result<DesignModule, String> {
     let module = DesignModule {
         id: uuid::Uuid::new_v4().to_string(),
-        name,
+        name: slugify_display_name(&name, "module"),
+        display_name: name.trim().to_string(),
         description,
         asset_count: 0,
         last_updated: chrono::Utc::now().to_rfc3339(),
         status: "active".to_string(),
     };
     
-    // 創建模組目錄
+    // 創建模組目錄；不同顯示名稱可能對應到同一個代稱，已存在時不覆寫既有模組
     let module_dir = PathBuf::from("design-assets").join(&module.name);
+    if module_dir.exists() {
+        return Err(format!("模組資料夾已存在: {}（{}）", module.name, module.display_name));
+    }
     if let Err(e) = std::fs::create_dir_all(&module_dir) {
         return Err(format!("創建模組目錄失敗: {}", e));
     }
     
-    // 創建子目錄
-    let subdirs = ["screenshots", "html", "css"];
-    for subdir in subdirs {
+    write_module_meta(&module.name, &ModuleMeta { display_name: Some(module.display_name.clone()), description: Some(module.description.clone()) })?;
+
+    // 創建子目錄（含專案自訂的資產類別）
+    let subdirs = asset_categories();
+    for subdir in subdirs.iter() {
         let subdir_path = module_dir.join(subdir);
         if let Err(e) = std::fs::create_dir_all(&subdir_path) {
             return Err(format!("創建子目錄 {} 失敗: {}", subdir, e));
@@ -256,10 +483,13 @@ pub async fn create_design_module(
     }
     
     // 創建 README.md
-    let readme_content = format!(
+    let mut readme_content = format!(
         "# {}\n\n{}\n\n## 設計資產\n- screenshots/: Figma 截圖\n- html/: HTML 結構檔案\n- css/: CSS 樣式檔案",
-        module.name, module.description
+        module.display_name, module.description
     );
+    for extra in subdirs.iter().filter(|c| !["screenshots", "html", "css"].contains(&c.as_str())) {
+        readme_content.push_str(&format!("\n- {}/", extra));
+    }
     
     let readme_path = module_dir.join("README.md");
     if let Err(e) = std::fs::write(&readme_path, readme_content) {
@@ -272,7 +502,7 @@ pub async fn create_design_module(
         use std::process::Command;
         let _ = Command::new("osascript")
             .arg("-e")
-            .arg(format!("display notification \"設計模組 '{}' 創建成功\" with title \"ErSlice\"", module.name))
+            .arg(format!("display notification \"設計模組 '{}' 創建成功\" with title \"ErSlice\"", module.display_name))
             .output();
     }
     
@@ -290,16 +520,20 @@ pub async fn get_design_modules() -> Result<Vec<DesignModule>, String> {
     
     let mut modules = Vec::new();
     
+    let ignore = IgnoreRules::load();
     if let Ok(entries) = std::fs::read_dir(&design_assets_dir) {
         for entry in entries {
             if let Ok(entry) = entry {
+                if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
                 let path = entry.path();
                 if path.is_dir() {
                     if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                        let meta = read_module_meta(&path);
                         let module = DesignModule {
                             id: name.to_string(),
                             name: name.to_string(),
-                            description: "設計資產模組".to_string(),
+                            display_name: meta.display_name.unwrap_or_else(|| name.to_string()),
+                            description: meta.description.unwrap_or_else(|| "設計資產模組".to_string()),
                             asset_count: count_assets(&path),
                             last_updated: get_last_modified(&path),
                             status: "active".to_string(),
@@ -325,16 +559,20 @@ pub async fn get_archived_design_modules() -> Result<Vec<DesignModule>, String>
 
     let mut modules = Vec::new();
 
+    let ignore = IgnoreRules::load();
     if let Ok(entries) = std::fs::read_dir(&archived_dir) {
         for entry in entries {
             if let Ok(entry) = entry {
+                if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
                 let path = entry.path();
                 if path.is_dir() {
                     if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                        let meta = read_module_meta(&path);
                         let module = DesignModule {
                             id: name.to_string(),
                             name: name.to_string(),
-                            description: "封存的設計資產模組".to_string(),
+                            display_name: meta.display_name.unwrap_or_else(|| name.to_string()),
+                            description: meta.description.unwrap_or_else(|| "封存的設計資產模組".to_string()),
                             asset_count: count_assets(&path),
                             last_updated: get_last_modified(&path),
                             status: "archived".to_string(),
@@ -353,9 +591,11 @@ pub async fn get_archived_design_modules() -> Result<Vec<DesignModule>, String>
 fn count_assets(module_dir: &PathBuf) -> usize {
     let mut count = 0;
     
+    let ignore = IgnoreRules::load();
     if let Ok(entries) = std::fs::read_dir(module_dir) {
         for entry in entries {
             if let Ok(entry) = entry {
+                if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
                 let path = entry.path();
                 if path.is_file() {
                     count += 1;
@@ -386,12 +626,52 @@ fn get_last_modified(path: &PathBuf) -> String {
     "未知".to_string()
 }
 
+// 內建的資產類型
+const ASSET_TYPES: [&str; 4] = ["screenshots", "html", "css", "fonts"];
+
+// 自訂類別名稱即資料夾名稱，不可與頁面結構或內部資料夾衝突
+fn is_valid_asset_category(name: &str) -> bool {
+    !name.is_empty()
+        && !["pages", "subpages", VERSIONS_DIR].contains(&name)
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+// 目前專案可用的資產類型：內建類型 + project.json 的 asset_categories
+pub(crate) fn asset_categories() -> Vec<String> {
+    let mut out: Vec<String> = ASSET_TYPES.iter().map(|s| s.to_string()).collect();
+    let extra = read_active_project_config().and_then(|c| c.asset_categories).unwrap_or_default();
+    for name in extra.iter().map(|n| n.trim().to_lowercase()) {
+        if is_valid_asset_category(&name) && !out.contains(&name) {
+            out.push(name);
+        }
+    }
+    out
+}
+
+/// 取得目前專案可用的資產類別（內建 + 自訂）
+#[tauri::command]
+pub async fn get_asset_categories() -> Result<Vec<String>, String> {
+    Ok(asset_categories())
+}
+
+// 資產類型對應的子目錄
+fn asset_type_dir(base_dir: &std::path::Path, asset_type: &str) -> Result<PathBuf, String> {
+    if asset_categories().iter().any(|c| c == asset_type) {
+        Ok(base_dir.join(asset_type))
+    } else {
+        Err("不支援的資產類型".to_string())
+    }
+}
+
 // 上傳設計資產
 #[tauri::command]
 pub async fn upload_design_asset(
     asset_path: String,
     asset_type: String,
     file_path: String,
+    optimize_images: Option<bool>,
+    overwrite_strategy: Option<String>,
+    breakpoint: Option<String>,
 ) -> Result<String, String> {
     let base_dir = PathBuf::from("design-assets").join(&asset_path);
     
@@ -400,12 +680,7 @@ pub async fn upload_design_asset(
         return Err(format!("無法建立資產目錄: {}", e));
     }
     
-    let target_dir = match asset_type.as_str() {
-        "screenshots" => base_dir.join("screenshots"),
-        "html" => base_dir.join("html"),
-        "css" => base_dir.join("css"),
-        _ => return Err("不支援的資產類型".to_string()),
-    };
+    let target_dir = screenshot_breakpoint_dir(asset_type_dir(&base_dir, &asset_type)?, &asset_type, breakpoint.as_deref())?;
     
     let source_path = PathBuf::from(file_path);
     let file_name = source_path.file_name()
@@ -420,10 +695,22 @@ pub async fn upload_design_asset(
     
     let target_path = target_dir.join(file_name);
     
-    // 複製檔案
-    if let Err(e) = std::fs::copy(&source_path, &target_path) {
+    // 複製檔案（未指定策略時沿用覆寫）
+    let strategy = overwrite_strategy.unwrap_or_else(|| "overwrite".to_string());
+    if let Err(e) = copy_file_with_strategy(&source_path, &target_path, &strategy) {
         return Err(format!("複製檔案失敗: {}", e));
     }
+
+    // 可選：重新壓縮過大的 PNG/JPEG
+    let mut saved_note = String::new();
+    if optimize_images.unwrap_or(false) {
+        let settings = image_compression_settings(None, None);
+        match compress_image_file(&target_path, &settings) {
+            Ok(Some((before, after))) => saved_note = format!("（已壓縮，節省 {} KB）", (before - after) / 1024),
+            Ok(None) => {}
+            Err(e) => log::warn!("壓縮圖片失敗 {}: {}", target_path.display(), e),
+        }
+    }
     
     // 使用系統通知
     #[cfg(target_os = "macos")]
@@ -435,17 +722,90 @@ pub async fn upload_design_asset(
             .output();
     }
     
-    Ok(format!("資產上傳成功: {}", target_path.display()))
+    Ok(format!("資產上傳成功: {}{}", target_path.display(), saved_note))
+}
+
+// 批量上傳設計資產：一次複製多個檔案並回報逐檔結果
+#[tauri::command]
+pub async fn upload_design_assets_bulk(
+    asset_path: String,
+    asset_type: String,
+    file_paths: Vec<String>,
+    overwrite_strategy: Option<String>,
+) -> Result<BulkUploadResult, String> {
+    let strategy = overwrite_strategy.unwrap_or_else(|| "overwrite".to_string());
+    let base_dir = PathBuf::from("design-assets").join(&asset_path);
+    let target_dir = asset_type_dir(&base_dir, &asset_type)?;
+    if let Err(e) = std::fs::create_dir_all(&target_dir) {
+        return Err(format!("無法建立資產類型目錄: {}", e));
+    }
+
+    let mut success: Vec<String> = Vec::new();
+    let mut failed: Vec<String> = Vec::new();
+    for file_path in file_paths.iter() {
+        let source_path = PathBuf::from(file_path);
+        let Some(file_name) = source_path.file_name() else {
+            failed.push(format!("{}: 無效的檔案路徑", file_path));
+            continue;
+        };
+        let target_path = target_dir.join(file_name);
+        match copy_file_with_strategy(&source_path, &target_path, &strategy) {
+            Ok(()) => success.push(target_path.to_string_lossy().to_string()),
+            Err(e) => failed.push(format!("{}: 複製檔案失敗: {}", file_path, e)),
+        }
+    }
+
+    // 使用系統通知（整批一次）
+    #[cfg(target_os = "macos")]
+    {
+        use std::process::Command;
+        let _ = Command::new("osascript")
+            .arg("-e")
+            .arg(format!("display notification \"{} 個資產上傳至 '{}'，失敗 {} 個\" with title \"ErSlice\"", success.len(), asset_path, failed.len()))
+            .output();
+    }
+
+    Ok(BulkUploadResult { total: file_paths.len(), success, failed })
 }
 
 // 生成切版說明包
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn generate_slice_package(
     module_name: String,
-    include_html: bool,
-    include_css: bool,
-    include_responsive: bool,
+    include_html: Option<bool>,
+    include_css: Option<bool>,
+    include_responsive: Option<bool>,
+    include_comparison: Option<bool>,
+    include_print_css: Option<bool>,
+    archive_format: Option<String>,
+    framework: Option<String>,
+    css_mode: Option<String>,
+    stylesheet_dialect: Option<String>,
+    storybook: Option<bool>,
+    output_formatting: Option<String>,
+    profile: Option<String>,
 ) -> Result<String, String> {
+    let profile = load_generation_profile(profile.as_deref())?.unwrap_or_default();
+    let include_html = include_html.or(profile.include_html).unwrap_or(true);
+    let include_css = include_css.or(profile.include_css).unwrap_or(true);
+    let include_responsive = include_responsive.or(profile.include_responsive).unwrap_or(false);
+    let include_comparison = include_comparison.or(profile.include_comparison).unwrap_or(false);
+    let include_print_css = include_print_css.or(profile.include_print_css).unwrap_or(false);
+    let dialect = resolve_stylesheet_dialect(stylesheet_dialect.as_deref().or(profile.stylesheet_dialect.as_deref()))?;
+    let formatting = resolve_output_formatting(output_formatting.as_deref().or(profile.output_formatting.as_deref()))?;
+    let archive_format = resolve_archive_format(archive_format.as_deref().or(profile.archive_format.as_deref()), false)?;
+    let framework = resolve_scaffold_framework(framework.as_deref())?;
+    let storybook = storybook.unwrap_or(false);
+    if storybook && framework.is_none() {
+        return Err("Storybook stories 需搭配框架骨架（framework）".to_string());
+    }
+    let started_at = chrono::Local::now();
+    let tailwind = match css_mode.as_deref().map(|m| m.trim().to_lowercase()).as_deref() {
+        None | Some("") | Some("css") => false,
+        Some("tailwind") => true,
+        Some(other) => return Err(format!("不支援的 CSS 模式: {}（可用：css, tailwind）", other)),
+    };
     let module_dir = PathBuf::from("design-assets").join(&module_name);
     
     if !module_dir.exists() {
@@ -453,34 +813,86 @@ pub async fn generate_slice_package(
     }
     
     // 創建輸出目錄
-    let output_dir = PathBuf::from("output").join(&module_name);
+    let output_dir = output_root().join(&module_name);
     if let Err(e) = std::fs::create_dir_all(&output_dir) {
         return Err(format!("創建輸出目錄失敗: {}", e));
     }
+    run_generation_hook(HookStage::Pre, &output_dir, Some(&module_name), None)?;
     
     // 複製資產
     if let Err(e) = copy_assets(&module_dir, &output_dir) {
         return Err(format!("複製資產失敗: {}", e));
     }
     
-    // 生成 HTML 模板
-    if include_html {
-        if let Err(e) = generate_html_template_with_strategy(&module_name, &output_dir, "overwrite") {
-            return Err(format!("生成 HTML 模板失敗: {}", e));
+    if tailwind {
+        // Tailwind：HTML 使用 utility class，以 tailwind.config.js / tailwind.css 取代 styles.css
+        generate_tailwind_assets(&module_name, &output_dir, include_html, include_responsive, "overwrite")?;
+    } else {
+        // 生成 HTML 模板
+        if include_html {
+            if let Err(e) = generate_html_template_with_strategy(&module_name, &output_dir, "overwrite") {
+                return Err(format!("生成 HTML 模板失敗: {}", e));
+            }
+        }
+
+        // 生成 CSS 樣式
+        if include_css {
+            if let Err(e) = generate_css_styles_with_strategy(&module_name, &output_dir, include_responsive, include_print_css, dialect, "overwrite") {
+                return Err(format!("生成 CSS 樣式失敗: {}", e));
+            }
         }
     }
     
-    // 生成 CSS 樣式
-    if include_css {
-        if let Err(e) = generate_css_styles_with_strategy(&module_name, &output_dir, include_responsive, "overwrite") {
-            return Err(format!("生成 CSS 樣式失敗: {}", e));
+    // 字型子集化與 @font-face
+    if let Err(e) = generate_font_assets(&module_name, &output_dir, "overwrite") {
+        return Err(format!("生成字型失敗: {}", e));
+    }
+    write_design_tokens_file(std::slice::from_ref(&module_name), &output_dir.join(DESIGN_TOKENS_FILE), "overwrite")?;
+
+    // 生成前後對照頁（current vs target 截圖）
+    if include_comparison {
+        if let Err(e) = generate_comparison_pages(&module_name, &output_dir, "overwrite") {
+            return Err(format!("生成對照頁失敗: {}", e));
         }
     }
-    
+
+    // 框架元件骨架（例如 framework: "react"）
+    if let Some(framework) = framework {
+        let styles = ScaffoldStyles { responsive: include_responsive, print: include_print_css };
+        generate_framework_scaffold(&module_name, &output_dir, framework, &styles, "overwrite")?;
+        if storybook {
+            generate_storybook_stories(&module_name, &output_dir, framework, "overwrite")?;
+        }
+        generate_framework_project(&module_name, &output_dir, framework, storybook, "overwrite")?;
+    }
+
     // 生成 AI 切版說明
-    if let Err(e) = generate_ai_spec_with_strategy(&module_name, &output_dir, "overwrite") {
+    if let Err(e) = generate_ai_spec_with_strategy(&module_name, &output_dir, "overwrite", include_comparison, include_print_css) {
         return Err(format!("生成 AI 切版說明失敗: {}", e));
     }
+
+    // 依專案設定改寫 HTML 資產路徑
+    apply_asset_path_rewrite(&output_dir)?;
+    apply_branding(&output_dir)?;
+    apply_output_formatting(&output_dir, formatting, started_at.into())?;
+    write_package_checksums(&output_dir)?;
+    let archive = write_package_archive(&output_dir, &output_root(), archive_format)?;
+    let archive_str = archive.as_ref().and_then(|a| a.primary());
+    run_generation_hook(HookStage::Post, &output_dir, Some(&module_name), archive_str.as_deref())?;
+    let options = serde_json::json!({
+        "include_html": include_html,
+        "include_css": include_css,
+        "include_responsive": include_responsive,
+        "include_comparison": include_comparison,
+        "include_print_css": include_print_css,
+        "framework": framework,
+        "storybook": storybook,
+        "tailwind": tailwind,
+        "stylesheet_dialect": format!("{:?}", dialect).to_lowercase(),
+        "output_formatting": format!("{:?}", formatting).to_lowercase(),
+    });
+    let modules = vec![ManifestModule::completed(&module_name, &output_dir, started_at, false)];
+    record_generation_manifest("module", started_at, options, modules, Some(&output_dir), archive_str.as_deref());
     
     // 使用系統通知
     #[cfg(target_os = "macos")]
@@ -492,25 +904,59 @@ pub async fn generate_slice_package(
             .output();
     }
     
-    Ok(format!("切版說明包生成成功: {}", output_dir.display()))
+    match (archive_str, archive.and_then(|a| a.summary())) {
+        (Some(a), Some(summary)) => Ok(format!("切版說明包生成成功: {}（封存檔: {}；{}）", output_dir.display(), a, summary)),
+        (Some(a), None) => Ok(format!("切版說明包生成成功: {}（封存檔: {}）", output_dir.display(), a)),
+        (None, _) => Ok(format!("切版說明包生成成功: {}", output_dir.display())),
+    }
 }
 
 // 批量生成：為所有設計資產模組生成切版說明包
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn generate_all_slice_packages(
-    include_html: bool,
-    include_css: bool,
-    include_responsive: bool,
-    overwrite_strategy: String,
+    app: tauri::AppHandle,
+    include_html: Option<bool>,
+    include_css: Option<bool>,
+    include_responsive: Option<bool>,
+    overwrite_strategy: Option<String>,
+    include_comparison: Option<bool>,
+    include_print_css: Option<bool>,
+    stylesheet_dialect: Option<String>,
+    only_changed: Option<bool>,
+    job_id: Option<String>,
+    validate: Option<bool>,
+    archive_format: Option<String>,
+    output_formatting: Option<String>,
+    profile: Option<String>,
 ) -> Result<BulkGenerationResult, String> {
+    let started_at = chrono::Local::now();
+    let profile = load_generation_profile(profile.as_deref())?.unwrap_or_default();
+    let include_html = include_html.or(profile.include_html).unwrap_or(true);
+    let include_css = include_css.or(profile.include_css).unwrap_or(true);
+    let include_responsive = include_responsive.or(profile.include_responsive).unwrap_or(false);
+    let overwrite_strategy = overwrite_strategy.or(profile.overwrite_strategy.clone()).unwrap_or_else(|| "overwrite".to_string());
+    let include_comparison = include_comparison.or(profile.include_comparison).unwrap_or(false);
+    let include_print_css = include_print_css.or(profile.include_print_css).unwrap_or(false);
+    let only_changed = only_changed.unwrap_or(false);
+    let dialect = resolve_stylesheet_dialect(stylesheet_dialect.as_deref().or(profile.stylesheet_dialect.as_deref()))?;
+    let archive_format = resolve_archive_format(archive_format.as_deref().or(profile.archive_format.as_deref()), false)?;
+    let formatting = resolve_output_formatting(output_formatting.as_deref().or(profile.output_formatting.as_deref()))?;
     let root = PathBuf::from("design-assets");
     if !root.exists() {
         return Err("設計資產目錄不存在".to_string());
     }
+    // 生成選項也納入指紋，選項不同時視為需要重新生成
+    let options_key = format!(
+        "html={};css={};responsive={};strategy={};comparison={};print={};dialect={:?};formatting={:?}",
+        include_html, include_css, include_responsive, overwrite_strategy, include_comparison, include_print_css, dialect, formatting
+    );
 
     let mut modules: Vec<String> = Vec::new();
+    let ignore = IgnoreRules::load();
     if let Ok(entries) = std::fs::read_dir(&root) {
         for entry in entries.flatten() {
+            if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
             let path = entry.path();
             if path.is_dir() {
                 if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
@@ -520,124 +966,340 @@ pub async fn generate_all_slice_packages(
         }
     }
 
+    let options = BulkModuleOptions {
+        include_html,
+        include_css,
+        include_responsive,
+        include_comparison,
+        include_print_css,
+        dialect,
+        overwrite_strategy: overwrite_strategy.clone(),
+        validate: validate.or(profile.validate).unwrap_or(false),
+        archive_format,
+        formatting,
+    };
     let mut success: Vec<String> = Vec::new();
     let mut failed: Vec<String> = Vec::new();
+    let mut warnings: Vec<String> = Vec::new();
+    let mut archives: Vec<String> = Vec::new();
+    let mut skipped_modules: Vec<String> = Vec::new();
+    let mut copy_stats = CopyStats::default();
 
+    // 指紋比對在主執行緒完成，只把需要重新生成的模組交給平行工作
+    let mut pending: Vec<(String, String)> = Vec::new();
     for module_name in modules.iter() {
-        let module_dir = root.join(module_name);
-
-        // 建立輸出目錄
-        let output_dir = PathBuf::from("output").join(module_name);
-        if let Err(e) = std::fs::create_dir_all(&output_dir) {
-            failed.push(format!("{}: 創建輸出失敗: {}", module_name, e));
+        let fingerprint = module_content_fingerprint(&root.join(module_name), &options_key);
+        let output_dir = output_root().join(module_name);
+        if only_changed && output_dir.exists() && crate::database::get_generation_fingerprint(module_name).ok().flatten().as_deref() == Some(fingerprint.as_str()) {
+            skipped_modules.push(module_name.clone());
             continue;
         }
-
-        // 複製資產
-        if let Err(e) = copy_assets_with_strategy(&module_dir, &output_dir, &overwrite_strategy) {
-            failed.push(format!("{}: 複製資產失敗: {}", module_name, e));
-            continue;
-        }
-
-        // 生成 HTML/CSS
-        if include_html {
-            if let Err(e) = generate_html_template_with_strategy(module_name, &output_dir, &overwrite_strategy) {
-                failed.push(format!("{}: 生成 HTML 失敗: {}", module_name, e));
-                continue;
-            }
-        }
-        if include_css {
-            if let Err(e) = generate_css_styles_with_strategy(module_name, &output_dir, include_responsive, &overwrite_strategy) {
-                failed.push(format!("{}: 生成 CSS 失敗: {}", module_name, e));
-                continue;
+        pending.push((module_name.clone(), fingerprint));
+    }
+
+    let names: Vec<String> = pending.iter().map(|(name, _)| name.clone()).collect();
+    let job = GenerationJob::start(app, job_id, names.len());
+    let (results, options, job) = run_bulk_generation_blocking(root, names, options, job).await?;
+    // 資料庫寫入保持序列，避免 SQLite 同時寫入時鎖定
+    let mut manifest_modules: Vec<ManifestModule> = skipped_modules.iter().map(|m| ManifestModule::skipped(m)).collect();
+    for ((module_name, fingerprint), (result, duration_ms)) in pending.iter().zip(results) {
+        manifest_modules.push(ManifestModule::from_bulk(module_name, &result, duration_ms));
+        match result {
+            Ok(output) => {
+                copy_stats.copied += output.stats.copied;
+                copy_stats.skipped += output.stats.skipped;
+                if let Err(e) = crate::database::set_generation_fingerprint(module_name, fingerprint) {
+                    log::warn!("記錄 {} 生成指紋失敗: {}", module_name, e);
+                }
+                warnings.extend(output.warnings);
+                archives.extend(output.archives);
+                success.push(output.message);
             }
+            Err(e) => failed.push(e),
         }
-
-        // 生成 AI 說明（與單項一致）
-        if let Err(e) = generate_ai_spec_with_strategy(module_name, &output_dir, &overwrite_strategy) {
-            failed.push(format!("{}: 生成 AI 說明失敗: {}", module_name, e));
-            continue;
-        }
-
-        success.push(format!("切版說明包生成成功: {}", output_dir.display()));
     }
+    let mut manifest_options = options.manifest_json();
+    manifest_options["only_changed"] = serde_json::json!(only_changed);
+    let cancelled = job.is_cancelled();
+    let job_id = job.finish();
+    notify_generation_webhook(GenerationWebhookPayload::new("bulk", &job_id, started_at, &manifest_modules, None, archives.clone(), cancelled));
+    record_generation_manifest("bulk", started_at, manifest_options, manifest_modules, None, None);
 
     Ok(BulkGenerationResult {
-        total: success.len() + failed.len(),
+        total: success.len() + failed.len() + skipped_modules.len(),
         success,
         failed,
+        files_copied: copy_stats.copied,
+        files_skipped: copy_stats.skipped,
+        skipped_modules,
+        cancelled,
+        job_id,
+        warnings,
+        archives,
     })
 }
 
 // 指定模組清單之批量生成
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn generate_selected_slice_packages(
+    app: tauri::AppHandle,
     modules: Vec<String>,
-    include_html: bool,
-    include_css: bool,
-    include_responsive: bool,
-    overwrite_strategy: String,
+    include_html: Option<bool>,
+    include_css: Option<bool>,
+    include_responsive: Option<bool>,
+    overwrite_strategy: Option<String>,
+    include_comparison: Option<bool>,
+    include_print_css: Option<bool>,
+    stylesheet_dialect: Option<String>,
+    job_id: Option<String>,
+    validate: Option<bool>,
+    archive_format: Option<String>,
+    output_formatting: Option<String>,
+    profile: Option<String>,
 ) -> Result<BulkGenerationResult, String> {
+    let started_at = chrono::Local::now();
+    let profile = load_generation_profile(profile.as_deref())?.unwrap_or_default();
+    let include_html = include_html.or(profile.include_html).unwrap_or(true);
+    let include_css = include_css.or(profile.include_css).unwrap_or(true);
+    let include_responsive = include_responsive.or(profile.include_responsive).unwrap_or(false);
+    let overwrite_strategy = overwrite_strategy.or(profile.overwrite_strategy.clone()).unwrap_or_else(|| "overwrite".to_string());
+    let include_comparison = include_comparison.or(profile.include_comparison).unwrap_or(false);
+    let include_print_css = include_print_css.or(profile.include_print_css).unwrap_or(false);
+    let dialect = resolve_stylesheet_dialect(stylesheet_dialect.as_deref().or(profile.stylesheet_dialect.as_deref()))?;
+    let archive_format = resolve_archive_format(archive_format.as_deref().or(profile.archive_format.as_deref()), false)?;
+    let formatting = resolve_output_formatting(output_formatting.as_deref().or(profile.output_formatting.as_deref()))?;
     let root = PathBuf::from("design-assets");
     if !root.exists() {
         return Err("設計資產目錄不存在".to_string());
     }
+    let options = BulkModuleOptions {
+        include_html,
+        include_css,
+        include_responsive,
+        include_comparison,
+        include_print_css,
+        dialect,
+        overwrite_strategy: overwrite_strategy.clone(),
+        validate: validate.or(profile.validate).unwrap_or(false),
+        archive_format,
+        formatting,
+    };
     let mut success: Vec<String> = Vec::new();
     let mut failed: Vec<String> = Vec::new();
-
-    for module_name in modules.iter() {
-        let module_dir = root.join(module_name);
-        if !module_dir.exists() {
-            failed.push(format!("{}: 模組不存在", module_name));
-            continue;
-        }
-        let output_dir = PathBuf::from("output").join(module_name);
-        if let Err(e) = std::fs::create_dir_all(&output_dir) {
-            failed.push(format!("{}: 創建輸出失敗: {}", module_name, e));
-            continue;
+    let mut warnings: Vec<String> = Vec::new();
+    let mut archives: Vec<String> = Vec::new();
+    let mut copy_stats = CopyStats::default();
+
+    let job = GenerationJob::start(app, job_id, modules.len());
+    let (results, options, job) = run_bulk_generation_blocking(root, modules.clone(), options, job).await?;
+    let mut manifest_modules: Vec<ManifestModule> = Vec::new();
+    for (module_name, (result, duration_ms)) in modules.iter().zip(results) {
+        manifest_modules.push(ManifestModule::from_bulk(module_name, &result, duration_ms));
+        match result {
+            Ok(output) => {
+                copy_stats.copied += output.stats.copied;
+                copy_stats.skipped += output.stats.skipped;
+                warnings.extend(output.warnings);
+                archives.extend(output.archives);
+                success.push(output.message);
+            }
+            Err(e) => failed.push(e),
         }
+    }
+    let cancelled = job.is_cancelled();
+    let job_id = job.finish();
+    notify_generation_webhook(GenerationWebhookPayload::new("selected", &job_id, started_at, &manifest_modules, None, archives.clone(), cancelled));
+    record_generation_manifest("selected", started_at, options.manifest_json(), manifest_modules, None, None);
 
-        if let Err(e) = copy_assets_with_strategy(&module_dir, &output_dir, &overwrite_strategy) {
-            failed.push(format!("{}: 複製資產失敗: {}", module_name, e));
-            continue;
-        }
+    Ok(BulkGenerationResult {
+        total: success.len() + failed.len(),
+        success,
+        failed,
+        files_copied: copy_stats.copied,
+        files_skipped: copy_stats.skipped,
+        skipped_modules: Vec::new(),
+        cancelled,
+        job_id,
+        warnings,
+        archives,
+    })
+}
 
-        if include_html {
-            if let Err(e) = generate_html_template_with_strategy(module_name, &output_dir, &overwrite_strategy) {
-                failed.push(format!("{}: 生成 HTML 失敗: {}", module_name, e));
-                continue;
-            }
-        }
-        if include_css {
-            if let Err(e) = generate_css_styles_with_strategy(module_name, &output_dir, include_responsive, &overwrite_strategy) {
-                failed.push(format!("{}: 生成 CSS 失敗: {}", module_name, e));
-                continue;
-            }
-        }
-        if let Err(e) = generate_ai_spec_with_strategy(module_name, &output_dir, &overwrite_strategy) {
-            failed.push(format!("{}: 生成 AI 說明失敗: {}", module_name, e));
-            continue;
+// 批量生成時每個模組共用的選項
+struct BulkModuleOptions {
+    include_html: bool,
+    include_css: bool,
+    include_responsive: bool,
+    include_comparison: bool,
+    include_print_css: bool,
+    dialect: StylesheetDialect,
+    overwrite_strategy: String,
+    // 生成後執行 validate_generated_output
+    validate: bool,
+    // 每個模組各自封存為 output/<module>.<副檔名>
+    archive_format: ArchiveFormat,
+    formatting: OutputFormatting,
+}
+
+impl BulkModuleOptions {
+    // 寫入生成紀錄的選項摘要
+    fn manifest_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "include_html": self.include_html,
+            "include_css": self.include_css,
+            "include_responsive": self.include_responsive,
+            "include_comparison": self.include_comparison,
+            "include_print_css": self.include_print_css,
+            "stylesheet_dialect": format!("{:?}", self.dialect).to_lowercase(),
+            "overwrite_strategy": self.overwrite_strategy,
+            "validate": self.validate,
+            "archive_format": format!("{:?}", self.archive_format).to_lowercase(),
+            "output_formatting": format!("{:?}", self.formatting).to_lowercase(),
+        })
+    }
+}
+
+// 單一模組的批量生成成果
+struct BulkModuleOutput {
+    message: String,
+    stats: CopyStats,
+    warnings: Vec<String>,
+    archives: Vec<String>,
+}
+
+// 單一模組的批量生成結果；失敗訊息以模組名稱開頭
+type BulkModuleResult = Result<BulkModuleOutput, String>;
+
+// 批量生成的同時處理模組數：project.json 的 generation_concurrency，未設定時取 CPU 核心數（最多 4）
+fn bulk_generation_concurrency() -> usize {
+    read_active_project_config()
+        .and_then(|c| c.generation_concurrency)
+        .unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1).min(4))
+        .max(1)
+}
+
+// 以有限數量的執行緒平行生成各模組；回傳結果（附耗時毫秒）與 modules 順序一致，取消後尚未開始的模組回報為已取消
+fn run_bulk_generation(root: &std::path::Path, modules: &[String], options: &BulkModuleOptions, job: &GenerationJob) -> Vec<(BulkModuleResult, u64)> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    let results: Vec<Mutex<Option<(BulkModuleResult, u64)>>> = modules.iter().map(|_| Mutex::new(None)).collect();
+    let next = AtomicUsize::new(0);
+    let workers = bulk_generation_concurrency().min(modules.len());
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                let Some(module_name) = modules.get(i) else { break };
+                if job.is_cancelled() { break; }
+                let started = std::time::Instant::now();
+                let result = generate_bulk_module(root, module_name, options);
+                let duration_ms = started.elapsed().as_millis() as u64;
+                *results[i].lock().unwrap_or_else(|e| e.into_inner()) = Some((result, duration_ms));
+                job.advance(module_name);
+            });
         }
+    });
+    results
+        .into_iter()
+        .zip(modules)
+        .map(|(slot, module_name)| {
+            slot.into_inner()
+                .unwrap_or_else(|e| e.into_inner())
+                .unwrap_or_else(|| (Err(format!("{}: 已取消", module_name)), 0))
+        })
+        .collect()
+}
+
+// run_bulk_generation 會阻塞到所有模組完成，改在阻塞執行緒池執行以免佔住 async 工作執行緒；選項與 job 於完成後交還
+async fn run_bulk_generation_blocking(
+    root: PathBuf,
+    modules: Vec<String>,
+    options: BulkModuleOptions,
+    job: GenerationJob,
+) -> Result<(Vec<(BulkModuleResult, u64)>, BulkModuleOptions, GenerationJob), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let results = run_bulk_generation(&root, &modules, &options, &job);
+        (results, options, job)
+    })
+    .await
+    .map_err(|e| format!("批量生成執行緒失敗: {}", e))
+}
+
+// 生成單一模組的切版說明包（批量生成用）；失敗訊息以模組名稱開頭
+fn generate_bulk_module(root: &std::path::Path, module_name: &str, options: &BulkModuleOptions) -> BulkModuleResult {
+    let started = SystemTime::now();
+    let strategy = options.overwrite_strategy.as_str();
+    let module_dir = root.join(module_name);
+    if !module_dir.exists() {
+        return Err(format!("{}: 模組不存在", module_name));
+    }
+    let output_dir = output_root().join(module_name);
+    let mut stats = CopyStats::default();
+
+    std::fs::create_dir_all(&output_dir).map_err(|e| format!("{}: 創建輸出失敗: {}", module_name, e))?;
+    run_generation_hook(HookStage::Pre, &output_dir, Some(module_name), None).map_err(|e| format!("{}: {}", module_name, e))?;
+
+    copy_assets_with_stats(&module_dir, &output_dir, strategy, &mut stats).map_err(|e| format!("{}: 複製資產失敗: {}", module_name, e))?;
+
+    if options.include_html {
+        generate_html_template_with_strategy(module_name, &output_dir, strategy).map_err(|e| format!("{}: 生成 HTML 失敗: {}", module_name, e))?;
+    }
+    if options.include_css {
+        generate_css_styles_with_strategy(module_name, &output_dir, options.include_responsive, options.include_print_css, options.dialect, strategy)
+            .map_err(|e| format!("{}: 生成 CSS 失敗: {}", module_name, e))?;
+    }
 
-        success.push(format!("切版說明包生成成功: {}", output_dir.display()));
+    generate_font_assets(module_name, &output_dir, strategy).map_err(|e| format!("{}: 生成字型失敗: {}", module_name, e))?;
+    write_design_tokens_file(&[module_name.to_string()], &output_dir.join(DESIGN_TOKENS_FILE), strategy).map_err(|e| format!("{}: {}", module_name, e))?;
+    if options.include_comparison {
+        generate_comparison_pages(module_name, &output_dir, strategy).map_err(|e| format!("{}: 生成對照頁失敗: {}", module_name, e))?;
     }
+    generate_ai_spec_with_strategy(module_name, &output_dir, strategy, options.include_comparison, options.include_print_css)
+        .map_err(|e| format!("{}: 生成 AI 說明失敗: {}", module_name, e))?;
 
-    Ok(BulkGenerationResult { total: success.len() + failed.len(), success, failed })
+    let archive = apply_asset_path_rewrite(&output_dir)
+        .and_then(|_| apply_branding(&output_dir))
+        .and_then(|_| apply_output_formatting(&output_dir, options.formatting, started))
+        .and_then(|_| write_package_checksums(&output_dir))
+        .and_then(|_| write_package_archive(&output_dir, &output_root(), options.archive_format))
+        .and_then(|archive| run_generation_hook(HookStage::Post, &output_dir, Some(module_name), archive.as_ref().and_then(|a| a.primary()).as_deref()).map(|_| archive))
+        .map_err(|e| format!("{}: {}", module_name, e))?
+        .unwrap_or_default();
+
+    let mut warnings: Vec<String> = if options.validate {
+        validate_generated_output(&output_dir).into_iter().map(|w| format!("{}: {}", module_name, w)).collect()
+    } else {
+        Vec::new()
+    };
+    warnings.extend(archive.excluded.iter().map(|f| format!("{}: {}: 超過截圖大小上限，未封存", module_name, f)));
+    let message = match archive.primary() {
+        Some(a) => format!("切版說明包生成成功: {}（封存檔: {}）", output_dir.display(), a),
+        None => format!("切版說明包生成成功: {}", output_dir.display()),
+    };
+    Ok(BulkModuleOutput { message, stats, warnings, archives: archive.part_paths() })
 }
 
 // 複製資產檔案
 fn copy_assets(source_dir: &PathBuf, target_dir: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+    copy_assets_filtered(source_dir, target_dir, &IgnoreRules::load())
+}
+
+fn copy_assets_filtered(source_dir: &PathBuf, target_dir: &PathBuf, ignore: &IgnoreRules) -> Result<(), Box<dyn std::error::Error>> {
     if let Ok(entries) = std::fs::read_dir(source_dir) {
         for entry in entries {
             if let Ok(entry) = entry {
+                if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
                 let path = entry.path();
+                if entry.file_name() == VERSIONS_DIR { continue; }
                 let target_path = target_dir.join(path.file_name().unwrap());
                 
                 if path.is_file() {
                     std::fs::copy(&path, &target_path)?;
                 } else if path.is_dir() {
                     std::fs::create_dir_all(&target_path)?;
-                    copy_assets(&path, &target_path)?;
+                    copy_assets_filtered(&path, &target_path, ignore)?;
                 }
             }
         }
@@ -824,7 +1486,29 @@ fn write_text_with_strategy(target_path: &PathBuf, content: &str, strategy: &str
             let path = if target_path.exists() { next_available_path(target_path) } else { target_path.clone() };
             std::fs::write(path, content)?;
         },
-        _ => { // overwrite
+        "version" => {
+            archive_existing_version(target_path)?;
+            std::fs::write(target_path, content)?;
+        },
+        "if-changed" => {
+            if std::fs::read(target_path).ok().as_deref() == Some(content.as_bytes()) { return Ok(()); }
+            std::fs::write(target_path, content)?;
+        },
+        "merge" if is_mergeable_markdown(target_path) => {
+            let merged = match std::fs::read_to_string(target_path) {
+                Ok(existing) => match merge_markdown(&existing, content) {
+                    Some(merged) => merged,
+                    // 既有檔案沒有標記：先封存舊版本，避免覆蓋手動編輯的內容
+                    None => {
+                        archive_existing_version(target_path)?;
+                        wrap_managed_markdown(content)
+                    }
+                },
+                Err(_) => wrap_managed_markdown(content),
+            };
+            std::fs::write(target_path, merged)?;
+        },
+        _ => { // overwrite（"merge" 用於非 Markdown 檔案時亦同）
             std::fs::write(target_path, content)?;
         }
     }
@@ -841,6 +1525,14 @@ fn copy_file_with_strategy(src: &PathBuf, dest: &PathBuf, strategy: &str) -> Res
             let path = if dest.exists() { next_available_path(dest) } else { dest.clone() };
             std::fs::copy(src, path)?;
         },
+        "version" => {
+            archive_existing_version(dest)?;
+            std::fs::copy(src, dest)?;
+        },
+        "if-changed" => {
+            if files_unchanged(src, dest) { return Ok(()); }
+            std::fs::copy(src, dest)?;
+        },
         _ => { // overwrite
             std::fs::copy(src, dest)?;
         }
@@ -849,21 +1541,83 @@ fn copy_file_with_strategy(src: &PathBuf, dest: &PathBuf, strategy: &str) -> Res
 }
 
 fn copy_assets_with_strategy(source_dir: &PathBuf, target_dir: &PathBuf, strategy: &str) -> Result<(), Box<dyn std::error::Error>> {
+    copy_assets_with_stats(source_dir, target_dir, strategy, &mut CopyStats::default())
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct CopyStats {
+    copied: usize,
+    skipped: usize,
+}
+
+// 大小不同即視為變更；大小相同時比對雜湊（還原版本或回收桶以 rename 保留舊 mtime，不能以 mtime 判斷）
+fn files_unchanged(src: &std::path::Path, dest: &std::path::Path) -> bool {
+    let (Ok(sm), Ok(dm)) = (std::fs::metadata(src), std::fs::metadata(dest)) else { return false };
+    if sm.len() != dm.len() {
+        return false;
+    }
+    match (file_sha256(src), file_sha256(dest)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn file_sha256(path: &std::path::Path) -> Option<Vec<u8>> {
+    use sha2::{Digest, Sha256};
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).ok()?;
+    Some(hasher.finalize().to_vec())
+}
+
+fn copy_assets_with_stats(source_dir: &PathBuf, target_dir: &PathBuf, strategy: &str, stats: &mut CopyStats) -> Result<(), Box<dyn std::error::Error>> {
+    copy_assets_with_stats_filtered(source_dir, target_dir, strategy, stats, &IgnoreRules::load())
+}
+
+fn copy_assets_with_stats_filtered(source_dir: &PathBuf, target_dir: &PathBuf, strategy: &str, stats: &mut CopyStats, ignore: &IgnoreRules) -> Result<(), Box<dyn std::error::Error>> {
     if let Ok(entries) = std::fs::read_dir(source_dir) {
         for entry in entries.flatten() {
+            if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
             let path = entry.path();
+            if entry.file_name() == VERSIONS_DIR { continue; }
+            if entry.file_name() == MODULE_TEMPLATES_DIR && is_generation_templates_dir(&path) { continue; }
             let target_path = target_dir.join(path.file_name().unwrap());
             if path.is_file() {
+                let unchanged = match strategy {
+                    "if-changed" => files_unchanged(&path, &target_path),
+                    "skip" => target_path.exists(),
+                    _ => false,
+                };
+                if unchanged {
+                    stats.skipped += 1;
+                    continue;
+                }
                 copy_file_with_strategy(&path, &target_path, strategy)?;
+                stats.copied += 1;
             } else if path.is_dir() {
                 std::fs::create_dir_all(&target_path)?;
-                copy_assets_with_strategy(&path, &target_path, strategy)?;
+                copy_assets_with_stats_filtered(&path, &target_path, strategy, stats, ignore)?;
             }
         }
     }
     Ok(())
 }
 
+// 歷史版本資料夾名稱（與資產檔案同層，不會被複製進輸出包）
+const VERSIONS_DIR: &str = "_versions";
+
+// 將既有檔案移至 _versions/<name>/<timestamp>/<name>，回傳封存路徑
+fn archive_existing_version(path: &std::path::Path) -> std::io::Result<Option<PathBuf>> {
+    if !path.is_file() { return Ok(None); }
+    let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else { return Ok(None) };
+    let ts = chrono::Local::now().format("%Y%m%d-%H%M%S%.3f").to_string();
+    let dir = parent.join(VERSIONS_DIR).join(name).join(ts);
+    std::fs::create_dir_all(&dir)?;
+    let archived = dir.join(name);
+    std::fs::rename(path, &archived)?;
+    Ok(Some(archived))
+}
+
 fn next_available_path(original: &PathBuf) -> PathBuf {
     use std::path::Path;
     let parent = original.parent().unwrap_or(Path::new("."));
@@ -882,160 +1636,159 @@ fn next_available_path(original: &PathBuf) -> PathBuf {
 }
 
 fn generate_html_template_with_strategy(module_name: &str, output_dir: &PathBuf, strategy: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let html_content = format!(
-        r#"<!DOCTYPE html>
-<html lang=\"zh-TW\">
-<head>
-    <meta charset=\"UTF-8\">
-    <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">
-    <title>{}</title>
-    <link rel=\"stylesheet\" href=\"styles.css\">
-</head>
-<body>
-    <div class=\"{}\">
-        <!-- 這裡是 {} 模組的 HTML 結構 -->
-        <header class=\"header\">
-            <h1>{}</h1>
-        </header>
-        
-        <main class=\"main-content\">
-            <p>請根據設計稿完善 HTML 結構</p>
-        </main>
-    </div>
-</body>
-</html>"#,
-        module_name, module_name.to_lowercase().replace(" ", "-"), module_name, module_name
-    );
+    write_module_html(output_dir, &module_html_content(module_name), strategy)
+}
+
+// index.html（RTL 專案另輸出 index.rtl.html）
+fn write_module_html(output_dir: &std::path::Path, html_content: &str, strategy: &str) -> Result<(), Box<dyn std::error::Error>> {
     let html_path = output_dir.join("index.html");
-    write_text_with_strategy(&html_path, &html_content, strategy)?;
+    write_text_with_strategy(&html_path, html_content, strategy)?;
+    if project_rtl_enabled() {
+        let rtl_content = html_content.replacen("<html ", "<html dir=\"rtl\" ", 1);
+        write_text_with_strategy(&output_dir.join("index.rtl.html"), &rtl_content, strategy)?;
+    }
     Ok(())
 }
 
+fn module_html_content(module_name: &str) -> String {
+    render_generation_template("index.html.hbs", Some(module_name), &module_html_context(module_name))
+}
+
+// index.html 與 Tailwind 版 index.html 模板共用的情境
+fn module_html_context(module_name: &str) -> serde_json::Value {
+    let placeholders = [page_state_placeholders(module_name), loading_skeleton_placeholders(module_name), tracking_attribute_stubs(module_name), focus_order_hints(module_name)].concat();
+    serde_json::json!({
+        "module": module_name,
+        "module_class": module_name.to_lowercase().replace(' ', "-"),
+        "display_name": module_display_name(module_name),
+        "title": seo_document_title(module_name),
+        "head_tags": format!("{}{}", seo_head_tags(module_name), font_stylesheet_link(module_name)),
+        "placeholders": placeholders,
+        "pages": generation_template_pages(module_name),
+    })
+}
+
 fn generate_css_styles_with_strategy(
     module_name: &str,
     output_dir: &PathBuf,
     include_responsive: bool,
+    include_print: bool,
+    dialect: StylesheetDialect,
     strategy: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let mut css_content = format!(
-        r#"/* {} 模組樣式 */
-
-.{} {{
-    font-family: 'Inter', system-ui, sans-serif;
-    line-height: 1.6;
-    color: #333;
-}}
-
-.header {{
-    background: #f8f9fa;
-    padding: 2rem;
-    text-align: center;
-    border-bottom: 1px solid #e9ecef;
-}}
-
-.header h1 {{
-    margin: 0;
-    color: #495057;
-    font-size: 2rem;
-    font-weight: 600;
-}}
-
-.main-content {{
-    padding: 2rem;
-    max-width: 1200px;
-    margin: 0 auto;
-}}
-
-.main-content p {{
-    font-size: 1.1rem;
-    color: #6c757d;
-    text-align: center;
-}}"#,
-        module_name, module_name.to_lowercase().replace(" ", "-")
-    );
-    if include_responsive {
-        css_content.push_str(
-            r#"
-
-/* 響應式設計 */
-@media (max-width: 768px) {
-    .header {
-        padding: 1rem;
-    }
-    
-    .header h1 {
-        font-size: 1.5rem;
-    }
-    
-    .main-content {
-        padding: 1rem;
-    }
-}
-
-@media (max-width: 480px) {
-    .header h1 {
-        font-size: 1.25rem;
-    }
-}"#
-        );
-    }
-    let css_path = output_dir.join("styles.css");
-    write_text_with_strategy(&css_path, &css_content, strategy)?;
+    let css_content = module_styles_css(module_name, include_responsive, include_print);
+    let (file_name, content) = match dialect {
+        StylesheetDialect::Css => match tokens_css_content(module_name) {
+            Some(tokens_css) => {
+                write_text_with_strategy(&output_dir.join("tokens.css"), &tokens_css, strategy)?;
+                ("styles.css", format!("@import url(\"tokens.css\");\n\n{}", css_content))
+            }
+            None => ("styles.css", css_content),
+        },
+        StylesheetDialect::Scss => ("styles.scss", css_to_preprocessor(module_name, &css_content, dialect)),
+        StylesheetDialect::Less => ("styles.less", css_to_preprocessor(module_name, &css_content, dialect)),
+    };
+    write_text_with_strategy(&output_dir.join(file_name), &content, strategy)?;
     Ok(())
 }
 
-fn generate_ai_spec_with_strategy(module_name: &str, output_dir: &PathBuf, strategy: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let spec_content = format!(
-        r#"# {} 模組切版說明
-
-## 概述
-這是 {} 模組的前端切版說明，AI 可以根據此說明完成前端開發。
-
-## 檔案結構
-```
-{0}/
-├── screenshots/     # 設計稿截圖
-├── html/           # HTML 結構檔案
-├── css/            # CSS 樣式檔案
-├── index.html      # 主頁面模板
-├── styles.css      # 樣式檔案
-└── ai-spec.md      # 本說明檔案
-```
-
-## 切版要求
-
-### 佈局結構
-- 使用語義化 HTML 標籤
-- 確保良好的可訪問性
-- 遵循設計稿的視覺層次
-
-### 樣式設計
-- 使用 CSS Grid 或 Flexbox 佈局
-- 實現響應式設計
-- 保持設計一致性
-
-### 互動功能
-- 實現必要的 JavaScript 功能
-- 確保良好的用戶體驗
-- 添加適當的動畫效果
-
-## 開發建議
-1. 先分析設計稿的佈局結構
-2. 建立 HTML 骨架
-3. 實現基礎樣式
-4. 添加響應式設計
-5. 完善互動功能
-6. 測試和優化
-
-## 注意事項
-- 確保跨瀏覽器相容性
-- 優化效能和載入速度
-- 遵循 Web 標準和最佳實踐
-"#,
-        module_name, module_name
-    );
+// styles.css 內容；顏色以主題自訂屬性（附預設值）引用，token 另由 tokens.css 提供
+fn module_styles_css(module_name: &str, include_responsive: bool, include_print: bool) -> String {
+    let font_family = read_css_tokens(module_name)
+        .and_then(|t| t.font_families.first().map(|f| format!("var(--font-1, {})", f.value)))
+        .unwrap_or_else(|| "'Inter', system-ui, sans-serif".to_string());
+    let mut extra_css = format!("{}{}", motion_css(module_name), loading_skeleton_css(module_name));
+    if include_print {
+        extra_css.push_str(PRINT_CSS);
+    }
+    let context = serde_json::json!({
+        "module": module_name,
+        "module_class": module_name.to_lowercase().replace(' ', "-"),
+        "font_family": font_family,
+        "responsive": include_responsive,
+        "print": include_print,
+        "extra_css": extra_css,
+        "tokens": read_css_tokens(module_name).map(|t| css_token_entries(&t)).unwrap_or_default(),
+    });
+    let mut css_content = render_generation_template("styles.css.hbs", Some(module_name), &context).trim_end().to_string();
+    if project_rtl_enabled() {
+        css_content = logical_css_properties(&css_content);
+        css_content.push_str(RTL_CSS);
+    }
+    css_content
+}
+
+fn generate_ai_spec_with_strategy(module_name: &str, output_dir: &PathBuf, strategy: &str, include_comparison: bool, include_print_css: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let lang = project_spec_language();
+    let mut spec_content = String::new();
+    spec_content.push_str(&module_overview_spec_section(module_name));
+    spec_content.push_str(&page_tree_spec_section(module_name));
+    spec_content.push_str(&screenshots_spec_section(module_name));
+    spec_content.push_str(&feature_flags_spec_section(module_name));
+    spec_content.push_str(&overlays_spec_section(module_name));
+    spec_content.push_str(&page_states_spec_section(module_name));
+    spec_content.push_str(&loading_states_spec_section(module_name));
+    spec_content.push_str(&breakpoint_screenshots_spec_section(module_name));
+    spec_content.push_str(&keyboard_spec_section(module_name));
+    spec_content.push_str(&accessibility_spec_section(module_name));
+    spec_content.push_str(&screenshot_flow_spec_section(module_name));
+    spec_content.push_str(&annotations_spec_section(module_name));
+    spec_content.push_str(&html_structure_spec_section(module_name));
+    if include_comparison {
+        spec_content.push_str(&comparison_spec_section(module_name));
+    }
+    if include_print_css {
+        spec_content.push_str(print_spec_section(lang));
+    }
+    if project_rtl_enabled() {
+        spec_content.push_str(rtl_spec_section(lang));
+    }
+    let props_section = component_props_spec_section(module_name);
+    if !props_section.is_empty() {
+        spec_content.push_str(&props_section);
+        write_text_with_strategy(&output_dir.join("props.d.ts"), &component_props_typescript(module_name), strategy)?;
+    }
+    if !collect_module_page_entries(module_name).is_empty() {
+        spec_content.push_str(lang.pick(
+            "\n## SEO\n各頁面的 title / description / og:image / canonical 已寫入 `index.html` 的 `<head>`（主頁面為實際標籤，其餘頁面置於 `<template data-page>`）；缺漏欄位請見 `seo-audit.md`。\n",
+            "\n## SEO\nEach page's title / description / og:image / canonical is written into the `<head>` of `index.html` (real tags for the main page, `<template data-page>` for the others); see `seo-audit.md` for missing fields.\n",
+            "\n## SEO\n各ページの title / description / og:image / canonical は `index.html` の `<head>` に書き込まれています（メインページは実際のタグ、その他のページは `<template data-page>` 内）。不足項目は `seo-audit.md` を参照してください。\n",
+        ));
+        write_text_with_strategy(&output_dir.join("seo-audit.md"), &seo_audit_markdown(module_name), strategy)?;
+    }
+    let test_cases = module_test_cases(module_name, lang);
+    if !test_cases.is_empty() {
+        spec_content.push_str(lang.pick(
+            "\n## 測試案例\n依頁面 meta（動作、表單欄位、連結、空/錯誤狀態）產出的 QA 檢查清單請見 `test-cases.md`，結構化版本 `test-cases.json` 可作為測試生成工具的輸入。\n",
+            "\n## Test Cases\nThe QA checklist derived from page meta (actions, form fields, links, empty/error states) is in `test-cases.md`; the structured `test-cases.json` can be fed to test generation tools.\n",
+            "\n## テストケース\nページ meta（アクション、フォームフィールド、リンク、空/エラー状態）から生成した QA チェックリストは `test-cases.md` を参照してください。構造化版の `test-cases.json` はテスト生成ツールの入力として使えます。\n",
+        ));
+        write_text_with_strategy(&output_dir.join("test-cases.md"), &test_cases_markdown(module_name, &test_cases, lang), strategy)?;
+        write_text_with_strategy(&output_dir.join("test-cases.json"), &serde_json::to_string_pretty(&test_cases)?, strategy)?;
+    }
+    let motion = read_module_motion(module_name);
+    if !motion.is_empty() {
+        spec_content.push_str(lang.pick(
+            "\n## 動效\n動效規格（時長、easing、觸發時機）請見 `motion.md`，CSS 以 `--motion-*` 自訂屬性提供預設值。\n",
+            "\n## Motion\nMotion specs (duration, easing, triggers) are in `motion.md`; the CSS provides defaults as `--motion-*` custom properties.\n",
+            "\n## モーション\nモーション仕様（時間、easing、トリガー）は `motion.md` を参照してください。CSS では `--motion-*` カスタムプロパティでデフォルト値を提供しています。\n",
+        ));
+        write_text_with_strategy(&output_dir.join("motion.md"), &motion_markdown(module_name, &motion), strategy)?;
+    }
+    spec_content.push_str(lang.pick(
+        "\n## 結構化規格\n頁面、路由、元件、資產與狀態的機器可讀版本請見 `spec.json`（供程式碼生成工具使用）。\n",
+        "\n## Structured Spec\nA machine-readable version of the pages, routes, components, assets and states is in `spec.json` (for code generation tools).\n",
+        "\n## 構造化仕様\nページ、ルート、コンポーネント、アセット、状態の機械可読版は `spec.json` を参照してください（コード生成ツール向け）。\n",
+    ));
+    write_text_with_strategy(&output_dir.join(SPEC_JSON_FILE), &serde_json::to_string_pretty(&module_spec(module_name))?, strategy)?;
+    let context = serde_json::json!({
+        "module": module_name,
+        "display_name": module_display_name(module_name),
+        "pages": generation_template_pages(module_name),
+        "sections": spec_content,
+    });
     let spec_path = output_dir.join("ai-spec.md");
-    write_text_with_strategy(&spec_path, &spec_content, strategy)?;
+    write_text_with_strategy(&spec_path, &render_generation_template(&lang.template_name("ai-spec.md.hbs"), Some(module_name), &context), strategy)?;
     Ok(())
 }
 
@@ -1048,8 +1801,8 @@ pub async fn get_or_init_default_project() -> Result<ProjectConfig, String> {
         let pdir = projects_root().join(&slug);
         let cfg_path = pdir.join("project.json");
         if cfg_path.exists() {
-            let raw = std::fs::read_to_string(&cfg_path).map_err(|e| format!("讀取 project.json 失敗: {}", e))?;
-            let cfg: ProjectConfig = serde_json::from_str(&raw).map_err(|e| format!("解析 project.json 失敗: {}", e))?;
+            let cfg: ProjectConfig = read_json_with_recovery(&cfg_path)
+                .ok_or_else(|| "解析 project.json 失敗（已隔離損壞檔案，詳見資料事件）".to_string())?;
             return Ok(cfg);
         }
     }
@@ -1071,14 +1824,15 @@ pub async fn get_or_init_default_project() -> Result<ProjectConfig, String> {
             overwrite_strategy_default: Some("overwrite".to_string()),
             mermaid_theme: Some("default".to_string()),
             mermaid_layout_direction: Some("TD".to_string()),
+            ..Default::default()
         };
         if let Err(e) = std::fs::write(&config_path, serde_json::to_string_pretty(&cfg).unwrap()) {
             return Err(format!("寫入 project.json 失敗: {}", e));
         }
         return Ok(cfg);
     }
-    let raw = std::fs::read_to_string(&config_path).map_err(|e| format!("讀取 project.json 失敗: {}", e))?;
-    let cfg: ProjectConfig = serde_json::from_str(&raw).map_err(|e| format!("解析 project.json 失敗: {}", e))?;
+    let cfg: ProjectConfig = read_json_with_recovery(&config_path)
+        .ok_or_else(|| "解析 project.json 失敗（已隔離損壞檔案，詳見資料事件）".to_string())?;
     Ok(cfg)
 }
 
@@ -1091,7 +1845,11 @@ pub async fn update_default_project(config: ProjectConfig) -> Result<ProjectConf
     if let Err(e) = fs::create_dir_all(&projects_root) {
         return Err(format!("建立 projects/default 失敗: {}", e));
     }
+    if let Some(lang) = config.spec_language.as_deref().filter(|l| SpecLanguage::parse(l).is_none()) {
+        return Err(format!("不支援的說明文件語言: {}（可用：{}）", lang, SPEC_LANGUAGES.join(", ")));
+    }
     let config_path = projects_root.join("project.json");
+    backup_json_file(&config_path);
     std::fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap())
         .map_err(|e| format!("寫入 project.json 失敗: {}", e))?;
     Ok(config)
@@ -1112,10 +1870,8 @@ pub async fn list_projects() -> Result<Vec<ProjectListItem>, String> {
                 if let Some(slug) = p.file_name().and_then(|s| s.to_str()) {
                     let cfgp = p.join("project.json");
                     if cfgp.exists() {
-                        if let Ok(raw) = std::fs::read_to_string(&cfgp) {
-                            if let Ok(cfg) = serde_json::from_str::<ProjectConfig>(&raw) {
-                                out.push(ProjectListItem { slug: slug.to_string(), name: cfg.name });
-                            }
+                        if let Some(cfg) = read_json_with_recovery::<ProjectConfig>(&cfgp) {
+                            out.push(ProjectListItem { slug: slug.to_string(), name: cfg.name });
                         }
                     }
                 }
@@ -1140,7 +1896,8 @@ pub async fn create_project(slug: String, name: String) -> Result<ProjectConfig,
     let cfg = ProjectConfig {
         name, slug: slug.clone(), design_assets_root: None, ai_doc_frontend_instructions: None, ai_doc_ui_friendly: None,
         zip_default: true, include_bone_default: false, include_specs_default: false, overwrite_strategy_default: Some("overwrite".into()),
-        mermaid_theme: Some("default".to_string()), mermaid_layout_direction: Some("TD".to_string())
+        mermaid_theme: Some("default".to_string()), mermaid_layout_direction: Some("TD".to_string()),
+        ..Default::default()
     };
     std::fs::write(dir.join("project.json"), serde_json::to_string_pretty(&cfg).unwrap()).map_err(|e| e.to_string())?;
     Ok(cfg)
@@ -1166,9 +1923,42 @@ pub async fn switch_project(slug: String) -> Result<ProjectConfig, String> {
     if !cfgp.exists() { return Err("專案不存在".into()); }
     write_active_slug(&slug)?;
     // 回傳新 active 設定
-    let raw = std::fs::read_to_string(&cfgp).map_err(|e| e.to_string())?;
-    let cfg: ProjectConfig = serde_json::from_str(&raw).map_err(|e| e.to_string())?;
-    Ok(cfg)
+    read_json_with_recovery::<ProjectConfig>(&cfgp).ok_or_else(|| "解析 project.json 失敗（已隔離損壞檔案，詳見資料事件）".to_string())
+}
+
+// 目前 active 專案的資料夾（projects/<slug>）
+fn active_project_dir() -> PathBuf {
+    projects_root().join(read_active_slug().unwrap_or_else(|| "default".to_string()))
+}
+
+// 讀取目前 active 專案的設定（不存在或無法解析時為 None）
+fn read_active_project_config() -> Option<ProjectConfig> {
+    read_json_with_recovery::<ProjectConfig>(&active_project_dir().join("project.json"))
+}
+
+// 生成輸出的根目錄：專案設定的 output_root，未設定時為 ./output
+fn output_root() -> PathBuf {
+    read_active_project_config()
+        .and_then(|c| c.output_root)
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| PathBuf::from(p.trim()))
+        .unwrap_or_else(|| PathBuf::from("output"))
+}
+
+/// 設定目前專案的輸出根目錄（由前端資料夾選擇器取得路徑）；傳入 None 或空字串恢復為 ./output，回傳實際使用的路徑
+#[tauri::command]
+pub async fn set_output_root(path: Option<String>) -> Result<String, String> {
+    let path = path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    if let Some(p) = path.as_ref() {
+        std::fs::create_dir_all(p).map_err(|e| format!("建立輸出目錄失敗: {}", e))?;
+        if !std::path::Path::new(p).is_dir() {
+            return Err(format!("輸出路徑不是資料夾: {}", p));
+        }
+    }
+    let mut cfg = get_or_init_default_project().await?;
+    cfg.output_root = path;
+    update_default_project(cfg).await?;
+    Ok(output_root().to_string_lossy().to_string())
 }
 
 // Helper function to get current Mermaid settings
@@ -1178,13 +1968,11 @@ fn get_mermaid_settings() -> MermaidOptions {
     let slug = read_active_slug().unwrap_or_else(|| "default".to_string());
     let config_path = projects_root.join(&slug).join("project.json");
     
-    if let Ok(raw) = std::fs::read_to_string(&config_path) {
-        if let Ok(cfg) = serde_json::from_str::<ProjectConfig>(&raw) {
-            return MermaidOptions {
-                theme: cfg.mermaid_theme.unwrap_or_else(|| "default".to_string()),
-                layout_direction: cfg.mermaid_layout_direction.unwrap_or_else(|| "TD".to_string())
-            };
-        }
+    if let Some(cfg) = read_json_with_recovery::<ProjectConfig>(&config_path) {
+        return MermaidOptions {
+            theme: cfg.mermaid_theme.unwrap_or_else(|| "default".to_string()),
+            layout_direction: cfg.mermaid_layout_direction.unwrap_or_else(|| "TD".to_string())
+        };
     }
     
     // Fallback defaults
@@ -1204,8 +1992,10 @@ pub async fn get_module_pages(module_name: String) -> Result<Vec<PageInfo>, Stri
     }
     let pages_dir = module_dir.join("pages");
     let mut list: Vec<PageInfo> = Vec::new();
+    let ignore = IgnoreRules::load();
     if let Ok(entries) = fs::read_dir(&pages_dir) {
         for entry in entries.flatten() {
+            if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
             let p = entry.path();
             if p.is_dir() {
                 if let Some(slug) = p.file_name().and_then(|s| s.to_str()) {
@@ -1223,6 +2013,9 @@ pub async fn create_module_page(module_name: String, slug: String) -> Result<Pag
     if !module_dir.exists() { return Err("設計模組不存在".to_string()); }
     if slug.trim().is_empty() { return Err("頁面代稱不可為空".to_string()); }
     if slug.contains('/') { return Err("頁面代稱不可包含 '/'".to_string()); }
+    // 非 ASCII 名稱（如中文）作為顯示名稱，資料夾改用自動產生的代稱
+    let display_name = slug.trim().to_string();
+    let slug = slugify_display_name(&display_name, "page");
     let page_dir = module_dir.join("pages").join(&slug);
     std::fs::create_dir_all(page_dir.join("screenshots")).map_err(|e| format!("建立資料夾失敗: {}", e))?;
     std::fs::create_dir_all(page_dir.join("html")).map_err(|e| format!("建立資料夾失敗: {}", e))?;
@@ -1235,7 +2028,8 @@ pub async fn create_module_page(module_name: String, slug: String) -> Result<Pag
     }
     let meta = serde_json::json!({
         "slug": slug,
-        "title": slug,
+        "display_name": display_name,
+        "title": display_name,
         "path": format!("/{}/{}", module_name, slug),
         "status": "draft",
         "route": format!("/{}/{}", module_name, slug),
@@ -1251,7 +2045,7 @@ pub async fn create_module_page(module_name: String, slug: String) -> Result<Pag
 pub async fn delete_module_page(module_name: String, slug: String) -> Result<String, String> {
     let page_dir = PathBuf::from("design-assets").join(&module_name).join("pages").join(&slug);
     if !page_dir.exists() { return Err("目標頁面不存在".to_string()); }
-    std::fs::remove_dir_all(&page_dir).map_err(|e| format!("刪除頁面失敗: {}", e))?;
+    move_to_trash(&page_dir, "page")?;
     Ok(format!("已刪除頁面: {}", slug))
 }
 
@@ -1302,8 +2096,10 @@ fn build_module_tree_uncached(module_name: &str) -> Result<Vec<PageNode>, String
     if !module_dir.exists() { return Err("設計模組不存在".to_string()); }
     let pages_dir = module_dir.join("pages");
     let mut map_pages: std::collections::BTreeMap<String, PageNode> = std::collections::BTreeMap::new();
+    let ignore = IgnoreRules::load();
     if let Ok(entries) = fs::read_dir(&pages_dir) {
         for entry in entries.flatten() {
+            if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
             let p = entry.path();
             if p.is_dir() {
                 if let Some(slug) = p.file_name().and_then(|s| s.to_str()) {
@@ -1312,12 +2108,14 @@ fn build_module_tree_uncached(module_name: &str) -> Result<Vec<PageNode>, String
                     let sub_dir = p.join("subpages");
                     if let Ok(sub_entries) = fs::read_dir(&sub_dir) {
                         for se in sub_entries.flatten() {
+                            if ignore.matches(&se.file_name().to_string_lossy()) { continue; }
                             let sp = se.path();
                             if sp.is_dir() {
                                 if let Some(ss) = sp.file_name().and_then(|s| s.to_str()) {
                                     let m = read_page_meta(&sp);
                                     children.push(PageNode {
                                         slug: ss.to_string(),
+                                        display_name: page_display_name(&m, ss),
                                         path: m.path.clone().unwrap_or_else(|| format!("/{}/{}/{}", module_name, slug, ss)),
                                         title: m.title.clone(),
                                         status: m.status.clone(),
@@ -1338,6 +2136,7 @@ fn build_module_tree_uncached(module_name: &str) -> Result<Vec<PageNode>, String
                     let m = read_page_meta(&p);
                     map_pages.insert(slug.to_string(), PageNode {
                         slug: slug.to_string(),
+                        display_name: page_display_name(&m, slug),
                         path: m.path.clone().unwrap_or_else(|| format!("/{}/{}", module_name, slug)),
                         title: m.title.clone(),
                         status: m.status.clone(),
@@ -1386,6 +2185,8 @@ fn build_module_tree_uncached(module_name: &str) -> Result<Vec<PageNode>, String
 pub async fn create_subpage(module_name: String, parent_slug: String, slug: String) -> Result<PageInfo, String> {
     if slug.trim().is_empty() { return Err("子頁代稱不可為空".to_string()); }
     if slug.contains('/') { return Err("子頁代稱不可包含 '/'".to_string()); }
+    let display_name = slug.trim().to_string();
+    let slug = slugify_display_name(&display_name, "page");
     let base = PathBuf::from("design-assets").join(&module_name).join("pages").join(&parent_slug).join("subpages").join(&slug);
     std::fs::create_dir_all(base.join("screenshots")).map_err(|e| format!("建立資料夾失敗: {}", e))?;
     std::fs::create_dir_all(base.join("html")).map_err(|e| format!("建立資料夾失敗: {}", e))?;
@@ -1398,7 +2199,8 @@ pub async fn create_subpage(module_name: String, parent_slug: String, slug: Stri
     }
     let meta = serde_json::json!({
         "slug": slug,
-        "title": slug,
+        "display_name": display_name,
+        "title": display_name,
         "path": format!("/{}/{}/{}", module_name, parent_slug, slug),
         "status": "draft",
         "route": format!("/{}/{}/{}", module_name, parent_slug, slug),
@@ -1414,7 +2216,7 @@ pub async fn create_subpage(module_name: String, parent_slug: String, slug: Stri
 pub async fn delete_subpage(module_name: String, parent_slug: String, slug: String) -> Result<String, String> {
     let base = PathBuf::from("design-assets").join(&module_name).join("pages").join(&parent_slug).join("subpages").join(&slug);
     if !base.exists() { return Err("子頁不存在".to_string()); }
-    std::fs::remove_dir_all(&base).map_err(|e| format!("刪除子頁失敗: {}", e))?;
+    move_to_trash(&base, "subpage")?;
     Ok(format!("已刪除子頁: {}", slug))
 }
 
@@ -1522,8 +2324,10 @@ pub async fn generate_project_mermaid() -> Result<MermaidResult, String> {
 
     // 掃描模組、頁面、子頁（尊重 _order.json 排序）
     let mut modules: Vec<String> = Vec::new();
+    let ignore = IgnoreRules::load();
     if let Ok(entries) = fs::read_dir(&root) {
         for entry in entries.flatten() {
+            if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
             let p = entry.path();
             if p.is_dir() {
                 if let Some(name) = p.file_name().and_then(|n| n.to_str()) {
@@ -1536,6 +2340,7 @@ pub async fn generate_project_mermaid() -> Result<MermaidResult, String> {
 
     let mut total_pages = 0usize;
     let mut total_subpages = 0usize;
+    let all_roles = collect_all_roles();
 
     let mut buf = String::new();
     let mermaid_settings = get_mermaid_settings();
@@ -1551,7 +2356,7 @@ pub async fn generate_project_mermaid() -> Result<MermaidResult, String> {
     buf.push_str("  subgraph Modules\n");
     for m in modules.iter() {
         let mid = sanitize_id(m);
-        buf.push_str(&format!("    {}[\"{}\"]\n", mid, m));
+        buf.push_str(&format!("    {}[\"{}\"]\n", mid, mermaid_text(&module_display_name(m))));
         buf.push_str(&format!("  class {} mainModule\n", mid));
     }
     buf.push_str("  end\n");
@@ -1564,6 +2369,7 @@ pub async fn generate_project_mermaid() -> Result<MermaidResult, String> {
         let mut page_slugs: Vec<String> = Vec::new();
         if let Ok(entries) = fs::read_dir(&module_dir) {
             for entry in entries.flatten() {
+                if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
                 let p = entry.path();
                 if p.is_dir() {
                     if let Some(slug) = p.file_name().and_then(|s| s.to_str()) {
@@ -1589,6 +2395,7 @@ pub async fn generate_project_mermaid() -> Result<MermaidResult, String> {
                     pmeta.status.as_ref().map(|s| format!(" ({})", s)).unwrap_or_default(),
                     pmeta.route.as_ref().map(|r| format!("\\n{}", r)).unwrap_or_default())
             } else { format!("/{}/{}", m, pslug) };
+            let p_label = format!("{}{}", with_display_name(&pmeta, pslug, p_label), role_badges(&pmeta, &all_roles));
             buf.push_str(&format!("  {} --> {}[\"{}\"]\n", mid, pid, p_label));
             let pclazz = pmeta.class.clone().unwrap_or_else(|| "pageLevel".into());
             buf.push_str(&format!("  class {} {}\n", pid, pclazz));
@@ -1597,6 +2404,7 @@ pub async fn generate_project_mermaid() -> Result<MermaidResult, String> {
             let sp_dir = module_dir.join(pslug).join("subpages");
             if let Ok(entries) = fs::read_dir(&sp_dir) {
                 for entry in entries.flatten() {
+                    if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
                     let p = entry.path();
                     if p.is_dir() {
                         if let Some(ss) = p.file_name().and_then(|s| s.to_str()) {
@@ -1620,6 +2428,7 @@ pub async fn generate_project_mermaid() -> Result<MermaidResult, String> {
                         smeta.status.as_ref().map(|s| format!(" ({})", s)).unwrap_or_default(),
                         smeta.route.as_ref().map(|r| format!("\\n{}", r)).unwrap_or_default())
                 } else { format!("/{}/{}/{}", m, pslug, sslug) };
+                let s_label = format!("{}{}", with_display_name(&smeta, sslug, s_label), role_badges(&smeta, &all_roles));
                 buf.push_str(&format!("  {} --> {}[\"{}\"]\n", pid, sid, s_label));
                 let sclazz = smeta.class.clone().unwrap_or_else(|| "componentLevel".into());
                 buf.push_str(&format!("  class {} {}\n", sid, sclazz));
@@ -1631,6 +2440,7 @@ pub async fn generate_project_mermaid() -> Result<MermaidResult, String> {
         let module_dir = root.join(m).join("pages");
         if let Ok(entries) = std::fs::read_dir(&module_dir) {
             for entry in entries.flatten() {
+                if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
                 let p = entry.path();
                 if !p.is_dir() { continue; }
                 let pslug = p.file_name().and_then(|s| s.to_str()).unwrap_or("");
@@ -1649,6 +2459,7 @@ pub async fn generate_project_mermaid() -> Result<MermaidResult, String> {
                 let sp_dir = p.join("subpages");
                 if let Ok(sentries) = std::fs::read_dir(&sp_dir) {
                     for se in sentries.flatten() {
+                        if ignore.matches(&se.file_name().to_string_lossy()) { continue; }
                         let sp = se.path();
                         if !sp.is_dir() { continue; }
                         let sslug = sp.file_name().and_then(|s| s.to_str()).unwrap_or("");
@@ -1730,6 +2541,7 @@ pub async fn update_page_meta(module_name: String, slug: String, meta: PageMetaU
     if let Some(v) = meta.class { cur.class = Some(v); }
     if let Some(v) = meta.links { cur.links = Some(v); }
     let s = serde_json::to_string_pretty(&cur).map_err(|e| e.to_string())?;
+    backup_json_file(&p);
     fs::write(p, s).map_err(|e| e.to_string())?;
     Ok("已更新頁面 meta".into())
 }
@@ -1753,6 +2565,7 @@ pub async fn update_subpage_meta(module_name: String, parent_slug: String, slug:
     if let Some(v) = meta.class { cur.class = Some(v); }
     if let Some(v) = meta.links { cur.links = Some(v); }
     let s = serde_json::to_string_pretty(&cur).map_err(|e| e.to_string())?;
+    backup_json_file(&p);
     fs::write(p, s).map_err(|e| e.to_string())?;
     Ok("已更新子頁 meta".into())
 }
@@ -1804,8 +2617,10 @@ pub async fn generate_project_mermaid_html() -> Result<String, String> {
     let cwd = std::env::current_dir().map_err(|e| e.to_string())?;
     let root = cwd.join("design-assets");
     // 掃描 modules/pages/subpages 生成與 generate_project_mermaid 一致的 id
+    let ignore = IgnoreRules::load();
     if let Ok(entries) = std::fs::read_dir(&root) {
         for e in entries.flatten() {
+            if ignore.matches(&e.file_name().to_string_lossy()) { continue; }
             let mpath = e.path();
             if !mpath.is_dir() { continue; }
             let mname = mpath.file_name().and_then(|s| s.to_str()).unwrap_or("");
@@ -1814,6 +2629,7 @@ pub async fn generate_project_mermaid_html() -> Result<String, String> {
             let pages = mpath.join("pages");
             if let Ok(pentries) = std::fs::read_dir(&pages) {
                 for pe in pentries.flatten() {
+                    if ignore.matches(&pe.file_name().to_string_lossy()) { continue; }
                     let ppath = pe.path();
                     if !ppath.is_dir() { continue; }
                     let pslug = ppath.file_name().and_then(|s| s.to_str()).unwrap_or("").to_string();
@@ -1822,6 +2638,7 @@ pub async fn generate_project_mermaid_html() -> Result<String, String> {
                     let sp = ppath.join("subpages");
                     if let Ok(sentries) = std::fs::read_dir(&sp) {
                         for se in sentries.flatten() {
+                            if ignore.matches(&se.file_name().to_string_lossy()) { continue; }
                             let spath = se.path();
                             if !spath.is_dir() { continue; }
                             let sslug = spath.file_name().and_then(|s| s.to_str()).unwrap_or("").to_string();
@@ -1874,7 +2691,7 @@ pub async fn generate_project_mermaid_html() -> Result<String, String> {
 "#, mermaid_settings.theme, links_json, content);
 
     let html_path = mmd_path.parent().unwrap_or_else(|| std::path::Path::new(".")).join("project-sitemap.html");
-    fs::write(&html_path, html).map_err(|e| format!("寫入 HTML 檔案失敗: {}", e))?;
+    fs::write(&html_path, brand_preview_html(&html)).map_err(|e| format!("寫入 HTML 檔案失敗: {}", e))?;
     Ok(html_path.to_string_lossy().to_string())
 }
 
@@ -1898,13 +2715,15 @@ pub async fn generate_project_mermaid_html() -> Result<String, String> {
     buf.push_str("  classDef table fill:#fce4ec,stroke:#e91e63,stroke-width:2px\n");
 
     let mid = sanitize_id(&module);
-    buf.push_str(&format!("  {}[\"{}\"]\n", mid, module));
+    buf.push_str(&format!("  {}[\"{}\"]\n", mid, mermaid_text(&module_display_name(&module))));
     buf.push_str(&format!("  class {} mainModule\n", mid));
 
     let order = load_order(&root.join(&module));
     let mut page_slugs: Vec<String> = Vec::new();
+    let ignore = IgnoreRules::load();
     if let Ok(entries) = fs::read_dir(&mdir) {
         for e in entries.flatten() {
+            if ignore.matches(&e.file_name().to_string_lossy()) { continue; }
             let p = e.path(); if !p.is_dir() { continue; }
             if let Some(s) = p.file_name().and_then(|x| x.to_str()) { page_slugs.push(s.to_string()); }
         }
@@ -1919,6 +2738,7 @@ pub async fn generate_project_mermaid_html() -> Result<String, String> {
         let p_label = if pmeta.status.is_some() || pmeta.route.is_some() {
             format!("/{}/{}{}{}", module, pslug, pmeta.status.as_ref().map(|s| format!(" ({})", s)).unwrap_or_default(), pmeta.route.as_ref().map(|r| format!("\\n{}", r)).unwrap_or_default())
         } else { format!("/{}/{}", module, pslug) };
+        let p_label = with_display_name(&pmeta, pslug, p_label);
         buf.push_str(&format!("  {} --> {}[\"{}\"]\n", mid, pid, p_label));
         let pclazz = pmeta.class.clone().unwrap_or_else(|| "pageLevel".into());
         buf.push_str(&format!("  class {} {}\n", pid, pclazz));
@@ -1927,6 +2747,7 @@ pub async fn generate_project_mermaid_html() -> Result<String, String> {
         let mut subs: Vec<String> = Vec::new();
         if let Ok(sentries) = fs::read_dir(&sp) {
             for se in sentries.flatten() {
+                if ignore.matches(&se.file_name().to_string_lossy()) { continue; }
                 let spath = se.path(); if !spath.is_dir() { continue; }
                 if let Some(s) = spath.file_name().and_then(|x| x.to_str()) { subs.push(s.to_string()); }
             }
@@ -1940,6 +2761,7 @@ pub async fn generate_project_mermaid_html() -> Result<String, String> {
             let s_label = if smeta.status.is_some() || smeta.route.is_some() {
                 format!("/{}/{}/{}{}{}", module, pslug, sslug, smeta.status.as_ref().map(|s| format!(" ({})", s)).unwrap_or_default(), smeta.route.as_ref().map(|r| format!("\\n{}", r)).unwrap_or_default())
             } else { format!("/{}/{}/{}", module, pslug, sslug) };
+            let s_label = with_display_name(&smeta, sslug, s_label);
             buf.push_str(&format!("  {} --> {}[\"{}\"]\n", pid, sid, s_label));
             let sclazz = smeta.class.clone().unwrap_or_else(|| "componentLevel".into());
             buf.push_str(&format!("  class {} {}\n", sid, sclazz));
@@ -1958,7 +2780,7 @@ pub async fn generate_project_mermaid_html() -> Result<String, String> {
   <script type=\"module\">import mermaid from 'https://cdn.jsdelivr.net/npm/mermaid@10/dist/mermaid.esm.min.mjs'; mermaid.initialize({{ startOnLoad: true, theme: '{}' }});</script>
 </head><body><h1>Module Sitemap - {module}</h1><div class=\"mermaid\">{graph}</div></body></html>"#, mermaid_settings.theme, module=module, graph=content);
     let html_path = PathBuf::from("ai-docs").join(format!("module-{}-sitemap.html", sanitize_id(&module)));
-  fs::write(&html_path, html).map_err(|e| e.to_string())?;
+  fs::write(&html_path, brand_preview_html(&html)).map_err(|e| e.to_string())?;
   Ok(html_path.to_string_lossy().to_string())
 }
 
@@ -1989,7 +2811,7 @@ pub async fn generate_module_crud_mermaid_html(module: String) -> Result<String,
     buf.push_str("  classDef form fill:#fff3e0,stroke:#ff9800,stroke-width:2px\n");
 
     // 模組節點
-    buf.push_str(&format!("  {}[\\\"{}\\\"]\n  class {} mainModule\n", mid, module, mid));
+    buf.push_str(&format!("  {}[\\\"{}\\\"]\n  class {} mainModule\n", mid, mermaid_text(&module_display_name(&module)), mid));
 
     // 頁面節點
     let pid_list = format!("{}_{}", mid, safe("list"));
@@ -2048,7 +2870,7 @@ pub async fn generate_module_crud_mermaid_html(module: String) -> Result<String,
   <script type=\"module\">import mermaid from 'https://cdn.jsdelivr.net/npm/mermaid@10/dist/mermaid.esm.min.mjs'; mermaid.initialize({{ startOnLoad: true, theme: '{}' }});</script>
 </head><body><h1>Module CRUD - {module}</h1><div class=\"mermaid\">{graph}</div></body></html>"#, mermaid_settings.theme, module=module, graph=content);
     let html_path = std::path::PathBuf::from("ai-docs").join(format!("module-{}-crud.html", sanitize_id(&module)));
-    fs::write(&html_path, html).map_err(|e| e.to_string())?;
+    fs::write(&html_path, brand_preview_html(&html)).map_err(|e| e.to_string())?;
     Ok(html_path.to_string_lossy().to_string())
 }
 
@@ -2114,7 +2936,7 @@ fn generate_detailed_page_structure(
     }
     
     // Generate modals and overlays
-    generate_modal_flows(buf, pid, &page_type, module, page);
+    generate_modal_flows(buf, pid, &page_type, module, page, pmeta);
     
     Ok(())
 }
@@ -2546,7 +3368,12 @@ fn generate_detailed_subpage_structure(
 }
 
 // Generate modal flows and interactions
-fn generate_modal_flows(buf: &mut String, page_id: &str, page_type: &str, _module: &str, _page: &str) {
+fn generate_modal_flows(buf: &mut String, page_id: &str, page_type: &str, _module: &str, _page: &str, pmeta: &PageMeta) {
+    // 頁面有宣告 overlays 時以宣告為準，不再推測通用對話框
+    if let Some(overlays) = &pmeta.overlays {
+        render_declared_overlays(buf, page_id, overlays);
+        return;
+    }
     match page_type {
         "list" => {
             // Bulk actions confirmation modal
@@ -2609,6 +3436,8 @@ async fn generate_detailed_page_mermaid_html(module: String, page: String) -> Re
     buf.push_str("  classDef dropdown fill:#fff3e0,stroke:#ff5722,stroke-width:2px\n");
     buf.push_str("  classDef notification fill:#e8eaf6,stroke:#3f51b5,stroke-width:2px\n");
     buf.push_str("  classDef loading fill:#f3e5f5,stroke:#673ab7,stroke-width:2px\n");
+    buf.push_str("  classDef drawer fill:#e0f2f1,stroke:#009688,stroke-width:2px\n");
+    buf.push_str("  classDef toast fill:#fffde7,stroke:#fbc02d,stroke-width:2px\n");
 
     let mid = sanitize_id(&module);
     let pid = format!("{}_{}", mid, sanitize_id(&page));
@@ -2620,8 +3449,10 @@ async fn generate_detailed_page_mermaid_html(module: String, page: String) -> Re
     // Enhanced subpages with detailed UI elements
     let sp = pdir.join("subpages");
     if sp.exists() {
+        let ignore = IgnoreRules::load();
         if let Ok(sentries) = std::fs::read_dir(&sp) {
             for se in sentries.flatten() {
+                if ignore.matches(&se.file_name().to_string_lossy()) { continue; }
                 let spath = se.path(); 
                 if !spath.is_dir() { continue; }
                 let sslug = spath.file_name().and_then(|s| s.to_str()).unwrap_or("");
@@ -2657,7 +3488,7 @@ async fn generate_detailed_page_mermaid_html(module: String, page: String) -> Re
   <script type=\"module\">import mermaid from 'https://cdn.jsdelivr.net/npm/mermaid@10/dist/mermaid.esm.min.mjs'; mermaid.initialize({{ startOnLoad: true, theme: '{}' }});</script>
 </head><body><h1>Page Sitemap - {module}/{page}</h1><div class=\"mermaid\">{graph}</div></body></html>"#, mermaid_settings.theme, module=module, page=page, graph=content);
     let html_path = std::path::PathBuf::from("ai-docs").join(format!("page-{}-{}-sitemap.html", sanitize_id(&module), sanitize_id(&page)));
-    fs::write(&html_path, html).map_err(|e| e.to_string())?;
+    fs::write(&html_path, brand_preview_html(&html)).map_err(|e| e.to_string())?;
     Ok(html_path.to_string_lossy().to_string())
 }
 
@@ -2705,8 +3536,10 @@ pub async fn export_sitemap() -> Result<String, String> {
     let root = std::path::PathBuf::from("design-assets");
     let mut modules = Vec::new();
     
+    let ignore = IgnoreRules::load();
     if let Ok(entries) = fs::read_dir(&root) {
         for entry in entries.flatten() {
+            if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
             let module_path = entry.path();
             if !module_path.is_dir() { continue; }
             
@@ -2720,6 +3553,7 @@ pub async fn export_sitemap() -> Result<String, String> {
             
             if let Ok(page_entries) = fs::read_dir(&pages_dir) {
                 for page_entry in page_entries.flatten() {
+                    if ignore.matches(&page_entry.file_name().to_string_lossy()) { continue; }
                     let page_path = page_entry.path();
                     if !page_path.is_dir() { continue; }
                     
@@ -2748,6 +3582,7 @@ pub async fn export_sitemap() -> Result<String, String> {
                     let subpages_dir = page_path.join("subpages");
                     if let Ok(sub_entries) = fs::read_dir(&subpages_dir) {
                         for sub_entry in sub_entries.flatten() {
+                            if ignore.matches(&sub_entry.file_name().to_string_lossy()) { continue; }
                             let sub_path = sub_entry.path();
                             if !sub_path.is_dir() { continue; }
                             
@@ -2910,6 +3745,7 @@ pub struct SitemapAnalytics {
     pub deepest_module: Option<String>,
     pub max_depth: usize,
     pub coverage_metrics: CoverageMetrics,
+    pub pages_missing_states: Vec<String>, // pages without empty-state or error-state definitions
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -2975,8 +3811,10 @@ async fn build_sitemap_analytics_uncached() -> Result<SitemapAnalytics, String>
     let mut pages_with_css = 0;
     let mut modules_completion: std::collections::HashMap<String, ModuleCompletion> = std::collections::HashMap::new();
     
+    let ignore = IgnoreRules::load();
     if let Ok(entries) = fs::read_dir(&root) {
         for entry in entries.flatten() {
+            if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
             let module_path = entry.path();
             if !module_path.is_dir() { continue; }
             
@@ -2993,6 +3831,7 @@ async fn build_sitemap_analytics_uncached() -> Result<SitemapAnalytics, String>
             let pages_dir = module_path.join("pages");
             if let Ok(page_entries) = fs::read_dir(&pages_dir) {
                 for page_entry in page_entries.flatten() {
+                    if ignore.matches(&page_entry.file_name().to_string_lossy()) { continue; }
                     let page_path = page_entry.path();
                     if !page_path.is_dir() { continue; }
                     
@@ -3043,6 +3882,7 @@ async fn build_sitemap_analytics_uncached() -> Result<SitemapAnalytics, String>
                     if let Ok(sub_entries) = fs::read_dir(&subpages_dir) {
                         let mut has_subpages = false;
                         for sub_entry in sub_entries.flatten() {
+                            if ignore.matches(&sub_entry.file_name().to_string_lossy()) { continue; }
                             let sub_path = sub_entry.path();
                             if !sub_path.is_dir() { continue; }
                             
@@ -3136,15 +3976,17 @@ async fn build_sitemap_analytics_uncached() -> Result<SitemapAnalytics, String>
         deepest_module,
         max_depth,
         coverage_metrics,
+        pages_missing_states: pages_missing_state_definitions(),
     })
 }
 
 fn get_files_in_dir(dir: &std::path::Path) -> Vec<String> {
+    let ignore = IgnoreRules::load();
     if let Ok(entries) = std::fs::read_dir(dir) {
         entries.filter_map(|entry| {
             entry.ok().and_then(|e| {
                 let path = e.path();
-                if path.is_file() {
+                if path.is_file() && !ignore.matches(&e.file_name().to_string_lossy()) {
                     path.file_name().and_then(|name| name.to_str()).map(|s| s.to_string())
                 } else {
                     None
@@ -3159,27 +4001,49 @@ fn get_files_in_dir(dir: &std::path::Path) -> Vec<String> {
 // 導出整包：
 // - 複製 design-assets (由 external_root 指定)
 // - 複製兩個 AI 說明文件到 ai-docs/
-// - 為每個模組生成 modules/<module> 下的 index.html/styles.css/ai-spec.md
+// - 為每個模組生成 modules/<module> 下的 index.html/styles.css/ai-spec.md，並依頁面樹生成 modules/<module>/<page>[/<subpage>]
 // - 可選 zip
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn generate_unified_slice_package(
+    app: tauri::AppHandle,
     external_design_assets_root: String,
     ai_doc_frontend_instructions: String,
     ai_doc_ui_friendly: String,
-    include_html: bool,
-    include_css: bool,
-    include_responsive: bool,
-    include_specs: bool,
-    overwrite_strategy: String,
+    include_html: Option<bool>,
+    include_css: Option<bool>,
+    include_responsive: Option<bool>,
+    include_specs: Option<bool>,
+    overwrite_strategy: Option<String>,
     make_zip: bool,
+    archive_format: Option<String>,
+    job_id: Option<String>,
+    include_comparison: Option<bool>,
+    include_print_css: Option<bool>,
+    validate: Option<bool>,
+    stylesheet_dialect: Option<String>,
+    profile: Option<String>,
 ) -> Result<UnifiedPackageResult, String> {
     use chrono::Local;
     use std::fs;
-    let ts = Local::now().format("%Y%m%d-%H%M%S").to_string();
-    let base_output = PathBuf::from("output");
+    let profile = load_generation_profile(profile.as_deref())?.unwrap_or_default();
+    let include_html = include_html.or(profile.include_html).unwrap_or(true);
+    let include_css = include_css.or(profile.include_css).unwrap_or(true);
+    let include_responsive = include_responsive.or(profile.include_responsive).unwrap_or(false);
+    let include_specs = include_specs.or(profile.include_specs).unwrap_or(false);
+    let include_comparison = include_comparison.or(profile.include_comparison).unwrap_or(false);
+    let include_print_css = include_print_css.or(profile.include_print_css).unwrap_or(false);
+    let validate = validate.or(profile.validate).unwrap_or(false);
+    let overwrite_strategy = overwrite_strategy.or(profile.overwrite_strategy.clone()).unwrap_or_else(|| "overwrite".to_string());
+    let archive_format = resolve_archive_format(archive_format.as_deref().or(profile.archive_format.as_deref()), make_zip)?;
+    let dialect = resolve_stylesheet_dialect(stylesheet_dialect.as_deref().or(profile.stylesheet_dialect.as_deref()))?;
+    let started_at = Local::now();
+    let ts = started_at.format("%Y%m%d-%H%M%S").to_string();
+    let base_output = output_root();
     if let Err(e) = fs::create_dir_all(&base_output) { return Err(format!("建立 output 失敗: {}", e)); }
     let out_dir = base_output.join(format!("slice-package-{}", ts));
     if let Err(e) = fs::create_dir_all(&out_dir) { return Err(format!("建立輸出資料夾失敗: {}", e)); }
+    run_generation_hook(HookStage::Pre, &out_dir, None, None)?;
 
     // 1) 複製 design-assets
     let source_assets = PathBuf::from(&external_design_assets_root);
@@ -3206,69 +4070,175 @@ pub async fn generate_unified_slice_package(
     // 3) 為每個模組生成 modules/<module>
     let modules_dir = out_dir.join("modules");
     if let Err(e) = fs::create_dir_all(&modules_dir) { return Err(format!("建立 modules 失敗: {}", e)); }
-    let mut count = 0usize;
+    let mut names: Vec<String> = Vec::new();
+    let ignore = IgnoreRules::load();
     if let Ok(entries) = fs::read_dir(&source_assets) {
         for entry in entries.flatten() {
+            if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
             let path = entry.path();
             if path.is_dir() {
                 if let Some(name) = path.file_name().and_then(|s| s.to_str()) {
-                    let module_out = modules_dir.join(name);
-                    if let Err(e) = fs::create_dir_all(&module_out) { return Err(format!("建立模組資料夾失敗: {}", e)); }
-                    if include_html {
-                        if let Err(e) = generate_html_template_with_strategy(name, &module_out, &overwrite_strategy) { return Err(format!("{}: 生成 HTML 失敗: {}", name, e)); }
-                    }
-                    if include_css {
-                        if let Err(e) = generate_css_styles_with_strategy(name, &module_out, include_responsive, &overwrite_strategy) { return Err(format!("{}: 生成 CSS 失敗: {}", name, e)); }
-                    }
-                    if include_specs {
-                        if let Err(e) = generate_ai_spec_with_strategy(name, &module_out, &overwrite_strategy) { return Err(format!("{}: 生成 AI 說明失敗: {}", name, e)); }
-                    }
-                    count += 1;
+                    names.push(name.to_string());
                 }
             }
         }
     }
-
-    // 4) 生成 README 索引
-    let readme = format!(
-        "# ErSlice 切版說明包\n\n- 設計資產: ./design-assets\n- AI 說明文件: ./ai-docs/ai-frontend-development-instructions.md, ./ai-docs/ai-ui-friendly-documentation-dev.md\n- 模組骨架（每模組）: ./modules/<module>/\n\n此包可直接提供給工程師或 AI 進行切版實作。\n"
-    );
-    if let Err(e) = std::fs::write(out_dir.join("README.md"), readme) {
-        return Err(format!("寫入 README 失敗: {}", e));
+    let job = GenerationJob::start(app, job_id, names.len());
+    // 單一模組輸出 modules/<module>；失敗訊息以模組名稱開頭，不中止其他模組
+    let generate_module = |name: &str, module_out: &PathBuf| -> Result<(), String> {
+        fs::create_dir_all(module_out).map_err(|e| format!("{}: 建立模組資料夾失敗: {}", name, e))?;
+        if include_html {
+            generate_html_template_with_strategy(name, module_out, &overwrite_strategy).map_err(|e| format!("{}: 生成 HTML 失敗: {}", name, e))?;
+        }
+        if include_css {
+            generate_css_styles_with_strategy(name, module_out, include_responsive, include_print_css, dialect, &overwrite_strategy).map_err(|e| format!("{}: 生成 CSS 失敗: {}", name, e))?;
+        }
+        generate_font_assets(name, module_out, &overwrite_strategy).map_err(|e| format!("{}: 生成字型失敗: {}", name, e))?;
+        write_design_tokens_file(&[name.to_string()], &module_out.join(DESIGN_TOKENS_FILE), &overwrite_strategy).map_err(|e| format!("{}: {}", name, e))?;
+        if include_comparison {
+            generate_comparison_pages(name, module_out, &overwrite_strategy).map_err(|e| format!("{}: 生成對照頁失敗: {}", name, e))?;
+        }
+        if include_specs {
+            generate_ai_spec_with_strategy(name, module_out, &overwrite_strategy, include_comparison, include_print_css).map_err(|e| format!("{}: 生成 AI 說明失敗: {}", name, e))?;
+        }
+        // 依頁面樹輸出 modules/<module>/<page>[/<subpage>]，讓輸出包與 sitemap 對應
+        let entries = if include_html || include_css || include_specs { collect_page_entries_in(&source_assets.join(name), name) } else { Vec::new() };
+        let components = scaffold_component_names(&entries);
+        let page_options = PageSkeletonOptions {
+            include_html,
+            include_css,
+            include_responsive,
+            include_spec: include_specs,
+            dialect,
+            strategy: &overwrite_strategy,
+            assets_base: Some(format!("design-assets/{}", name)),
+        };
+        for (entry, component) in entries.iter().zip(components.iter()) {
+            let rel = match &entry.subpage {
+                Some(sub) => format!("{}/{}", entry.page, sub),
+                None => entry.page.clone(),
+            };
+            write_page_skeleton(entry, component, &module_out.join(&rel), &format!("{}/{}", name, rel), &page_options)
+                .map_err(|e| format!("{}/{}: {}", name, rel, e))?;
+        }
+        Ok(())
+    };
+    let mut count = 0usize;
+    let mut failed: Vec<String> = Vec::new();
+    let mut manifest_modules: Vec<ManifestModule> = Vec::new();
+    for name in names.iter().map(|n| n.as_str()) {
+        if job.is_cancelled() { break; }
+        let module_started = Local::now();
+        let module_out = modules_dir.join(name);
+        match generate_module(name, &module_out) {
+            Ok(()) => {
+                count += 1;
+                manifest_modules.push(ManifestModule::completed(name, &module_out, module_started, true));
+            }
+            Err(e) => {
+                manifest_modules.push(ManifestModule::failed(name, &e, module_started));
+                failed.push(e);
+            }
+        }
+        job.advance(name);
+    }
+    let cancelled = job.is_cancelled();
+    let job_id = job.finish();
+    let options = serde_json::json!({
+        "external_design_assets_root": external_design_assets_root,
+        "include_html": include_html,
+        "include_css": include_css,
+        "include_responsive": include_responsive,
+        "include_specs": include_specs,
+        "include_comparison": include_comparison,
+        "include_print_css": include_print_css,
+        "validate": validate,
+        "stylesheet_dialect": format!("{:?}", dialect).to_lowercase(),
+        "overwrite_strategy": overwrite_strategy,
+        "archive_format": format!("{:?}", archive_format).to_lowercase(),
+    });
+    if cancelled {
+        // 未完成的輸出包缺少 README 與校驗碼，直接移除
+        let _ = fs::remove_dir_all(&out_dir);
+        notify_generation_webhook(GenerationWebhookPayload::new("unified", &job_id, started_at, &manifest_modules, None, Vec::new(), true));
+        record_generation_manifest("unified", started_at, options, manifest_modules, None, None);
+        return Ok(UnifiedPackageResult {
+            output_dir: out_dir.to_string_lossy().to_string(),
+            zip_path: None,
+            archive_path: None,
+            modules_count: count,
+            failed,
+            archive_parts: Vec::new(),
+            archive_excluded: Vec::new(),
+            warnings: Vec::new(),
+            job_id,
+            cancelled: true,
+        });
     }
 
-    // 5) zip（可選）
-    let mut zip_path: Option<String> = None;
-    if make_zip {
-        let zip_file = base_output.join(format!("{}.zip", out_dir.file_name().unwrap().to_string_lossy()));
-        #[cfg(target_os = "macos")]
-        {
-            use std::process::Command;
-            let cwd = base_output.clone();
-            let folder_name = out_dir.file_name().unwrap().to_string_lossy().to_string();
-            let status = Command::new("zip")
-                .current_dir(&cwd)
-                .args(["-r", "-q", zip_file.file_name().unwrap().to_str().unwrap(), &folder_name])
-                .status();
-            match status {
-                Ok(s) if s.success() => {
-                    zip_path = Some(zip_file.to_string_lossy().to_string());
-                }
-                Ok(s) => return Err(format!("zip 指令失敗，代碼: {}", s)),
-                Err(e) => return Err(format!("執行 zip 失敗: {}", e)),
+    let packaged: Result<(PackageArchive, Vec<String>), String> = async {
+        // 依專案設定改寫 HTML 資產路徑（以整包為根目錄）
+        apply_asset_path_rewrite(&out_dir)?;
+
+        // 4) 生成 README 索引：每模組的頁面/資產數、完成度與 sitemap 連結
+        let completion = analyze_sitemap().await.map(|a| a.coverage_metrics.modules_completion).unwrap_or_default();
+        let mut rows: Vec<UnifiedReadmeRow> = Vec::new();
+        for name in names.iter() {
+            let module_out = modules_dir.join(name);
+            // 模組 sitemap（僅限目前工作區內的模組）
+            let sitemap = match generate_module_mermaid_html(name.clone()).await {
+                Ok(path) => fs::copy(&path, module_out.join("sitemap.html")).is_ok(),
+                Err(_) => false,
+            };
+            rows.push(unified_readme_row(&source_assets.join(name), name, completion.get(name).map(|c| c.completion_rate), include_specs, sitemap));
+        }
+        let doc_names: Vec<String> = [&ai_doc_frontend_instructions, &ai_doc_ui_friendly]
+            .iter()
+            .filter_map(|d| std::path::Path::new(d.as_str()).file_name().map(|n| n.to_string_lossy().to_string()))
+            .collect();
+        let readme = unified_readme(&started_at.format("%Y-%m-%d %H:%M").to_string(), &doc_names, &rows);
+        write_text_with_strategy(&out_dir.join("README.md"), &readme, &overwrite_strategy).map_err(|e| format!("寫入 README 失敗: {}", e))?;
+        write_scaffold_seed_files(&out_dir, &overwrite_strategy)?;
+        apply_branding(&out_dir)?;
+        apply_output_formatting(&out_dir, resolve_output_formatting(profile.output_formatting.as_deref())?, started_at.into())?;
+        let mut warnings: Vec<String> = Vec::new();
+        if validate {
+            for name in names.iter() {
+                warnings.extend(validate_generated_output(&modules_dir.join(name)).into_iter().map(|w| format!("{}: {}", name, w)));
             }
         }
-        #[cfg(not(target_os = "macos"))]
-        {
-            // 非 macOS 環境暫不壓縮，回傳資料夾成功
-            zip_path = None;
+        write_package_checksums(&out_dir)?;
+
+        // 5) 封存（zip / tar.gz，可選）
+        let archive = write_package_archive(&out_dir, &base_output, archive_format)?.unwrap_or_default();
+        run_generation_hook(HookStage::Post, &out_dir, None, archive.primary().as_deref())?;
+        Ok((archive, warnings))
+    }
+    .await;
+    let (archive, warnings) = match packaged {
+        Ok(packaged) => packaged,
+        Err(e) => {
+            notify_generation_webhook(GenerationWebhookPayload::new("unified", &job_id, started_at, &manifest_modules, Some(&out_dir), Vec::new(), false).with_failure(e.clone()));
+            record_generation_manifest("unified", started_at, options, manifest_modules, Some(&out_dir), None);
+            return Err(e);
         }
-    }
+    };
+    let archive_path = archive.primary();
+    let zip_path = if archive_format == ArchiveFormat::Zip { archive_path.clone() } else { None };
+    notify_generation_webhook(GenerationWebhookPayload::new("unified", &job_id, started_at, &manifest_modules, Some(&out_dir), archive.part_paths(), false));
+    record_generation_manifest("unified", started_at, options, manifest_modules, Some(&out_dir), archive_path.as_deref());
 
     Ok(UnifiedPackageResult {
         output_dir: out_dir.to_string_lossy().to_string(),
         zip_path,
+        archive_path,
         modules_count: count,
+        failed,
+        archive_parts: archive.part_paths(),
+        archive_excluded: archive.excluded,
+        warnings,
+        job_id,
+        cancelled: false,
     })
 }
 
@@ -3282,6 +4252,9 @@ pub async fn list_assets(asset_path: String) -> Result<AssetList, String> {
             screenshots: Vec::new(),
             html: Vec::new(),
             css: Vec::new(),
+            fonts: Vec::new(),
+            custom: Default::default(),
+            screenshot_variants: Default::default(),
         });
     }
 
@@ -3289,12 +4262,17 @@ pub async fn list_assets(asset_path: String) -> Result<AssetList, String> {
         screenshots: Vec::new(),
         html: Vec::new(),
         css: Vec::new(),
+        fonts: Vec::new(),
+        custom: Default::default(),
+        screenshot_variants: Default::default(),
     };
 
+    let ignore = IgnoreRules::load();
     let read_dir = |sub: &str, vec: &mut Vec<String>| {
         let p = base_dir.join(sub);
         if let Ok(entries) = std::fs::read_dir(&p) {
             for entry in entries.flatten() {
+                if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
                 let path = entry.path();
                 if path.is_file() {
                     if let Some(file_name) = path.file_name() {
@@ -3307,9 +4285,30 @@ pub async fn list_assets(asset_path: String) -> Result<AssetList, String> {
         }
     };
 
+    let order = read_asset_order(&base_dir);
     read_dir("screenshots", &mut result.screenshots);
     read_dir("html", &mut result.html);
     read_dir("css", &mut result.css);
+    read_dir("fonts", &mut result.fonts);
+    apply_asset_order(&mut result.screenshots, order.get("screenshots"));
+    apply_asset_order(&mut result.html, order.get("html"));
+    apply_asset_order(&mut result.css, order.get("css"));
+    apply_asset_order(&mut result.fonts, order.get("fonts"));
+    for category in asset_categories().into_iter().skip(ASSET_TYPES.len()) {
+        let mut files = Vec::new();
+        read_dir(&category, &mut files);
+        apply_asset_order(&mut files, order.get(&category));
+        result.custom.insert(category, files);
+    }
+    for bp in configured_breakpoints() {
+        let mut files = Vec::new();
+        read_dir(&format!("screenshots/{}", bp.name), &mut files);
+        // 斷點變體沿用同名截圖的順序
+        apply_asset_order(&mut files, order.get("screenshots"));
+        if !files.is_empty() {
+            result.screenshot_variants.insert(bp.name, files);
+        }
+    }
 
     Ok(result)
 }
@@ -3326,22 +4325,78 @@ pub async fn delete_design_asset(
         return Err("資產路徑不存在".to_string());
     }
 
-    let target_dir = match asset_type.as_str() {
-        "screenshots" => base_dir.join("screenshots"),
-        "html" => base_dir.join("html"),
-        "css" => base_dir.join("css"),
-        _ => return Err("不支援的資產類型".to_string()),
-    };
+    let target_dir = asset_type_dir(&base_dir, &asset_type)?;
 
     let target_path = target_dir.join(&file_name);
     if !target_path.exists() {
         return Err("檔案不存在".to_string());
     }
 
-    std::fs::remove_file(&target_path)
-        .map_err(|e| format!("刪除檔案失敗: {}", e))?;
+    move_to_trash(&target_path, "asset")?;
+    move_asset_tags(&base_dir, &format!("{}/{}", asset_type, file_name), None);
+
+    Ok(format!("已移至回收桶: {}", target_path.display()))
+}
+
+// 重新命名指定資產（同目錄內）
+#[tauri::command]
+pub async fn rename_design_asset(
+    asset_path: String,
+    asset_type: String,
+    from_name: String,
+    to_name: String,
+) -> Result<String, String> {
+    let base_dir = PathBuf::from("design-assets").join(&asset_path);
+    if !base_dir.exists() {
+        return Err("資產路徑不存在".to_string());
+    }
+
+    let target_dir = asset_type_dir(&base_dir, &asset_type)?;
+
+    let to_name = to_name.trim().to_string();
+    if !is_plain_file_name(&to_name) {
+        return Err("新檔名無效".to_string());
+    }
+    if !is_plain_file_name(&from_name) {
+        return Err("原檔名無效".to_string());
+    }
+    let from_path = target_dir.join(&from_name);
+    if !from_path.is_file() {
+        return Err("檔案不存在".to_string());
+    }
+    let to_path = target_dir.join(&to_name);
+    // 目標已存在時，只有在不分大小寫的檔案系統上指向同一個檔案（僅大小寫不同）才允許
+    if to_path.exists() && !is_same_file(&from_path, &to_path) {
+        return Err(format!("目標檔名已存在: {}", to_name));
+    }
+
+    std::fs::rename(&from_path, &to_path)
+        .map_err(|e| format!("重新命名失敗: {}", e))?;
+    move_asset_tags(&base_dir, &format!("{}/{}", asset_type, from_name), Some((&base_dir, format!("{}/{}", asset_type, to_name))));
+
+    if let Some(module_name) = asset_path.split('/').next() {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(module_name);
+    }
+
+    Ok(format!("已重新命名: {}", to_path.display()))
+}
+
+// 兩個路徑是否指向同一個檔案（不分大小寫的檔案系統上僅大小寫不同的路徑）
+#[cfg(unix)]
+fn is_same_file(a: &std::path::Path, b: &std::path::Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    matches!((std::fs::metadata(a), std::fs::metadata(b)), (Ok(x), Ok(y)) if x.dev() == y.dev() && x.ino() == y.ino())
+}
+
+#[cfg(not(unix))]
+fn is_same_file(a: &std::path::Path, b: &std::path::Path) -> bool {
+    matches!((a.canonicalize(), b.canonicalize()), (Ok(x), Ok(y)) if x == y)
+}
 
-    Ok(format!("已刪除: {}", target_path.display()))
+// 單一檔名：不可為空、不含路徑分隔符，也不可為 . 或 ..
+fn is_plain_file_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains('/') && !name.contains('\\') && name != "." && name != ".."
 }
 
 // 封存模組（移動至 design-assets-archived）
@@ -3368,8 +4423,7 @@ pub async fn delete_design_module(module_name: String) -> Result<String, String>
     if !module_dir.exists() {
         return Err("設計模組不存在".to_string());
     }
-    std::fs::remove_dir_all(&module_dir)
-        .map_err(|e| format!("刪除模組失敗: {}", e))?;
+    move_to_trash(&module_dir, "module")?;
     Ok(format!("已刪除模組: {}", module_name))
 }
 
@@ -3484,7 +4538,7 @@ pub async fn generate_user_workflow_mermaid_html(module: String) -> Result<Strin
 </head><body><h1>📊 User Workflow - {module} Module</h1><p>Complete user journey and interaction flows</p><div class="mermaid">{graph}</div></body></html>"#, mermaid_settings.theme, module=module, graph=content);
     
     let html_path = std::path::PathBuf::from("ai-docs").join(format!("workflow-{}-user-journey.html", sanitize_id(&module)));
-    fs::write(&html_path, html).map_err(|e| e.to_string())?;
+    fs::write(&html_path, brand_preview_html(&html)).map_err(|e| e.to_string())?;
     Ok(html_path.to_string_lossy().to_string())
 }
 
@@ -3858,3 +4912,11196 @@ pub async fn delete_ai_spec_from_db(id: String) -> Result<String, String> {
         Err(e) => Err(format!("刪除 AI 規格失敗: {}", e))
     }
 }
+
+// ====== Navigation model（導覽選單） ======
+
+const NAV_SURFACES: [&str; 3] = ["main", "footer", "user_menu"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NavPlacement {
+    pub surface: String,
+    pub order: Option<i32>,
+    pub label: Option<String>,
+    pub icon: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NavItem {
+    pub id: String,
+    pub label: String,
+    pub route: String,
+    pub module: String,
+    pub order: i32,
+    pub icon: Option<String>,
+    pub children: Vec<NavItem>,
+}
+
+/// 設定頁面（或子頁）所屬的導覽區塊與排序；傳入空陣列即移出所有導覽
+#[tauri::command]
+pub async fn set_page_navigation(module_name: String, parent_slug: Option<String>, slug: String, placements: Vec<NavPlacement>) -> Result<String, String> {
+    let dir = resolve_page_dir(&module_name, parent_slug.as_deref(), &slug);
+    if !dir.exists() { return Err("頁面不存在".into()); }
+    for p in placements.iter() {
+        if !NAV_SURFACES.contains(&p.surface.as_str()) {
+            return Err(format!("不支援的導覽區塊: {}（可用: {}）", p.surface, NAV_SURFACES.join(", ")));
+        }
+    }
+    let mut meta = read_page_meta(&dir);
+    meta.nav = if placements.is_empty() { None } else { Some(placements) };
+    write_page_meta(&dir, &meta)?;
+    {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(&module_name);
+    }
+    Ok("已更新導覽設定".into())
+}
+
+fn build_navigation_tree() -> std::collections::BTreeMap<String, Vec<NavItem>> {
+    let entries = collect_page_entries();
+    let mut surfaces: std::collections::BTreeMap<String, Vec<NavItem>> = std::collections::BTreeMap::new();
+    for s in NAV_SURFACES.iter() { surfaces.insert(s.to_string(), Vec::new()); }
+
+    // 先放頁面，再把子頁掛到同區塊的父頁底下（父頁不在該區塊時放在頂層）
+    for pass_subpages in [false, true] {
+        for e in entries.iter().filter(|e| e.subpage.is_some() == pass_subpages) {
+            let placements = match &e.meta.nav { Some(v) => v, None => continue };
+            for pl in placements.iter() {
+                let fallback_label = e.subpage.clone().unwrap_or_else(|| e.page.clone());
+                let id = match &e.subpage {
+                    Some(sub) => format!("{}_{}_{}", sanitize_id(&e.module), sanitize_id(&e.page), sanitize_id(sub)),
+                    None => format!("{}_{}", sanitize_id(&e.module), sanitize_id(&e.page)),
+                };
+                let item = NavItem {
+                    id,
+                    label: pl.label.clone().or_else(|| e.meta.title.clone()).unwrap_or(fallback_label),
+                    route: e.meta.route.clone().unwrap_or_else(|| e.route_path()),
+                    module: e.module.clone(),
+                    order: pl.order.unwrap_or(i32::MAX),
+                    icon: pl.icon.clone(),
+                    children: Vec::new(),
+                };
+                let list = surfaces.entry(pl.surface.clone()).or_default();
+                let parent_id = format!("{}_{}", sanitize_id(&e.module), sanitize_id(&e.page));
+                match list.iter_mut().find(|i| pass_subpages && i.id == parent_id) {
+                    Some(parent) => parent.children.push(item),
+                    None => list.push(item),
+                }
+            }
+        }
+    }
+    for list in surfaces.values_mut() {
+        list.sort_by_key(|i| i.order);
+        for item in list.iter_mut() { item.children.sort_by_key(|c| c.order); }
+    }
+    surfaces
+}
+
+/// 導出導覽模型：format 為 "json"（ai-docs/nav.json）或 "ts"（ai-docs/nav.config.ts）
+#[tauri::command]
+pub async fn export_navigation(format: String) -> Result<String, String> {
+    use std::fs;
+    let surfaces = build_navigation_tree();
+    let ai_docs = PathBuf::from("ai-docs");
+    fs::create_dir_all(&ai_docs).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&surfaces).map_err(|e| e.to_string())?;
+    let (path, content) = match format.as_str() {
+        "json" => (ai_docs.join("nav.json"), json),
+        "ts" | "typescript" => {
+            let surface_union = NAV_SURFACES.iter().map(|s| format!("'{}'", s)).collect::<Vec<_>>().join(" | ");
+            let content = format!(
+                "// Auto-generated by ErSlice - 請勿手動修改，改由 ErSlice 的頁面導覽設定維護\n\nexport type NavSurface = {};\n\nexport interface NavItem {{\n  id: string\n  label: string\n  route: string\n  module: string\n  order: number\n  icon: string | null\n  children: NavItem[]\n}}\n\nexport const navigation: Record<NavSurface, NavItem[]> = {};\n\nexport default navigation\n",
+                surface_union, json
+            );
+            (ai_docs.join("nav.config.ts"), content)
+        }
+        _ => return Err("不支援的格式（可用: json, ts）".into()),
+    };
+    fs::write(&path, content).map_err(|e| format!("寫入導覽檔案失敗: {}", e))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+// ====== 角色權限矩陣 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PageAccess {
+    // 可存取此頁面的角色
+    pub roles: Vec<String>,
+    // 頁面內動作（如 create / delete / export）各自允許的角色
+    pub actions: std::collections::BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RoleCoverage {
+    pub role: String,
+    pub pages: usize,
+    pub actions: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RoleCoverageReport {
+    pub total_pages: usize,
+    pub roles: Vec<RoleCoverage>,
+    pub pages_without_roles: Vec<String>,
+    pub actions_without_roles: Vec<String>,
+}
+
+// 角色徽章顏色（依角色名稱排序後循環使用）
+const ROLE_BADGE_COLORS: [&str; 8] = ["🔴", "🟠", "🟡", "🟢", "🔵", "🟣", "🟤", "⚫"];
+
+fn collect_all_roles() -> Vec<String> {
+    let mut roles: std::collections::BTreeSet<String> = std::collections::BTreeSet::new();
+    for e in collect_page_entries() {
+        if let Some(access) = &e.meta.access {
+            roles.extend(access.roles.iter().cloned());
+            for rs in access.actions.values() { roles.extend(rs.iter().cloned()); }
+        }
+    }
+    roles.into_iter().collect()
+}
+
+fn role_badges(meta: &PageMeta, all_roles: &[String]) -> String {
+    let access = match &meta.access { Some(a) if !a.roles.is_empty() => a, _ => return String::new() };
+    let badges: Vec<String> = access.roles.iter().map(|r| {
+        let idx = all_roles.iter().position(|x| x == r).unwrap_or(0);
+        format!("{}{}", ROLE_BADGE_COLORS[idx % ROLE_BADGE_COLORS.len()], r)
+    }).collect();
+    format!("\\n{}", badges.join(" "))
+}
+
+/// 設定頁面（或子頁）可存取的角色，以及各動作允許的角色
+#[tauri::command]
+pub async fn set_page_roles(
+    module_name: String,
+    parent_slug: Option<String>,
+    slug: String,
+    roles: Vec<String>,
+    actions: Option<std::collections::BTreeMap<String, Vec<String>>>,
+) -> Result<String, String> {
+    let dir = resolve_page_dir(&module_name, parent_slug.as_deref(), &slug);
+    if !dir.exists() { return Err("頁面不存在".into()); }
+    // 保留輸入順序並去除重複（不相鄰的重複也會移除）
+    let clean = |v: Vec<String>| -> Vec<String> {
+        let mut seen = std::collections::HashSet::new();
+        v.into_iter().map(|r| r.trim().to_string()).filter(|r| !r.is_empty() && seen.insert(r.clone())).collect()
+    };
+    let access = PageAccess {
+        roles: clean(roles),
+        actions: actions.unwrap_or_default().into_iter().map(|(k, v)| (k, clean(v))).collect(),
+    };
+    let mut meta = read_page_meta(&dir);
+    meta.access = if access.roles.is_empty() && access.actions.is_empty() { None } else { Some(access) };
+    write_page_meta(&dir, &meta)?;
+    {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(&module_name);
+    }
+    Ok("已更新頁面角色權限".into())
+}
+
+/// 角色覆蓋報告：每個角色可存取的頁面/動作數，以及尚未定義角色的頁面
+#[tauri::command]
+pub async fn get_role_coverage() -> Result<RoleCoverageReport, String> {
+    let entries = collect_page_entries();
+    let all_roles = collect_all_roles();
+    let mut pages_without_roles = Vec::new();
+    let mut actions_without_roles = Vec::new();
+    for e in entries.iter() {
+        match &e.meta.access {
+            Some(a) => {
+                if a.roles.is_empty() { pages_without_roles.push(e.route_path()); }
+                for (action, rs) in a.actions.iter() {
+                    if rs.is_empty() { actions_without_roles.push(format!("{}#{}", e.route_path(), action)); }
+                }
+            }
+            None => pages_without_roles.push(e.route_path()),
+        }
+    }
+    let roles = all_roles.iter().map(|role| {
+        let pages = entries.iter().filter(|e| e.meta.access.as_ref().is_some_and(|a| a.roles.contains(role))).count();
+        let actions = entries.iter()
+            .filter_map(|e| e.meta.access.as_ref())
+            .map(|a| a.actions.values().filter(|rs| rs.contains(role)).count())
+            .sum();
+        RoleCoverage { role: role.clone(), pages, actions }
+    }).collect();
+    Ok(RoleCoverageReport { total_pages: entries.len(), roles, pages_without_roles, actions_without_roles })
+}
+
+/// 生成 ai-docs/access-matrix.md（頁面 × 角色）
+#[tauri::command]
+pub async fn generate_access_matrix() -> Result<String, String> {
+    use std::fs;
+    let entries = collect_page_entries();
+    let all_roles = collect_all_roles();
+    let mut md = String::new();
+    md.push_str("# Access Matrix\n\n");
+    md.push_str(&format!("> Auto-generated by ErSlice · {}\n\n", chrono::Local::now().format("%Y-%m-%d %H:%M")));
+    if all_roles.is_empty() {
+        md.push_str("尚未為任何頁面設定角色。\n");
+    } else {
+        md.push_str("## 頁面存取\n\n");
+        md.push_str(&format!("| 頁面 | {} |\n", all_roles.join(" | ")));
+        md.push_str(&format!("|---|{}\n", "---|".repeat(all_roles.len())));
+        for e in entries.iter() {
+            let cells: Vec<&str> = all_roles.iter().map(|r| {
+                if e.meta.access.as_ref().is_some_and(|a| a.roles.contains(r)) { "✅" } else { "—" }
+            }).collect();
+            md.push_str(&format!("| `{}` | {} |\n", e.route_path(), cells.join(" | ")));
+        }
+        let with_actions: Vec<&PageEntry> = entries.iter().filter(|e| e.meta.access.as_ref().is_some_and(|a| !a.actions.is_empty())).collect();
+        if !with_actions.is_empty() {
+            md.push_str("\n## 動作權限\n\n");
+            md.push_str(&format!("| 頁面 | 動作 | {} |\n", all_roles.join(" | ")));
+            md.push_str(&format!("|---|---|{}\n", "---|".repeat(all_roles.len())));
+            for e in with_actions {
+                if let Some(access) = &e.meta.access {
+                    for (action, rs) in access.actions.iter() {
+                        let cells: Vec<&str> = all_roles.iter().map(|r| if rs.contains(r) { "✅" } else { "—" }).collect();
+                        md.push_str(&format!("| `{}` | {} | {} |\n", e.route_path(), action, cells.join(" | ")));
+                    }
+                }
+            }
+        }
+    }
+    let path = PathBuf::from("ai-docs").join("access-matrix.md");
+    fs::create_dir_all(path.parent().unwrap()).map_err(|e| e.to_string())?;
+    fs::write(&path, md).map_err(|e| format!("寫入權限矩陣失敗: {}", e))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+// ====== Feature flags ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeatureFlagUsage {
+    pub flag: String,
+    pub pages: Vec<String>,
+}
+
+fn collect_feature_flags() -> Vec<FeatureFlagUsage> {
+    let mut map: std::collections::BTreeMap<String, Vec<String>> = std::collections::BTreeMap::new();
+    for e in collect_page_entries() {
+        if let Some(flag) = e.meta.feature_flag.as_ref().filter(|f| !f.trim().is_empty()) {
+            map.entry(flag.clone()).or_default().push(e.route_path());
+        }
+    }
+    map.into_iter().map(|(flag, pages)| FeatureFlagUsage { flag, pages }).collect()
+}
+
+// ai-spec 的 Feature Flags 區段；模組內沒有任何旗標時回傳空字串
+fn feature_flags_spec_section(module_name: &str) -> String {
+    let flagged: Vec<PageEntry> = collect_module_page_entries(module_name)
+        .into_iter()
+        .filter(|e| e.meta.feature_flag.as_ref().is_some_and(|f| !f.trim().is_empty()))
+        .collect();
+    if flagged.is_empty() { return String::new(); }
+    let lang = project_spec_language();
+    let mut out = String::from(lang.pick(
+        "\n## Feature Flags\n以下頁面位於功能旗標之後，實作時需以旗標包覆路由與入口：\n\n| 頁面 | 旗標 |\n|---|---|\n",
+        "\n## Feature Flags\nThe following pages sit behind feature flags; wrap their routes and entry points with the flag when implementing:\n\n| Page | Flag |\n|---|---|\n",
+        "\n## Feature Flags\n以下のページは機能フラグの背後にあります。実装時はルートと入口をフラグで囲んでください：\n\n| ページ | フラグ |\n|---|---|\n",
+    ));
+    for e in flagged.iter() {
+        out.push_str(&format!("| `{}` | `{}` |\n", e.route_path(), e.meta.feature_flag.clone().unwrap_or_default()));
+    }
+    out
+}
+
+/// 設定頁面（或子頁）的功能旗標；flag 為 None 或空字串時移除
+#[tauri::command]
+pub async fn set_page_feature_flag(module_name: String, parent_slug: Option<String>, slug: String, flag: Option<String>) -> Result<String, String> {
+    let dir = resolve_page_dir(&module_name, parent_slug.as_deref(), &slug);
+    if !dir.exists() { return Err("頁面不存在".into()); }
+    let flag = flag.map(|f| f.trim().to_string()).filter(|f| !f.is_empty());
+    if let Some(f) = &flag {
+        if f.chars().any(|c| c.is_whitespace()) { return Err("旗標名稱不可包含空白".into()); }
+    }
+    let mut meta = read_page_meta(&dir);
+    meta.feature_flag = flag;
+    write_page_meta(&dir, &meta)?;
+    {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(&module_name);
+    }
+    Ok("已更新功能旗標".into())
+}
+
+/// 列出所有功能旗標與使用該旗標的頁面
+#[tauri::command]
+pub async fn list_feature_flags() -> Result<Vec<FeatureFlagUsage>, String> {
+    Ok(collect_feature_flags())
+}
+
+/// 導出功能旗標 JSON 供旗標服務匯入；target 為 "launchdarkly"、"unleash" 或預設的通用格式
+#[tauri::command]
+pub async fn export_feature_flags(target: Option<String>) -> Result<String, String> {
+    use std::fs;
+    let flags = collect_feature_flags();
+    let describe = |f: &FeatureFlagUsage| format!("ErSlice pages: {}", f.pages.join(", "));
+    let (file_name, value) = match target.as_deref().unwrap_or("generic") {
+        "launchdarkly" => ("feature-flags.launchdarkly.json", serde_json::json!({
+            "items": flags.iter().map(|f| serde_json::json!({
+                "key": f.flag,
+                "name": f.flag,
+                "description": describe(f),
+                "kind": "boolean",
+                "variations": [{ "value": true }, { "value": false }],
+                "tags": ["erslice"],
+            })).collect::<Vec<_>>(),
+        })),
+        "unleash" => ("feature-flags.unleash.json", serde_json::json!({
+            "version": 1,
+            "features": flags.iter().map(|f| serde_json::json!({
+                "name": f.flag,
+                "description": describe(f),
+                "type": "release",
+                "enabled": false,
+            })).collect::<Vec<_>>(),
+        })),
+        "generic" => ("feature-flags.json", serde_json::json!({
+            "generated_at": chrono::Utc::now().to_rfc3339(),
+            "flags": flags.iter().map(|f| serde_json::json!({
+                "key": f.flag,
+                "default": false,
+                "pages": f.pages,
+            })).collect::<Vec<_>>(),
+        })),
+        other => return Err(format!("不支援的導出目標: {}", other)),
+    };
+    let path = PathBuf::from("ai-docs").join(file_name);
+    fs::create_dir_all(path.parent().unwrap()).map_err(|e| e.to_string())?;
+    fs::write(&path, serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?)
+        .map_err(|e| format!("寫入旗標檔案失敗: {}", e))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+// ====== 從執行中的網站批量擷取截圖 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CaptureTarget {
+    pub url: String,
+    pub module_name: String,
+    pub parent_slug: Option<String>,
+    pub slug: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CaptureResult {
+    pub url: String,
+    pub page_path: String,
+    pub breakpoint: String,
+    pub file: Option<String>,
+    pub error: Option<String>,
+}
+
+fn default_breakpoints() -> Vec<Breakpoint> {
+    vec![
+        Breakpoint { name: "desktop".into(), width: 1440, height: 900 },
+        Breakpoint { name: "tablet".into(), width: 768, height: 1024 },
+        Breakpoint { name: "mobile".into(), width: 375, height: 812 },
+    ]
+}
+
+fn configured_breakpoints() -> Vec<Breakpoint> {
+    read_active_project_config()
+        .and_then(|c| c.screenshot_breakpoints)
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(default_breakpoints)
+}
+
+// 以無頭瀏覽器擷取單一 URL 截圖至 dest
+fn capture_url_screenshot(url: &str, bp: &Breakpoint, dest: &std::path::Path) -> Result<(), String> {
+    use std::process::Command;
+    if !(url.starts_with("http://") || url.starts_with("https://") || url.starts_with("file://")) {
+        return Err(format!("不支援的 URL: {}", url));
+    }
+    if let Some(parent) = dest.parent() { std::fs::create_dir_all(parent).map_err(|e| e.to_string())?; }
+    let cfg = read_active_project_config();
+    let backend = cfg.as_ref().and_then(|c| c.screenshot_backend.clone()).unwrap_or_else(|| "playwright".into());
+    let output = match backend.as_str() {
+        "chrome" => {
+            let chrome = cfg.as_ref().and_then(|c| c.chrome_path.clone())
+                .or_else(|| std::env::var("CHROME_PATH").ok())
+                .unwrap_or_else(|| "google-chrome".into());
+            Command::new(chrome)
+                .args(["--headless=new", "--disable-gpu", "--hide-scrollbars"])
+                .arg(format!("--window-size={},{}", bp.width, bp.height))
+                .arg(format!("--screenshot={}", dest.display()))
+                .arg(url)
+                .output()
+        }
+        "playwright" => {
+            let npx = if cfg!(target_os = "windows") { "npx.cmd" } else { "npx" };
+            Command::new(npx)
+                .args(["--yes", "playwright", "screenshot", "--full-page"])
+                .arg(format!("--viewport-size={}, {}", bp.width, bp.height))
+                .arg(url)
+                .arg(dest)
+                .output()
+        }
+        other => return Err(format!("不支援的截圖工具: {}", other)),
+    };
+    match output {
+        Ok(o) if o.status.success() && dest.exists() => Ok(()),
+        Ok(o) => Err(format!("截圖失敗（{}）: {}", backend, String::from_utf8_lossy(&o.stderr).trim())),
+        Err(e) => Err(format!("無法執行截圖工具 {}: {}", backend, e)),
+    }
+}
+
+/// 依對照表擷取網址截圖（每個斷點一張），存入對應頁面 screenshots/current-<breakpoint>.png
+#[tauri::command]
+pub async fn capture_screenshots_from_urls(mapping: Vec<CaptureTarget>) -> Result<Vec<CaptureResult>, String> {
+    let breakpoints = configured_breakpoints();
+    let mut results: Vec<CaptureResult> = Vec::new();
+    for target in mapping.iter() {
+        let dir = resolve_page_dir(&target.module_name, target.parent_slug.as_deref(), &target.slug);
+        let page_path = match &target.parent_slug {
+            Some(parent) => format!("/{}/{}/{}", target.module_name, parent, target.slug),
+            None => format!("/{}/{}", target.module_name, target.slug),
+        };
+        for bp in breakpoints.iter() {
+            if !dir.exists() {
+                results.push(CaptureResult { url: target.url.clone(), page_path: page_path.clone(), breakpoint: bp.name.clone(), file: None, error: Some("頁面不存在".into()) });
+                continue;
+            }
+            let dest = dir.join("screenshots").join(format!("current-{}.png", sanitize_id(&bp.name)));
+            match capture_url_screenshot(&target.url, bp, &dest) {
+                Ok(()) => results.push(CaptureResult { url: target.url.clone(), page_path: page_path.clone(), breakpoint: bp.name.clone(), file: Some(dest.to_string_lossy().to_string()), error: None }),
+                Err(e) => results.push(CaptureResult { url: target.url.clone(), page_path: page_path.clone(), breakpoint: bp.name.clone(), file: None, error: Some(e) }),
+            }
+        }
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(&target.module_name);
+    }
+    Ok(results)
+}
+
+// ====== 前後對照（current vs target）切版包 ======
+
+const IMAGE_EXTS: [&str; 6] = ["png", "jpg", "jpeg", "webp", "gif", "svg"];
+
+#[derive(Debug, Clone)]
+struct ComparisonPair {
+    entry: PageEntry,
+    label: String,
+    // 相對於模組目錄的路徑（切版包內沿用同一結構）
+    current: String,
+    target: String,
+}
+
+fn is_image_file(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| IMAGE_EXTS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+// 以 current-<label> 命名的截圖為「現況」，其餘圖檔視為「目標設計」；
+// 優先配對 target-<label> 或同名 <label>，否則取第一張目標圖。
+fn find_comparison_pairs(module_name: &str) -> Vec<ComparisonPair> {
+    let mut pairs = Vec::new();
+    for entry in collect_module_page_entries(module_name) {
+        let rel_dir = match &entry.subpage {
+            Some(sub) => format!("pages/{}/subpages/{}/screenshots", entry.page, sub),
+            None => format!("pages/{}/screenshots", entry.page),
+        };
+        let shots = PathBuf::from("design-assets").join(module_name).join(&rel_dir);
+        let mut files: Vec<String> = std::fs::read_dir(&shots)
+            .map(|rd| rd.flatten().map(|e| e.path()).filter(|p| p.is_file() && is_image_file(p))
+                .filter_map(|p| p.file_name().and_then(|n| n.to_str()).map(|s| s.to_string())).collect())
+            .unwrap_or_default();
+        files.sort();
+        let stem = |f: &str| std::path::Path::new(f).file_stem().and_then(|s| s.to_str()).unwrap_or("").to_lowercase();
+        let (currents, targets): (Vec<String>, Vec<String>) = files.into_iter().partition(|f| stem(f).starts_with("current"));
+        for cur in currents.iter() {
+            let label = stem(cur).trim_start_matches("current").trim_start_matches(['-', '_']).to_string();
+            if let Some(t) = match_target_screenshot(&targets, &label) {
+                pairs.push(ComparisonPair {
+                    entry: entry.clone(),
+                    label: if label.is_empty() { "default".to_string() } else { label },
+                    current: format!("{}/{}", rel_dir, cur),
+                    target: format!("{}/{}", rel_dir, t),
+                });
+            }
+        }
+    }
+    pairs
+}
+
+fn match_target_screenshot<'a>(targets: &'a [String], label: &str) -> Option<&'a String> {
+    let stem = |f: &str| std::path::Path::new(f).file_stem().and_then(|s| s.to_str()).unwrap_or("").to_lowercase();
+    targets.iter().find(|t| !label.is_empty() && (stem(t) == format!("target-{}", label) || stem(t) == label))
+        .or_else(|| targets.iter().find(|t| stem(t).starts_with("target")))
+        .or_else(|| targets.first())
+}
+
+fn comparison_file_name(pair: &ComparisonPair) -> String {
+    let base = match &pair.entry.subpage {
+        Some(sub) => format!("{}__{}", pair.entry.page, sub),
+        None => pair.entry.page.clone(),
+    };
+    format!("{}-{}.html", sanitize_id(&base), sanitize_id(&pair.label))
+}
+
+// 於 output_dir/comparison 產生左右並排的對照頁與索引
+fn generate_comparison_pages(module_name: &str, output_dir: &std::path::Path, strategy: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let pairs = find_comparison_pairs(module_name);
+    if pairs.is_empty() {
+        return Ok(());
+    }
+    let dir = output_dir.join("comparison");
+    std::fs::create_dir_all(&dir)?;
+    let style = "body{font-family:system-ui,sans-serif;margin:0;padding:16px;background:#f5f5f5}h1{font-size:18px}.grid{display:grid;grid-template-columns:1fr 1fr;gap:16px}figure{margin:0;background:#fff;padding:8px;border:1px solid #ddd}figcaption{font-weight:600;margin-bottom:8px}img{max-width:100%;display:block}";
+    let mut index = format!("<!DOCTYPE html>\n<html lang=\"zh-TW\">\n<head>\n<meta charset=\"UTF-8\">\n<title>{} 前後對照</title>\n<style>{}</style>\n</head>\n<body>\n<h1>{} 前後對照</h1>\n<ul>\n", module_name, style, module_name);
+    for pair in pairs.iter() {
+        let file = comparison_file_name(pair);
+        let route = pair.entry.route_path();
+        let html = format!(
+            "<!DOCTYPE html>\n<html lang=\"zh-TW\">\n<head>\n<meta charset=\"UTF-8\">\n<title>{route} ({label})</title>\n<style>{style}</style>\n</head>\n<body>\n<h1>{route} <small>({label})</small></h1>\n<div class=\"grid\">\n<figure><figcaption>現況 Current</figcaption><img src=\"../{cur}\" alt=\"current\"></figure>\n<figure><figcaption>目標 Target</figcaption><img src=\"../{tgt}\" alt=\"target\"></figure>\n</div>\n<p><a href=\"index.html\">← 返回列表</a></p>\n</body>\n</html>\n",
+            route = route, label = pair.label, style = style, cur = pair.current, tgt = pair.target
+        );
+        write_text_with_strategy(&dir.join(&file), &html, strategy)?;
+        index.push_str(&format!("<li><a href=\"{}\">{} ({})</a></li>\n", file, route, pair.label));
+    }
+    index.push_str("</ul>\n</body>\n</html>\n");
+    write_text_with_strategy(&dir.join("index.html"), &index, strategy)?;
+    Ok(())
+}
+
+// ai-spec 的差異清單：將工作定位為「改版」而非從零建置
+fn comparison_spec_section(module_name: &str) -> String {
+    let pairs = find_comparison_pairs(module_name);
+    if pairs.is_empty() {
+        return String::new();
+    }
+    let lang = project_spec_language();
+    let mut out = String::from(lang.pick(
+        "\n## 差異清單（Before / After）\n\n此模組已有現行實作，請以「改版」方式進行：保留既有結構，只調整與目標設計不同之處。對照頁位於 `comparison/`。\n\n",
+        "\n## Change List (Before / After)\n\nThis module already has an implementation, so treat the work as a redesign: keep the existing structure and only change what differs from the target design. Comparison pages are in `comparison/`.\n\n",
+        "\n## 差分リスト（Before / After）\n\nこのモジュールには既存の実装があります。「改修」として進め、既存の構造を保ったまま目標デザインと異なる箇所のみ調整してください。比較ページは `comparison/` にあります。\n\n",
+    ));
+    for pair in pairs.iter() {
+        out.push_str(&fill_spec_text(
+            lang.pick(
+                "- [ ] `{}`（{}）：比對 `comparison/{}`，列出並修正版面、間距、字體、色彩與元件差異\n",
+                "- [ ] `{}` ({}): compare with `comparison/{}`, then list and fix differences in layout, spacing, typography, colour and components\n",
+                "- [ ] `{}`（{}）：`comparison/{}` と比較し、レイアウト・余白・フォント・色・コンポーネントの差分を洗い出して修正する\n",
+            ),
+            &[pair.entry.route_path(), pair.label.clone(), comparison_file_name(pair)],
+        ));
+    }
+    out
+}
+
+// ====== Design QA（實作 vs 設計稿像素比對） ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QaBreakpointResult {
+    pub breakpoint: String,
+    pub design: Option<String>,
+    pub actual: Option<String>,
+    pub diff: Option<String>,
+    pub score: Option<f64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QaPageResult {
+    pub page_path: String,
+    pub url: String,
+    pub score: Option<f64>,
+    pub breakpoints: Vec<QaBreakpointResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DesignQaReport {
+    pub module: String,
+    pub created_at: String,
+    pub report_dir: String,
+    pub average_score: Option<f64>,
+    pub pages: Vec<QaPageResult>,
+}
+
+// 每個通道差異超過此值視為不同像素
+const QA_PIXEL_THRESHOLD: u8 = 24;
+
+// 將實作截圖縮放至設計稿尺寸後逐像素比對，輸出差異圖並回傳分數（0-100）
+fn pixel_diff_score(design: &std::path::Path, actual: &std::path::Path, diff_out: &std::path::Path) -> Result<f64, String> {
+    use image::{imageops::FilterType, Rgba, RgbaImage};
+    let design_img = image::open(design).map_err(|e| format!("讀取設計稿失敗: {}", e))?.to_rgba8();
+    let (w, h) = design_img.dimensions();
+    if w == 0 || h == 0 {
+        return Err("設計稿尺寸無效".to_string());
+    }
+    let actual_img = image::open(actual).map_err(|e| format!("讀取實作截圖失敗: {}", e))?.to_rgba8();
+    let actual_img = if actual_img.dimensions() == (w, h) {
+        actual_img
+    } else {
+        image::imageops::resize(&actual_img, w, h, FilterType::Triangle)
+    };
+    let mut diff_img = RgbaImage::new(w, h);
+    let mut different: u64 = 0;
+    for (x, y, dp) in design_img.enumerate_pixels() {
+        let ap = actual_img.get_pixel(x, y);
+        let changed = dp.0.iter().zip(ap.0.iter()).take(3).any(|(a, b)| a.abs_diff(*b) > QA_PIXEL_THRESHOLD);
+        if changed {
+            different += 1;
+            diff_img.put_pixel(x, y, Rgba([255, 0, 0, 255]));
+        } else {
+            // 相同像素以淡化的灰階呈現，方便辨識差異位置
+            let gray = ((dp.0[0] as u32 + dp.0[1] as u32 + dp.0[2] as u32) / 3) as u8;
+            let faded = 255 - (255 - gray) / 4;
+            diff_img.put_pixel(x, y, Rgba([faded, faded, faded, 255]));
+        }
+    }
+    diff_img.save(diff_out).map_err(|e| format!("寫入差異圖失敗: {}", e))?;
+    let total = (w as u64) * (h as u64);
+    let score = 100.0 * (1.0 - different as f64 / total as f64);
+    Ok((score * 10.0).round() / 10.0)
+}
+
+// 依斷點名稱找設計稿：target-<bp>、<bp> 或以 -<bp> / _<bp> 結尾；沒有對應時不以其他斷點的設計稿代替
+fn match_breakpoint_design<'a>(targets: &'a [String], breakpoint: &str) -> Option<&'a String> {
+    let stem = |f: &str| std::path::Path::new(f).file_stem().and_then(|s| s.to_str()).unwrap_or("").to_lowercase();
+    targets.iter().find(|t| {
+        let s = stem(t);
+        s == breakpoint || s == format!("target-{}", breakpoint) || s.ends_with(&format!("-{}", breakpoint)) || s.ends_with(&format!("_{}", breakpoint))
+    })
+}
+
+fn average(scores: impl Iterator<Item = f64>) -> Option<f64> {
+    let v: Vec<f64> = scores.collect();
+    if v.is_empty() { None } else { Some(((v.iter().sum::<f64>() / v.len() as f64) * 10.0).round() / 10.0) }
+}
+
+/// 擷取實作頁面截圖並與設計稿比對，產生每頁評分的 QA 報告（output/qa/<module>-<時間>/）
+/// implementation_url_map 的 key 為 "page" 或 "page/subpage"
+#[tauri::command]
+pub async fn run_design_qa(module: String, implementation_url_map: std::collections::HashMap<String, String>) -> Result<DesignQaReport, String> {
+    use chrono::Local;
+    let module_dir = PathBuf::from("design-assets").join(&module);
+    if !module_dir.exists() {
+        return Err("設計模組不存在".to_string());
+    }
+    let ts = Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let report_dir = output_root().join("qa").join(format!("{}-{}", module, ts));
+    std::fs::create_dir_all(&report_dir).map_err(|e| format!("建立 QA 輸出失敗: {}", e))?;
+    let breakpoints = configured_breakpoints();
+
+    let mut keys: Vec<&String> = implementation_url_map.keys().collect();
+    keys.sort();
+    let mut pages: Vec<QaPageResult> = Vec::new();
+    for key in keys {
+        let url = &implementation_url_map[key];
+        let mut parts = key.trim_matches('/').splitn(2, '/');
+        let page = parts.next().unwrap_or("").to_string();
+        let sub = parts.next().map(|s| s.to_string());
+        let page_dir = match &sub {
+            Some(s) => resolve_page_dir(&module, Some(&page), s),
+            None => resolve_page_dir(&module, None, &page),
+        };
+        let page_path = format!("/{}/{}", module, key.trim_matches('/'));
+        let mut results: Vec<QaBreakpointResult> = Vec::new();
+        let targets: Vec<String> = std::fs::read_dir(page_dir.join("screenshots"))
+            .map(|rd| rd.flatten().map(|e| e.path())
+                .filter(|p| p.is_file() && is_image_file(p) && p.extension().and_then(|e| e.to_str()) != Some("svg"))
+                .filter_map(|p| p.file_name().and_then(|n| n.to_str()).map(|s| s.to_string()))
+                .filter(|n| !n.to_lowercase().starts_with("current"))
+                .collect())
+            .unwrap_or_default();
+        let mut targets = targets;
+        targets.sort();
+        for bp in breakpoints.iter() {
+            let mut r = QaBreakpointResult { breakpoint: bp.name.clone(), design: None, actual: None, diff: None, score: None, error: None };
+            if !page_dir.exists() {
+                r.error = Some("頁面不存在".to_string());
+                results.push(r);
+                continue;
+            }
+            let Some(design_name) = match_breakpoint_design(&targets, &bp.name.to_lowercase()) else {
+                r.error = Some(format!("找不到 {} 斷點的設計稿截圖（需命名為 target-{} 或以 -{} 結尾）", bp.name, bp.name, bp.name));
+                results.push(r);
+                continue;
+            };
+            let design = page_dir.join("screenshots").join(design_name);
+            let base = format!("{}-{}", sanitize_id(key), sanitize_id(&bp.name));
+            let actual = report_dir.join(format!("{}-actual.png", base));
+            let diff = report_dir.join(format!("{}-diff.png", base));
+            r.design = Some(design.to_string_lossy().to_string());
+            if let Err(e) = capture_url_screenshot(url, bp, &actual) {
+                r.error = Some(e);
+                results.push(r);
+                continue;
+            }
+            r.actual = Some(actual.to_string_lossy().to_string());
+            match pixel_diff_score(&design, &actual, &diff) {
+                Ok(score) => {
+                    r.score = Some(score);
+                    r.diff = Some(diff.to_string_lossy().to_string());
+                }
+                Err(e) => r.error = Some(e),
+            }
+            results.push(r);
+        }
+        let score = average(results.iter().filter_map(|r| r.score));
+        pages.push(QaPageResult { page_path, url: url.clone(), score, breakpoints: results });
+    }
+
+    let report = DesignQaReport {
+        module: module.clone(),
+        created_at: Local::now().to_rfc3339(),
+        report_dir: report_dir.to_string_lossy().to_string(),
+        average_score: average(pages.iter().filter_map(|p| p.score)),
+        pages,
+    };
+
+    let mut md = format!("# {} Design QA 報告\n\n- 產生時間: {}\n- 平均分數: {}\n\n| 頁面 | 斷點 | 分數 | 備註 |\n|---|---|---|---|\n",
+        module, report.created_at, report.average_score.map(|s| format!("{:.1}", s)).unwrap_or_else(|| "-".into()));
+    for p in report.pages.iter() {
+        for b in p.breakpoints.iter() {
+            md.push_str(&format!("| {} | {} | {} | {} |\n",
+                p.page_path, b.breakpoint,
+                b.score.map(|s| format!("{:.1}", s)).unwrap_or_else(|| "-".into()),
+                b.error.clone().unwrap_or_default()));
+        }
+    }
+    std::fs::write(report_dir.join("report.md"), md).map_err(|e| format!("寫入 QA 報告失敗: {}", e))?;
+    let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+    std::fs::write(report_dir.join("report.json"), json).map_err(|e| format!("寫入 QA 報告失敗: {}", e))?;
+    Ok(report)
+}
+
+// ====== Sitemap 基準線（凍結 / 變更核准） ======
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SitemapBaseline {
+    pub label: String,
+    pub created_at: String,
+    pub approved_at: Option<String>,
+    // 凍結當下的結構路徑：/module、/module/page、/module/page/subpage
+    pub paths: Vec<String>,
+    pub history: Vec<BaselineApproval>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BaselineApproval {
+    pub approved_at: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PendingChange {
+    pub path: String,
+    // "added" | "removed"
+    pub kind: String,
+}
+
+fn baseline_path() -> PathBuf {
+    active_project_dir().join("sitemap-baseline.json")
+}
+
+fn read_baseline() -> Option<SitemapBaseline> {
+    let raw = std::fs::read_to_string(baseline_path()).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn write_baseline(baseline: &SitemapBaseline) -> Result<(), String> {
+    let path = baseline_path();
+    if let Some(parent) = path.parent() { std::fs::create_dir_all(parent).map_err(|e| e.to_string())?; }
+    let json = serde_json::to_string_pretty(baseline).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("寫入基準線失敗: {}", e))
+}
+
+fn current_structure_paths() -> Vec<String> {
+    let mut paths: Vec<String> = list_dir_names(&PathBuf::from("design-assets")).into_iter().map(|m| format!("/{}", m)).collect();
+    paths.extend(collect_page_entries().iter().map(|e| e.route_path()));
+    paths.sort();
+    paths
+}
+
+fn diff_against_baseline(baseline: &SitemapBaseline) -> Vec<PendingChange> {
+    let current = current_structure_paths();
+    let mut changes: Vec<PendingChange> = current.iter()
+        .filter(|p| !baseline.paths.contains(p))
+        .map(|p| PendingChange { path: p.clone(), kind: "added".into() })
+        .collect();
+    changes.extend(baseline.paths.iter()
+        .filter(|p| !current.contains(p))
+        .map(|p| PendingChange { path: p.clone(), kind: "removed".into() }));
+    changes
+}
+
+/// 凍結目前的 sitemap 結構為基準線，之後的結構變更會列為待核准
+#[tauri::command]
+pub async fn freeze_sitemap(label: String) -> Result<SitemapBaseline, String> {
+    let baseline = SitemapBaseline {
+        label,
+        created_at: chrono::Local::now().to_rfc3339(),
+        approved_at: None,
+        paths: current_structure_paths(),
+        history: Vec::new(),
+    };
+    write_baseline(&baseline)?;
+    Ok(baseline)
+}
+
+/// 列出相對於基準線的待核准結構變更
+#[tauri::command]
+pub async fn list_pending_changes() -> Result<Vec<PendingChange>, String> {
+    let baseline = read_baseline().ok_or("尚未凍結 sitemap 基準線")?;
+    Ok(diff_against_baseline(&baseline))
+}
+
+/// 核准所有待核准變更：將基準線更新為目前結構並記錄核准歷程
+#[tauri::command]
+pub async fn approve_changes() -> Result<SitemapBaseline, String> {
+    let mut baseline = read_baseline().ok_or("尚未凍結 sitemap 基準線")?;
+    let changes = diff_against_baseline(&baseline);
+    if changes.is_empty() {
+        return Ok(baseline);
+    }
+    let now = chrono::Local::now().to_rfc3339();
+    let (added, removed): (Vec<PendingChange>, Vec<PendingChange>) = changes.into_iter().partition(|c| c.kind == "added");
+    baseline.history.push(BaselineApproval {
+        approved_at: now.clone(),
+        added: added.into_iter().map(|c| c.path).collect(),
+        removed: removed.into_iter().map(|c| c.path).collect(),
+    });
+    baseline.paths = current_structure_paths();
+    baseline.approved_at = Some(now);
+    write_baseline(&baseline)?;
+    Ok(baseline)
+}
+
+// ====== 合併他人工作區（離線協作） ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MergeConflict {
+    pub path: String,
+    // 實際採用的處理方式："skip" | "overwrite" | "rename:<新 slug>"
+    pub resolution: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MergeReport {
+    pub source: String,
+    pub strategy: String,
+    pub merged_at: String,
+    pub added: Vec<String>,
+    pub conflicts: Vec<MergeConflict>,
+    pub report_path: String,
+}
+
+// 複製頁面層級檔案（不含 subpages 目錄）
+fn copy_page_files(src: &std::path::Path, dst: &std::path::Path, strategy: &str) -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(dst)?;
+    for entry in std::fs::read_dir(src)?.flatten() {
+        let path = entry.path();
+        if path.file_name().and_then(|n| n.to_str()) == Some("subpages") { continue; }
+        let target = dst.join(entry.file_name());
+        if path.is_dir() {
+            std::fs::create_dir_all(&target)?;
+            copy_assets_with_strategy(&path, &target, strategy)?;
+        } else {
+            copy_file_with_strategy(&path, &target, strategy)?;
+        }
+    }
+    Ok(())
+}
+
+// 依處理方式合併單一頁面/子頁目錄，回傳記錄用的 resolution 字串
+fn merge_leaf_dir(src: &std::path::Path, dst: &PathBuf, resolution: &str, include_subpages: bool) -> Result<(String, Option<String>), Box<dyn std::error::Error>> {
+    match resolution {
+        "skip" => Ok(("skip".into(), None)),
+        "rename" => {
+            let target = next_available_path(dst);
+            std::fs::create_dir_all(&target)?;
+            copy_assets(&src.to_path_buf(), &target)?;
+            let slug = target.file_name().and_then(|n| n.to_str()).unwrap_or("").to_string();
+            Ok((format!("rename:{}", slug), Some(slug)))
+        }
+        _ => {
+            if include_subpages {
+                copy_assets(&src.to_path_buf(), dst)?;
+            } else {
+                copy_page_files(src, dst, "overwrite")?;
+            }
+            Ok(("overwrite".into(), None))
+        }
+    }
+}
+
+fn merge_module(
+    src_module: &std::path::Path,
+    module: &str,
+    strategy: &str,
+    resolutions: &std::collections::HashMap<String, String>,
+    report: &mut MergeReport,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let dst_module = PathBuf::from("design-assets").join(module);
+    if !dst_module.exists() {
+        std::fs::create_dir_all(&dst_module)?;
+        copy_assets(&src_module.to_path_buf(), &dst_module)?;
+        report.added.push(format!("/{}", module));
+        return Ok(());
+    }
+    let resolve = |path: &str| resolutions.get(path).map(|s| s.as_str()).unwrap_or(strategy).to_string();
+
+    // 模組層級資產（screenshots/html/css）沿用檔案複製策略
+    for dir in ["screenshots", "html", "css"] {
+        let src = src_module.join(dir);
+        if src.exists() {
+            let dst = dst_module.join(dir);
+            std::fs::create_dir_all(&dst)?;
+            copy_assets_with_strategy(&src, &dst, &resolve(&format!("/{}", module)))?;
+        }
+    }
+
+    let src_order = load_order(src_module);
+    let mut order = load_order(&dst_module);
+    let dst_pages = dst_module.join("pages");
+    for page in list_dir_names(&src_module.join("pages")) {
+        let src_page = src_module.join("pages").join(&page);
+        let dst_page = dst_pages.join(&page);
+        let page_path = format!("/{}/{}", module, page);
+        if !dst_page.exists() {
+            std::fs::create_dir_all(&dst_page)?;
+            copy_assets(&src_page, &dst_page)?;
+            if !order.pages.contains(&page) { order.pages.push(page.clone()); }
+            if let Some(subs) = src_order.subpages.get(&page) { order.subpages.insert(page.clone(), subs.clone()); }
+            report.added.push(page_path);
+            continue;
+        }
+        let (resolution, renamed) = merge_leaf_dir(&src_page, &dst_page, &resolve(&page_path), false)?;
+        report.conflicts.push(MergeConflict { path: page_path, resolution });
+        if let Some(slug) = renamed {
+            order.pages.push(slug);
+            continue;
+        }
+        // 頁面本身保留時，仍逐一合併其子頁
+        for sub in list_dir_names(&src_page.join("subpages")) {
+            let src_sub = src_page.join("subpages").join(&sub);
+            let dst_sub = dst_page.join("subpages").join(&sub);
+            let sub_path = format!("/{}/{}/{}", module, page, sub);
+            let entry = order.subpages.entry(page.clone()).or_default();
+            if !dst_sub.exists() {
+                std::fs::create_dir_all(&dst_sub)?;
+                copy_assets(&src_sub, &dst_sub)?;
+                if !entry.contains(&sub) { entry.push(sub.clone()); }
+                report.added.push(sub_path);
+                continue;
+            }
+            let (resolution, renamed) = merge_leaf_dir(&src_sub, &dst_sub, &resolve(&sub_path), true)?;
+            if let Some(slug) = renamed { entry.push(slug); }
+            report.conflicts.push(MergeConflict { path: sub_path, resolution });
+        }
+    }
+    save_order(&dst_module, order)?;
+    Ok(())
+}
+
+/// 合併另一個 ErSlice 工作區（資料夾或 .zip 匯出檔）的設計資產。
+/// strategy 為預設衝突處理（"skip" / "overwrite" / "rename"），resolutions 可針對個別路徑覆寫。
+#[tauri::command]
+pub async fn merge_workspace(
+    path: String,
+    strategy: String,
+    resolutions: Option<std::collections::HashMap<String, String>>,
+) -> Result<MergeReport, String> {
+    use chrono::Local;
+    let resolutions = resolutions.unwrap_or_default();
+    if let Some(invalid) = std::iter::once(&strategy).chain(resolutions.values()).find(|s| !["skip", "overwrite", "rename"].contains(&s.as_str())) {
+        return Err(format!("不支援的合併策略: {}（可用：skip, overwrite, rename）", invalid));
+    }
+    let source = PathBuf::from(&path);
+    if !source.exists() {
+        return Err("來源工作區不存在".to_string());
+    }
+    let ts = Local::now().format("%Y%m%d-%H%M%S").to_string();
+
+    // 匯出檔先解壓至暫存資料夾
+    let mut temp_dir: Option<PathBuf> = None;
+    let root = if source.is_file() {
+        let tmp = output_root().join(format!(".merge-{}", ts));
+        std::fs::create_dir_all(&tmp).map_err(|e| format!("建立暫存資料夾失敗: {}", e))?;
+        let extracted = std::fs::File::open(&source)
+            .map_err(|e| format!("開啟匯出檔失敗: {}", e))
+            .and_then(|f| zip::ZipArchive::new(f).map_err(|e| format!("讀取 zip 失敗: {}", e)))
+            .and_then(|mut zip| zip.extract(&tmp).map_err(|e| format!("解壓 zip 失敗: {}", e)));
+        if let Err(e) = extracted {
+            let _ = std::fs::remove_dir_all(&tmp);
+            return Err(e);
+        }
+        temp_dir = Some(tmp.clone());
+        tmp
+    } else {
+        source.clone()
+    };
+
+    // 支援指向工作區根目錄、design-assets 目錄，或壓縮檔內含單一根資料夾
+    let assets_root = std::iter::once(root.join("design-assets"))
+        .chain(list_dir_names(&root).into_iter().map(|d| root.join(d).join("design-assets")))
+        .find(|p| p.is_dir())
+        .unwrap_or_else(|| root.clone());
+
+    let mut report = MergeReport {
+        source: path.clone(),
+        strategy: strategy.clone(),
+        merged_at: Local::now().to_rfc3339(),
+        added: Vec::new(),
+        conflicts: Vec::new(),
+        report_path: String::new(),
+    };
+    let result = (|| -> Result<(), String> {
+        for module in list_dir_names(&assets_root) {
+            merge_module(&assets_root.join(&module), &module, &strategy, &resolutions, &mut report)
+                .map_err(|e| format!("{}: 合併失敗: {}", module, e))?;
+            let mut cache = SITEMAP_CACHE.lock().unwrap();
+            cache.invalidate_module(&module);
+        }
+        Ok(())
+    })();
+    if let Some(tmp) = temp_dir {
+        let _ = std::fs::remove_dir_all(tmp);
+    }
+    result?;
+
+    let reports_dir = active_project_dir().join("merge-reports");
+    std::fs::create_dir_all(&reports_dir).map_err(|e| e.to_string())?;
+    let report_path = reports_dir.join(format!("merge-{}.json", ts));
+    report.report_path = report_path.to_string_lossy().to_string();
+    let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+    std::fs::write(&report_path, json).map_err(|e| format!("寫入合併報告失敗: {}", e))?;
+    Ok(report)
+}
+
+// ====== 停滯頁面偵測與提醒 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StalePage {
+    pub path: String,
+    pub status: String,
+    pub last_modified: String,
+    pub idle_days: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StalenessReport {
+    pub threshold_days: u64,
+    pub statuses: Vec<String>,
+    pub stale: Vec<StalePage>,
+}
+
+lazy_static::lazy_static! {
+    // 每次重新排程時遞增，舊的提醒執行緒看到代數不同即自行結束
+    static ref STALE_REMINDER_GENERATION: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+}
+
+// 頁面資料夾內（不含子頁）最新的修改時間
+fn latest_mtime(dir: &std::path::Path) -> Option<SystemTime> {
+    let mut latest = std::fs::metadata(dir).and_then(|m| m.modified()).ok();
+    for entry in std::fs::read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        if path.file_name().and_then(|n| n.to_str()) == Some("subpages") { continue; }
+        let t = if path.is_dir() { latest_mtime(&path) } else { entry.metadata().and_then(|m| m.modified()).ok() };
+        if let Some(t) = t {
+            if latest.map_or(true, |l| t > l) { latest = Some(t); }
+        }
+    }
+    latest
+}
+
+fn build_staleness_report(days: u64, statuses: &[String]) -> StalenessReport {
+    let now = SystemTime::now();
+    let mut stale: Vec<StalePage> = Vec::new();
+    for entry in collect_page_entries() {
+        let Some(status) = entry.meta.status.clone() else { continue };
+        if !statuses.iter().any(|s| s.eq_ignore_ascii_case(&status)) { continue; }
+        let dir = match &entry.subpage {
+            Some(sub) => resolve_page_dir(&entry.module, Some(&entry.page), sub),
+            None => resolve_page_dir(&entry.module, None, &entry.page),
+        };
+        let Some(modified) = latest_mtime(&dir) else { continue };
+        let idle_days = now.duration_since(modified).unwrap_or_default().as_secs() / 86_400;
+        if idle_days >= days {
+            stale.push(StalePage {
+                path: entry.route_path(),
+                status,
+                last_modified: chrono::DateTime::<chrono::Local>::from(modified).to_rfc3339(),
+                idle_days,
+            });
+        }
+    }
+    stale.sort_by_key(|p| std::cmp::Reverse(p.idle_days));
+    StalenessReport { threshold_days: days, statuses: statuses.to_vec(), stale }
+}
+
+fn default_stale_statuses() -> Vec<String> {
+    vec!["in-progress".to_string()]
+}
+
+/// 列出狀態仍為進行中、但資產與 meta 已超過 N 天（預設 14）未變動的頁面
+#[tauri::command]
+pub async fn get_staleness_report(days: Option<u64>, statuses: Option<Vec<String>>) -> Result<StalenessReport, String> {
+    let statuses = statuses.filter(|v| !v.is_empty()).unwrap_or_else(default_stale_statuses);
+    Ok(build_staleness_report(days.unwrap_or(14), &statuses))
+}
+
+/// 排程本機通知，定期摘要停滯頁面；interval_hours 為 0 時停止提醒
+#[tauri::command]
+pub async fn schedule_stale_reminders(app: tauri::AppHandle, interval_hours: u64, days: Option<u64>) -> Result<String, String> {
+    use std::sync::atomic::Ordering;
+    use tauri_plugin_notification::NotificationExt;
+    let generation = STALE_REMINDER_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    if interval_hours == 0 {
+        return Ok("已停止停滯頁面提醒".into());
+    }
+    let days = days.unwrap_or(14);
+    std::thread::spawn(move || loop {
+        if STALE_REMINDER_GENERATION.load(Ordering::SeqCst) != generation { break; }
+        let report = build_staleness_report(days, &default_stale_statuses());
+        if !report.stale.is_empty() {
+            let preview: Vec<String> = report.stale.iter().take(3).map(|p| p.path.clone()).collect();
+            let _ = app.notification()
+                .builder()
+                .title("ErSlice")
+                .body(format!("{} 個頁面超過 {} 天未更新：{}", report.stale.len(), days, preview.join("、")))
+                .show();
+        }
+        // 以分鐘為單位睡眠，以便取消時能及時結束
+        for _ in 0..interval_hours * 60 {
+            if STALE_REMINDER_GENERATION.load(Ordering::SeqCst) != generation { return; }
+            std::thread::sleep(Duration::from_secs(60));
+        }
+    });
+    Ok(format!("已排程每 {} 小時提醒停滯頁面", interval_hours))
+}
+
+// ====== Figma 導出下載管理 ======
+
+// 依副檔名對應資產類型（screenshots / html / css）
+fn asset_type_for_path(path: &std::path::Path) -> Option<&'static str> {
+    let ext = path.extension().and_then(|e| e.to_str())?.to_lowercase();
+    match ext.as_str() {
+        "png" | "jpg" | "jpeg" | "webp" | "gif" | "svg" => Some("screenshots"),
+        "html" | "htm" => Some("html"),
+        "css" => Some("css"),
+        "ttf" | "otf" | "woff" | "woff2" => Some("fonts"),
+        _ => None,
+    }
+}
+
+// 遞迴將來源資料夾中的已知資產依類型放入模組，回傳匯入檔案數
+fn import_files_by_type(src_dir: &std::path::Path, module_dir: &std::path::Path) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut count = 0;
+    for entry in std::fs::read_dir(src_dir)?.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            count += import_files_by_type(&path, module_dir)?;
+        } else if let Some(kind) = asset_type_for_path(&path) {
+            let dir = module_dir.join(kind);
+            std::fs::create_dir_all(&dir)?;
+            copy_file_with_strategy(&path, &dir.join(entry.file_name()), "rename")?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+fn sha256_file(path: &std::path::Path) -> Result<String, String> {
+    use sha2::{Digest, Sha256};
+    use std::io::Read;
+    let mut file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf).map_err(|e| e.to_string())?;
+        if n == 0 { break; }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+// 以 Range 續傳下載到 dest（.part 存在時從已下載位置繼續）
+fn download_resumable(url: &str, dest: &std::path::Path) -> Result<u64, String> {
+    use std::io::Write;
+    let existing = std::fs::metadata(dest).map(|m| m.len()).unwrap_or(0);
+    let mut req = ureq::get(url);
+    if existing > 0 {
+        req = req.set("Range", &format!("bytes={}-", existing));
+    }
+    let resp = match req.call() {
+        Ok(r) => r,
+        // 416：檔案已完整下載
+        Err(ureq::Error::Status(416, _)) if existing > 0 => return Ok(existing),
+        Err(e) => return Err(format!("下載失敗: {}", e)),
+    };
+    let append = existing > 0 && resp.status() == 206;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(dest)
+        .map_err(|e| e.to_string())?;
+    let mut reader = resp.into_reader();
+    let written = std::io::copy(&mut reader, &mut file).map_err(|e| format!("下載中斷: {}", e))?;
+    file.flush().map_err(|e| e.to_string())?;
+    Ok(if append { existing + written } else { written })
+}
+
+/// 列出 figma_exports 記錄（下載佇列）
+#[tauri::command]
+pub async fn list_figma_exports() -> Result<Vec<crate::database::FigmaExport>, String> {
+    crate::database::FigmaExport::list_all().map_err(|e| format!("讀取導出記錄失敗: {}", e))
+}
+
+/// 下載 figma_exports 中的導出檔（可續傳），驗證 checksum 後解壓至對應模組，並更新該筆狀態
+#[tauri::command]
+pub async fn download_figma_export(id: String) -> Result<crate::database::FigmaExport, String> {
+    use crate::database::FigmaExport;
+    let mut export = FigmaExport::read(&id)
+        .map_err(|e| format!("讀取導出記錄失敗: {}", e))?
+        .ok_or("導出記錄不存在")?;
+    let url = export.download_url.clone().filter(|u| !u.is_empty()).ok_or("此導出記錄沒有下載網址")?;
+
+    let fail = |export: &mut FigmaExport, msg: String| -> String {
+        export.status = Some("failed".into());
+        export.error_message = Some(msg.clone());
+        let _ = export.update_download_state();
+        msg
+    };
+
+    let downloads = output_root().join("figma-downloads");
+    std::fs::create_dir_all(&downloads).map_err(|e| e.to_string())?;
+    let file_name = url.split('?').next().unwrap_or("").rsplit('/').next().filter(|n| n.contains('.'))
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| format!("{}.{}", sanitize_id(&id), export.export_format.to_lowercase()));
+    let final_path = downloads.join(format!("{}-{}", sanitize_id(&id), file_name));
+    let part_path = downloads.join(format!("{}-{}.part", sanitize_id(&id), file_name));
+
+    if !final_path.exists() {
+        export.status = Some("downloading".into());
+        export.error_message = None;
+        let _ = export.update_download_state();
+        let bytes = match download_resumable(&url, &part_path) {
+            Ok(b) => b,
+            Err(e) => {
+                export.downloaded_bytes = std::fs::metadata(&part_path).map(|m| m.len() as i64).ok();
+                return Err(fail(&mut export, e));
+            }
+        };
+        export.downloaded_bytes = Some(bytes as i64);
+        std::fs::rename(&part_path, &final_path).map_err(|e| fail(&mut export, e.to_string()))?;
+    }
+
+    // checksum：有預期值則驗證，否則記錄實際值
+    let actual = sha256_file(&final_path).map_err(|e| fail(&mut export, e))?;
+    match export.checksum.as_deref().map(|c| c.trim().to_lowercase()) {
+        Some(expected) if !expected.is_empty() && expected != actual => {
+            let _ = std::fs::remove_file(&final_path);
+            return Err(fail(&mut export, format!("checksum 不符：預期 {}，實際 {}", expected, actual)));
+        }
+        _ => export.checksum = Some(actual),
+    }
+    let size = std::fs::metadata(&final_path).map(|m| m.len()).unwrap_or(0);
+    export.file_size = Some(format!("{:.1} MB", size as f64 / 1_048_576.0));
+    export.local_path = Some(final_path.to_string_lossy().to_string());
+
+    // 解壓並依類型匯入模組
+    export.status = Some("extracting".into());
+    let _ = export.update_download_state();
+    // 未指定目標模組時以 Figma 檔名建立新模組：資料夾使用代稱，原名稱寫入 module.json
+    let target = export.target_module.clone().filter(|m| !m.is_empty());
+    if target.as_deref().is_some_and(|m| !is_plain_file_name(m)) {
+        return Err(fail(&mut export, "目標模組名稱無效".to_string()));
+    }
+    let module = target.clone().unwrap_or_else(|| slugify_display_name(&export.name, "module"));
+    let module_dir = PathBuf::from("design-assets").join(&module);
+    std::fs::create_dir_all(&module_dir).map_err(|e| fail(&mut export, e.to_string()))?;
+    if target.is_none() && !module_dir.join(MODULE_META_FILE).exists() {
+        let meta = ModuleMeta { display_name: Some(export.name.trim().to_string()), description: None };
+        write_module_meta(&module, &meta).map_err(|e| fail(&mut export, e))?;
+    }
+    let is_zip = final_path.extension().and_then(|e| e.to_str()).map(|e| e.eq_ignore_ascii_case("zip")).unwrap_or(false);
+    let imported = if is_zip {
+        let tmp = downloads.join(format!(".extract-{}", sanitize_id(&id)));
+        let _ = std::fs::remove_dir_all(&tmp);
+        std::fs::create_dir_all(&tmp).map_err(|e| fail(&mut export, e.to_string()))?;
+        let extracted = std::fs::File::open(&final_path)
+            .map_err(|e| format!("開啟下載檔失敗: {}", e))
+            .and_then(|f| zip::ZipArchive::new(f).map_err(|e| format!("讀取 zip 失敗: {}", e)))
+            .and_then(|mut zip| zip.extract(&tmp).map_err(|e| format!("解壓 zip 失敗: {}", e)));
+        if let Err(e) = extracted {
+            let _ = std::fs::remove_dir_all(&tmp);
+            return Err(fail(&mut export, e));
+        }
+        let n = import_files_by_type(&tmp, &module_dir).map_err(|e| fail(&mut export, e.to_string()));
+        let _ = std::fs::remove_dir_all(&tmp);
+        n?
+    } else if let Some(kind) = asset_type_for_path(&final_path) {
+        let dir = module_dir.join(kind);
+        std::fs::create_dir_all(&dir).map_err(|e| fail(&mut export, e.to_string()))?;
+        copy_file_with_strategy(&final_path, &dir.join(&file_name), "rename").map_err(|e| fail(&mut export, e.to_string()))?;
+        1
+    } else {
+        0
+    };
+
+    export.status = Some("completed".into());
+    export.error_message = None;
+    export.update_download_state().map_err(|e| format!("更新導出記錄失敗: {}", e))?;
+    log::info!("Figma 導出 {} 已匯入 {} 個檔案至 {}", id, imported, module);
+    let mut cache = SITEMAP_CACHE.lock().unwrap();
+    cache.invalidate_module(&module);
+    Ok(export)
+}
+
+// ====== 資料夾遞迴匯入（保留結構） ======
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FolderImportSummary {
+    pub module: String,
+    pub files_imported: usize,
+    pub files_skipped: usize,
+    pub pages_created: Vec<String>,
+    pub subpages_created: Vec<String>,
+}
+
+// 常見的匯出子資料夾名稱對應到資產類型
+fn asset_type_for_folder(name: &str) -> Option<&'static str> {
+    match name.to_lowercase().as_str() {
+        "screenshots" | "screenshot" | "images" | "image" | "img" | "screens" | "exports" => Some("screenshots"),
+        "html" | "markup" | "templates" => Some("html"),
+        "css" | "styles" | "style" | "stylesheets" => Some("css"),
+        "fonts" | "font" => Some("fonts"),
+        _ => None,
+    }
+}
+
+// 匯入一個層級（模組/頁面/子頁）的檔案：已知子資料夾依類型放入，零散檔案依副檔名分類
+fn import_level_files(src: &std::path::Path, dst: &std::path::Path, summary: &mut FolderImportSummary) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+    let mut other_dirs: Vec<PathBuf> = Vec::new();
+    let mut entries: Vec<_> = std::fs::read_dir(src)?.flatten().map(|e| e.path()).collect();
+    entries.sort();
+    for path in entries {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("").to_string();
+        if name.starts_with('.') { continue; }
+        if path.is_dir() {
+            if asset_type_for_folder(&name).is_some() {
+                summary.files_imported += import_files_by_type(&path, dst)?;
+            } else {
+                other_dirs.push(path);
+            }
+        } else if let Some(kind) = asset_type_for_path(&path) {
+            let dir = dst.join(kind);
+            std::fs::create_dir_all(&dir)?;
+            copy_file_with_strategy(&path, &dir.join(&name), "rename")?;
+            summary.files_imported += 1;
+        } else {
+            summary.files_skipped += 1;
+        }
+    }
+    Ok(other_dirs)
+}
+
+/// 匯入整個設計匯出資料夾：已知子資料夾對應資產類型，其餘巢狀資料夾建立為頁面/子頁
+#[tauri::command]
+pub async fn import_asset_folder(source_dir: String, module_name: String) -> Result<FolderImportSummary, String> {
+    let source = PathBuf::from(&source_dir);
+    if !source.is_dir() {
+        return Err("來源資料夾不存在".to_string());
+    }
+    if module_name.trim().is_empty() || module_name.contains('/') {
+        return Err("模組名稱無效".to_string());
+    }
+    let module_dir = PathBuf::from("design-assets").join(&module_name);
+    for subdir in ["screenshots", "html", "css"] {
+        std::fs::create_dir_all(module_dir.join(subdir)).map_err(|e| format!("創建子目錄 {} 失敗: {}", subdir, e))?;
+    }
+
+    let mut summary = FolderImportSummary { module: module_name.clone(), ..Default::default() };
+    let err = |e: Box<dyn std::error::Error>| format!("匯入失敗: {}", e);
+    let mut page_dirs = import_level_files(&source, &module_dir, &mut summary).map_err(err)?;
+    // 若來源本身有 pages/ 容器，其子資料夾即為頁面
+    if let Some(pos) = page_dirs.iter().position(|p| p.file_name().and_then(|n| n.to_str()) == Some("pages")) {
+        let container = page_dirs.remove(pos);
+        page_dirs.extend(list_dir_names(&container).into_iter().map(|d| container.join(d)));
+    }
+
+    let mut order = load_order(&module_dir);
+    for page_src in page_dirs {
+        // 資料夾名稱作為顯示名稱，實際資料夾與順序使用與 create_module_page 相同的代稱
+        let name = page_src.file_name().and_then(|n| n.to_str()).unwrap_or("").trim().to_string();
+        if name.is_empty() { continue; }
+        let mut slug = slugify_display_name(&name, "page");
+        if !module_dir.join("pages").join(&slug).join("page.json").exists() {
+            slug = create_module_page(module_name.clone(), name.clone()).await?.slug;
+            summary.pages_created.push(format!("/{}/{}", module_name, slug));
+        }
+        let page_dir = module_dir.join("pages").join(&slug);
+        if !order.pages.contains(&slug) { order.pages.push(slug.clone()); }
+        let mut sub_dirs = import_level_files(&page_src, &page_dir, &mut summary).map_err(err)?;
+        if let Some(pos) = sub_dirs.iter().position(|p| p.file_name().and_then(|n| n.to_str()) == Some("subpages")) {
+            let container = sub_dirs.remove(pos);
+            sub_dirs.extend(list_dir_names(&container).into_iter().map(|d| container.join(d)));
+        }
+        for sub_src in sub_dirs {
+            let name = sub_src.file_name().and_then(|n| n.to_str()).unwrap_or("").trim().to_string();
+            if name.is_empty() { continue; }
+            let mut sub = slugify_display_name(&name, "page");
+            if !page_dir.join("subpages").join(&sub).join("page.json").exists() {
+                sub = create_subpage(module_name.clone(), slug.clone(), name.clone()).await?.slug;
+                summary.subpages_created.push(format!("/{}/{}/{}", module_name, slug, sub));
+            }
+            let sub_dir = page_dir.join("subpages").join(&sub);
+            let entry = order.subpages.entry(slug.clone()).or_default();
+            if !entry.contains(&sub) { entry.push(sub.clone()); }
+            // 子頁以下不再建立層級，所有檔案依類型攤平匯入
+            summary.files_imported += import_files_by_type(&sub_src, &sub_dir).map_err(err)?;
+        }
+    }
+    save_order(&module_dir, order).map_err(|e| format!("寫入頁面順序失敗: {}", e))?;
+
+    let mut cache = SITEMAP_CACHE.lock().unwrap();
+    cache.invalidate_module(&module_name);
+    Ok(summary)
+}
+
+// ====== HTML 資產路徑改寫與驗證 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AssetValidationReport {
+    pub package_dir: String,
+    pub html_files: usize,
+    pub references: usize,
+    pub rewritten: usize,
+    // "<html 檔>: <引用>" 形式
+    pub missing: Vec<String>,
+}
+
+fn is_external_ref(r: &str) -> bool {
+    let lower = r.to_lowercase();
+    r.is_empty() || r.starts_with('#') || r.starts_with("//")
+        || ["http:", "https:", "data:", "mailto:", "tel:", "javascript:", "{{", "{%"].iter().any(|p| lower.starts_with(p))
+}
+
+// 掃描 design-assets 來源檔案（套用工作區忽略規則）
+fn collect_files_recursive(dir: &std::path::Path, out: &mut Vec<PathBuf>) {
+    collect_files_filtered(dir, &IgnoreRules::load(), out);
+}
+
+fn collect_files_filtered(dir: &std::path::Path, ignore: &IgnoreRules, out: &mut Vec<PathBuf>) {
+    if let Ok(rd) = std::fs::read_dir(dir) {
+        for entry in rd.flatten() {
+            if ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
+            let path = entry.path();
+            if entry.file_name() == VERSIONS_DIR { continue; }
+            if path.is_dir() { collect_files_filtered(&path, ignore, out); } else { out.push(path); }
+        }
+    }
+}
+
+// 掃描輸出包內的檔案：不套用忽略規則（.gitignore、.storybook 等生成檔需隨包輸出），只略過版本與生成紀錄目錄
+fn collect_output_files(dir: &std::path::Path, out: &mut Vec<PathBuf>) {
+    if let Ok(rd) = std::fs::read_dir(dir) {
+        for entry in rd.flatten() {
+            if is_output_internal_dir(&entry.file_name().to_string_lossy()) { continue; }
+            let path = entry.path();
+            if path.is_dir() { collect_output_files(&path, out); } else { out.push(path); }
+        }
+    }
+}
+
+fn is_output_internal_dir(name: &str) -> bool {
+    name == VERSIONS_DIR || name == GENERATION_HISTORY_DIR
+}
+
+// 不略過任何項目的完整掃描（用於驗證收到的輸出包）
+fn collect_all_files(dir: &std::path::Path, out: &mut Vec<PathBuf>) {
+    if let Ok(rd) = std::fs::read_dir(dir) {
+        for entry in rd.flatten() {
+            let path = entry.path();
+            if path.is_dir() { collect_all_files(&path, out); } else { out.push(path); }
+        }
+    }
+}
+
+// 將 target 相對於 from_dir 表示（兩者皆為包內相對路徑）
+fn relative_path(from_dir: &std::path::Path, target: &std::path::Path) -> String {
+    let from: Vec<_> = from_dir.components().collect();
+    let to: Vec<_> = target.components().collect();
+    let common = from.iter().zip(to.iter()).take_while(|(a, b)| a == b).count();
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    parts.extend(to[common..].iter().map(|c| c.as_os_str().to_string_lossy().to_string()));
+    let joined = parts.join("/");
+    if joined.starts_with("..") { joined } else { format!("./{}", joined) }
+}
+
+// 掃描 src= / href= 屬性，以 f 決定替換值（None 表示保留原值）
+fn rewrite_html_refs(html: &str, mut f: impl FnMut(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    loop {
+        let next = ["src=", "href="].iter().filter_map(|attr| rest.find(attr).map(|i| (i, attr.len()))).min();
+        let Some((idx, len)) = next else { break };
+        let value_start = idx + len;
+        let quote = rest[value_start..].chars().next();
+        match quote {
+            Some(q @ ('"' | '\'')) => {
+                let body = &rest[value_start + 1..];
+                let Some(end) = body.find(q) else { break };
+                out.push_str(&rest[..value_start + 1]);
+                let value = &body[..end];
+                out.push_str(&f(value).unwrap_or_else(|| value.to_string()));
+                out.push(q);
+                rest = &body[end + 1..];
+            }
+            _ => {
+                out.push_str(&rest[..value_start]);
+                rest = &rest[value_start..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+// 檢查（並可選擇改寫）包內所有 HTML 的本地資產引用
+fn rewrite_package_asset_refs(package_root: &std::path::Path, base: Option<&str>) -> Result<AssetValidationReport, String> {
+    let mut files = Vec::new();
+    collect_output_files(package_root, &mut files);
+    let rel_files: Vec<PathBuf> = files.iter().filter_map(|f| f.strip_prefix(package_root).ok().map(|p| p.to_path_buf())).collect();
+    let mut report = AssetValidationReport { package_dir: package_root.to_string_lossy().to_string(), ..Default::default() };
+
+    for rel_html in rel_files.iter().filter(|f| matches!(f.extension().and_then(|e| e.to_str()), Some("html" | "htm"))) {
+        let abs = package_root.join(rel_html);
+        let Ok(html) = std::fs::read_to_string(&abs) else { continue };
+        report.html_files += 1;
+        let html_dir = rel_html.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+        let mut rewritten = 0usize;
+        let mut missing: Vec<String> = Vec::new();
+        let new_html = rewrite_html_refs(&html, |value| {
+            if is_external_ref(value) { return None; }
+            report.references += 1;
+            let clean = value.split(['?', '#']).next().unwrap_or(value);
+            let suffix = &value[clean.len()..];
+            // 先依原路徑解析，找不到時以檔名在包內搜尋
+            let direct = if clean.starts_with('/') { PathBuf::from(clean.trim_start_matches('/')) } else { html_dir.join(clean) };
+            let normalized: PathBuf = direct.components().fold(PathBuf::new(), |mut acc, c| {
+                match c {
+                    std::path::Component::ParentDir => { acc.pop(); }
+                    std::path::Component::CurDir => {}
+                    other => acc.push(other.as_os_str()),
+                }
+                acc
+            });
+            let found = if package_root.join(&normalized).is_file() {
+                Some(normalized)
+            } else {
+                let name = std::path::Path::new(clean).file_name();
+                rel_files.iter().find(|f| f.file_name() == name).cloned()
+            };
+            let Some(target) = found else {
+                missing.push(format!("{}: {}", rel_html.display(), value));
+                return None;
+            };
+            let new_value = match base {
+                Some("relative") => relative_path(&html_dir, &target),
+                Some(prefix) => format!("{}/{}", prefix.trim_end_matches('/'), target.to_string_lossy().replace('\\', "/")),
+                None => return None,
+            };
+            let new_value = format!("{}{}", new_value, suffix);
+            if new_value == value { return None; }
+            rewritten += 1;
+            Some(new_value)
+        });
+        report.missing.extend(missing);
+        if rewritten > 0 {
+            std::fs::write(&abs, new_html).map_err(|e| format!("寫入 {} 失敗: {}", abs.display(), e))?;
+            report.rewritten += rewritten;
+        }
+    }
+    Ok(report)
+}
+
+// 生成流程使用：專案有設定 asset_base_url 時改寫，缺漏的引用寫入 asset-validation.json
+fn apply_asset_path_rewrite(package_root: &std::path::Path) -> Result<(), String> {
+    let Some(base) = read_active_project_config().and_then(|c| c.asset_base_url).filter(|b| !b.trim().is_empty()) else {
+        return Ok(());
+    };
+    let report = rewrite_package_asset_refs(package_root, Some(base.trim()))?;
+    if !report.missing.is_empty() {
+        log::warn!("{} 有 {} 個資產引用找不到檔案", package_root.display(), report.missing.len());
+        let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+        std::fs::write(package_root.join("asset-validation.json"), json).map_err(|e| format!("寫入資產驗證報告失敗: {}", e))?;
+    }
+    Ok(())
+}
+
+/// 驗證輸出包內 HTML 引用的資產皆存在（不修改檔案）
+#[tauri::command]
+pub async fn validate_package_assets(package_dir: String) -> Result<AssetValidationReport, String> {
+    let root = PathBuf::from(&package_dir);
+    if !root.is_dir() {
+        return Err("輸出資料夾不存在".to_string());
+    }
+    rewrite_package_asset_refs(&root, None)
+}
+
+// ====== 字型子集化與 @font-face ======
+
+const FONT_EXTS: [&str; 4] = ["ttf", "otf", "woff", "woff2"];
+
+fn module_font_files(module_name: &str) -> Vec<PathBuf> {
+    let dir = PathBuf::from("design-assets").join(module_name).join("fonts");
+    let mut fonts: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .map(|rd| rd.flatten().map(|e| e.path())
+            .filter(|p| p.is_file() && p.extension().and_then(|e| e.to_str()).map(|e| FONT_EXTS.contains(&e.to_lowercase().as_str())).unwrap_or(false))
+            .collect())
+        .unwrap_or_default();
+    fonts.sort();
+    fonts
+}
+
+fn font_stylesheet_link(module_name: &str) -> String {
+    if module_font_files(module_name).is_empty() {
+        String::new()
+    } else {
+        "    <link rel=\"stylesheet\" href=\"fonts.css\">\n".to_string()
+    }
+}
+
+// 去除標籤後的可見文字
+fn strip_html_tags(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => { in_tag = false; out.push(' '); }
+            _ if !in_tag => out.push(ch),
+            _ => {}
+        }
+    }
+    out
+}
+
+// 模組內 HTML 文案與頁面標題所用到的字元（去重）
+fn module_copy_characters(module_name: &str) -> String {
+    let mut files = Vec::new();
+    collect_files_recursive(&PathBuf::from("design-assets").join(module_name), &mut files);
+    let mut text = String::new();
+    for f in files.iter().filter(|f| matches!(f.extension().and_then(|e| e.to_str()), Some("html" | "htm"))) {
+        if let Ok(html) = std::fs::read_to_string(f) {
+            text.push_str(&strip_html_tags(&html));
+        }
+    }
+    for entry in collect_module_page_entries(module_name) {
+        if let Some(t) = entry.meta.title { text.push_str(&t); }
+    }
+    let mut chars: Vec<char> = text.chars().filter(|c| !c.is_control()).collect();
+    chars.sort_unstable();
+    chars.dedup();
+    chars.into_iter().collect()
+}
+
+// 由檔名推測 font-family / weight / style（例如 Inter-BoldItalic）
+fn font_face_descriptor(stem: &str) -> (String, u16, &'static str) {
+    let lower = stem.to_lowercase();
+    let weights = [
+        ("thin", 100), ("extralight", 200), ("ultralight", 200), ("light", 300), ("regular", 400), ("book", 400),
+        ("medium", 500), ("semibold", 600), ("demibold", 600), ("extrabold", 800), ("ultrabold", 800), ("bold", 700), ("black", 900), ("heavy", 900),
+    ];
+    let weight = weights.iter().find(|(k, _)| lower.contains(k)).map(|(_, w)| *w).unwrap_or(400);
+    let style = if lower.contains("italic") || lower.contains("oblique") { "italic" } else { "normal" };
+    let family = stem.split(['-', '_']).next().unwrap_or(stem).to_string();
+    (family, weight, style)
+}
+
+fn font_format(ext: &str) -> &'static str {
+    match ext {
+        "woff2" => "woff2",
+        "woff" => "woff",
+        "otf" => "opentype",
+        _ => "truetype",
+    }
+}
+
+// 若模組有字型：以 pyftsubset 子集化為 woff2 輸出至 fonts/，並產生 fonts.css；工具不可用時沿用原檔
+fn generate_font_assets(module_name: &str, output_dir: &std::path::Path, strategy: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let fonts = module_font_files(module_name);
+    if fonts.is_empty() {
+        return Ok(());
+    }
+    let fonts_out = output_dir.join("fonts");
+    std::fs::create_dir_all(&fonts_out)?;
+    let unicode_range = read_active_project_config().and_then(|c| c.font_unicode_range).filter(|r| !r.trim().is_empty());
+    let copy_text = if unicode_range.is_none() { module_copy_characters(module_name) } else { String::new() };
+    let text_file = fonts_out.join(".subset-text.txt");
+    if !copy_text.is_empty() {
+        std::fs::write(&text_file, &copy_text)?;
+    }
+
+    let mut css = format!("/* {} 字型（ErSlice 自動產生） */\n", module_name);
+    for font in fonts.iter() {
+        let stem = font.file_stem().and_then(|s| s.to_str()).unwrap_or("font").to_string();
+        let ext = font.extension().and_then(|e| e.to_str()).unwrap_or("ttf").to_lowercase();
+        let subset_name = format!("{}.subset.woff2", stem);
+        let subset_path = fonts_out.join(&subset_name);
+        let mut cmd = std::process::Command::new("pyftsubset");
+        cmd.arg(font).arg(format!("--output-file={}", subset_path.display())).arg("--flavor=woff2").arg("--layout-features=*");
+        match (&unicode_range, copy_text.is_empty()) {
+            (Some(range), _) => { cmd.arg(format!("--unicodes={}", range.trim())); }
+            (None, false) => { cmd.arg(format!("--text-file={}", text_file.display())); }
+            (None, true) => { cmd.arg("--unicodes=U+0020-007E"); }
+        }
+        let subset_ok = matches!(cmd.output(), Ok(o) if o.status.success()) && subset_path.exists();
+        let (file_name, format) = if subset_ok {
+            // 資產複製時帶入的原始字型不再需要，移除以縮小輸出包
+            if let Some(name) = font.file_name() { let _ = std::fs::remove_file(fonts_out.join(name)); }
+            (subset_name, "woff2")
+        } else {
+            log::warn!("字型子集化失敗或 pyftsubset 不可用，沿用原始字型: {}", font.display());
+            let name = font.file_name().and_then(|n| n.to_str()).unwrap_or("font").to_string();
+            copy_file_with_strategy(font, &fonts_out.join(&name), "overwrite")?;
+            (name, font_format(&ext))
+        };
+        let (family, weight, style) = font_face_descriptor(&stem);
+        css.push_str(&format!(
+            "\n@font-face {{\n    font-family: \"{}\";\n    src: url(\"fonts/{}\") format(\"{}\");\n    font-weight: {};\n    font-style: {};\n    font-display: swap;\n{}}}\n",
+            family, file_name, format, weight, style,
+            unicode_range.as_ref().map(|r| format!("    unicode-range: {};\n", r.trim())).unwrap_or_default()
+        ));
+    }
+    let _ = std::fs::remove_file(&text_file);
+    write_text_with_strategy(&output_dir.join("fonts.css"), &css, strategy)?;
+    Ok(())
+}
+
+// ====== 跨模組/頁面搬移與複製資產 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AssetLocation {
+    pub module_name: String,
+    pub page: Option<String>,
+    pub subpage: Option<String>,
+    pub asset_type: String,
+}
+
+impl AssetLocation {
+    fn dir(&self) -> Result<PathBuf, String> {
+        let mut base = PathBuf::from("design-assets").join(&self.module_name);
+        if let Some(page) = &self.page {
+            base = base.join("pages").join(page);
+            if let Some(sub) = &self.subpage {
+                base = base.join("subpages").join(sub);
+            }
+        } else if self.subpage.is_some() {
+            return Err("指定子頁時必須同時指定頁面".to_string());
+        }
+        if !base.exists() {
+            return Err("資產路徑不存在".to_string());
+        }
+        asset_type_dir(&base, &self.asset_type)
+    }
+}
+
+// 依覆寫策略決定目標路徑；skip 且已存在時回傳 None
+fn resolve_transfer_target(dest: &PathBuf, strategy: &str) -> Option<PathBuf> {
+    match strategy {
+        "skip" if dest.exists() => None,
+        "rename" if dest.exists() => Some(next_available_path(dest)),
+        "version" => {
+            if let Err(e) = archive_existing_version(dest) { log::warn!("封存舊版本失敗: {}", e); }
+            Some(dest.clone())
+        }
+        _ => Some(dest.clone()),
+    }
+}
+
+fn transfer_design_asset(source: &AssetLocation, destination: &AssetLocation, file_name: &str, strategy: &str, keep_source: bool) -> Result<String, String> {
+    let from = source.dir()?.join(file_name);
+    if !from.is_file() {
+        return Err("檔案不存在".to_string());
+    }
+    let dest_dir = destination.dir()?;
+    std::fs::create_dir_all(&dest_dir).map_err(|e| format!("無法建立資產類型目錄: {}", e))?;
+    // 須在決定目標前檢查：version 策略會先把既有的目標（即來源本身）封存走
+    if dest_dir.join(file_name) == from {
+        return Err("來源與目的地相同".to_string());
+    }
+    let Some(to) = resolve_transfer_target(&dest_dir.join(file_name), strategy) else {
+        return Ok(format!("目標已存在，略過: {}", dest_dir.join(file_name).display()));
+    };
+    if keep_source {
+        std::fs::copy(&from, &to).map_err(|e| format!("複製檔案失敗: {}", e))?;
+    } else if std::fs::rename(&from, &to).is_err() {
+        // 跨裝置時 rename 會失敗，改為複製後刪除
+        std::fs::copy(&from, &to).map_err(|e| format!("搬移檔案失敗: {}", e))?;
+        std::fs::remove_file(&from).map_err(|e| format!("刪除來源檔案失敗: {}", e))?;
+    }
+
+    // 標籤跟著檔案走（複製時兩邊都保留）
+    if let (Some(from_base), Some(to_base)) = (from.parent().and_then(|p| p.parent()), to.parent().and_then(|p| p.parent())) {
+        let from_key = format!("{}/{}", source.asset_type, file_name);
+        let to_key = format!("{}/{}", destination.asset_type, to.file_name().and_then(|n| n.to_str()).unwrap_or(file_name));
+        if keep_source {
+            if let Some(tags) = read_asset_tags(from_base).get(&from_key).cloned() {
+                let mut to_tags = read_asset_tags(to_base);
+                to_tags.insert(to_key, tags);
+                let _ = write_asset_tags(to_base, &to_tags);
+            }
+        } else {
+            move_asset_tags(from_base, &from_key, Some((to_base, to_key)));
+        }
+    }
+
+    if let Err(e) = crate::database::relocate_asset_rows(
+        &from.to_string_lossy(),
+        &to.to_string_lossy(),
+        &destination.asset_type,
+        &destination.module_name,
+        destination.page.as_deref(),
+        destination.subpage.as_deref(),
+        keep_source,
+    ) {
+        log::warn!("更新資產記錄失敗: {}", e);
+    }
+
+    let mut cache = SITEMAP_CACHE.lock().unwrap();
+    cache.invalidate_module(&source.module_name);
+    cache.invalidate_module(&destination.module_name);
+    Ok(format!("{}: {}", if keep_source { "已複製" } else { "已搬移" }, to.display()))
+}
+
+/// 將資產搬移到其他模組/頁面/子頁（strategy：skip / rename / overwrite）
+#[tauri::command]
+pub async fn move_design_asset(source: AssetLocation, destination: AssetLocation, file_name: String, strategy: String) -> Result<String, String> {
+    transfer_design_asset(&source, &destination, &file_name, &strategy, false)
+}
+
+/// 將資產複製到其他模組/頁面/子頁（strategy：skip / rename / overwrite）
+#[tauri::command]
+pub async fn copy_design_asset(source: AssetLocation, destination: AssetLocation, file_name: String, strategy: String) -> Result<String, String> {
+    transfer_design_asset(&source, &destination, &file_name, &strategy, true)
+}
+
+// ====== 資產標籤 ======
+
+// 每個資產目錄（模組/頁面/子頁）旁的標籤 sidecar：{ "<type>/<file>": ["tag", ...] }
+const ASSET_TAGS_FILE: &str = "asset-tags.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaggedAsset {
+    pub asset_path: String,
+    pub asset_type: String,
+    pub file_name: String,
+    pub path: String,
+    pub tags: Vec<String>,
+}
+
+fn read_asset_tags(base_dir: &std::path::Path) -> std::collections::BTreeMap<String, Vec<String>> {
+    std::fs::read_to_string(base_dir.join(ASSET_TAGS_FILE))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_asset_tags(base_dir: &std::path::Path, tags: &std::collections::BTreeMap<String, Vec<String>>) -> Result<(), String> {
+    let path = base_dir.join(ASSET_TAGS_FILE);
+    if tags.is_empty() {
+        if path.exists() { std::fs::remove_file(&path).map_err(|e| e.to_string())?; }
+        return Ok(());
+    }
+    let json = serde_json::to_string_pretty(tags).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("寫入資產標籤失敗: {}", e))
+}
+
+// 資產改名/搬移/刪除時同步標籤與標註；to 為 None 表示移除
+fn move_asset_tags(from_base: &std::path::Path, from_key: &str, to: Option<(&std::path::Path, String)>) {
+    move_asset_annotations(from_base, from_key, to.clone());
+    let mut from_tags = read_asset_tags(from_base);
+    let Some(tags) = from_tags.remove(from_key) else { return };
+    match to {
+        Some((to_base, to_key)) if to_base == from_base => {
+            from_tags.insert(to_key, tags);
+            let _ = write_asset_tags(from_base, &from_tags);
+        }
+        Some((to_base, to_key)) => {
+            let _ = write_asset_tags(from_base, &from_tags);
+            let mut to_tags = read_asset_tags(to_base);
+            to_tags.insert(to_key, tags);
+            let _ = write_asset_tags(to_base, &to_tags);
+        }
+        None => {
+            let _ = write_asset_tags(from_base, &from_tags);
+        }
+    }
+}
+
+/// 設定單一資產檔案的標籤（空陣列即清除）
+#[tauri::command]
+pub async fn set_asset_tags(asset_path: String, asset_type: String, file_name: String, tags: Vec<String>) -> Result<Vec<String>, String> {
+    let base_dir = PathBuf::from("design-assets").join(&asset_path);
+    let target = asset_type_dir(&base_dir, &asset_type)?.join(&file_name);
+    if !target.is_file() {
+        return Err("檔案不存在".to_string());
+    }
+    let mut normalized: Vec<String> = tags.iter().map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty()).collect();
+    normalized.sort();
+    normalized.dedup();
+    let mut all = read_asset_tags(&base_dir);
+    let key = format!("{}/{}", asset_type, file_name);
+    if normalized.is_empty() {
+        all.remove(&key);
+    } else {
+        all.insert(key, normalized.clone());
+    }
+    write_asset_tags(&base_dir, &all)?;
+    Ok(normalized)
+}
+
+fn collect_tagged_assets(dir: &std::path::Path, root: &std::path::Path, out: &mut Vec<TaggedAsset>) {
+    for (key, tags) in read_asset_tags(dir) {
+        let Some((asset_type, file_name)) = key.split_once('/') else { continue };
+        let asset_path = dir.strip_prefix(root).unwrap_or(dir).to_string_lossy().replace('\\', "/");
+        out.push(TaggedAsset {
+            asset_path,
+            asset_type: asset_type.to_string(),
+            file_name: file_name.to_string(),
+            path: dir.join(asset_type).join(file_name).to_string_lossy().to_string(),
+            tags,
+        });
+    }
+    let categories = asset_categories();
+    for name in list_dir_names(dir) {
+        if categories.contains(&name) { continue; }
+        collect_tagged_assets(&dir.join(name), root, out);
+    }
+}
+
+/// 跨模組列出帶有指定標籤的資產
+#[tauri::command]
+pub async fn list_assets_by_tag(tag: String) -> Result<Vec<TaggedAsset>, String> {
+    let root = PathBuf::from("design-assets");
+    let tag = tag.trim().to_lowercase();
+    let mut all = Vec::new();
+    collect_tagged_assets(&root, &root, &mut all);
+    Ok(all.into_iter().filter(|a| a.tags.contains(&tag)).collect())
+}
+
+// ====== 頁面 overlays（modal / drawer / toast） ======
+
+const OVERLAY_KINDS: [&str; 3] = ["modal", "drawer", "toast"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PageOverlay {
+    pub kind: String,
+    pub name: String,
+    // 觸發條件，例如「點擊刪除」、「儲存成功後」
+    pub trigger: Option<String>,
+    pub description: Option<String>,
+}
+
+fn overlay_icon(kind: &str) -> &'static str {
+    match kind {
+        "drawer" => "📂",
+        "toast" => "🔔",
+        _ => "🪟",
+    }
+}
+
+fn render_declared_overlays(buf: &mut String, page_id: &str, overlays: &[PageOverlay]) {
+    for (i, ov) in overlays.iter().enumerate() {
+        let oid = format!("{}_overlay_{}", page_id, i);
+        let trigger = ov.trigger.as_deref().filter(|t| !t.is_empty()).unwrap_or(ov.kind.as_str());
+        let desc = ov.description.as_ref().map(|d| format!("\\n{}", d)).unwrap_or_default();
+        buf.push_str(&format!(
+            "  {} -.->|{}| {}[\\\"{} {}{}\\\"]\n",
+            page_id, trigger.replace('|', "/"), oid, overlay_icon(&ov.kind), ov.name, desc
+        ));
+        buf.push_str(&format!("  class {} {}\n", oid, ov.kind));
+    }
+}
+
+fn overlays_spec_section(module_name: &str) -> String {
+    let entries: Vec<PageEntry> = collect_module_page_entries(module_name)
+        .into_iter()
+        .filter(|e| e.meta.overlays.as_ref().is_some_and(|o| !o.is_empty()))
+        .collect();
+    if entries.is_empty() { return String::new(); }
+    let lang = project_spec_language();
+    let mut out = String::from(lang.pick(
+        "\n## Overlays（Modal / Drawer / Toast）\n以下為各頁面宣告的浮層元件與觸發條件：\n\n| 頁面 | 類型 | 名稱 | 觸發條件 | 說明 |\n|---|---|---|---|---|\n",
+        "\n## Overlays (Modal / Drawer / Toast)\nOverlay components declared on each page and what triggers them:\n\n| Page | Type | Name | Trigger | Description |\n|---|---|---|---|---|\n",
+        "\n## オーバーレイ（Modal / Drawer / Toast）\n各ページで宣言されたオーバーレイコンポーネントと表示条件：\n\n| ページ | 種類 | 名前 | 表示条件 | 説明 |\n|---|---|---|---|---|\n",
+    ));
+    for e in entries.iter() {
+        for ov in e.meta.overlays.iter().flatten() {
+            out.push_str(&format!(
+                "| `{}` | {} | {} | {} | {} |\n",
+                e.route_path(), ov.kind, ov.name,
+                ov.trigger.clone().unwrap_or_else(|| "-".into()),
+                ov.description.clone().unwrap_or_default()
+            ));
+        }
+    }
+    out
+}
+
+/// 設定頁面（或子頁）宣告的 overlays；傳入空陣列表示此頁沒有浮層
+#[tauri::command]
+pub async fn set_page_overlays(module_name: String, parent_slug: Option<String>, slug: String, overlays: Vec<PageOverlay>) -> Result<String, String> {
+    let dir = resolve_page_dir(&module_name, parent_slug.as_deref(), &slug);
+    if !dir.exists() { return Err("頁面不存在".into()); }
+    let mut cleaned: Vec<PageOverlay> = Vec::new();
+    for mut ov in overlays.into_iter() {
+        ov.kind = ov.kind.trim().to_lowercase();
+        ov.name = ov.name.trim().to_string();
+        if !OVERLAY_KINDS.contains(&ov.kind.as_str()) {
+            return Err(format!("不支援的 overlay 類型: {}（可用：{}）", ov.kind, OVERLAY_KINDS.join(", ")));
+        }
+        if ov.name.is_empty() { return Err("overlay 名稱不可為空".into()); }
+        cleaned.push(ov);
+    }
+    let mut meta = read_page_meta(&dir);
+    meta.overlays = Some(cleaned);
+    write_page_meta(&dir, &meta)?;
+    {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(&module_name);
+    }
+    Ok("已更新頁面 overlays".into())
+}
+
+// ====== Empty / Error state 定義 ======
+
+fn has_text(v: &Option<String>) -> bool {
+    v.as_ref().is_some_and(|s| !s.trim().is_empty())
+}
+
+fn pages_missing_state_definitions() -> Vec<String> {
+    collect_page_entries()
+        .into_iter()
+        .filter(|e| !has_text(&e.meta.empty_state) || !has_text(&e.meta.error_state))
+        .map(|e| e.route_path())
+        .collect()
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// 骨架 HTML 中的空狀態/錯誤狀態佔位區塊（預設隱藏）
+fn page_state_placeholders(module_name: &str) -> String {
+    let mut out = String::new();
+    for e in collect_module_page_entries(module_name) {
+        let route = e.route_path();
+        if has_text(&e.meta.empty_state) || has_text(&e.meta.error_state) {
+            out.push_str(&format!("            <!-- {} -->\n", route));
+        }
+        if let Some(copy) = e.meta.empty_state.as_ref().filter(|s| !s.trim().is_empty()) {
+            out.push_str(&format!("            <section class=\"empty-state\" data-page=\"{}\" hidden>\n                <p>{}</p>\n            </section>\n", route, escape_html(copy)));
+        }
+        if let Some(behavior) = e.meta.error_state.as_ref().filter(|s| !s.trim().is_empty()) {
+            out.push_str(&format!("            <section class=\"error-state\" data-page=\"{}\" role=\"alert\" hidden>\n                <p>{}</p>\n            </section>\n", route, escape_html(behavior)));
+        }
+    }
+    out
+}
+
+fn page_states_spec_section(module_name: &str) -> String {
+    let entries = collect_module_page_entries(module_name);
+    if entries.is_empty() { return String::new(); }
+    let lang = project_spec_language();
+    let undefined = lang.pick("⚠️ 尚未定義", "⚠️ Not defined", "⚠️ 未定義");
+    let colon = lang.pick("：", ": ", "：");
+    let mut out = String::from(lang.pick(
+        "\n## 空狀態（Empty State）\n資料為空時顯示的文案與行為：\n\n",
+        "\n## Empty State\nCopy and behaviour shown when there is no data:\n\n",
+        "\n## 空状態（Empty State）\nデータが空のときに表示する文言と動作：\n\n",
+    ));
+    for e in entries.iter() {
+        out.push_str(&format!("- `{}`{}{}\n", e.route_path(), colon, e.meta.empty_state.as_deref().filter(|s| !s.trim().is_empty()).unwrap_or(undefined)));
+    }
+    out.push_str(lang.pick(
+        "\n## 錯誤狀態（Error State）\n載入或操作失敗時的處理方式：\n\n",
+        "\n## Error State\nHow failures while loading or performing actions are handled:\n\n",
+        "\n## エラー状態（Error State）\n読み込みや操作に失敗したときの対応：\n\n",
+    ));
+    for e in entries.iter() {
+        out.push_str(&format!("- `{}`{}{}\n", e.route_path(), colon, e.meta.error_state.as_deref().filter(|s| !s.trim().is_empty()).unwrap_or(undefined)));
+    }
+    out
+}
+
+/// 設定頁面（或子頁）的空狀態文案與錯誤狀態行為；傳入 None 表示不變更，空字串表示清除
+#[tauri::command]
+pub async fn set_page_states(module_name: String, parent_slug: Option<String>, slug: String, empty_state: Option<String>, error_state: Option<String>) -> Result<String, String> {
+    let dir = resolve_page_dir(&module_name, parent_slug.as_deref(), &slug);
+    if !dir.exists() { return Err("頁面不存在".into()); }
+    let mut meta = read_page_meta(&dir);
+    if let Some(v) = empty_state { meta.empty_state = Some(v.trim().to_string()).filter(|s| !s.is_empty()); }
+    if let Some(v) = error_state { meta.error_state = Some(v.trim().to_string()).filter(|s| !s.is_empty()); }
+    write_page_meta(&dir, &meta)?;
+    {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(&module_name);
+    }
+    Ok("已更新頁面狀態定義".into())
+}
+
+// ====== 圖片壓縮 ======
+
+#[derive(Debug, Clone)]
+struct ImageCompressionSettings {
+    quality: u8,
+    min_size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CompressionReport {
+    pub files_scanned: usize,
+    pub files_compressed: usize,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub bytes_saved: u64,
+    pub errors: Vec<String>,
+}
+
+fn image_compression_settings(quality: Option<u8>, min_size_kb: Option<u64>) -> ImageCompressionSettings {
+    let cfg = read_active_project_config();
+    let quality = quality.or(cfg.as_ref().and_then(|c| c.image_quality)).unwrap_or(82).clamp(1, 100);
+    let min_kb = min_size_kb.or(cfg.as_ref().and_then(|c| c.image_min_size_kb)).unwrap_or(500);
+    ImageCompressionSettings { quality, min_size: min_kb * 1024 }
+}
+
+// 重新編碼單一 PNG/JPEG；僅在檔案超過門檻且結果較小時取代，回傳 (原大小, 新大小)
+fn compress_image_file(path: &std::path::Path, settings: &ImageCompressionSettings) -> Result<Option<(u64, u64)>, String> {
+    use image::codecs::jpeg::JpegEncoder;
+    use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    if !matches!(ext.as_str(), "png" | "jpg" | "jpeg") {
+        return Ok(None);
+    }
+    let before = std::fs::metadata(path).map_err(|e| e.to_string())?.len();
+    if before < settings.min_size {
+        return Ok(None);
+    }
+    let img = image::open(path).map_err(|e| format!("讀取圖片失敗: {}", e))?;
+    let mut buf: Vec<u8> = Vec::new();
+    if ext == "png" {
+        let encoder = PngEncoder::new_with_quality(&mut buf, CompressionType::Best, FilterType::Adaptive);
+        img.write_with_encoder(encoder).map_err(|e| format!("PNG 編碼失敗: {}", e))?;
+    } else {
+        let encoder = JpegEncoder::new_with_quality(&mut buf, settings.quality);
+        img.to_rgb8().write_with_encoder(encoder).map_err(|e| format!("JPEG 編碼失敗: {}", e))?;
+    }
+    let after = buf.len() as u64;
+    if after >= before {
+        return Ok(None);
+    }
+    std::fs::write(path, &buf).map_err(|e| format!("寫入圖片失敗: {}", e))?;
+    Ok(Some((before, after)))
+}
+
+/// 壓縮模組（含各頁面/子頁）screenshots 內過大的 PNG/JPEG，回報節省空間
+#[tauri::command]
+pub async fn compress_module_screenshots(module_name: String, quality: Option<u8>, min_size_kb: Option<u64>) -> Result<CompressionReport, String> {
+    let module_dir = PathBuf::from("design-assets").join(&module_name);
+    if !module_dir.exists() {
+        return Err("設計模組不存在".to_string());
+    }
+    let settings = image_compression_settings(quality, min_size_kb);
+    let mut files = Vec::new();
+    collect_files_recursive(&module_dir, &mut files);
+    let mut report = CompressionReport::default();
+    for file in files.iter().filter(|f| f.parent().and_then(|p| p.file_name()).and_then(|n| n.to_str()) == Some("screenshots")) {
+        report.files_scanned += 1;
+        match compress_image_file(file, &settings) {
+            Ok(Some((before, after))) => {
+                report.files_compressed += 1;
+                report.bytes_before += before;
+                report.bytes_after += after;
+            }
+            Ok(None) => {}
+            Err(e) => report.errors.push(format!("{}: {}", file.display(), e)),
+        }
+    }
+    report.bytes_saved = report.bytes_before.saturating_sub(report.bytes_after);
+    Ok(report)
+}
+
+// ====== 鍵盤操作與焦點順序 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FocusOrderValidation {
+    pub focus_order: Vec<String>,
+    // 在元件清單中找不到的項目（仍會儲存，供修正參考）
+    pub unknown: Vec<String>,
+    pub inventory: Vec<String>,
+}
+
+const INTERACTIVE_TAGS: [&str; 5] = ["button", "a", "input", "select", "textarea"];
+
+fn html_attr(tag: &str, name: &str) -> Option<String> {
+    for quote in ['"', '\''] {
+        let pat = format!("{}={}", name, quote);
+        if let Some(i) = tag.find(&pat) {
+            let rest = &tag[i + pat.len()..];
+            if let Some(end) = rest.find(quote) {
+                return Some(rest[..end].to_string()).filter(|v| !v.is_empty());
+            }
+        }
+    }
+    None
+}
+
+// 頁面的互動元件清單：HTML 中互動標籤的 id/name/aria-label/data-component，加上 meta.component 與 overlays
+fn page_component_inventory(dir: &std::path::Path, meta: &PageMeta) -> Vec<String> {
+    let mut items: Vec<String> = Vec::new();
+    for name in get_files_in_dir(&dir.join("html")) {
+        let Ok(html) = std::fs::read_to_string(dir.join("html").join(&name)) else { continue };
+        // ASCII 小寫以保持位元組位置與原文一致
+        let lower = html.to_ascii_lowercase();
+        let mut pos = 0;
+        while let Some(i) = lower[pos..].find('<') {
+            let start = pos + i + 1;
+            let end = lower[start..].find('>').map(|e| start + e).unwrap_or(lower.len());
+            let tag = &html[start..end];
+            let tag_name: String = lower[start..end].chars().take_while(|c| c.is_ascii_alphanumeric()).collect();
+            if INTERACTIVE_TAGS.contains(&tag_name.as_str()) {
+                for attr in ["data-component", "id", "name", "aria-label"] {
+                    if let Some(v) = html_attr(tag, attr) { items.push(v); break; }
+                }
+            }
+            pos = end;
+        }
+    }
+    if let Some(c) = meta.component.as_ref().filter(|c| !c.is_empty()) { items.push(c.clone()); }
+    items.extend(meta.overlays.iter().flatten().map(|o| o.name.clone()));
+    items.sort();
+    items.dedup();
+    items
+}
+
+// 骨架 HTML 中的焦點順序佔位元素（預設隱藏）：依 DOM 順序排列並以 data-focus-order 標示，避免正數 tabindex
+fn focus_order_hints(module_name: &str) -> String {
+    let mut out = String::new();
+    for e in collect_module_page_entries(module_name) {
+        let Some(order) = e.meta.focus_order.as_ref().filter(|o| !o.is_empty()) else { continue };
+        let route = e.route_path();
+        out.push_str(&format!("            <!-- {} 鍵盤焦點順序 -->\n", route));
+        out.push_str(&format!("            <section class=\"focus-order\" data-page=\"{}\" hidden>\n", route));
+        for (i, item) in order.iter().enumerate() {
+            let item = escape_html(item);
+            out.push_str(&format!(
+                "                <div class=\"focus-order-item\" data-focus-order=\"{}\" data-focus-target=\"{}\" tabindex=\"0\">{}</div>\n",
+                i + 1,
+                item,
+                item
+            ));
+        }
+        out.push_str("            </section>\n");
+    }
+    out
+}
+
+fn keyboard_spec_section(module_name: &str) -> String {
+    let entries: Vec<PageEntry> = collect_module_page_entries(module_name)
+        .into_iter()
+        .filter(|e| e.meta.focus_order.as_ref().is_some_and(|o| !o.is_empty()))
+        .collect();
+    if entries.is_empty() { return String::new(); }
+    let mut out = String::from(project_spec_language().pick(
+        "\n## 鍵盤操作（Keyboard Interaction）\n- `Tab` / `Shift+Tab` 依下列順序移動焦點，焦點樣式需清楚可見\n- `Enter` / `Space` 觸發按鈕與連結\n- `Esc` 關閉 modal / drawer，並將焦點還原至觸發元件\n\n### 焦點順序\n",
+        "\n## Keyboard Interaction\n- `Tab` / `Shift+Tab` move focus in the order below; the focus style must be clearly visible\n- `Enter` / `Space` activate buttons and links\n- `Esc` closes modals / drawers and returns focus to the triggering element\n\n### Focus Order\n",
+        "\n## キーボード操作（Keyboard Interaction）\n- `Tab` / `Shift+Tab` で以下の順にフォーカスを移動し、フォーカススタイルは明確に表示する\n- `Enter` / `Space` でボタンとリンクを実行する\n- `Esc` で modal / drawer を閉じ、フォーカスをトリガー要素に戻す\n\n### フォーカス順序\n",
+    ));
+    for e in entries.iter() {
+        out.push_str(&format!("\n`{}`\n", e.route_path()));
+        for (i, item) in e.meta.focus_order.iter().flatten().enumerate() {
+            out.push_str(&format!("{}. {}\n", i + 1, item));
+        }
+    }
+    out
+}
+
+/// 設定頁面（或子頁）的焦點順序並與元件清單比對；不在清單中的項目會列於 unknown
+#[tauri::command]
+pub async fn set_page_focus_order(module_name: String, parent_slug: Option<String>, slug: String, focus_order: Vec<String>) -> Result<FocusOrderValidation, String> {
+    let dir = resolve_page_dir(&module_name, parent_slug.as_deref(), &slug);
+    if !dir.exists() { return Err("頁面不存在".into()); }
+    let order: Vec<String> = focus_order.iter().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
+    let mut seen = std::collections::HashSet::new();
+    if let Some(dup) = order.iter().find(|s| !seen.insert(s.as_str())) {
+        return Err(format!("焦點順序重複: {}", dup));
+    }
+    let mut meta = read_page_meta(&dir);
+    let inventory = page_component_inventory(&dir, &meta);
+    let unknown: Vec<String> = order.iter().filter(|s| !inventory.contains(s)).cloned().collect();
+    meta.focus_order = if order.is_empty() { None } else { Some(order.clone()) };
+    write_page_meta(&dir, &meta)?;
+    {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(&module_name);
+    }
+    Ok(FocusOrderValidation { focus_order: order, unknown, inventory })
+}
+
+// ====== SVG 最佳化 ======
+
+// 編輯器專用的命名空間（屬性與元素皆移除）
+const SVG_EDITOR_PREFIXES: [&str; 6] = ["inkscape:", "sodipodi:", "sketch:", "dc:", "cc:", "rdf:"];
+
+// 移除 <tag ...>...</tag> 或 <tag .../>
+fn remove_svg_element(svg: &str, tag: &str) -> String {
+    let mut out = svg.to_string();
+    let open = format!("<{}", tag);
+    let close = format!("</{}>", tag);
+    while let Some(start) = out.find(&open) {
+        // 確認是完整標籤名稱（避免 <metadata 比對到 <metadataX）
+        let after = out[start + open.len()..].chars().next();
+        if !matches!(after, Some(' ' | '>' | '/' | '\n' | '\r' | '\t')) { break; }
+        let Some(gt) = out[start..].find('>').map(|i| start + i) else { break };
+        let end = if out[..gt].ends_with('/') {
+            gt + 1
+        } else {
+            match out[gt..].find(&close) { Some(i) => gt + i + close.len(), None => break }
+        };
+        out.replace_range(start..end, "");
+    }
+    out
+}
+
+fn remove_between(svg: &str, open: &str, close: &str) -> String {
+    let mut out = svg.to_string();
+    while let Some(start) = out.find(open) {
+        let Some(end) = out[start..].find(close).map(|i| start + i + close.len()) else { break };
+        out.replace_range(start..end, "");
+    }
+    out
+}
+
+// 移除名稱以指定前綴開頭的屬性（含 xmlns:<prefix> 宣告）
+fn remove_prefixed_attrs(svg: &str, prefix: &str) -> String {
+    let mut out = svg.to_string();
+    let ns = format!("xmlns:{}", prefix.trim_end_matches(':'));
+    for pat in [format!(" {}", prefix), format!(" {}", ns)] {
+        let mut from = 0;
+        while let Some(i) = out[from..].find(&pat).map(|i| from + i) {
+            let Some(eq) = out[i..].find('=').map(|e| i + e) else { break };
+            let quote = out[eq + 1..].chars().next();
+            let Some(q) = quote.filter(|c| *c == '"' || *c == '\'') else { from = i + 1; continue };
+            let Some(end) = out[eq + 2..].find(q).map(|e| eq + 2 + e + 1) else { break };
+            out.replace_range(i..end, "");
+            from = i;
+        }
+    }
+    out
+}
+
+// 會被降低小數位數的幾何屬性
+const SVG_NUMERIC_ATTRS: [&str; 18] = [
+    "d", "points", "x", "y", "x1", "y1", "x2", "y2", "cx", "cy", "r", "rx", "ry",
+    "width", "height", "viewBox", "transform", "stroke-width",
+];
+
+fn format_svg_number(v: f64, precision: usize) -> String {
+    let mut s = format!("{:.*}", precision, v);
+    if s.contains('.') { s = s.trim_end_matches('0').trim_end_matches('.').to_string(); }
+    if s == "-0" { s = "0".into(); }
+    // 0.5 → .5、-0.5 → -.5（SVG 合法寫法）
+    if let Some(rest) = s.strip_prefix("0.") { s = format!(".{}", rest); }
+    if let Some(rest) = s.strip_prefix("-0.") { s = format!("-.{}", rest); }
+    s
+}
+
+// 將幾何屬性值中的小數四捨五入到指定位數
+fn reduce_svg_precision(svg: &str, precision: usize) -> String {
+    let mut out = String::with_capacity(svg.len());
+    let chars: Vec<char> = svg.chars().collect();
+    let (mut in_tag, mut quote, mut numeric): (bool, Option<char>, bool) = (false, None, false);
+    // 上一個輸出的數字是否含小數點（None 表示上一個字元不是數字）
+    let mut last_num_dot: Option<bool> = None;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if quote.is_none() {
+            if c == '<' { in_tag = true; } else if c == '>' { in_tag = false; } else if in_tag && (c == '"' || c == '\'') {
+                quote = Some(c);
+                let before: String = out.trim_end_matches('=').chars().rev().take_while(|ch| !ch.is_whitespace()).collect();
+                let attr: String = before.chars().rev().collect();
+                numeric = SVG_NUMERIC_ATTRS.contains(&attr.as_str());
+                last_num_dot = None;
+            }
+            out.push(c);
+            i += 1;
+            continue;
+        }
+        if Some(c) == quote { quote = None; out.push(c); i += 1; continue; }
+        let starts_number = c.is_ascii_digit() || (c == '.' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit()));
+        if !numeric || !starts_number {
+            out.push(c);
+            last_num_dot = None;
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < chars.len() && chars[i].is_ascii_digit() { i += 1; }
+        if i + 1 < chars.len() && chars[i] == '.' && chars[i + 1].is_ascii_digit() {
+            i += 1;
+            while i < chars.len() && chars[i].is_ascii_digit() { i += 1; }
+        }
+        // 指數表示法維持原樣
+        if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+            let token: String = chars[start..i].iter().collect();
+            out.push_str(&token);
+            last_num_dot = None;
+            continue;
+        }
+        let token: String = chars[start..i].iter().collect();
+        let negative = out.ends_with('-');
+        let mut s = if token.contains('.') {
+            token.parse::<f64>().map(|v| format_svg_number(v, precision)).unwrap_or_else(|_| token.clone())
+        } else {
+            token.clone()
+        };
+        if negative && s == "0" { s = token.clone(); }
+        // 與前一個數字相連時，確保不會黏在一起改變意義
+        if let Some(prev_dot) = last_num_dot {
+            if !s.starts_with('.') || !prev_dot { out.push(' '); }
+        }
+        last_num_dot = Some(s.contains('.'));
+        out.push_str(&s);
+    }
+    out
+}
+
+fn minify_svg(svg: &str, precision: Option<usize>) -> String {
+    let mut out = remove_between(svg, "<!--", "-->");
+    out = remove_between(&out, "<?xml", "?>");
+    out = remove_between(&out, "<!DOCTYPE", ">");
+    out = remove_svg_element(&out, "metadata");
+    out = remove_svg_element(&out, "sodipodi:namedview");
+    out = remove_svg_element(&out, "rdf:RDF");
+    for prefix in SVG_EDITOR_PREFIXES {
+        out = remove_prefixed_attrs(&out, prefix);
+    }
+    if let Some(p) = precision {
+        out = reduce_svg_precision(&out, p);
+    }
+    // 收斂空白：標籤間空白移除，其餘連續空白合併
+    let mut collapsed = String::with_capacity(out.len());
+    let mut last_space = false;
+    for ch in out.chars() {
+        if ch.is_whitespace() {
+            if !last_space { collapsed.push(' '); }
+            last_space = true;
+        } else {
+            last_space = false;
+            collapsed.push(ch);
+        }
+    }
+    collapsed.replace("> <", "><").replace(" />", "/>").trim().to_string()
+}
+
+/// 最佳化模組內（含頁面/子頁）所有 SVG：移除編輯器 metadata、收斂空白，precision 指定時降低小數位數
+#[tauri::command]
+pub async fn optimize_module_svgs(module_name: String, precision: Option<u8>) -> Result<CompressionReport, String> {
+    let module_dir = PathBuf::from("design-assets").join(&module_name);
+    if !module_dir.exists() {
+        return Err("設計模組不存在".to_string());
+    }
+    let mut files = Vec::new();
+    collect_files_recursive(&module_dir, &mut files);
+    let mut report = CompressionReport::default();
+    for file in files.iter().filter(|f| f.extension().and_then(|e| e.to_str()).is_some_and(|e| e.eq_ignore_ascii_case("svg"))) {
+        report.files_scanned += 1;
+        let raw = match std::fs::read_to_string(file) {
+            Ok(r) => r,
+            Err(e) => { report.errors.push(format!("{}: {}", file.display(), e)); continue; }
+        };
+        let minified = minify_svg(&raw, precision.map(|p| p as usize));
+        let (before, after) = (raw.len() as u64, minified.len() as u64);
+        if after >= before { continue; }
+        if let Err(e) = std::fs::write(file, &minified) {
+            report.errors.push(format!("{}: {}", file.display(), e));
+            continue;
+        }
+        report.files_compressed += 1;
+        report.bytes_before += before;
+        report.bytes_after += after;
+    }
+    report.bytes_saved = report.bytes_before.saturating_sub(report.bytes_after);
+    Ok(report)
+}
+
+// ====== 動效規格（Motion） ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MotionSpec {
+    // 元件名稱（會轉為 CSS class 與自訂屬性名稱）
+    pub component: String,
+    pub page: Option<String>,
+    // 觸發時機：hover / focus / enter / exit / press 等
+    pub trigger: String,
+    // 動畫屬性，例如 "opacity, transform"
+    pub properties: Option<String>,
+    pub duration_ms: u32,
+    pub easing: Option<String>,
+    pub delay_ms: Option<u32>,
+    pub description: Option<String>,
+}
+
+fn module_motion_path(module_name: &str) -> PathBuf {
+    PathBuf::from("design-assets").join(module_name).join("motion.json")
+}
+
+fn read_module_motion(module_name: &str) -> Vec<MotionSpec> {
+    std::fs::read_to_string(module_motion_path(module_name))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn css_ident(s: &str) -> String {
+    let mut out = String::new();
+    for ch in s.trim().chars() {
+        if ch.is_ascii_alphanumeric() { out.push(ch.to_ascii_lowercase()); } else if !out.ends_with('-') { out.push('-'); }
+    }
+    out.trim_matches('-').to_string()
+}
+
+fn motion_var_name(m: &MotionSpec) -> String {
+    format!("{}-{}", css_ident(&m.component), css_ident(&m.trigger))
+}
+
+fn motion_css(module_name: &str) -> String {
+    let motion = read_module_motion(module_name);
+    if motion.is_empty() { return String::new(); }
+    let mut out = String::from("\n\n/* 動效（來自 motion.json） */\n:root {\n");
+    for m in motion.iter() {
+        let var = motion_var_name(m);
+        out.push_str(&format!("    --motion-{}-duration: {}ms;\n", var, m.duration_ms));
+        out.push_str(&format!("    --motion-{}-easing: {};\n", var, m.easing.as_deref().unwrap_or("ease")));
+        if let Some(d) = m.delay_ms { out.push_str(&format!("    --motion-{}-delay: {}ms;\n", var, d)); }
+    }
+    out.push_str("}\n");
+    for m in motion.iter() {
+        let var = motion_var_name(m);
+        let props = m.properties.as_deref().filter(|p| !p.trim().is_empty()).unwrap_or("all");
+        let transition = props.split(',').map(|p| p.trim()).filter(|p| !p.is_empty())
+            .map(|p| format!("{} var(--motion-{var}-duration) var(--motion-{var}-easing){}", p,
+                if m.delay_ms.is_some() { format!(" var(--motion-{}-delay)", var) } else { String::new() }, var = var))
+            .collect::<Vec<_>>()
+            .join(", ");
+        out.push_str(&format!("\n/* {} — {} */\n.{} {{\n    transition: {};\n}}\n", m.component, m.trigger, css_ident(&m.component), transition));
+    }
+    out.push_str("\n@media (prefers-reduced-motion: reduce) {\n    * {\n        transition-duration: 0.01ms !important;\n        animation-duration: 0.01ms !important;\n    }\n}\n");
+    out
+}
+
+fn motion_markdown(module_name: &str, motion: &[MotionSpec]) -> String {
+    let mut out = format!("# {} 動效規格\n\n| 元件 | 頁面 | 觸發 | 屬性 | 時長 | Easing | 延遲 | 說明 |\n|---|---|---|---|---|---|---|---|\n", module_name);
+    for m in motion.iter() {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {}ms | {} | {} | {} |\n",
+            m.component,
+            m.page.clone().unwrap_or_else(|| "-".into()),
+            m.trigger,
+            m.properties.clone().unwrap_or_else(|| "all".into()),
+            m.duration_ms,
+            m.easing.clone().unwrap_or_else(|| "ease".into()),
+            m.delay_ms.map(|d| format!("{}ms", d)).unwrap_or_else(|| "-".into()),
+            m.description.clone().unwrap_or_default()
+        ));
+    }
+    out.push_str("\n實作時請使用 `styles.css` 中的 `--motion-*` 自訂屬性，並尊重 `prefers-reduced-motion`。\n");
+    out
+}
+
+/// 取得模組的動效規格
+#[tauri::command]
+pub async fn get_module_motion(module_name: String) -> Result<Vec<MotionSpec>, String> {
+    if !PathBuf::from("design-assets").join(&module_name).exists() {
+        return Err("設計模組不存在".to_string());
+    }
+    Ok(read_module_motion(&module_name))
+}
+
+/// 設定模組的動效規格（整份取代）
+#[tauri::command]
+pub async fn set_module_motion(module_name: String, motion: Vec<MotionSpec>) -> Result<String, String> {
+    if !PathBuf::from("design-assets").join(&module_name).exists() {
+        return Err("設計模組不存在".to_string());
+    }
+    for m in motion.iter() {
+        if css_ident(&m.component).is_empty() { return Err("元件名稱不可為空".into()); }
+        if css_ident(&m.trigger).is_empty() { return Err(format!("{}: 觸發時機不可為空", m.component)); }
+    }
+    let json = serde_json::to_string_pretty(&motion).map_err(|e| e.to_string())?;
+    std::fs::write(module_motion_path(&module_name), json).map_err(|e| format!("寫入 motion.json 失敗: {}", e))?;
+    Ok(format!("已更新 {} 筆動效規格", motion.len()))
+}
+
+// ====== 內容雜湊去重 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DuplicateGroup {
+    pub hash: String,
+    pub size: u64,
+    // 保留的檔案（依路徑排序的第一個）
+    pub kept: String,
+    pub duplicates: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DedupeReport {
+    pub module: String,
+    pub action: String,
+    pub files_scanned: usize,
+    pub groups: Vec<DuplicateGroup>,
+    pub bytes_reclaimable: u64,
+    pub errors: Vec<String>,
+}
+
+// 先在 dup 旁建立暫存硬連結再覆蓋 dup；任何一步失敗時保留原檔
+fn replace_with_hard_link(kept: &std::path::Path, dup: &std::path::Path) -> std::io::Result<()> {
+    let name = dup.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let tmp = dup.with_file_name(format!(".{}.erslice-link", name));
+    let _ = std::fs::remove_file(&tmp);
+    std::fs::hard_link(kept, &tmp)?;
+    std::fs::rename(&tmp, dup).inspect_err(|_| {
+        let _ = std::fs::remove_file(&tmp);
+    })
+}
+
+/// 以 SHA-256 找出模組內（screenshots/html/css/fonts，含頁面與子頁）的重複資產；
+/// action："report"（預設，只回報）、"remove"（刪除重複檔）、"hardlink"（以硬連結取代）
+#[tauri::command]
+pub async fn dedupe_module_assets(module_name: String, action: Option<String>) -> Result<DedupeReport, String> {
+    let module_dir = PathBuf::from("design-assets").join(&module_name);
+    if !module_dir.exists() {
+        return Err("設計模組不存在".to_string());
+    }
+    let action = action.unwrap_or_else(|| "report".to_string());
+    if !["report", "remove", "hardlink"].contains(&action.as_str()) {
+        return Err(format!("不支援的處理方式: {}", action));
+    }
+    let mut files = Vec::new();
+    collect_files_recursive(&module_dir, &mut files);
+    let categories = asset_categories();
+    let mut files: Vec<PathBuf> = files.into_iter()
+        .filter(|f| f.parent().and_then(|p| p.file_name()).and_then(|n| n.to_str()).is_some_and(|n| categories.iter().any(|c| c == n)))
+        .collect();
+    files.sort();
+
+    let mut report = DedupeReport { module: module_name.clone(), action: action.clone(), files_scanned: files.len(), ..Default::default() };
+    // 先依大小分組，只對可能重複的檔案計算雜湊
+    let mut by_size: std::collections::BTreeMap<u64, Vec<PathBuf>> = std::collections::BTreeMap::new();
+    for f in files.into_iter() {
+        if let Ok(m) = std::fs::metadata(&f) { by_size.entry(m.len()).or_default().push(f); }
+    }
+    for (size, candidates) in by_size.into_iter().filter(|(_, v)| v.len() > 1) {
+        let mut by_hash: std::collections::BTreeMap<String, Vec<PathBuf>> = std::collections::BTreeMap::new();
+        for f in candidates {
+            match sha256_file(&f) {
+                Ok(h) => by_hash.entry(h).or_default().push(f),
+                Err(e) => report.errors.push(format!("{}: {}", f.display(), e)),
+            }
+        }
+        for (hash, group) in by_hash.into_iter().filter(|(_, v)| v.len() > 1) {
+            let kept = group[0].clone();
+            let dups: Vec<PathBuf> = group[1..].to_vec();
+            for dup in dups.iter() {
+                let result = match action.as_str() {
+                    "remove" => std::fs::remove_file(dup),
+                    "hardlink" => replace_with_hard_link(&kept, dup),
+                    _ => Ok(()),
+                };
+                if let Err(e) = result { report.errors.push(format!("{}: {}", dup.display(), e)); }
+            }
+            report.bytes_reclaimable += size * dups.len() as u64;
+            report.groups.push(DuplicateGroup {
+                hash,
+                size,
+                kept: kept.to_string_lossy().to_string(),
+                duplicates: dups.iter().map(|d| d.to_string_lossy().to_string()).collect(),
+            });
+        }
+    }
+    if action != "report" {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(&module_name);
+    }
+    Ok(report)
+}
+
+// ====== 資產歷史版本 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AssetVersion {
+    pub timestamp: String,
+    pub path: String,
+    pub size: u64,
+}
+
+/// 列出資產的歷史版本（新到舊）
+#[tauri::command]
+pub async fn list_asset_versions(asset_path: String, asset_type: String, file_name: String) -> Result<Vec<AssetVersion>, String> {
+    let base_dir = PathBuf::from("design-assets").join(&asset_path);
+    let versions_dir = asset_type_dir(&base_dir, &asset_type)?.join(VERSIONS_DIR).join(&file_name);
+    let mut versions: Vec<AssetVersion> = list_dir_names(&versions_dir)
+        .into_iter()
+        .filter_map(|ts| {
+            let path = versions_dir.join(&ts).join(&file_name);
+            let size = std::fs::metadata(&path).ok()?.len();
+            Some(AssetVersion { timestamp: ts, path: path.to_string_lossy().to_string(), size })
+        })
+        .collect();
+    versions.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    Ok(versions)
+}
+
+/// 還原指定版本；目前的檔案會先封存為新版本，因此還原也可再還原
+#[tauri::command]
+pub async fn restore_asset_version(asset_path: String, asset_type: String, file_name: String, timestamp: String) -> Result<String, String> {
+    let base_dir = PathBuf::from("design-assets").join(&asset_path);
+    let type_dir = asset_type_dir(&base_dir, &asset_type)?;
+    let version_dir = type_dir.join(VERSIONS_DIR).join(&file_name).join(&timestamp);
+    let version_file = version_dir.join(&file_name);
+    if !version_file.is_file() {
+        return Err("找不到指定版本".to_string());
+    }
+    let target = type_dir.join(&file_name);
+    archive_existing_version(&target).map_err(|e| format!("封存目前版本失敗: {}", e))?;
+    std::fs::rename(&version_file, &target).map_err(|e| format!("還原版本失敗: {}", e))?;
+    let _ = std::fs::remove_dir(&version_dir);
+    if let Some(module_name) = asset_path.split('/').next() {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(module_name);
+    }
+    Ok(format!("已還原版本 {}: {}", timestamp, target.display()))
+}
+
+// ====== 載入中骨架（Skeleton Loading） ======
+
+// 僅 list/detail 類頁面支援骨架載入；頁面需在 page.json 設定 loading_skeleton: true
+fn loading_skeleton_pages(module_name: &str) -> Vec<(PageEntry, &'static str)> {
+    collect_module_page_entries(module_name)
+        .into_iter()
+        .filter(|e| e.meta.loading_skeleton == Some(true))
+        .filter_map(|e| {
+            let slug = e.subpage.clone().unwrap_or_else(|| e.page.clone());
+            match detect_page_type(&slug, &e.meta).as_str() {
+                "list" => Some((e, "list")),
+                "detail" => Some((e, "detail")),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn loading_skeleton_placeholders(module_name: &str) -> String {
+    let mut out = String::new();
+    for (e, kind) in loading_skeleton_pages(module_name) {
+        let route = e.route_path();
+        out.push_str(&format!("            <!-- {} 載入中骨架 -->\n", route));
+        out.push_str(&format!("            <section class=\"skeleton skeleton-{}\" data-page=\"{}\" aria-busy=\"true\" aria-live=\"polite\" hidden>\n", kind, route));
+        if kind == "list" {
+            for _ in 0..5 {
+                out.push_str("                <div class=\"skeleton-row\">\n                    <span class=\"skeleton-block skeleton-avatar\"></span>\n                    <span class=\"skeleton-block skeleton-line\"></span>\n                    <span class=\"skeleton-block skeleton-line skeleton-line-short\"></span>\n                </div>\n");
+            }
+        } else {
+            out.push_str("                <span class=\"skeleton-block skeleton-title\"></span>\n                <span class=\"skeleton-block skeleton-media\"></span>\n");
+            for _ in 0..3 {
+                out.push_str("                <span class=\"skeleton-block skeleton-line\"></span>\n");
+            }
+            out.push_str("                <span class=\"skeleton-block skeleton-line skeleton-line-short\"></span>\n");
+        }
+        out.push_str("            </section>\n");
+    }
+    out
+}
+
+fn loading_skeleton_css(module_name: &str) -> String {
+    if loading_skeleton_pages(module_name).is_empty() { return String::new(); }
+    r#"
+/* 載入中骨架 */
+.skeleton {
+    display: flex;
+    flex-direction: column;
+    gap: 0.75rem;
+}
+
+.skeleton[hidden] {
+    display: none;
+}
+
+.skeleton-row {
+    display: flex;
+    align-items: center;
+    gap: 0.75rem;
+}
+
+.skeleton-block {
+    display: block;
+    border-radius: 4px;
+    background: linear-gradient(90deg, #e9ecef 25%, #f8f9fa 37%, #e9ecef 63%);
+    background-size: 400% 100%;
+    animation: skeleton-shimmer 1.4s ease infinite;
+}
+
+.skeleton-avatar {
+    flex: none;
+    width: 2.5rem;
+    height: 2.5rem;
+    border-radius: 50%;
+}
+
+.skeleton-line {
+    flex: 1;
+    height: 0.875rem;
+}
+
+.skeleton-line-short {
+    flex: 0 0 30%;
+    max-width: 30%;
+}
+
+.skeleton-title {
+    width: 40%;
+    height: 1.5rem;
+}
+
+.skeleton-media {
+    width: 100%;
+    height: 12rem;
+}
+
+@keyframes skeleton-shimmer {
+    0% { background-position: 100% 50%; }
+    100% { background-position: 0 50%; }
+}
+
+@media (prefers-reduced-motion: reduce) {
+    .skeleton-block {
+        animation: none;
+    }
+}
+"#
+    .to_string()
+}
+
+fn loading_states_spec_section(module_name: &str) -> String {
+    let pages = loading_skeleton_pages(module_name);
+    if pages.is_empty() { return String::new(); }
+    let lang = project_spec_language();
+    let mut out = String::from(lang.pick(
+        "\n## 載入狀態（Skeleton Loading）\n以下頁面在資料載入期間需顯示骨架畫面，請沿用 `index.html` 的 `.skeleton` 結構與 `styles.css` 的 shimmer 樣式，不要自行設計其他載入指示：\n\n",
+        "\n## Loading States (Skeleton Loading)\nThe following pages show a skeleton while data is loading. Reuse the `.skeleton` markup in `index.html` and the shimmer styles in `styles.css`; do not design other loading indicators:\n\n",
+        "\n## ローディング状態（Skeleton Loading）\n以下のページではデータ読み込み中にスケルトンを表示します。`index.html` の `.skeleton` 構造と `styles.css` の shimmer スタイルを流用し、独自のローディング表示は作らないでください：\n\n",
+    ));
+    for (e, kind) in pages.iter() {
+        let layout = if *kind == "list" {
+            lang.pick("列表（多列佔位）", "List (several placeholder rows)", "一覧（複数行のプレースホルダー）")
+        } else {
+            lang.pick("詳情（標題＋主圖＋內文佔位）", "Detail (title + hero image + body placeholders)", "詳細（タイトル＋メイン画像＋本文のプレースホルダー）")
+        };
+        out.push_str(&format!("- `{}`{}{}\n", e.route_path(), lang.pick("：", ": ", "："), layout));
+    }
+    out.push_str(lang.pick(
+        "\n載入期間移除骨架區塊的 `hidden`，完成後恢復 `hidden` 並將 `aria-busy` 設為 `false`；載入失敗時改顯示錯誤狀態區塊。\n",
+        "\nWhile loading, remove `hidden` from the skeleton block; once done, restore `hidden` and set `aria-busy` to `false`. If loading fails, show the error state block instead.\n",
+        "\n読み込み中はスケルトンブロックの `hidden` を外し、完了後に `hidden` を戻して `aria-busy` を `false` にします。失敗した場合はエラー状態ブロックを表示します。\n",
+    ));
+    out
+}
+
+/// 設定頁面（或子頁）是否在生成骨架時附帶載入中骨架；僅 list/detail 類頁面生效
+#[tauri::command]
+pub async fn set_page_loading_skeleton(module_name: String, parent_slug: Option<String>, slug: String, enabled: bool) -> Result<String, String> {
+    let dir = resolve_page_dir(&module_name, parent_slug.as_deref(), &slug);
+    if !dir.exists() { return Err("頁面不存在".into()); }
+    let mut meta = read_page_meta(&dir);
+    let page_type = detect_page_type(&slug, &meta);
+    if enabled && page_type != "list" && page_type != "detail" {
+        return Err(format!("載入中骨架僅支援 list/detail 頁面（目前為 {}）", page_type));
+    }
+    meta.loading_skeleton = if enabled { Some(true) } else { None };
+    write_page_meta(&dir, &meta)?;
+    {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(&module_name);
+    }
+    Ok(if enabled { "已啟用載入中骨架".into() } else { "已停用載入中骨架".into() })
+}
+
+// ====== 列印樣式 ======
+
+const PRINT_CSS: &str = r#"
+
+/* 列印樣式 */
+@media print {
+    nav,
+    header nav,
+    aside,
+    .sidebar,
+    .skeleton,
+    .empty-state,
+    .error-state,
+    [role="dialog"],
+    [data-print="hide"] {
+        display: none !important;
+    }
+
+    body {
+        background: #fff;
+        color: #000;
+        font-size: 12pt;
+    }
+
+    .header {
+        background: none;
+        border-bottom: 1px solid #000;
+        padding: 0 0 0.5rem;
+    }
+
+    .main-content {
+        max-width: none;
+        padding: 0;
+    }
+
+    .table-wrapper,
+    [class*="table-container"] {
+        overflow: visible !important;
+        max-height: none !important;
+    }
+
+    table {
+        width: 100%;
+        border-collapse: collapse;
+    }
+
+    thead {
+        display: table-header-group;
+    }
+
+    tr,
+    img {
+        break-inside: avoid;
+    }
+
+    th,
+    td {
+        border: 1px solid #999;
+        padding: 4pt 6pt;
+        white-space: normal;
+    }
+
+    a[href^="http"]::after {
+        content: " (" attr(href) ")";
+        font-size: 90%;
+    }
+}"#;
+
+fn print_spec_section(lang: SpecLanguage) -> &'static str {
+    lang.pick(
+        r#"
+## 列印需求
+此模組需支援列印，`styles.css` 已附 `@media print` 基礎規則：
+- 隱藏導覽列、側邊欄、浮層與載入/空/錯誤狀態區塊；其他不需列印的元素請加上 `data-print="hide"`
+- 表格展開為完整寬度並取消捲動容器的高度限制，表頭於每頁重複
+- 表格列與圖片避免跨頁斷開；外部連結於列印時附上網址
+
+實作時請以瀏覽器列印預覽確認報表類頁面的分頁與欄寬。
+"#,
+        r#"
+## Print Requirements
+This module must support printing; `styles.css` ships baseline `@media print` rules:
+- Navigation, sidebars, overlays and loading/empty/error blocks are hidden; mark any other non-printable element with `data-print="hide"`
+- Tables expand to full width, scroll containers drop their height limits, and table headers repeat on every page
+- Table rows and images avoid page breaks; external links print their URL
+
+Check page breaks and column widths of report-style pages in the browser print preview.
+"#,
+        r#"
+## 印刷要件
+このモジュールは印刷に対応する必要があります。`styles.css` には `@media print` の基本ルールが含まれています：
+- ナビゲーション、サイドバー、オーバーレイ、読み込み/空/エラー状態ブロックは非表示。その他の印刷不要な要素には `data-print="hide"` を付ける
+- テーブルは全幅に展開し、スクロールコンテナの高さ制限を解除、ヘッダーは各ページで繰り返す
+- テーブル行と画像はページをまたいで分割しない。外部リンクは印刷時に URL を併記する
+
+レポート系ページの改ページと列幅はブラウザの印刷プレビューで確認してください。
+"#,
+    )
+}
+
+// ====== 跨模組資產搜尋 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AssetSearchHit {
+    pub module_name: String,
+    pub page: Option<String>,
+    pub subpage: Option<String>,
+    pub asset_type: String,
+    pub file_name: String,
+    pub path: String,
+    pub tags: Vec<String>,
+    pub archived: bool,
+    // 命中來源：file_name / tag / page
+    pub matched_on: Vec<String>,
+}
+
+struct AssetSearchQuery<'a> {
+    needle: String,
+    categories: Vec<String>,
+    asset_type: Option<&'a str>,
+    archived: bool,
+}
+
+// 資產所在層級：模組根、頁面或子頁
+struct AssetSearchLevel<'a> {
+    module_name: &'a str,
+    page: Option<&'a str>,
+    subpage: Option<&'a str>,
+    title: Option<&'a str>,
+}
+
+fn search_asset_level(dir: &std::path::Path, level: &AssetSearchLevel, query: &AssetSearchQuery, out: &mut Vec<AssetSearchHit>) {
+    let tags = read_asset_tags(dir);
+    let ignore = IgnoreRules::load();
+    let page_hit = [level.title, level.page, level.subpage]
+        .iter()
+        .flatten()
+        .any(|v| v.to_lowercase().contains(&query.needle));
+    for asset_type in query.categories.iter() {
+        if query.asset_type.is_some_and(|t| t != asset_type) { continue; }
+        let Ok(rd) = std::fs::read_dir(dir.join(asset_type)) else { continue };
+        for entry in rd.flatten() {
+            let path = entry.path();
+            if !path.is_file() || ignore.matches(&entry.file_name().to_string_lossy()) { continue; }
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let file_tags = tags.get(&format!("{}/{}", asset_type, file_name)).cloned().unwrap_or_default();
+            let mut matched_on = Vec::new();
+            if file_name.to_lowercase().contains(&query.needle) { matched_on.push("file_name".to_string()); }
+            if file_tags.iter().any(|t| t.to_lowercase().contains(&query.needle)) { matched_on.push("tag".to_string()); }
+            if page_hit { matched_on.push("page".to_string()); }
+            if matched_on.is_empty() { continue; }
+            out.push(AssetSearchHit {
+                module_name: level.module_name.to_string(),
+                page: level.page.map(str::to_string),
+                subpage: level.subpage.map(str::to_string),
+                asset_type: asset_type.to_string(),
+                file_name,
+                path: path.to_string_lossy().to_string(),
+                tags: file_tags,
+                archived: query.archived,
+                matched_on,
+            });
+        }
+    }
+}
+
+fn search_module_assets(module_dir: &std::path::Path, module_name: &str, query: &AssetSearchQuery, out: &mut Vec<AssetSearchHit>) {
+    let root = AssetSearchLevel { module_name, page: None, subpage: None, title: None };
+    search_asset_level(module_dir, &root, query, out);
+    let pages_dir = module_dir.join("pages");
+    for page in list_dir_names(&pages_dir) {
+        let page_dir = pages_dir.join(&page);
+        let meta = read_page_meta(&page_dir);
+        let level = AssetSearchLevel { module_name, page: Some(&page), subpage: None, title: meta.title.as_deref() };
+        search_asset_level(&page_dir, &level, query, out);
+        let subpages_dir = page_dir.join("subpages");
+        for sub in list_dir_names(&subpages_dir) {
+            let sub_dir = subpages_dir.join(&sub);
+            let smeta = read_page_meta(&sub_dir);
+            let level = AssetSearchLevel { module_name, page: Some(&page), subpage: Some(&sub), title: smeta.title.as_deref() };
+            search_asset_level(&sub_dir, &level, query, out);
+        }
+    }
+}
+
+/// 跨模組（含封存模組）搜尋資產檔名、標籤與頁面名稱，回傳所在模組/頁面/子頁
+#[tauri::command]
+pub async fn search_assets(query: String, asset_type: Option<String>, module: Option<String>) -> Result<Vec<AssetSearchHit>, String> {
+    let needle = query.trim().to_lowercase();
+    if needle.is_empty() {
+        return Err("搜尋關鍵字不可為空".to_string());
+    }
+    let categories = asset_categories();
+    if let Some(t) = asset_type.as_deref() {
+        if !categories.iter().any(|c| c == t) {
+            return Err(format!("不支援的資產類型: {}", t));
+        }
+    }
+    let mut hits = Vec::new();
+    for (root, archived) in [("design-assets", false), ("design-assets-archived", true)] {
+        let root = PathBuf::from(root);
+        let query = AssetSearchQuery { needle: needle.clone(), categories: categories.clone(), asset_type: asset_type.as_deref(), archived };
+        for module_name in list_dir_names(&root) {
+            if module.as_deref().is_some_and(|m| m != module_name) { continue; }
+            search_module_assets(&root.join(&module_name), &module_name, &query, &mut hits);
+        }
+    }
+    Ok(hits)
+}
+
+// ====== RTL 版面 ======
+
+fn project_rtl_enabled() -> bool {
+    read_active_project_config().and_then(|c| c.rtl).unwrap_or(false)
+}
+
+// 實體方向屬性 → 邏輯屬性（僅比對完整屬性名稱）
+const LOGICAL_PROPERTY_MAP: [(&str, &str); 16] = [
+    ("border-top-left-radius", "border-start-start-radius"),
+    ("border-top-right-radius", "border-start-end-radius"),
+    ("border-bottom-left-radius", "border-end-start-radius"),
+    ("border-bottom-right-radius", "border-end-end-radius"),
+    ("margin-left", "margin-inline-start"),
+    ("margin-right", "margin-inline-end"),
+    ("padding-left", "padding-inline-start"),
+    ("padding-right", "padding-inline-end"),
+    ("border-left", "border-inline-start"),
+    ("border-right", "border-inline-end"),
+    ("border-top", "border-block-start"),
+    ("border-bottom", "border-block-end"),
+    ("margin-top", "margin-block-start"),
+    ("margin-bottom", "margin-block-end"),
+    ("padding-top", "padding-block-start"),
+    ("padding-bottom", "padding-block-end"),
+];
+
+// 將生成的 CSS 改寫為邏輯屬性，讓同一份樣式在 LTR/RTL 下都正確
+fn logical_css_properties(css: &str) -> String {
+    css.lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let indent = &line[..line.len() - trimmed.len()];
+            let Some((prop, value)) = trimmed.split_once(':') else { return line.to_string() };
+            let prop = prop.trim();
+            if let Some((_, logical)) = LOGICAL_PROPERTY_MAP.iter().find(|(physical, _)| *physical == prop) {
+                return format!("{}{}:{}", indent, logical, value);
+            }
+            let value = match (prop, value.trim()) {
+                ("text-align" | "float" | "clear", v) if v.starts_with("left") => value.replacen("left", "start", 1),
+                ("text-align" | "float" | "clear", v) if v.starts_with("right") => value.replacen("right", "end", 1),
+                _ => return line.to_string(),
+            };
+            let value = if prop == "text-align" { value } else { value.replacen("start", "inline-start", 1).replacen("end", "inline-end", 1) };
+            format!("{}{}:{}", indent, prop, value)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+const RTL_CSS: &str = r#"
+
+/* RTL 版面 */
+[dir="rtl"] {
+    direction: rtl;
+    unicode-bidi: isolate;
+}
+
+/* 具方向性的圖示（箭頭、返回鍵）於 RTL 下鏡像 */
+[dir="rtl"] .icon-directional {
+    transform: scaleX(-1);
+}
+"#;
+
+fn rtl_spec_section(lang: SpecLanguage) -> &'static str {
+    lang.pick(
+        r#"
+## RTL 版面檢查清單
+此專案需支援由右至左語系（阿拉伯文、希伯來文等）。`index.rtl.html` 為 `dir="rtl"` 版本，`styles.css` 已改用邏輯屬性。
+- [ ] 一律使用邏輯屬性（`margin-inline-start`、`padding-inline-end`、`inset-inline-start`），不要寫 left/right
+- [ ] `text-align` 使用 `start`/`end`；flex/grid 排列隨 `dir` 自動反轉，勿手動 `row-reverse`
+- [ ] 返回、下一步等方向性圖示加上 `.icon-directional` 以便鏡像；Logo、勾選、播放等圖示不鏡像
+- [ ] 數字、電話、程式碼與 URL 保持 LTR（可用 `<bdi>` 或 `dir="ltr"` 包覆）
+- [ ] 表單欄位、麵包屑、分頁器與輪播的順序於 RTL 下正確
+- [ ] 動畫與轉場的水平位移方向同步反轉
+- [ ] 以實際阿拉伯文/希伯來文內容檢查截斷、換行與字型
+"#,
+        r#"
+## RTL Layout Checklist
+This project must support right-to-left scripts (Arabic, Hebrew, etc.). `index.rtl.html` is the `dir="rtl"` version and `styles.css` already uses logical properties.
+- [ ] Always use logical properties (`margin-inline-start`, `padding-inline-end`, `inset-inline-start`); never write left/right
+- [ ] Use `start`/`end` for `text-align`; flex/grid order flips with `dir` automatically, so do not add `row-reverse` by hand
+- [ ] Add `.icon-directional` to directional icons such as back/next so they mirror; logos, check marks and play icons do not mirror
+- [ ] Keep numbers, phone numbers, code and URLs LTR (wrap them in `<bdi>` or `dir="ltr"`)
+- [ ] Form fields, breadcrumbs, paginators and carousels are ordered correctly in RTL
+- [ ] Horizontal offsets in animations and transitions are mirrored as well
+- [ ] Check truncation, wrapping and fonts with real Arabic/Hebrew content
+"#,
+        r#"
+## RTL レイアウトチェックリスト
+このプロジェクトは右から左に書く言語（アラビア語、ヘブライ語など）に対応する必要があります。`index.rtl.html` は `dir="rtl"` 版で、`styles.css` は論理プロパティを使用しています。
+- [ ] 常に論理プロパティ（`margin-inline-start`、`padding-inline-end`、`inset-inline-start`）を使い、left/right は書かない
+- [ ] `text-align` は `start`/`end` を使う。flex/grid の並びは `dir` に従って自動で反転するため、手動で `row-reverse` にしない
+- [ ] 戻る・次へなど方向を示すアイコンには `.icon-directional` を付けて反転させる。ロゴ、チェック、再生アイコンは反転しない
+- [ ] 数字、電話番号、コード、URL は LTR のまま（`<bdi>` または `dir="ltr"` で囲む）
+- [ ] フォーム、パンくず、ページネーション、カルーセルの順序が RTL で正しい
+- [ ] アニメーションとトランジションの水平移動も反転させる
+- [ ] 実際のアラビア語/ヘブライ語のテキストで省略、折り返し、フォントを確認する
+"#,
+    )
+}
+
+// ====== SEO metadata ======
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PageSeo {
+    pub meta_title: Option<String>,
+    pub description: Option<String>,
+    // og:image 參考，可為資產相對路徑（如 screenshots/og.png）或完整 URL
+    pub og_image: Option<String>,
+    pub canonical: Option<String>,
+}
+
+const SEO_FIELDS: [&str; 4] = ["meta_title", "description", "og_image", "canonical"];
+
+fn seo_missing_fields(seo: Option<&PageSeo>) -> Vec<&'static str> {
+    let empty = PageSeo::default();
+    let seo = seo.unwrap_or(&empty);
+    [&seo.meta_title, &seo.description, &seo.og_image, &seo.canonical]
+        .iter()
+        .zip(SEO_FIELDS.iter())
+        .filter(|(v, _)| !has_text(v))
+        .map(|(_, name)| *name)
+        .collect()
+}
+
+fn seo_tags(seo: &PageSeo, indent: &str) -> String {
+    let mut out = String::new();
+    if let Some(v) = seo.meta_title.as_ref().filter(|s| !s.trim().is_empty()) {
+        out.push_str(&format!("{}<meta property=\"og:title\" content=\"{}\">\n", indent, escape_html(v)));
+    }
+    if let Some(v) = seo.description.as_ref().filter(|s| !s.trim().is_empty()) {
+        out.push_str(&format!("{}<meta name=\"description\" content=\"{}\">\n", indent, escape_html(v)));
+        out.push_str(&format!("{}<meta property=\"og:description\" content=\"{}\">\n", indent, escape_html(v)));
+    }
+    if let Some(v) = seo.og_image.as_ref().filter(|s| !s.trim().is_empty()) {
+        out.push_str(&format!("{}<meta property=\"og:image\" content=\"{}\">\n", indent, escape_html(v)));
+    }
+    if let Some(v) = seo.canonical.as_ref().filter(|s| !s.trim().is_empty()) {
+        out.push_str(&format!("{}<link rel=\"canonical\" href=\"{}\">\n", indent, escape_html(v)));
+    }
+    out
+}
+
+// 模組主頁面（排序第一個有 SEO 設定的頁面）
+fn primary_seo_entry(module_name: &str) -> Option<PageEntry> {
+    collect_module_page_entries(module_name).into_iter().find(|e| e.meta.seo.is_some())
+}
+
+fn seo_document_title(module_name: &str) -> String {
+    primary_seo_entry(module_name)
+        .and_then(|e| e.meta.seo.and_then(|s| s.meta_title))
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| module_display_name(module_name))
+}
+
+// <head> 內的 SEO 標籤：主頁面直接輸出，其餘頁面放在 <template data-page> 供路由切換時套用
+fn seo_head_tags(module_name: &str) -> String {
+    let Some(primary) = primary_seo_entry(module_name) else { return String::new() };
+    let mut out = String::new();
+    if let Some(seo) = primary.meta.seo.as_ref() {
+        out.push_str(&seo_tags(seo, "    "));
+    }
+    for e in collect_module_page_entries(module_name) {
+        let Some(seo) = e.meta.seo.as_ref() else { continue };
+        if e.route_path() == primary.route_path() { continue; }
+        let tags = seo_tags(seo, "        ");
+        let title = seo.meta_title.as_ref().filter(|s| !s.trim().is_empty()).map(|t| format!("        <title>{}</title>\n", escape_html(t))).unwrap_or_default();
+        if tags.is_empty() && title.is_empty() { continue; }
+        out.push_str(&format!("    <template data-page=\"{}\">\n{}{}    </template>\n", e.route_path(), title, tags));
+    }
+    out
+}
+
+fn seo_audit_markdown(module_name: &str) -> String {
+    let entries = collect_module_page_entries(module_name);
+    let mut out = format!("# {} SEO 檢查\n\n", module_name);
+    let missing: Vec<(String, Vec<&str>)> = entries
+        .iter()
+        .map(|e| (e.route_path(), seo_missing_fields(e.meta.seo.as_ref())))
+        .filter(|(_, m)| !m.is_empty())
+        .collect();
+    out.push_str(&format!("共 {} 個頁面，{} 個頁面缺少 SEO 欄位。\n\n", entries.len(), missing.len()));
+    if missing.is_empty() {
+        out.push_str("✅ 所有頁面的 SEO 欄位皆已填寫。\n");
+        return out;
+    }
+    out.push_str("| 頁面 | 缺少欄位 |\n|---|---|\n");
+    for (route, fields) in missing.iter() {
+        out.push_str(&format!("| `{}` | {} |\n", route, fields.join(", ")));
+    }
+    out
+}
+
+/// 設定頁面（或子頁）的 SEO 欄位；欄位為空字串表示清除
+#[tauri::command]
+pub async fn set_page_seo(module_name: String, parent_slug: Option<String>, slug: String, seo: PageSeo) -> Result<PageSeo, String> {
+    let dir = resolve_page_dir(&module_name, parent_slug.as_deref(), &slug);
+    if !dir.exists() { return Err("頁面不存在".into()); }
+    let clean = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    let seo = PageSeo {
+        meta_title: clean(seo.meta_title),
+        description: clean(seo.description),
+        og_image: clean(seo.og_image),
+        canonical: clean(seo.canonical),
+    };
+    let mut meta = read_page_meta(&dir);
+    meta.seo = if seo_missing_fields(Some(&seo)).len() == SEO_FIELDS.len() { None } else { Some(seo.clone()) };
+    write_page_meta(&dir, &meta)?;
+    {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(&module_name);
+    }
+    Ok(seo)
+}
+
+// ====== 事件追蹤計畫 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrackingEventProperty {
+    pub name: String,
+    // 例如 string / number / boolean
+    pub value_type: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrackingEvent {
+    pub name: String,
+    // 觸發時機，例如「點擊送出按鈕」
+    pub trigger: Option<String>,
+    #[serde(default)]
+    pub properties: Vec<TrackingEventProperty>,
+}
+
+fn is_valid_event_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-')
+}
+
+// 骨架 HTML 中帶 data-track-* 屬性的佔位按鈕（預設隱藏，實作時將屬性移到實際元素）
+fn tracking_attribute_stubs(module_name: &str) -> String {
+    let mut out = String::new();
+    for e in collect_module_page_entries(module_name) {
+        let Some(events) = e.meta.events.as_ref().filter(|v| !v.is_empty()) else { continue };
+        let route = e.route_path();
+        out.push_str(&format!("            <!-- {} 追蹤事件 -->\n", route));
+        out.push_str(&format!("            <section class=\"tracking-stubs\" data-page=\"{}\" hidden>\n", route));
+        for ev in events.iter() {
+            let props: Vec<&str> = ev.properties.iter().map(|p| p.name.as_str()).collect();
+            let trigger = ev.trigger.as_deref().filter(|t| !t.trim().is_empty());
+            out.push_str(&format!(
+                "                <button type=\"button\" data-track-event=\"{}\" data-track-trigger=\"{}\" data-track-props=\"{}\">{}</button>\n",
+                escape_html(&ev.name),
+                escape_html(trigger.unwrap_or("")),
+                escape_html(&props.join(",")),
+                escape_html(trigger.unwrap_or(&ev.name))
+            ));
+        }
+        out.push_str("            </section>\n");
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 設定頁面（或子頁）的追蹤事件；傳入空陣列表示清除
+#[tauri::command]
+pub async fn set_page_events(module_name: String, parent_slug: Option<String>, slug: String, events: Vec<TrackingEvent>) -> Result<String, String> {
+    let dir = resolve_page_dir(&module_name, parent_slug.as_deref(), &slug);
+    if !dir.exists() { return Err("頁面不存在".into()); }
+    let mut cleaned: Vec<TrackingEvent> = Vec::new();
+    for mut ev in events.into_iter() {
+        ev.name = ev.name.trim().to_string();
+        if !is_valid_event_name(&ev.name) {
+            return Err(format!("事件名稱無效: {}（僅限英數字、底線、點與連字號）", ev.name));
+        }
+        if cleaned.iter().any(|c| c.name == ev.name) {
+            return Err(format!("事件名稱重複: {}", ev.name));
+        }
+        ev.trigger = ev.trigger.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+        ev.properties.retain(|p| !p.name.trim().is_empty());
+        cleaned.push(ev);
+    }
+    let mut meta = read_page_meta(&dir);
+    meta.events = if cleaned.is_empty() { None } else { Some(cleaned) };
+    write_page_meta(&dir, &meta)?;
+    {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(&module_name);
+    }
+    Ok("已更新頁面追蹤事件".into())
+}
+
+/// 導出全專案的事件追蹤計畫；format 為 "csv" 或 "json"（預設）
+#[tauri::command]
+pub async fn export_tracking_plan(format: Option<String>) -> Result<String, String> {
+    use std::fs;
+    let rows: Vec<(PageEntry, TrackingEvent)> = collect_page_entries()
+        .into_iter()
+        .flat_map(|e| {
+            let events = e.meta.events.clone().unwrap_or_default();
+            events.into_iter().map(move |ev| (e.clone(), ev))
+        })
+        .collect();
+    let (file_name, content) = match format.as_deref().unwrap_or("json") {
+        "csv" => {
+            let mut out = String::from("module,page,subpage,route,event,trigger,property,property_type,property_description\n");
+            for (e, ev) in rows.iter() {
+                let base = [
+                    csv_field(&e.module),
+                    csv_field(&e.page),
+                    csv_field(e.subpage.as_deref().unwrap_or("")),
+                    csv_field(&e.route_path()),
+                    csv_field(&ev.name),
+                    csv_field(ev.trigger.as_deref().unwrap_or("")),
+                ].join(",");
+                if ev.properties.is_empty() {
+                    out.push_str(&format!("{},,,\n", base));
+                }
+                for p in ev.properties.iter() {
+                    out.push_str(&format!(
+                        "{},{},{},{}\n",
+                        base,
+                        csv_field(&p.name),
+                        csv_field(p.value_type.as_deref().unwrap_or("")),
+                        csv_field(p.description.as_deref().unwrap_or(""))
+                    ));
+                }
+            }
+            ("tracking-plan.csv", out)
+        }
+        "json" => {
+            let value = serde_json::json!({
+                "generated_at": chrono::Utc::now().to_rfc3339(),
+                "events": rows.iter().map(|(e, ev)| serde_json::json!({
+                    "module": e.module,
+                    "page": e.page,
+                    "subpage": e.subpage,
+                    "route": e.route_path(),
+                    "event": ev.name,
+                    "trigger": ev.trigger,
+                    "properties": ev.properties,
+                })).collect::<Vec<_>>(),
+            });
+            ("tracking-plan.json", serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?)
+        }
+        other => return Err(format!("不支援的導出格式: {}", other)),
+    };
+    let path = PathBuf::from("ai-docs").join(file_name);
+    fs::create_dir_all(path.parent().unwrap()).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| format!("寫入追蹤計畫失敗: {}", e))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+// ====== 從遠端 URL 下載資產 ======
+
+// 單檔下載上限（50 MB）
+const MAX_REMOTE_ASSET_BYTES: u64 = 50 * 1024 * 1024;
+
+// 各資產類型接受的 Content-Type；自訂類別不限制
+fn content_type_allowed(asset_type: &str, content_type: &str) -> bool {
+    match asset_type {
+        "screenshots" => content_type.starts_with("image/"),
+        "html" => matches!(content_type, "text/html" | "application/xhtml+xml" | "text/plain"),
+        "css" => matches!(content_type, "text/css" | "text/plain"),
+        "fonts" => content_type.starts_with("font/")
+            || matches!(content_type, "application/font-woff" | "application/font-woff2" | "application/x-font-ttf" | "application/vnd.ms-fontobject" | "application/octet-stream"),
+        _ => true,
+    }
+}
+
+fn extension_for_content_type(content_type: &str) -> Option<&'static str> {
+    match content_type {
+        "image/png" => Some("png"),
+        "image/jpeg" => Some("jpg"),
+        "image/webp" => Some("webp"),
+        "image/gif" => Some("gif"),
+        "image/svg+xml" => Some("svg"),
+        "text/html" | "application/xhtml+xml" => Some("html"),
+        "text/css" => Some("css"),
+        "font/woff2" => Some("woff2"),
+        "font/woff" | "application/font-woff" => Some("woff"),
+        "font/ttf" | "application/x-font-ttf" => Some("ttf"),
+        "font/otf" => Some("otf"),
+        _ => None,
+    }
+}
+
+// 由 URL 路徑取得檔名（去除 query/fragment），並替換不安全字元
+fn file_name_from_url(url: &str) -> Option<String> {
+    let without_query = url.split(['?', '#']).next().unwrap_or(url);
+    let after_scheme = without_query.split_once("://").map(|(_, rest)| rest).unwrap_or(without_query);
+    let (_, path) = after_scheme.split_once('/')?;
+    let last = path.rsplit('/').next().filter(|s| !s.is_empty())?;
+    let cleaned: String = last.chars().map(|c| if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' }).collect();
+    Some(cleaned).filter(|s| !s.trim_matches(['.', '_']).is_empty())
+}
+
+/// 從 HTTP(S) URL 下載圖片/HTML 等資產至模組，驗證 Content-Type 並限制檔案大小
+#[tauri::command]
+pub async fn download_design_asset(
+    asset_path: String,
+    asset_type: String,
+    url: String,
+    file_name: Option<String>,
+    overwrite_strategy: Option<String>,
+) -> Result<String, String> {
+    use std::io::Read;
+    let url = url.trim().to_string();
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return Err("僅支援 http/https 網址".to_string());
+    }
+    let base_dir = PathBuf::from("design-assets").join(&asset_path);
+    let target_dir = asset_type_dir(&base_dir, &asset_type)?;
+
+    let resp = ureq::get(&url).call().map_err(|e| format!("下載失敗: {}", e))?;
+    let content_type = resp.content_type().to_lowercase();
+    if !content_type_allowed(&asset_type, &content_type) {
+        return Err(format!("Content-Type {} 不符合資產類型 {}", content_type, asset_type));
+    }
+    if let Some(len) = resp.header("Content-Length").and_then(|v| v.parse::<u64>().ok()) {
+        if len > MAX_REMOTE_ASSET_BYTES {
+            return Err(format!("檔案過大: {} MB（上限 {} MB）", len / 1024 / 1024, MAX_REMOTE_ASSET_BYTES / 1024 / 1024));
+        }
+    }
+
+    let mut name = file_name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .or_else(|| file_name_from_url(&url))
+        .unwrap_or_else(|| format!("download-{}", chrono::Local::now().format("%Y%m%d%H%M%S")));
+    if name.contains(['/', '\\']) {
+        return Err("檔案名稱不可包含路徑".to_string());
+    }
+    if std::path::Path::new(&name).extension().is_none() {
+        if let Some(ext) = extension_for_content_type(&content_type) {
+            name = format!("{}.{}", name, ext);
+        }
+    }
+
+    // 讀取時再次限制大小（伺服器可能未提供 Content-Length）
+    let mut body: Vec<u8> = Vec::new();
+    resp.into_reader()
+        .take(MAX_REMOTE_ASSET_BYTES + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| format!("下載中斷: {}", e))?;
+    if body.len() as u64 > MAX_REMOTE_ASSET_BYTES {
+        return Err(format!("檔案超過上限 {} MB", MAX_REMOTE_ASSET_BYTES / 1024 / 1024));
+    }
+
+    std::fs::create_dir_all(&target_dir).map_err(|e| format!("無法建立資產類型目錄: {}", e))?;
+    let tmp = std::env::temp_dir().join(format!("erslice-download-{}", uuid::Uuid::new_v4()));
+    std::fs::write(&tmp, &body).map_err(|e| format!("寫入暫存檔失敗: {}", e))?;
+    let target_path = target_dir.join(&name);
+    let strategy = overwrite_strategy.unwrap_or_else(|| "overwrite".to_string());
+    let result = copy_file_with_strategy(&tmp, &target_path, &strategy);
+    let _ = std::fs::remove_file(&tmp);
+    result.map_err(|e| format!("寫入資產失敗: {}", e))?;
+
+    if let Some(module_name) = asset_path.split('/').next() {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(module_name);
+    }
+    Ok(format!("資產下載成功: {}（{} KB）", target_path.display(), body.len() / 1024))
+}
+
+// ====== 資產預覽（base64） ======
+
+// 預覽預設上限（10 MB），避免大檔經 IPC 傳輸卡住前端
+const DEFAULT_PREVIEW_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AssetPreview {
+    pub file_name: String,
+    pub mime_type: String,
+    pub size: u64,
+    // 可直接用於 <img src> / <iframe src> 的 data URL
+    pub data_url: String,
+}
+
+fn mime_for_extension(ext: &str) -> &'static str {
+    match ext {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "webp" => "image/webp",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" => "text/javascript",
+        "json" => "application/json",
+        "md" | "txt" => "text/plain",
+        "woff2" => "font/woff2",
+        "woff" => "font/woff",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        _ => "application/octet-stream",
+    }
+}
+
+/// 讀取資產內容為 base64 data URL 供前端預覽（design-assets 不在 Tauri asset scope 內）
+#[tauri::command]
+pub async fn read_asset_base64(asset_path: String, asset_type: String, file_name: String, max_bytes: Option<u64>) -> Result<AssetPreview, String> {
+    use base64::Engine;
+    if file_name.contains(['/', '\\']) || file_name.starts_with("..") {
+        return Err("無效的檔案名稱".to_string());
+    }
+    let base_dir = PathBuf::from("design-assets").join(&asset_path);
+    let path = asset_type_dir(&base_dir, &asset_type)?.join(&file_name);
+    if !path.is_file() {
+        return Err("檔案不存在".to_string());
+    }
+    let size = std::fs::metadata(&path).map_err(|e| e.to_string())?.len();
+    let limit = max_bytes.unwrap_or(DEFAULT_PREVIEW_MAX_BYTES);
+    if size > limit {
+        return Err(format!("檔案過大，無法預覽: {} KB（上限 {} KB）", size / 1024, limit / 1024));
+    }
+    let bytes = std::fs::read(&path).map_err(|e| format!("讀取檔案失敗: {}", e))?;
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    let mime_type = mime_for_extension(&ext).to_string();
+    let data_url = format!("data:{};base64,{}", mime_type, base64::engine::general_purpose::STANDARD.encode(&bytes));
+    Ok(AssetPreview { file_name, mime_type, size, data_url })
+}
+
+// ====== 元件 Props 規格 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ComponentProp {
+    pub name: String,
+    // TypeScript 型別字串，例如 string、number、'primary' | 'ghost'、() => void
+    #[serde(rename = "type")]
+    pub prop_type: String,
+    pub default: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+    pub description: Option<String>,
+}
+
+fn is_valid_prop_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+// 元件名稱：優先使用 page.json 的 component，否則由 slug 轉為 PascalCase
+fn component_name_for(entry: &PageEntry) -> String {
+    let source = entry.meta.component.clone()
+        .filter(|c| !c.trim().is_empty())
+        .unwrap_or_else(|| entry.subpage.clone().unwrap_or_else(|| entry.page.clone()));
+    pascal_case_identifier(&source)
+}
+
+// 將 slug/名稱轉為 PascalCase 識別字；開頭非字母時加上 Component 前綴
+fn pascal_case_identifier(source: &str) -> String {
+    let name: String = source
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut cs = w.chars();
+            cs.next().map(|f| f.to_ascii_uppercase().to_string() + cs.as_str()).unwrap_or_default()
+        })
+        .collect();
+    if name.chars().next().is_some_and(|c| c.is_ascii_alphabetic()) { name } else { format!("Component{}", name) }
+}
+
+fn pages_with_props(module_name: &str) -> Vec<(PageEntry, Vec<ComponentProp>)> {
+    collect_module_page_entries(module_name)
+        .into_iter()
+        .filter_map(|e| {
+            let props = e.meta.props.clone().filter(|p| !p.is_empty())?;
+            Some((e, props))
+        })
+        .collect()
+}
+
+// 單一元件的 TypeScript props interface（供 React/Vue 骨架共用）
+fn props_interface(component: &str, props: &[ComponentProp]) -> String {
+    let mut out = format!("export interface {}Props {{\n", component);
+    for p in props.iter() {
+        let mut doc: Vec<String> = Vec::new();
+        if let Some(d) = p.description.as_ref().filter(|d| !d.trim().is_empty()) { doc.push(d.trim().to_string()); }
+        if let Some(d) = p.default.as_ref().filter(|d| !d.trim().is_empty()) { doc.push(format!("@default {}", d.trim())); }
+        if !doc.is_empty() {
+            out.push_str(&format!("  /** {} */\n", doc.join(" ")));
+        }
+        out.push_str(&format!("  {}{}: {};\n", p.name, if p.required { "" } else { "?" }, p.prop_type));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn component_props_typescript(module_name: &str) -> String {
+    let mut out = format!("// {} 模組元件 props（由 ErSlice 依 page.json 生成）\n", module_name);
+    for (e, props) in pages_with_props(module_name) {
+        out.push_str(&format!("\n// {}\n", e.route_path()));
+        out.push_str(&props_interface(&component_name_for(&e), &props));
+    }
+    out
+}
+
+fn component_props_spec_section(module_name: &str) -> String {
+    let pages = pages_with_props(module_name);
+    if pages.is_empty() { return String::new(); }
+    let lang = project_spec_language();
+    let mut out = String::from(lang.pick(
+        "\n## 元件 Props\n型別定義見 `props.d.ts`，實作時請沿用相同的 props 名稱與預設值：\n",
+        "\n## Component Props\nType definitions are in `props.d.ts`; keep the same prop names and defaults when implementing:\n",
+        "\n## コンポーネント Props\n型定義は `props.d.ts` を参照してください。実装時は同じ props 名とデフォルト値を使ってください：\n",
+    ));
+    for (e, props) in pages.iter() {
+        out.push_str(&fill_spec_text(
+            lang.pick(
+                "\n### {}Props（`{}`）\n\n| Prop | 型別 | 預設值 | 必填 | 說明 |\n|---|---|---|---|---|\n",
+                "\n### {}Props (`{}`)\n\n| Prop | Type | Default | Required | Description |\n|---|---|---|---|---|\n",
+                "\n### {}Props（`{}`）\n\n| Prop | 型 | デフォルト | 必須 | 説明 |\n|---|---|---|---|---|\n",
+            ),
+            &[component_name_for(e), e.route_path()],
+        ));
+        for p in props.iter() {
+            out.push_str(&format!(
+                "| `{}` | `{}` | {} | {} | {} |\n",
+                p.name,
+                p.prop_type.replace('|', "\\|"),
+                p.default.as_deref().map(|d| format!("`{}`", d)).unwrap_or_else(|| "-".into()),
+                if p.required { "✅" } else { "" },
+                p.description.clone().unwrap_or_default()
+            ));
+        }
+    }
+    out
+}
+
+/// 設定元件/元件庫頁面的 props 規格；傳入空陣列表示清除
+#[tauri::command]
+pub async fn set_page_props(module_name: String, parent_slug: Option<String>, slug: String, props: Vec<ComponentProp>) -> Result<String, String> {
+    let dir = resolve_page_dir(&module_name, parent_slug.as_deref(), &slug);
+    if !dir.exists() { return Err("頁面不存在".into()); }
+    let mut cleaned: Vec<ComponentProp> = Vec::new();
+    for mut p in props.into_iter() {
+        p.name = p.name.trim().to_string();
+        p.prop_type = p.prop_type.trim().to_string();
+        if !is_valid_prop_name(&p.name) {
+            return Err(format!("prop 名稱無效: {}", p.name));
+        }
+        if cleaned.iter().any(|c| c.name == p.name) {
+            return Err(format!("prop 名稱重複: {}", p.name));
+        }
+        if p.prop_type.is_empty() { p.prop_type = "unknown".into(); }
+        p.default = p.default.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+        cleaned.push(p);
+    }
+    let mut meta = read_page_meta(&dir);
+    meta.props = if cleaned.is_empty() { None } else { Some(cleaned) };
+    write_page_meta(&dir, &meta)?;
+    {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(&module_name);
+    }
+    Ok("已更新元件 props".into())
+}
+
+// ====== 同步至 monorepo ======
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RepoSyncReport {
+    pub module: String,
+    pub target_dir: String,
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub unchanged: Vec<String>,
+    // repo 中已存在且內容不同的檔案
+    pub conflicts: Vec<String>,
+    // false 表示因衝突未寫入任何檔案（created 為預計新增的檔案，需指定 overwrite_strategy 後重試）
+    pub applied: bool,
+}
+
+// 模組在 repo 內的目標路徑（相對 repo 根目錄）
+fn repo_target_for(module_name: &str, cfg: Option<&ProjectConfig>) -> String {
+    cfg.and_then(|c| c.repo_targets.as_ref().and_then(|m| m.get(module_name).cloned()))
+        .or_else(|| cfg.and_then(|c| c.repo_target_default.clone()))
+        .unwrap_or_else(|| "src/features/<module>".to_string())
+        .replace("<module>", module_name)
+}
+
+/// 將 output/<module> 生成的骨架複製到 repo 內對應路徑；
+/// 有內容不同的既有檔案時，未指定 overwrite_strategy 則只回報衝突不寫入
+#[tauri::command]
+pub async fn sync_package_to_repo(module: String, repo_root: String, overwrite_strategy: Option<String>) -> Result<RepoSyncReport, String> {
+    let source = output_root().join(&module);
+    if !source.exists() {
+        return Err("尚未生成此模組的切版說明包".to_string());
+    }
+    let repo_root = PathBuf::from(&repo_root);
+    if !repo_root.is_dir() {
+        return Err("repo 根目錄不存在".to_string());
+    }
+    let rel_target = repo_target_for(&module, read_active_project_config().as_ref());
+    if rel_target.split(['/', '\\']).any(|seg| seg == "..") || std::path::Path::new(&rel_target).is_absolute() {
+        return Err(format!("目標路徑必須位於 repo 內: {}", rel_target));
+    }
+    let target_dir = repo_root.join(&rel_target);
+
+    let mut files = Vec::new();
+    collect_output_files(&source, &mut files);
+    files.sort();
+    let mut report = RepoSyncReport { module: module.clone(), target_dir: target_dir.to_string_lossy().to_string(), ..Default::default() };
+    let mut plan: Vec<(PathBuf, PathBuf, bool)> = Vec::new();
+    for src in files.iter() {
+        let rel = relative_path(&source, src);
+        let dest = target_dir.join(&rel);
+        if !dest.exists() {
+            report.created.push(rel);
+            plan.push((src.clone(), dest, false));
+        } else if sha256_file(src)? == sha256_file(&dest)? {
+            report.unchanged.push(rel);
+        } else {
+            report.conflicts.push(rel);
+            plan.push((src.clone(), dest, true));
+        }
+    }
+
+    if !report.conflicts.is_empty() && overwrite_strategy.is_none() {
+        return Ok(report);
+    }
+    let strategy = overwrite_strategy.unwrap_or_else(|| "overwrite".to_string());
+    for (src, dest, conflict) in plan.iter() {
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("建立資料夾失敗: {}", e))?;
+        }
+        copy_file_with_strategy(src, dest, if *conflict { &strategy } else { "overwrite" })
+            .map_err(|e| format!("複製 {} 失敗: {}", dest.display(), e))?;
+    }
+    if strategy != "skip" {
+        report.updated = std::mem::take(&mut report.conflicts);
+    }
+    report.applied = true;
+    Ok(report)
+}
+
+// ====== 頁面/子頁層級的資產 ======
+
+// 頁面或子頁相對 design-assets 的 asset_path（供既有的模組層級指令共用）
+fn page_asset_path(module_name: &str, page: &str, subpage: Option<&str>) -> Result<String, String> {
+    let dir = resolve_page_dir(module_name, subpage.map(|_| page), subpage.unwrap_or(page));
+    if !dir.exists() {
+        return Err(if subpage.is_some() { "子頁不存在".into() } else { "頁面不存在".into() });
+    }
+    Ok(match subpage {
+        Some(sub) => format!("{}/pages/{}/subpages/{}", module_name, page, sub),
+        None => format!("{}/pages/{}", module_name, page),
+    })
+}
+
+/// 列出頁面（或子頁）層級的資產
+#[tauri::command]
+pub async fn list_page_assets(module_name: String, page: String, subpage: Option<String>) -> Result<AssetList, String> {
+    let asset_path = page_asset_path(&module_name, &page, subpage.as_deref())?;
+    list_assets(asset_path).await
+}
+
+/// 上傳資產至頁面（或子頁）層級；location.page 必填
+#[tauri::command]
+pub async fn upload_page_asset(
+    location: AssetLocation,
+    file_path: String,
+    optimize_images: Option<bool>,
+    overwrite_strategy: Option<String>,
+    breakpoint: Option<String>,
+) -> Result<String, String> {
+    let page = location.page.as_deref().ok_or("請指定頁面")?;
+    let asset_path = page_asset_path(&location.module_name, page, location.subpage.as_deref())?;
+    let result = upload_design_asset(asset_path, location.asset_type.clone(), file_path, optimize_images, overwrite_strategy, breakpoint).await?;
+    {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(&location.module_name);
+    }
+    Ok(result)
+}
+
+// ====== 輸出包的 repo 初始檔案 ======
+
+const SCAFFOLD_SEED_FILES: [&str; 3] = ["gitignore", "editorconfig", "package_json"];
+
+const SEED_GITIGNORE: &str = "node_modules/
+dist/
+build/
+.cache/
+storybook-static/
+coverage/
+*.log
+.env
+.env.*
+!.env.example
+.DS_Store
+Thumbs.db
+.idea/
+.vscode/*
+!.vscode/extensions.json
+";
+
+const SEED_EDITORCONFIG: &str = "root = true
+
+[*]
+charset = utf-8
+end_of_line = lf
+indent_style = space
+indent_size = 2
+insert_final_newline = true
+trim_trailing_whitespace = true
+
+[*.md]
+trim_trailing_whitespace = false
+";
+
+fn seed_package_json(name: &str) -> String {
+    let value = serde_json::json!({
+        "name": name,
+        "version": "0.1.0",
+        "private": true,
+        "scripts": {
+            "dev": "npx --yes serve .",
+            "lint": "npx --yes prettier --check \"modules/**/*.{html,css,ts,tsx,vue,svelte}\"",
+            "format": "npx --yes prettier --write \"modules/**/*.{html,css,ts,tsx,vue,svelte}\"",
+            "storybook": "storybook dev -p 6006",
+            "build-storybook": "storybook build"
+        }
+    });
+    serde_json::to_string_pretty(&value).unwrap_or_default() + "\n"
+}
+
+// npm 套件名稱：小寫、僅限 a-z0-9 與 -
+fn npm_package_name(s: &str) -> String {
+    let name = css_ident(s);
+    if name.is_empty() { "erslice-slice-package".to_string() } else { name }
+}
+
+// 依專案設定在輸出包根目錄寫入 .gitignore / .editorconfig / package.json，讓輸出可直接作為 repo 起點
+fn write_scaffold_seed_files(out_dir: &std::path::Path, strategy: &str) -> Result<(), String> {
+    let cfg = read_active_project_config();
+    let files = cfg.as_ref().and_then(|c| c.scaffold_files.clone()).unwrap_or_else(|| SCAFFOLD_SEED_FILES.iter().map(|s| s.to_string()).collect());
+    for f in files.iter() {
+        let (name, content) = match f.as_str() {
+            "gitignore" => (".gitignore", SEED_GITIGNORE.to_string()),
+            "editorconfig" => (".editorconfig", SEED_EDITORCONFIG.to_string()),
+            "package_json" => ("package.json", seed_package_json(&npm_package_name(cfg.as_ref().map(|c| c.slug.as_str()).unwrap_or("")))),
+            other => {
+                log::warn!("未知的 scaffold_files 項目: {}", other);
+                continue;
+            }
+        };
+        write_text_with_strategy(&out_dir.join(name), &content, strategy).map_err(|e| format!("寫入 {} 失敗: {}", name, e))?;
+    }
+    Ok(())
+}
+
+// ====== 資產完整性檢查 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IntegrityIssue {
+    pub module: String,
+    pub path: String,
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AssetIntegrityReport {
+    pub modules_scanned: usize,
+    pub files_scanned: usize,
+    pub zero_byte_files: Vec<IntegrityIssue>,
+    pub unreadable_images: Vec<IntegrityIssue>,
+    // HTML 引用的 CSS/圖片不存在
+    pub broken_html_refs: Vec<IntegrityIssue>,
+    // assets 資料表中指向已刪除檔案的記錄
+    pub orphan_db_rows: Vec<IntegrityIssue>,
+}
+
+// 只檢查指向樣式與圖片的引用（頁面連結等由路由負責）
+fn is_checked_html_ref(r: &str) -> bool {
+    let path = r.split(['?', '#']).next().unwrap_or(r);
+    let ext = std::path::Path::new(path).extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    ext == "css" || IMAGE_EXTS.contains(&ext.as_str())
+}
+
+fn check_image_readable(path: &std::path::Path) -> Result<(), String> {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    match ext.as_str() {
+        "png" | "jpg" | "jpeg" | "webp" => image::image_dimensions(path).map(|_| ()).map_err(|e| e.to_string()),
+        "svg" => {
+            let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+            if text.contains("<svg") { Ok(()) } else { Err("不是有效的 SVG".to_string()) }
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_html_refs(html_path: &std::path::Path, module: &str, report: &mut AssetIntegrityReport) {
+    let Ok(html) = std::fs::read_to_string(html_path) else { return };
+    let html_dir = html_path.parent().unwrap_or(std::path::Path::new("."));
+    // html/ 內的檔案也常以層級目錄（模組/頁面根）為基準引用
+    let level_dir = html_dir.parent().unwrap_or(html_dir);
+    let _ = rewrite_html_refs(&html, |r| {
+        if is_external_ref(r) || !is_checked_html_ref(r) { return None; }
+        let clean = r.split(['?', '#']).next().unwrap_or(r).trim_start_matches('/');
+        if !html_dir.join(clean).exists() && !level_dir.join(clean).exists() {
+            report.broken_html_refs.push(IntegrityIssue {
+                module: module.to_string(),
+                path: html_path.to_string_lossy().to_string(),
+                detail: format!("找不到引用: {}", r),
+            });
+        }
+        None
+    });
+}
+
+/// 掃描所有模組的資產：0 位元組檔案、無法讀取的圖片、HTML 中失效的 CSS/圖片引用，以及指向已刪除檔案的資料庫記錄
+#[tauri::command]
+pub async fn check_asset_integrity() -> Result<AssetIntegrityReport, String> {
+    let root = PathBuf::from("design-assets");
+    let mut report = AssetIntegrityReport::default();
+    for module in list_dir_names(&root) {
+        report.modules_scanned += 1;
+        let mut files = Vec::new();
+        collect_files_recursive(&root.join(&module), &mut files);
+        files.sort();
+        for f in files.iter() {
+            let name = f.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if name.starts_with('.') || name.ends_with(".json") || name.ends_with(".md") { continue; }
+            report.files_scanned += 1;
+            let issue = |detail: String| IntegrityIssue { module: module.clone(), path: f.to_string_lossy().to_string(), detail };
+            if std::fs::metadata(f).map(|m| m.len() == 0).unwrap_or(false) {
+                report.zero_byte_files.push(issue("檔案大小為 0".to_string()));
+                continue;
+            }
+            let ext = f.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+            if IMAGE_EXTS.contains(&ext.as_str()) {
+                if let Err(e) = check_image_readable(f) {
+                    report.unreadable_images.push(issue(format!("無法讀取圖片: {}", e)));
+                }
+            } else if ext == "html" || ext == "htm" {
+                check_html_refs(f, &module, &mut report);
+            }
+        }
+    }
+    match crate::database::list_asset_paths() {
+        Ok(rows) => {
+            for (id, file_path) in rows {
+                if !PathBuf::from(&file_path).exists() && !root.join(&file_path).exists() {
+                    let module = file_path.split(['/', '\\']).find(|s| !s.is_empty() && *s != "design-assets").unwrap_or("").to_string();
+                    report.orphan_db_rows.push(IntegrityIssue { module, path: file_path, detail: format!("資產記錄 {} 指向不存在的檔案", id) });
+                }
+            }
+        }
+        Err(e) => log::warn!("讀取資產記錄失敗: {}", e),
+    }
+    Ok(report)
+}
+
+// ====== 批量刪除資產 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BulkDeleteResult {
+    pub dry_run: bool,
+    // 符合條件的檔案（dry_run 時即為將被刪除的清單）
+    pub matched: Vec<String>,
+    pub removed: usize,
+    pub failed: Vec<String>,
+}
+
+// 簡易 glob：`*` 不跨越 `/`，`**` 可跨越，`?` 為單一字元
+fn glob_match(pattern: &str, text: &str) -> bool {
+    fn go(p: &[char], t: &[char]) -> bool {
+        match p.first() {
+            None => t.is_empty(),
+            Some('*') if p.get(1) == Some(&'*') => {
+                let rest = &p[2..];
+                (0..=t.len()).any(|i| go(rest, &t[i..]))
+            }
+            Some('*') => {
+                let rest = &p[1..];
+                for i in 0..=t.len() {
+                    if go(rest, &t[i..]) { return true; }
+                    if t.get(i) == Some(&'/') { break; }
+                }
+                false
+            }
+            Some('?') => !t.is_empty() && t[0] != '/' && go(&p[1..], &t[1..]),
+            Some(c) => t.first() == Some(c) && go(&p[1..], &t[1..]),
+        }
+    }
+    let p: Vec<char> = pattern.to_lowercase().chars().collect();
+    let t: Vec<char> = text.to_lowercase().chars().collect();
+    go(&p, &t)
+}
+
+// 模組內所有可放資產的層級：模組根、各頁面、各子頁
+fn module_asset_levels(module_dir: &std::path::Path) -> Vec<PathBuf> {
+    let mut levels = vec![module_dir.to_path_buf()];
+    let pages_dir = module_dir.join("pages");
+    for page in list_dir_names(&pages_dir) {
+        let page_dir = pages_dir.join(&page);
+        let subpages_dir = page_dir.join("subpages");
+        levels.push(page_dir);
+        levels.extend(list_dir_names(&subpages_dir).into_iter().map(|s| subpages_dir.join(s)));
+    }
+    levels
+}
+
+fn parse_cutoff(s: &str) -> Result<SystemTime, String> {
+    let s = s.trim();
+    let dt = chrono::DateTime::parse_from_rfc3339(s)
+        .map(|d| d.with_timezone(&chrono::Utc))
+        .or_else(|_| {
+            chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc())
+        })
+        .map_err(|_| format!("無法解析日期: {}（請使用 YYYY-MM-DD 或 RFC3339）", s))?;
+    Ok(SystemTime::from(dt))
+}
+
+/// 依條件批量刪除模組內的資產（glob 比對檔名或相對路徑、資產類型、早於指定日期）；dry_run 時只回報不刪除
+#[tauri::command]
+pub async fn delete_assets_bulk(
+    module_name: String,
+    pattern: Option<String>,
+    asset_type: Option<String>,
+    older_than: Option<String>,
+    dry_run: bool,
+) -> Result<BulkDeleteResult, String> {
+    let module_dir = PathBuf::from("design-assets").join(&module_name);
+    if !module_dir.exists() {
+        return Err("設計模組不存在".to_string());
+    }
+    if pattern.is_none() && asset_type.is_none() && older_than.is_none() {
+        return Err("請至少指定一個篩選條件".to_string());
+    }
+    let categories = asset_categories();
+    if let Some(t) = asset_type.as_deref() {
+        if !categories.iter().any(|c| c == t) {
+            return Err(format!("不支援的資產類型: {}", t));
+        }
+    }
+    let cutoff = older_than.as_deref().map(parse_cutoff).transpose()?;
+    let ignore = IgnoreRules::load();
+
+    let mut result = BulkDeleteResult { dry_run, ..Default::default() };
+    for level in module_asset_levels(&module_dir) {
+        for category in categories.iter() {
+            if asset_type.as_deref().is_some_and(|t| t != category) { continue; }
+            let Ok(rd) = std::fs::read_dir(level.join(category)) else { continue };
+            let mut files: Vec<PathBuf> = rd.flatten()
+                .filter(|e| !ignore.matches(&e.file_name().to_string_lossy()))
+                .map(|e| e.path())
+                .filter(|p| p.is_file())
+                .collect();
+            files.sort();
+            for f in files {
+                let file_name = f.file_name().and_then(|n| n.to_str()).unwrap_or("").to_string();
+                let rel = f.strip_prefix(&module_dir).unwrap_or(&f).to_string_lossy().replace('\\', "/");
+                if let Some(p) = pattern.as_deref() {
+                    let target = if p.contains('/') { rel.as_str() } else { file_name.as_str() };
+                    if !glob_match(p, target) { continue; }
+                }
+                if let Some(cutoff) = cutoff {
+                    let modified = std::fs::metadata(&f).and_then(|m| m.modified()).unwrap_or(SystemTime::now());
+                    if modified >= cutoff { continue; }
+                }
+                result.matched.push(rel.clone());
+                if dry_run { continue; }
+                match move_to_trash(&f, "asset") {
+                    Ok(_) => {
+                        result.removed += 1;
+                        move_asset_tags(&level, &format!("{}/{}", category, file_name), None);
+                    }
+                    Err(e) => result.failed.push(format!("{}: {}", rel, e)),
+                }
+            }
+        }
+    }
+    if result.removed > 0 {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(&module_name);
+    }
+    Ok(result)
+}
+
+// ====== 授權表頭與品牌變數 ======
+
+// 不處理的資料夾：複製進來的設計資產與 AI 文件不屬於生成內容
+fn is_branding_skipped_dir(name: &str, categories: &[String]) -> bool {
+    categories.iter().any(|c| c == name) || matches!(name, "pages" | "design-assets" | "ai-docs" | "node_modules")
+}
+
+fn collect_generated_text_files(dir: &std::path::Path, categories: &[String], out: &mut Vec<PathBuf>) {
+    let Ok(rd) = std::fs::read_dir(dir) else { return };
+    for entry in rd.flatten() {
+        if is_output_internal_dir(&entry.file_name().to_string_lossy()) { continue; }
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            if !is_branding_skipped_dir(&name, categories) {
+                collect_generated_text_files(&path, categories, out);
+            }
+        } else if matches!(path.extension().and_then(|e| e.to_str()), Some("html" | "css" | "scss" | "less" | "md")) {
+            out.push(path);
+        }
+    }
+}
+
+fn substitute_brand_vars(text: &str, vars: &HashMap<String, String>, project: &str) -> String {
+    let mut out = text.replace("{{year}}", &chrono::Local::now().format("%Y").to_string()).replace("{{project}}", project);
+    for (k, v) in vars.iter() {
+        out = out.replace(&format!("{{{{brand.{}}}}}", k), v);
+    }
+    out
+}
+
+// 依副檔名加上表頭註解；已有相同表頭時不重複加入
+fn with_file_header(content: &str, ext: &str, header: &str) -> String {
+    let comment = match ext {
+        "css" | "scss" | "less" => format!("/*\n{}\n*/\n", header.replace("*/", "* /")),
+        _ => format!("<!--\n{}\n-->\n", header.replace("-->", "- ->")),
+    };
+    if content.contains(comment.trim_end()) { return content.to_string(); }
+    if ext == "html" {
+        if let Some(rest) = content.strip_prefix("<!DOCTYPE html>\n") {
+            return format!("<!DOCTYPE html>\n{}{}", comment, rest);
+        }
+    }
+    format!("{}{}", comment, content)
+}
+
+fn brand_css_block(vars: &HashMap<String, String>) -> String {
+    let mut keys: Vec<&String> = vars.keys().collect();
+    keys.sort();
+    let mut out = String::from("\n/* 品牌變數 */\n:root {\n");
+    for k in keys {
+        out.push_str(&format!("    --brand-{}: {};\n", css_ident(k), vars[k]));
+    }
+    out.push_str("}\n");
+    out
+}
+
+// 依專案設定為輸出包內生成的 HTML/CSS/MD 加上表頭註解並替換品牌變數
+fn apply_branding(package_root: &std::path::Path) -> Result<(), String> {
+    let Some(cfg) = read_active_project_config() else { return Ok(()) };
+    let vars = cfg.branding.clone().unwrap_or_default();
+    let header = cfg.file_header.as_ref().map(|h| h.trim()).filter(|h| !h.is_empty()).map(|h| substitute_brand_vars(h, &vars, &cfg.name));
+    let block = cfg.brand_block.clone().filter(|b| !b.is_empty());
+    if header.is_none() && vars.is_empty() && block.is_none() { return Ok(()); }
+    let logo = match block.as_ref() {
+        Some(b) => copy_brand_logo(b, package_root)?,
+        None => None,
+    };
+    let mut files = Vec::new();
+    collect_generated_text_files(package_root, &asset_categories(), &mut files);
+    for f in files.iter() {
+        let ext = f.extension().and_then(|e| e.to_str()).unwrap_or("").to_string();
+        let original = std::fs::read_to_string(f).map_err(|e| format!("讀取 {} 失敗: {}", f.display(), e))?;
+        let mut content = substitute_brand_vars(&original, &vars, &cfg.name);
+        if matches!(ext.as_str(), "css" | "scss" | "less") && !vars.is_empty() && !content.contains("--brand-") {
+            content.push_str(&brand_css_block(&vars));
+        }
+        if let (Some(b), "html") = (block.as_ref(), ext.as_str()) {
+            let rel_dir = f.parent().and_then(|d| d.strip_prefix(package_root).ok()).unwrap_or(std::path::Path::new(""));
+            let logo_src = logo.as_ref().map(|l| relative_path(rel_dir, l));
+            content = inject_brand_block(&content, b, &cfg.name, logo_src.as_deref());
+        }
+        if let Some(h) = header.as_deref() {
+            content = with_file_header(&content, &ext, h);
+        }
+        if content != original {
+            std::fs::write(f, content).map_err(|e| format!("寫入 {} 失敗: {}", f.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BrandBlock {
+    // logo 圖檔路徑（相對路徑以工作目錄為準）；輸出包內複製為 brand/<檔名>
+    pub logo_path: Option<String>,
+    // 頁首顯示的名稱；未設定時使用專案名稱
+    pub project_name: Option<String>,
+    // 頁尾文字，可用 {{year}}、{{project}}
+    pub footer_text: Option<String>,
+}
+
+impl BrandBlock {
+    fn is_empty(&self) -> bool {
+        [&self.logo_path, &self.project_name, &self.footer_text].iter().all(|v| v.as_deref().map(str::trim).unwrap_or("").is_empty())
+    }
+
+    fn logo(&self) -> Option<PathBuf> {
+        self.logo_path.as_deref().map(str::trim).filter(|p| !p.is_empty()).map(PathBuf::from)
+    }
+}
+
+// 已注入品牌區塊的標記，避免重複生成時重複注入
+const BRAND_BLOCK_MARKER: &str = "<!-- erslice:brand -->";
+const BRAND_BLOCK_STYLE: &str = "<style>.erslice-brand{display:flex;align-items:center;gap:12px;padding:12px 16px;border-bottom:1px solid #e5e7eb;font:600 16px/1.4 -apple-system,BlinkMacSystemFont,'Segoe UI',Roboto,sans-serif;color:#111827}.erslice-brand img{height:32px;width:auto}.erslice-brand-footer{padding:12px 16px;border-top:1px solid #e5e7eb;font:13px/1.5 -apple-system,BlinkMacSystemFont,'Segoe UI',Roboto,sans-serif;color:#6b7280;text-align:center}</style>";
+
+// 將 logo 複製到輸出包的 brand/ 下，回傳包內相對路徑
+fn copy_brand_logo(block: &BrandBlock, package_root: &std::path::Path) -> Result<Option<PathBuf>, String> {
+    let Some(src) = block.logo() else { return Ok(None) };
+    let Some(name) = src.file_name() else { return Ok(None) };
+    if !src.is_file() {
+        return Err(format!("找不到品牌 logo: {}", src.display()));
+    }
+    let rel = PathBuf::from("brand").join(name);
+    std::fs::create_dir_all(package_root.join("brand")).map_err(|e| format!("建立 brand 資料夾失敗: {}", e))?;
+    std::fs::copy(&src, package_root.join(&rel)).map_err(|e| format!("複製品牌 logo 失敗: {}", e))?;
+    Ok(Some(rel))
+}
+
+// 在 <body> 後插入頁首、</body> 前插入頁尾；沒有 <body> 的片段或已注入者原樣回傳
+fn inject_brand_block(html: &str, block: &BrandBlock, project: &str, logo_src: Option<&str>) -> String {
+    if html.contains(BRAND_BLOCK_MARKER) { return html.to_string(); }
+    let Some(body_start) = html.find("<body") else { return html.to_string() };
+    let Some(body_open_end) = html[body_start..].find('>').map(|i| body_start + i + 1) else { return html.to_string() };
+    let Some(body_close) = html.rfind("</body>").filter(|i| *i >= body_open_end) else { return html.to_string() };
+    let name = block.project_name.as_deref().map(str::trim).filter(|n| !n.is_empty()).unwrap_or(project);
+    let no_vars = HashMap::new();
+    let mut header = format!("\n{}\n{}\n<header class=\"erslice-brand\">", BRAND_BLOCK_MARKER, BRAND_BLOCK_STYLE);
+    if let Some(src) = logo_src {
+        header.push_str(&format!("<img src=\"{}\" alt=\"{}\">", escape_html(src), escape_html(name)));
+    }
+    header.push_str(&format!("<span>{}</span></header>\n", escape_html(name)));
+    let footer = block
+        .footer_text
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(|t| format!("<footer class=\"erslice-brand-footer\">{}</footer>\n", escape_html(&substitute_brand_vars(t, &no_vars, project))))
+        .unwrap_or_default();
+    format!("{}{}{}{}{}", &html[..body_open_end], header, &html[body_open_end..body_close], footer, &html[body_close..])
+}
+
+// Mermaid 預覽頁位於輸出包之外，logo 以 data URL 內嵌
+fn brand_preview_html(html: &str) -> String {
+    let Some(cfg) = read_active_project_config() else { return html.to_string() };
+    let Some(block) = cfg.brand_block.as_ref().filter(|b| !b.is_empty()) else { return html.to_string() };
+    let logo_src = block.logo().and_then(|p| {
+        use base64::Engine;
+        let bytes = std::fs::read(&p).ok()?;
+        let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+        Some(format!("data:{};base64,{}", mime_for_extension(&ext), base64::engine::general_purpose::STANDARD.encode(bytes)))
+    });
+    inject_brand_block(html, block, &cfg.name, logo_src.as_deref())
+}
+
+// ====== 匯出模組原始資產（zip） ======
+
+// 將 files 以 root 為基準的相對路徑寫入 zip
+// checksums 為此封存檔專屬的校驗碼清單，寫入 <prefix>/checksums.txt
+fn write_zip_archive(root: &std::path::Path, files: &[PathBuf], zip_path: &std::path::Path, prefix: &str, checksums: Option<&str>) -> Result<u64, String> {
+    use std::io::Write;
+    let file = std::fs::File::create(zip_path).map_err(|e| format!("建立 zip 失敗: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip_file_options(read_active_project_config().and_then(|c| c.zip_compression_level));
+    let mut total = 0u64;
+    for f in files.iter() {
+        let rel = f.strip_prefix(root).unwrap_or(f).to_string_lossy().replace('\\', "/");
+        let name = archive_entry_name(prefix, &rel);
+        let bytes = std::fs::read(f).map_err(|e| format!("讀取 {} 失敗: {}", f.display(), e))?;
+        zip.start_file(name, options).map_err(|e| format!("寫入 zip 失敗: {}", e))?;
+        zip.write_all(&bytes).map_err(|e| format!("寫入 zip 失敗: {}", e))?;
+        total += bytes.len() as u64;
+    }
+    if let Some(listing) = checksums {
+        zip.start_file(archive_entry_name(prefix, PACKAGE_CHECKSUMS_FILE), options).map_err(|e| format!("寫入 zip 失敗: {}", e))?;
+        zip.write_all(listing.as_bytes()).map_err(|e| format!("寫入 zip 失敗: {}", e))?;
+    }
+    zip.finish().map_err(|e| format!("完成 zip 失敗: {}", e))?;
+    Ok(total)
+}
+
+// 依壓縮等級決定 zip 項目選項：0 為 stored，其餘限制在 deflate 的 1-9
+fn zip_file_options(level: Option<i64>) -> zip::write::SimpleFileOptions {
+    let options = zip::write::SimpleFileOptions::default().unix_permissions(0o644);
+    match level {
+        Some(l) if l <= 0 => options.compression_method(zip::CompressionMethod::Stored),
+        Some(l) => options
+            .compression_method(zip::CompressionMethod::Deflated)
+            .compression_level(Some(l.min(9))),
+        None => options.compression_method(zip::CompressionMethod::Deflated),
+    }
+}
+
+/// 僅將模組的原始設計資產（不含生成的骨架）打包為 zip，輸出至 output/exports
+#[tauri::command]
+pub async fn export_module_assets_zip(module_name: String) -> Result<String, String> {
+    let module_dir = PathBuf::from("design-assets").join(&module_name);
+    if !module_dir.exists() {
+        return Err("設計模組不存在".to_string());
+    }
+    let mut files = Vec::new();
+    collect_files_recursive(&module_dir, &mut files);
+    files.retain(|f| !f.file_name().and_then(|n| n.to_str()).unwrap_or("").starts_with('.'));
+    files.sort();
+    if files.is_empty() {
+        return Err("此模組沒有任何資產".to_string());
+    }
+    let export_dir = output_root().join("exports");
+    std::fs::create_dir_all(&export_dir).map_err(|e| format!("建立輸出目錄失敗: {}", e))?;
+    let ts = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let zip_path = export_dir.join(format!("{}-assets-{}.zip", module_name, ts));
+    write_zip_archive(&module_dir, &files, &zip_path, &module_name, None)?;
+    Ok(zip_path.to_string_lossy().to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveFormat {
+    Zip,
+    TarGz,
+    None,
+}
+
+// 解析 archive_format（"zip" | "tar.gz" | "none"）；未指定時沿用 make_zip
+fn resolve_archive_format(format: Option<&str>, make_zip: bool) -> Result<ArchiveFormat, String> {
+    match format.map(|f| f.trim().to_lowercase()) {
+        None => Ok(if make_zip { ArchiveFormat::Zip } else { ArchiveFormat::None }),
+        Some(f) => match f.as_str() {
+            "zip" => Ok(ArchiveFormat::Zip),
+            "tar.gz" | "tgz" => Ok(ArchiveFormat::TarGz),
+            "none" | "" => Ok(ArchiveFormat::None),
+            other => Err(format!("不支援的封存格式: {}", other)),
+        },
+    }
+}
+
+// 封存結果：分割時 parts 依序為 <名稱>.partN.<副檔名>；excluded 為超過截圖大小上限而未封存的檔案（相對輸出包）
+#[derive(Debug, Default)]
+struct PackageArchive {
+    parts: Vec<PathBuf>,
+    excluded: Vec<String>,
+}
+
+impl PackageArchive {
+    // 第一個封存檔（未分割時即唯一的封存檔），供 hook 與生成紀錄使用
+    fn primary(&self) -> Option<String> {
+        self.parts.first().map(|p| p.to_string_lossy().to_string())
+    }
+
+    fn part_paths(&self) -> Vec<String> {
+        self.parts.iter().map(|p| p.to_string_lossy().to_string()).collect()
+    }
+
+    // 有分割或排除檔案時的摘要
+    fn summary(&self) -> Option<String> {
+        let mut notes = Vec::new();
+        if self.parts.len() > 1 {
+            notes.push(format!("分割為 {} 個封存檔", self.parts.len()));
+        }
+        if !self.excluded.is_empty() {
+            notes.push(format!("排除 {} 個過大截圖: {}", self.excluded.len(), self.excluded.join(", ")));
+        }
+        if notes.is_empty() { None } else { Some(notes.join("；")) }
+    }
+}
+
+// 每個封存項目預估的標頭與目錄開銷，分割時一併計入
+const ARCHIVE_ENTRY_OVERHEAD: u64 = 1024;
+
+// 依原始大小將檔案依序分組，每組不超過 limit（單一檔案超過 limit 時自成一組）；
+// 截圖等已壓縮的檔案在封存後幾乎不會變小，以原始大小估算可確保每個分段不超過上限
+fn split_archive_files(files: Vec<PathBuf>, limit: Option<u64>) -> Vec<Vec<PathBuf>> {
+    let Some(limit) = limit.filter(|l| *l > 0) else { return vec![files] };
+    let mut groups: Vec<Vec<PathBuf>> = Vec::new();
+    let mut current: Vec<PathBuf> = Vec::new();
+    let mut size = 0u64;
+    for f in files {
+        let len = std::fs::metadata(&f).map(|m| m.len()).unwrap_or(0) + ARCHIVE_ENTRY_OVERHEAD;
+        if !current.is_empty() && size + len > limit {
+            groups.push(std::mem::take(&mut current));
+            size = 0;
+        }
+        size += len;
+        current.push(f);
+    }
+    if !current.is_empty() || groups.is_empty() {
+        groups.push(current);
+    }
+    groups
+}
+
+// 將輸出包資料夾封存至 dest_dir/<資料夾名稱>.<副檔名>，封存內以資料夾名稱為根目錄；
+// 依專案設定排除過大的截圖，並在超過 archive_split_mb 時分成多個可各自解壓的分段。
+// 輸出包有 checksums.txt 時，每個分段改放只列出該分段實際封存檔案的清單，以便各自驗證
+fn write_package_archive(package_dir: &std::path::Path, dest_dir: &std::path::Path, format: ArchiveFormat) -> Result<Option<PackageArchive>, String> {
+    if format == ArchiveFormat::None {
+        return Ok(None);
+    }
+    let cfg = read_active_project_config();
+    let folder_name = package_dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| "package".to_string());
+    let mut files = Vec::new();
+    collect_output_files(package_dir, &mut files);
+    files.sort();
+    let checksummed = package_dir.join(PACKAGE_CHECKSUMS_FILE).is_file();
+    if checksummed {
+        files.retain(|f| f.strip_prefix(package_dir).map(|rel| rel != std::path::Path::new(PACKAGE_CHECKSUMS_FILE)).unwrap_or(true));
+    }
+    let mut archive = PackageArchive::default();
+    if let Some(max_kb) = cfg.as_ref().and_then(|c| c.archive_max_screenshot_kb).filter(|k| *k > 0) {
+        files.retain(|f| {
+            let rel = f.strip_prefix(package_dir).unwrap_or(f);
+            let oversized = rel.components().any(|c| c.as_os_str() == "screenshots")
+                && is_image_file(f)
+                && std::fs::metadata(f).map(|m| m.len() > max_kb * 1024).unwrap_or(false);
+            if oversized {
+                archive.excluded.push(rel.to_string_lossy().replace('\\', "/"));
+            }
+            !oversized
+        });
+    }
+    let groups = split_archive_files(files, cfg.as_ref().and_then(|c| c.archive_split_mb).map(|mb| mb * 1024 * 1024));
+    let ext = match format {
+        ArchiveFormat::Zip => "zip",
+        ArchiveFormat::TarGz => "tar.gz",
+        ArchiveFormat::None => unreachable!(),
+    };
+    for (i, group) in groups.iter().enumerate() {
+        let name = if groups.len() > 1 { format!("{}.part{}.{}", folder_name, i + 1, ext) } else { format!("{}.{}", folder_name, ext) };
+        let path = dest_dir.join(name);
+        let checksums = if checksummed { Some(package_checksums_listing(package_dir, group)?) } else { None };
+        match format {
+            ArchiveFormat::Zip => write_zip_archive(package_dir, group, &path, &folder_name, checksums.as_deref())?,
+            _ => write_tar_gz_archive(package_dir, group, &path, &folder_name, checksums.as_deref())?,
+        };
+        archive.parts.push(path);
+    }
+    Ok(Some(archive))
+}
+
+// 封存項目名稱：prefix 為空時即相對路徑
+fn archive_entry_name(prefix: &str, rel: &str) -> String {
+    if prefix.is_empty() { rel.to_string() } else { format!("{}/{}", prefix, rel) }
+}
+
+fn write_tar_gz_archive(root: &std::path::Path, files: &[PathBuf], tar_path: &std::path::Path, prefix: &str, checksums: Option<&str>) -> Result<u64, String> {
+    let file = std::fs::File::create(tar_path).map_err(|e| format!("建立 tar.gz 失敗: {}", e))?;
+    let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    builder.mode(tar::HeaderMode::Deterministic);
+    let mut total = 0u64;
+    for f in files.iter() {
+        let rel = f.strip_prefix(root).unwrap_or(f).to_string_lossy().replace('\\', "/");
+        let name = archive_entry_name(prefix, &rel);
+        builder.append_path_with_name(f, &name).map_err(|e| format!("寫入 tar.gz 失敗: {}", e))?;
+        total += std::fs::metadata(f).map(|m| m.len()).unwrap_or(0);
+    }
+    if let Some(listing) = checksums {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(listing.len() as u64);
+        header.set_mode(0o644);
+        builder
+            .append_data(&mut header, archive_entry_name(prefix, PACKAGE_CHECKSUMS_FILE), listing.as_bytes())
+            .map_err(|e| format!("寫入 tar.gz 失敗: {}", e))?;
+    }
+    let encoder = builder.into_inner().map_err(|e| format!("完成 tar.gz 失敗: {}", e))?;
+    encoder.finish().map_err(|e| format!("完成 tar.gz 失敗: {}", e))?;
+    Ok(total)
+}
+
+// ====== 語言一致性檢查 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LanguageIssue {
+    pub route: String,
+    // title / notes
+    pub field: String,
+    pub text: String,
+    pub language: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct LanguageReport {
+    // 多數文字所使用的語言（zh / en）
+    pub dominant_language: String,
+    pub counts: HashMap<String, usize>,
+    pub inconsistent: Vec<LanguageIssue>,
+    pub stubs_added: usize,
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32, 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF | 0x3040..=0x30FF | 0xAC00..=0xD7AF)
+}
+
+// 依字元比例判斷語言：zh / en / mixed / unknown
+fn detect_text_language(text: &str) -> &'static str {
+    let cjk = text.chars().filter(|c| is_cjk(*c)).count();
+    let latin_words = text
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|w| w.len() >= 2)
+        .count();
+    match (cjk, latin_words) {
+        (0, 0) => "unknown",
+        (_, 0) => "zh",
+        (0, _) => "en",
+        // 中文句子夾雜少量英文術語（如 API、Email）仍視為中文
+        (c, w) if c >= 4 && w <= 2 => "zh",
+        _ => "mixed",
+    }
+}
+
+/// 檢查頁面標題與備註的語言一致性，列出與多數語言不同或中英混雜的項目；
+/// add_translation_stubs 為 true 時在 page.json 的 translations 補上待翻譯欄位
+#[tauri::command]
+pub async fn get_language_report(add_translation_stubs: Option<bool>) -> Result<LanguageReport, String> {
+    let entries = collect_page_entries();
+    let mut items: Vec<(usize, &'static str, String, &'static str)> = Vec::new();
+    for (i, e) in entries.iter().enumerate() {
+        for (field, value) in [("title", &e.meta.title), ("notes", &e.meta.notes)] {
+            let Some(text) = value.as_ref().filter(|t| !t.trim().is_empty()) else { continue };
+            let lang = detect_text_language(text);
+            if lang != "unknown" {
+                items.push((i, field, text.clone(), lang));
+            }
+        }
+    }
+    let mut report = LanguageReport::default();
+    for (_, _, _, lang) in items.iter() {
+        *report.counts.entry(lang.to_string()).or_insert(0) += 1;
+    }
+    let zh = report.counts.get("zh").copied().unwrap_or(0);
+    let en = report.counts.get("en").copied().unwrap_or(0);
+    report.dominant_language = if en > zh { "en".into() } else { "zh".into() };
+
+    let add_stubs = add_translation_stubs.unwrap_or(false);
+    let mut touched: std::collections::BTreeMap<usize, PageMeta> = std::collections::BTreeMap::new();
+    for (i, field, text, lang) in items.into_iter() {
+        if lang == report.dominant_language { continue; }
+        let e = &entries[i];
+        report.inconsistent.push(LanguageIssue { route: e.route_path(), field: field.to_string(), text, language: lang.to_string() });
+        if add_stubs {
+            let meta = touched.entry(i).or_insert_with(|| e.meta.clone());
+            let key = format!("{}.{}", field, report.dominant_language);
+            let translations = meta.translations.get_or_insert_with(Default::default);
+            if let std::collections::btree_map::Entry::Vacant(slot) = translations.entry(key) {
+                slot.insert(String::new());
+                report.stubs_added += 1;
+            }
+        }
+    }
+    for (i, meta) in touched.iter() {
+        let e = &entries[*i];
+        let dir = resolve_page_dir(&e.module, e.subpage.as_ref().map(|_| e.page.as_str()), e.subpage.as_deref().unwrap_or(&e.page));
+        write_page_meta(&dir, meta)?;
+    }
+    if !touched.is_empty() {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        for (i, _) in touched.iter() {
+            cache.invalidate_module(&entries[*i].module);
+        }
+    }
+    Ok(report)
+}
+
+// ====== 掃描忽略規則 ======
+
+// 預設忽略：隱藏檔（.DS_Store、.git 等）、node_modules、macOS 壓縮殘留與暫存檔
+const DEFAULT_IGNORE_PATTERNS: [&str; 6] = [".*", "node_modules", "__MACOSX", "Thumbs.db", "desktop.ini", "*.tmp"];
+
+// 保留給 ErSlice 自身使用、不可被忽略的檔名
+const NEVER_IGNORED: [&str; 7] = [ASSET_TAGS_FILE, ASSET_ANNOTATIONS_FILE, HTML_STRUCTURE_FILE, CSS_TOKENS_FILE, DESIGN_TOKENS_FILE, ASSET_ORDER_FILE, "_order.json"];
+
+struct IgnoreRules {
+    patterns: Vec<String>,
+}
+
+impl IgnoreRules {
+    fn load() -> Self {
+        let patterns = read_active_project_config()
+            .and_then(|c| c.ignore_patterns)
+            .unwrap_or_else(|| DEFAULT_IGNORE_PATTERNS.iter().map(|s| s.to_string()).collect());
+        IgnoreRules { patterns: patterns.into_iter().map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).collect() }
+    }
+
+    // 以單一檔名/資料夾名稱比對（不含路徑）
+    fn matches(&self, name: &str) -> bool {
+        !NEVER_IGNORED.contains(&name) && self.patterns.iter().any(|p| glob_match(p, name))
+    }
+}
+
+// 供檔案監看器使用
+pub(crate) fn is_ignored_name(name: &str) -> bool {
+    name == VERSIONS_DIR || IgnoreRules::load().matches(name)
+}
+
+/// 取得目前生效的忽略規則（未設定時為預設值）
+#[tauri::command]
+pub async fn get_ignore_patterns() -> Result<Vec<String>, String> {
+    Ok(IgnoreRules::load().patterns)
+}
+
+// ====== 斷點截圖變體 ======
+
+// 截圖依斷點放在 screenshots/<breakpoint>/；斷點名稱需為專案設定（或預設 desktop/tablet/mobile）之一
+fn screenshot_breakpoint_dir(type_dir: PathBuf, asset_type: &str, breakpoint: Option<&str>) -> Result<PathBuf, String> {
+    let Some(bp) = breakpoint.map(str::trim).filter(|b| !b.is_empty()) else { return Ok(type_dir) };
+    if asset_type != "screenshots" {
+        return Err("只有截圖可以指定斷點".to_string());
+    }
+    let names: Vec<String> = configured_breakpoints().into_iter().map(|b| b.name).collect();
+    if !names.iter().any(|n| n == bp) {
+        return Err(format!("未知的斷點: {}（可用：{}）", bp, names.join(", ")));
+    }
+    Ok(type_dir.join(bp))
+}
+
+fn breakpoint_screenshots_spec_section(module_name: &str) -> String {
+    let module_dir = PathBuf::from("design-assets").join(module_name);
+    let breakpoints = configured_breakpoints();
+    let ignore = IgnoreRules::load();
+    let lang = project_spec_language();
+    let mut rows: Vec<String> = Vec::new();
+    for level in module_asset_levels(&module_dir) {
+        let label = level.strip_prefix(&module_dir).ok()
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| lang.pick("(模組)", "(module)", "(モジュール)").to_string());
+        for bp in breakpoints.iter() {
+            let mut files = get_files_in_dir(&level.join("screenshots").join(&bp.name));
+            files.retain(|f| !ignore.matches(f));
+            if files.is_empty() { continue; }
+            files.sort();
+            let list: Vec<String> = files.iter().map(|f| format!("`{}`", f)).collect();
+            let size = fill_spec_text(lang.pick("{}（{}×{}）", "{} ({}×{})", "{}（{}×{}）"), &[bp.name.clone(), bp.width.to_string(), bp.height.to_string()]);
+            rows.push(format!("| {} | {} | {} |\n", label, size, list.join("<br>")));
+        }
+    }
+    if rows.is_empty() { return String::new(); }
+    let mut out = String::from(lang.pick(
+        "\n## 響應式截圖\n同一畫面在各斷點的設計稿（位於 `screenshots/<breakpoint>/`），請依斷點實作對應版面：\n\n| 位置 | 斷點 | 截圖 |\n|---|---|---|\n",
+        "\n## Responsive Screenshots\nDesigns of the same screen at each breakpoint (in `screenshots/<breakpoint>/`); implement the matching layout for every breakpoint:\n\n| Location | Breakpoint | Screenshots |\n|---|---|---|\n",
+        "\n## レスポンシブスクリーンショット\n同じ画面のブレークポイント別デザイン（`screenshots/<breakpoint>/`）です。ブレークポイントごとに対応するレイアウトを実装してください：\n\n| 場所 | ブレークポイント | スクリーンショット |\n|---|---|---|\n",
+    ));
+    for r in rows { out.push_str(&r); }
+    out
+}
+
+// ====== 從既有程式碼匯入元件清單 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CodebaseImportSummary {
+    pub module: String,
+    pub components: Vec<String>,
+    pub props_extracted: usize,
+    pub styles_copied: usize,
+    pub skipped: Vec<String>,
+}
+
+// 找出從 from 之後第一個 open 對應的結束位置，回傳區塊內容（不含括號）
+fn extract_balanced(src: &str, from: usize, open: char, close: char) -> Option<&str> {
+    let start = from + src[from..].find(open)?;
+    let mut depth = 0usize;
+    for (i, c) in src[start..].char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(&src[start + open.len_utf8()..start + i]);
+            }
+        }
+    }
+    None
+}
+
+// 依分隔字元切開，但忽略括號/角括號/字串內的分隔字元
+fn split_top_level(s: &str, seps: &[char]) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut cur = String::new();
+    let mut prev = '\0';
+    for c in s.chars() {
+        if let Some(q) = quote {
+            if c == q && prev != '\\' { quote = None; }
+            cur.push(c);
+        } else if matches!(c, '\'' | '"' | '`') {
+            quote = Some(c);
+            cur.push(c);
+        } else if matches!(c, '{' | '(' | '[' | '<') {
+            depth += 1;
+            cur.push(c);
+        } else if matches!(c, '}' | ')' | ']') || (c == '>' && prev != '=') {
+            depth -= 1;
+            cur.push(c);
+        } else if depth <= 0 && seps.contains(&c) {
+            if !cur.trim().is_empty() { parts.push(cur.trim().to_string()); }
+            cur.clear();
+        } else {
+            cur.push(c);
+        }
+        prev = c;
+    }
+    if !cur.trim().is_empty() { parts.push(cur.trim().to_string()); }
+    parts
+}
+
+fn strip_code_comments(src: &str) -> String {
+    let mut out = String::with_capacity(src.len());
+    let mut rest = src;
+    while let Some(i) = rest.find("/*") {
+        out.push_str(&rest[..i]);
+        rest = rest[i + 2..].find("*/").map(|j| &rest[i + 2 + j + 2..]).unwrap_or("");
+    }
+    out.push_str(rest);
+    out.lines()
+        .map(|l| match l.find("//") {
+            // 避免切到 URL（例如 'https://'）
+            Some(i) if !l[..i].ends_with(':') => &l[..i],
+            _ => l,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// 解析 TypeScript 物件型別成員：name?: type
+fn parse_ts_members(block: &str) -> Vec<ComponentProp> {
+    split_top_level(&strip_code_comments(block), &[';', ',', '\n'])
+        .into_iter()
+        .filter_map(|m| {
+            let (name, ty) = m.split_once(':')?;
+            let name = name.trim().trim_start_matches("readonly ").trim();
+            let optional = name.ends_with('?');
+            let name = name.trim_end_matches('?').trim().trim_matches(['\'', '"']);
+            if !is_valid_prop_name(name) { return None; }
+            Some(ComponentProp { name: name.to_string(), prop_type: ty.trim().to_string(), default: None, required: !optional, description: None })
+        })
+        .collect()
+}
+
+// 解析物件字面值成員的預設值：{ a = 1, b } 或 { a: 1 }
+fn parse_default_values(block: &str, assign: char) -> HashMap<String, String> {
+    split_top_level(&strip_code_comments(block), &[','])
+        .into_iter()
+        .filter_map(|item| {
+            let (k, v) = item.split_once(assign)?;
+            let k = k.trim().trim_matches(['\'', '"']);
+            if !is_valid_prop_name(k) || v.trim().is_empty() || v.starts_with(['=', '>']) { return None; }
+            Some((k.to_string(), v.trim().to_string()))
+        })
+        .collect()
+}
+
+fn vue_constructor_type(ctor: &str) -> String {
+    match ctor.trim() {
+        "String" => "string".into(),
+        "Number" => "number".into(),
+        "Boolean" => "boolean".into(),
+        "Array" => "unknown[]".into(),
+        "Object" => "Record<string, unknown>".into(),
+        "Function" => "(...args: unknown[]) => unknown".into(),
+        "Date" => "Date".into(),
+        other if other.starts_with('[') => other.trim_matches(['[', ']']).split(',').map(vue_constructor_type).collect::<Vec<_>>().join(" | "),
+        other => other.to_string(),
+    }
+}
+
+// Vue runtime props 宣告：{ a: String, b: { type: Number, required: true, default: 0 } }
+fn parse_vue_runtime_props(block: &str) -> Vec<ComponentProp> {
+    split_top_level(&strip_code_comments(block), &[','])
+        .into_iter()
+        .filter_map(|item| {
+            let (name, def) = item.split_once(':')?;
+            let name = name.trim().trim_matches(['\'', '"']);
+            if !is_valid_prop_name(name) { return None; }
+            let def = def.trim();
+            if let Some(inner) = def.strip_prefix('{').and_then(|d| d.strip_suffix('}')) {
+                let fields: HashMap<String, String> = split_top_level(inner, &[','])
+                    .into_iter()
+                    .filter_map(|f| f.split_once(':').map(|(k, v)| (k.trim().to_string(), v.trim().to_string())))
+                    .collect();
+                Some(ComponentProp {
+                    name: name.to_string(),
+                    prop_type: fields.get("type").map(|t| vue_constructor_type(t)).unwrap_or_else(|| "unknown".into()),
+                    default: fields.get("default").cloned(),
+                    required: fields.get("required").is_some_and(|r| r == "true"),
+                    description: None,
+                })
+            } else {
+                Some(ComponentProp { name: name.to_string(), prop_type: vue_constructor_type(def), default: None, required: false, description: None })
+            }
+        })
+        .collect()
+}
+
+fn extract_react_props(src: &str, component: &str) -> Vec<ComponentProp> {
+    let named = [format!("interface {}Props", component), format!("type {}Props", component)];
+    let pos = named.iter().find_map(|n| src.find(n.as_str()))
+        .or_else(|| src.find("Props {").and_then(|i| src[..i].rfind("interface ")))
+        .or_else(|| src.find("Props = {").and_then(|i| src[..i].rfind("type ")));
+    let mut props = pos.and_then(|p| extract_balanced(src, p, '{', '}')).map(parse_ts_members).unwrap_or_default();
+    // 解構參數中的預設值：function Button({ size = 'md' }: ButtonProps)
+    let decl = [format!("function {}(", component), format!("const {} =", component), format!("const {}:", component)]
+        .iter()
+        .find_map(|d| src.find(d.as_str()));
+    if let Some(d) = decl {
+        if let Some(params) = extract_balanced(src, d, '(', ')') {
+            if let Some(destructured) = params.trim_start().strip_prefix('{').and_then(|_| extract_balanced(params, 0, '{', '}')) {
+                let defaults = parse_default_values(destructured, '=');
+                for p in props.iter_mut() {
+                    if let Some(v) = defaults.get(&p.name) { p.default = Some(v.clone()); }
+                }
+            }
+        }
+    }
+    props
+}
+
+fn extract_vue_props(src: &str) -> Vec<ComponentProp> {
+    let mut props = if let Some(i) = src.find("defineProps<") {
+        extract_balanced(src, i, '{', '}').map(parse_ts_members).unwrap_or_default()
+    } else if let Some(i) = src.find("defineProps(").or_else(|| src.find("props:")) {
+        extract_balanced(src, i, '{', '}').map(parse_vue_runtime_props).unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    // withDefaults(defineProps<...>(), { size: 'md' })
+    if let Some(i) = src.find("withDefaults(") {
+        if let Some(args) = extract_balanced(src, i, '(', ')') {
+            if let Some(j) = args.find("()") {
+                if let Some(block) = extract_balanced(args, j, '{', '}') {
+                    let defaults = parse_default_values(block, ':');
+                    for p in props.iter_mut() {
+                        if let Some(v) = defaults.get(&p.name) { p.default = Some(v.clone()); }
+                    }
+                }
+            }
+        }
+    }
+    props
+}
+
+// Vue SFC 的 <style> 區塊：回傳 (副檔名, 內容)
+fn extract_vue_style(src: &str) -> Option<(&'static str, String)> {
+    let start = src.find("<style")?;
+    let tag_end = start + src[start..].find('>')?;
+    let tag = &src[start..tag_end];
+    let body_end = tag_end + src[tag_end..].find("</style>")?;
+    let body = src[tag_end + 1..body_end].trim();
+    if body.is_empty() { return None; }
+    let ext = if tag.contains("lang=\"scss\"") { "scss" } else if tag.contains("lang=\"less\"") { "less" } else { "css" };
+    Some((ext, format!("{}\n", body)))
+}
+
+fn kebab_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 && !out.ends_with('-') { out.push('-'); }
+            out.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            out.push(c);
+        } else if !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    out.trim_matches('-').to_string()
+}
+
+// 元件檔案：副檔名符合框架、檔名為 PascalCase（index 檔以資料夾名稱為元件名），排除測試與 stories
+fn component_source_name(path: &std::path::Path, framework: &str) -> Option<String> {
+    let ext = path.extension()?.to_str()?;
+    let ok_ext = match framework {
+        "react" => matches!(ext, "tsx" | "jsx"),
+        "vue" => ext == "vue",
+        _ => false,
+    };
+    if !ok_ext { return None; }
+    let stem = path.file_stem()?.to_str()?;
+    if stem.contains(".test") || stem.contains(".spec") || stem.contains(".stories") { return None; }
+    let name = if stem == "index" { path.parent()?.file_name()?.to_str()? } else { stem };
+    name.chars().next().filter(|c| c.is_ascii_uppercase()).map(|_| name.to_string())
+}
+
+fn collect_component_sources(dir: &std::path::Path, framework: &str, ignore: &IgnoreRules, out: &mut Vec<(String, PathBuf)>) {
+    let Ok(rd) = std::fs::read_dir(dir) else { return };
+    for entry in rd.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if ignore.matches(&name) || matches!(name.as_str(), "dist" | "build" | "coverage" | "__tests__") { continue; }
+        let path = entry.path();
+        if path.is_dir() {
+            collect_component_sources(&path, framework, ignore, out);
+        } else if let Some(component) = component_source_name(&path, framework) {
+            out.push((component, path));
+        }
+    }
+}
+
+// React 元件旁的樣式檔：Button.css、Button.module.scss、index.css 等
+fn sibling_style_files(source: &std::path::Path) -> Vec<PathBuf> {
+    let Some(dir) = source.parent() else { return Vec::new() };
+    let stem = source.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    get_files_in_dir(dir)
+        .into_iter()
+        .filter(|f| {
+            let is_style = [".css", ".scss", ".less"].iter().any(|e| f.ends_with(e));
+            let base = f.split('.').next().unwrap_or("");
+            is_style && (base == stem || (stem == "index" && dir.file_name().and_then(|n| n.to_str()) == Some(base)))
+        })
+        .map(|f| dir.join(f))
+        .collect()
+}
+
+/// 掃描既有 React/Vue 專案，為每個元件建立元件庫模組的頁面（名稱、props、既有樣式），作為改版前的現況清單
+#[tauri::command]
+pub async fn import_from_codebase(path: String, framework: String, module_name: Option<String>) -> Result<CodebaseImportSummary, String> {
+    let framework = framework.trim().to_lowercase();
+    if !matches!(framework.as_str(), "react" | "vue") {
+        return Err(format!("不支援的框架: {}（可用：react, vue）", framework));
+    }
+    let root = PathBuf::from(&path);
+    if !root.is_dir() {
+        return Err("程式碼路徑不存在".to_string());
+    }
+    let module_name = module_name.map(|m| m.trim().to_string()).filter(|m| !m.is_empty()).unwrap_or_else(|| "component-library".to_string());
+    let display_name = module_name;
+    let module_name = slugify_display_name(&display_name, "module");
+    let module_dir = PathBuf::from("design-assets").join(&module_name);
+    if !module_dir.exists() {
+        create_design_module(display_name, format!("從 {} 匯入的元件清單", path)).await?;
+    }
+
+    let mut sources = Vec::new();
+    collect_component_sources(&root, &framework, &IgnoreRules::load(), &mut sources);
+    sources.sort();
+    let mut summary = CodebaseImportSummary { module: module_name.clone(), ..Default::default() };
+    let mut order = load_order(&module_dir);
+    for (component, source) in sources.iter() {
+        let slug = kebab_case(component);
+        let page_dir = module_dir.join("pages").join(&slug);
+        if page_dir.exists() {
+            summary.skipped.push(format!("{}: 頁面已存在", component));
+            continue;
+        }
+        let src = match std::fs::read_to_string(source) {
+            Ok(s) => s,
+            Err(e) => {
+                summary.skipped.push(format!("{}: 讀取失敗 {}", component, e));
+                continue;
+            }
+        };
+        create_module_page(module_name.clone(), slug.clone()).await?;
+        let props = if framework == "react" { extract_react_props(&src, component) } else { extract_vue_props(&src) };
+        summary.props_extracted += props.len();
+
+        let css_dir = page_dir.join("css");
+        if framework == "vue" {
+            if let Some((ext, css)) = extract_vue_style(&src) {
+                std::fs::write(css_dir.join(format!("{}.{}", component, ext)), css).map_err(|e| format!("寫入樣式失敗: {}", e))?;
+                summary.styles_copied += 1;
+            }
+        } else {
+            for style in sibling_style_files(source) {
+                let Some(name) = style.file_name() else { continue };
+                std::fs::copy(&style, css_dir.join(name)).map_err(|e| format!("複製樣式失敗: {}", e))?;
+                summary.styles_copied += 1;
+            }
+        }
+
+        let mut meta = read_page_meta(&page_dir);
+        meta.title = Some(component.clone());
+        meta.component = Some(component.clone());
+        meta.area = Some("library".into());
+        meta.class = Some("componentLevel".into());
+        meta.notes = Some(format!("匯入自 {}", source.strip_prefix(&root).unwrap_or(source).to_string_lossy()));
+        meta.props = if props.is_empty() { None } else { Some(props) };
+        write_page_meta(&page_dir, &meta)?;
+        if !order.pages.contains(&slug) { order.pages.push(slug.clone()); }
+        summary.components.push(component.clone());
+    }
+    save_order(&module_dir, order).map_err(|e| format!("寫入頁面排序失敗: {}", e))?;
+    {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(&module_name);
+    }
+    Ok(summary)
+}
+
+// ====== 設計與實作覆蓋率 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CoverageMatch {
+    pub route: String,
+    pub module: String,
+    pub page: String,
+    pub subpage: Option<String>,
+    pub implemented_route: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ImplementationCoverage {
+    pub matched: Vec<CoverageMatch>,
+    pub designed_not_built: Vec<CoverageMatch>,
+    pub built_not_designed: Vec<String>,
+    pub coverage_percent: f64,
+    pub report_path: String,
+}
+
+// 路由比對用的正規化：去掉查詢字串、尾斜線，並把 :id / [id] / {id} / $id / * 等參數段視為同一種
+fn normalize_route_for_coverage(route: &str) -> String {
+    let route = route.split(['?', '#']).next().unwrap_or("").trim();
+    let segments: Vec<String> = route
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let is_param = s.starts_with(':') || s.starts_with('$') || s == "*"
+                || (s.starts_with('[') && s.ends_with(']'))
+                || (s.starts_with('{') && s.ends_with('}'));
+            if is_param { ":param".to_string() } else { s.to_lowercase() }
+        })
+        .collect();
+    format!("/{}", segments.join("/"))
+}
+
+fn coverage_markdown(report: &ImplementationCoverage) -> String {
+    let mut md = String::from("# 實作覆蓋率報告\n\n");
+    md.push_str(&format!("- 產生時間：{}\n", chrono::Utc::now().to_rfc3339()));
+    md.push_str(&format!("- 覆蓋率：{:.1}%（已實作 {} / 設計 {}）\n\n",
+        report.coverage_percent, report.matched.len(), report.matched.len() + report.designed_not_built.len()));
+    md.push_str("## 已設計未實作\n\n");
+    if report.designed_not_built.is_empty() { md.push_str("（無）\n"); }
+    for m in report.designed_not_built.iter() {
+        md.push_str(&format!("- `{}` — {}/{}{}\n", m.route, m.module, m.page, m.subpage.as_ref().map(|s| format!("/{}", s)).unwrap_or_default()));
+    }
+    md.push_str("\n## 已實作未設計\n\n");
+    if report.built_not_designed.is_empty() { md.push_str("（無）\n"); }
+    for r in report.built_not_designed.iter() {
+        md.push_str(&format!("- `{}`\n", r));
+    }
+    md.push_str("\n## 已對應\n\n| 設計路由 | 實作路由 | 頁面 |\n|---|---|---|\n");
+    for m in report.matched.iter() {
+        md.push_str(&format!("| `{}` | `{}` | {}/{}{} |\n", m.route, m.implemented_route, m.module, m.page, m.subpage.as_ref().map(|s| format!("/{}", s)).unwrap_or_default()));
+    }
+    md
+}
+
+/// 以實際應用的路由清單比對設計頁面，產出已對應、已設計未實作、已實作未設計的缺口分析（同時寫入 ai-docs/implementation-coverage.md）
+#[tauri::command]
+pub async fn map_implementation(routes_manifest: Vec<String>) -> Result<ImplementationCoverage, String> {
+    let mut built: std::collections::BTreeMap<String, String> = std::collections::BTreeMap::new();
+    for r in routes_manifest.iter().map(|r| r.trim()).filter(|r| !r.is_empty()) {
+        built.entry(normalize_route_for_coverage(r)).or_insert_with(|| r.to_string());
+    }
+    let mut report = ImplementationCoverage::default();
+    let mut used: std::collections::HashSet<String> = std::collections::HashSet::new();
+    for e in collect_page_entries() {
+        let route = e.meta.route.clone().unwrap_or_else(|| e.route_path());
+        let key = normalize_route_for_coverage(&route);
+        let item = CoverageMatch {
+            route,
+            module: e.module.clone(),
+            page: e.page.clone(),
+            subpage: e.subpage.clone(),
+            implemented_route: built.get(&key).cloned().unwrap_or_default(),
+        };
+        if built.contains_key(&key) {
+            used.insert(key);
+            report.matched.push(item);
+        } else {
+            report.designed_not_built.push(item);
+        }
+    }
+    report.built_not_designed = built.iter().filter(|(k, _)| !used.contains(*k)).map(|(_, v)| v.clone()).collect();
+    let designed = report.matched.len() + report.designed_not_built.len();
+    report.coverage_percent = if designed == 0 { 0.0 } else { report.matched.len() as f64 * 100.0 / designed as f64 };
+
+    let path = PathBuf::from("ai-docs").join("implementation-coverage.md");
+    std::fs::create_dir_all(path.parent().unwrap()).map_err(|e| e.to_string())?;
+    report.report_path = path.to_string_lossy().to_string();
+    std::fs::write(&path, coverage_markdown(&report)).map_err(|e| format!("寫入覆蓋率報告失敗: {}", e))?;
+    Ok(report)
+}
+
+// ====== 回收桶 ======
+
+const TRASH_DIR: &str = ".trash";
+const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+const TRASH_ENTRY_FILE: &str = "entry.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrashEntry {
+    pub id: String,
+    // asset / page / subpage / module
+    pub kind: String,
+    pub original_path: String,
+    pub name: String,
+    pub deleted_at: String,
+    pub size: u64,
+}
+
+fn trash_retention_days() -> u32 {
+    read_active_project_config().and_then(|c| c.trash_retention_days).unwrap_or(DEFAULT_TRASH_RETENTION_DAYS)
+}
+
+fn path_size(path: &std::path::Path) -> u64 {
+    if path.is_dir() {
+        std::fs::read_dir(path).map(|rd| rd.flatten().map(|e| path_size(&e.path())).sum()).unwrap_or(0)
+    } else {
+        std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+    }
+}
+
+// 將檔案或目錄移入 .trash/<id>/，保留原路徑以便還原
+fn move_to_trash(path: &std::path::Path, kind: &str) -> Result<TrashEntry, String> {
+    let _ = purge_expired_trash();
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let stamp = chrono::Utc::now().format("%Y%m%d%H%M%S%3f").to_string();
+    let root = PathBuf::from(TRASH_DIR);
+    let mut id = format!("{}-{}", stamp, name);
+    let mut n = 1;
+    while root.join(&id).exists() {
+        n += 1;
+        id = format!("{}-{}-{}", stamp, name, n);
+    }
+    let slot = root.join(&id);
+    std::fs::create_dir_all(&slot).map_err(|e| format!("創建回收桶失敗: {}", e))?;
+    let entry = TrashEntry {
+        id,
+        kind: kind.to_string(),
+        original_path: path.to_string_lossy().replace('\\', "/"),
+        name: name.clone(),
+        deleted_at: chrono::Utc::now().to_rfc3339(),
+        size: path_size(path),
+    };
+    if let Err(e) = std::fs::rename(path, slot.join(&name)) {
+        let _ = std::fs::remove_dir_all(&slot);
+        return Err(format!("移至回收桶失敗: {}", e));
+    }
+    std::fs::write(slot.join(TRASH_ENTRY_FILE), serde_json::to_string_pretty(&entry).unwrap())
+        .map_err(|e| format!("寫入回收桶紀錄失敗: {}", e))?;
+    if let Some(module) = entry.original_path.strip_prefix("design-assets/").and_then(|p| p.split('/').next()) {
+        invalidate_sitemap_cache(Some(module));
+    }
+    Ok(entry)
+}
+
+fn read_trash_entries() -> Vec<TrashEntry> {
+    let mut out: Vec<TrashEntry> = list_dir_names(&PathBuf::from(TRASH_DIR))
+        .iter()
+        .filter_map(|id| std::fs::read_to_string(PathBuf::from(TRASH_DIR).join(id).join(TRASH_ENTRY_FILE)).ok())
+        .filter_map(|s| serde_json::from_str(&s).ok())
+        .collect();
+    out.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    out
+}
+
+// 刪除早於 days 天的回收桶項目，回傳清除數量
+fn purge_trash_older_than(days: u32) -> Result<usize, String> {
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(days as i64);
+    let mut removed = 0;
+    for entry in read_trash_entries() {
+        let expired = chrono::DateTime::parse_from_rfc3339(&entry.deleted_at)
+            .map(|t| t.with_timezone(&chrono::Utc) <= cutoff)
+            .unwrap_or(true);
+        if expired {
+            std::fs::remove_dir_all(PathBuf::from(TRASH_DIR).join(&entry.id)).map_err(|e| format!("清除回收桶失敗: {}", e))?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+fn purge_expired_trash() -> Result<usize, String> {
+    purge_trash_older_than(trash_retention_days())
+}
+
+/// 列出回收桶中的項目（新到舊），逾保留期限者會先自動清除
+#[tauri::command]
+pub async fn list_trash() -> Result<Vec<TrashEntry>, String> {
+    purge_expired_trash()?;
+    Ok(read_trash_entries())
+}
+
+/// 將回收桶項目還原至原路徑；原路徑已有同名檔案/目錄時拒絕還原
+#[tauri::command]
+pub async fn restore_from_trash(id: String) -> Result<String, String> {
+    if id.contains('/') || id.contains('\\') || id.starts_with('.') {
+        return Err("回收桶項目無效".to_string());
+    }
+    let slot = PathBuf::from(TRASH_DIR).join(&id);
+    let entry: TrashEntry = std::fs::read_to_string(slot.join(TRASH_ENTRY_FILE))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .ok_or_else(|| "回收桶項目不存在".to_string())?;
+    let target = PathBuf::from(&entry.original_path);
+    if target.exists() {
+        return Err(format!("原路徑已存在同名項目: {}", entry.original_path));
+    }
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("創建目錄失敗: {}", e))?;
+    }
+    std::fs::rename(slot.join(&entry.name), &target).map_err(|e| format!("還原失敗: {}", e))?;
+    let _ = std::fs::remove_dir_all(&slot);
+    if let Some(module) = entry.original_path.strip_prefix("design-assets/").and_then(|p| p.split('/').next()) {
+        invalidate_sitemap_cache(Some(module));
+    }
+    Ok(format!("已還原: {}", entry.original_path))
+}
+
+/// 清空回收桶；指定 older_than_days 時只清除早於該天數的項目，回傳清除數量
+#[tauri::command]
+pub async fn empty_trash(older_than_days: Option<u32>) -> Result<usize, String> {
+    purge_trash_older_than(older_than_days.unwrap_or(0))
+}
+
+// ====== 頁面測試案例 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PageTestCase {
+    pub id: String,
+    pub route: String,
+    // happy_path / validation / empty_state / error_state / navigation / overlay / access
+    pub kind: String,
+    pub title: String,
+    pub steps: Vec<String>,
+    pub expected: String,
+}
+
+#[derive(Debug, Clone)]
+struct FormField {
+    name: String,
+    field_type: String,
+    required: bool,
+}
+
+// 頁面 HTML 中的表單欄位（input/select/textarea），用於產出驗證錯誤案例
+fn page_form_fields(dir: &std::path::Path) -> Vec<FormField> {
+    let mut fields: Vec<FormField> = Vec::new();
+    for name in get_files_in_dir(&dir.join("html")) {
+        let Ok(html) = std::fs::read_to_string(dir.join("html").join(&name)) else { continue };
+        let lower = html.to_ascii_lowercase();
+        let mut pos = 0;
+        while let Some(i) = lower[pos..].find('<') {
+            let start = pos + i + 1;
+            let end = lower[start..].find('>').map(|e| start + e).unwrap_or(lower.len());
+            let tag = &html[start..end];
+            let tag_name: String = lower[start..end].chars().take_while(|c| c.is_ascii_alphanumeric()).collect();
+            if matches!(tag_name.as_str(), "input" | "select" | "textarea") {
+                let field_type = if tag_name == "input" { html_attr(tag, "type").unwrap_or_else(|| "text".into()).to_lowercase() } else { tag_name.clone() };
+                let label = ["name", "id", "aria-label", "placeholder"].iter().find_map(|a| html_attr(tag, a));
+                if let Some(label) = label.filter(|_| !matches!(field_type.as_str(), "hidden" | "submit" | "button" | "reset")) {
+                    let required = lower[start..end].split_whitespace().any(|t| t == "required" || t.starts_with("required=") || t == "required/");
+                    if !fields.iter().any(|f| f.name == label) {
+                        fields.push(FormField { name: label, field_type, required });
+                    }
+                }
+            }
+            pos = end;
+        }
+    }
+    fields
+}
+
+fn invalid_input_example(field_type: &str, lang: SpecLanguage) -> &'static str {
+    match field_type {
+        "email" => lang.pick("輸入格式錯誤的 email（例如 `abc@`）", "Enter a malformed email (e.g. `abc@`)", "形式が正しくない email を入力する（例：`abc@`）"),
+        "url" => lang.pick("輸入格式錯誤的網址（例如 `htp:/x`）", "Enter a malformed URL (e.g. `htp:/x`)", "形式が正しくない URL を入力する（例：`htp:/x`）"),
+        "number" | "range" => lang.pick("輸入非數字或超出範圍的值", "Enter a non-numeric or out-of-range value", "数値以外または範囲外の値を入力する"),
+        "tel" => lang.pick("輸入含字母的電話號碼", "Enter a phone number containing letters", "英字を含む電話番号を入力する"),
+        "date" | "datetime-local" | "time" => lang.pick("輸入不存在的日期/時間", "Enter a date/time that does not exist", "存在しない日付/時刻を入力する"),
+        "password" => lang.pick("輸入不符合強度規則的密碼", "Enter a password that fails the strength rules", "強度ルールを満たさないパスワードを入力する"),
+        _ => lang.pick("輸入超出長度限制或含非法字元的值", "Enter a value that is too long or contains invalid characters", "長さ制限を超える値または不正な文字を含む値を入力する"),
+    }
+}
+
+fn page_test_cases(e: &PageEntry, lang: SpecLanguage) -> Vec<PageTestCase> {
+    let dir = page_entry_dir(e);
+    let route = e.meta.route.clone().unwrap_or_else(|| e.route_path());
+    let title = e.meta.title.clone().unwrap_or_else(|| e.subpage.clone().unwrap_or_else(|| e.page.clone()));
+    let id_prefix = format!("{}-{}{}", e.module, e.page, e.subpage.as_ref().map(|s| format!("-{}", s)).unwrap_or_default());
+    let mut cases: Vec<PageTestCase> = Vec::new();
+    let mut push = |kind: &str, title: String, steps: Vec<String>, expected: String| {
+        cases.push(PageTestCase { id: format!("{}-{:02}", id_prefix, cases.len() + 1), route: route.clone(), kind: kind.to_string(), title, steps, expected });
+    };
+    let t = |zh: &str, en: &str, ja: &str, args: &[&str]| fill_spec_text(lang.pick(zh, en, ja), &args.iter().map(|a| a.to_string()).collect::<Vec<_>>());
+    let open = t("開啟 `{}`", "Open `{}`", "`{}` を開く", &[&route]);
+    let fields = page_form_fields(&dir);
+
+    let mut happy = vec![open.clone()];
+    for f in fields.iter() {
+        happy.push(t("於 `{}`（{}）輸入有效值", "Enter a valid value in `{}` ({})", "`{}`（{}）に有効な値を入力する", &[&f.name, &f.field_type]));
+    }
+    if let Some(action) = e.meta.action.as_ref().filter(|a| !a.is_empty()) {
+        happy.push(t("執行「{}」", "Perform \"{}\"", "「{}」を実行する", &[action]));
+    }
+    push(
+        "happy_path",
+        t("{}：主要流程", "{}: main flow", "{}：メインフロー", &[&title]),
+        happy,
+        t("頁面正確顯示，主要動作完成且無錯誤訊息", "The page renders correctly and the main action completes without errors", "ページが正しく表示され、メインの操作がエラーなく完了する", &[]),
+    );
+
+    for f in fields.iter().filter(|f| f.required) {
+        push(
+            "validation",
+            t("{}：`{}` 為必填", "{}: `{}` is required", "{}：`{}` は必須", &[&title, &f.name]),
+            vec![open.clone(), t("`{}` 留空後送出", "Leave `{}` empty and submit", "`{}` を空のまま送信する", &[&f.name])],
+            t("`{}` 顯示必填錯誤訊息，表單不送出", "`{}` shows a required-field error and the form is not submitted", "`{}` に必須エラーが表示され、フォームは送信されない", &[&f.name]),
+        );
+    }
+    for f in fields.iter().filter(|f| !matches!(f.field_type.as_str(), "checkbox" | "radio" | "select" | "file" | "color")) {
+        push(
+            "validation",
+            t("{}：`{}` 格式錯誤", "{}: invalid `{}`", "{}：`{}` の形式エラー", &[&title, &f.name]),
+            vec![open.clone(), invalid_input_example(&f.field_type, lang).to_string(), t("送出", "Submit", "送信する", &[])],
+            t("`{}` 顯示格式錯誤訊息", "`{}` shows a format error", "`{}` に形式エラーが表示される", &[&f.name]),
+        );
+    }
+    if let Some(empty) = e.meta.empty_state.as_ref().filter(|s| !s.is_empty()) {
+        push(
+            "empty_state",
+            t("{}：無資料", "{}: no data", "{}：データなし", &[&title]),
+            vec![t("準備沒有任何資料的帳號/環境", "Prepare an account/environment with no data", "データが一件もないアカウント/環境を用意する", &[]), open.clone()],
+            t("顯示空狀態：{}", "Shows the empty state: {}", "空状態を表示する：{}", &[empty]),
+        );
+    }
+    if let Some(err) = e.meta.error_state.as_ref().filter(|s| !s.is_empty()) {
+        push(
+            "error_state",
+            t("{}：載入失敗", "{}: loading fails", "{}：読み込み失敗", &[&title]),
+            vec![t("模擬 API 失敗或斷線", "Simulate an API failure or network loss", "API の失敗や切断を再現する", &[]), open.clone()],
+            t("顯示錯誤狀態：{}", "Shows the error state: {}", "エラー状態を表示する：{}", &[err]),
+        );
+    }
+    for link in e.meta.links.iter().flatten() {
+        let label = link.label.clone().unwrap_or_else(|| link.to.clone());
+        push(
+            "navigation",
+            t("{}：前往 {}", "{}: go to {}", "{}：{} へ移動", &[&title, &label]),
+            vec![open.clone(), t("點擊「{}」", "Click \"{}\"", "「{}」をクリックする", &[&label])],
+            t("導向 `{}`", "Navigates to `{}`", "`{}` に遷移する", &[&link.to]),
+        );
+    }
+    for o in e.meta.overlays.iter().flatten() {
+        let trigger = o.trigger.clone().unwrap_or_else(|| t("觸發 {}", "Trigger {}", "{} を表示させる", &[&o.name]));
+        push(
+            "overlay",
+            format!("{}：{} {}", title, o.kind, o.name),
+            vec![open.clone(), trigger],
+            t("顯示 {}「{}」，可正常關閉", "Shows {} \"{}\", which can be closed normally", "{}「{}」が表示され、正常に閉じられる", &[&o.kind, &o.name]),
+        );
+    }
+    if let Some(access) = e.meta.access.as_ref().filter(|a| !a.roles.is_empty()) {
+        push(
+            "access",
+            t("{}：未授權角色", "{}: unauthorized role", "{}：権限のないロール", &[&title]),
+            vec![t("以不在允許清單的角色登入", "Sign in with a role that is not allowed", "許可リストにないロールでログインする", &[]), open.clone()],
+            t("無法存取（允許角色：{}）", "Access is denied (allowed roles: {})", "アクセスできない（許可ロール：{}）", &[&access.roles.join(", ")]),
+        );
+    }
+    cases
+}
+
+fn module_test_cases(module_name: &str, lang: SpecLanguage) -> Vec<PageTestCase> {
+    collect_module_page_entries(module_name).iter().flat_map(|e| page_test_cases(e, lang)).collect()
+}
+
+fn test_cases_markdown(module_name: &str, cases: &[PageTestCase], lang: SpecLanguage) -> String {
+    let mut md = fill_spec_text(
+        lang.pick(
+            "# {} 測試案例\n\n> 由頁面 meta 自動產生，可作為 QA 檢查清單；結構化版本見 `test-cases.json`。\n",
+            "# {} Test Cases\n\n> Generated from page meta for use as a QA checklist; see `test-cases.json` for the structured version.\n",
+            "# {} テストケース\n\n> ページ meta から自動生成した QA チェックリストです。構造化版は `test-cases.json` を参照してください。\n",
+        ),
+        &[module_name.to_string()],
+    );
+    let mut current = String::new();
+    for c in cases {
+        if c.route != current {
+            current = c.route.clone();
+            md.push_str(&format!("\n## `{}`\n", current));
+        }
+        md.push_str(&format!("\n- [ ] **{}** {}（{}）\n", c.id, c.title, c.kind));
+        for (i, step) in c.steps.iter().enumerate() {
+            md.push_str(&format!("  {}. {}\n", i + 1, step));
+        }
+        md.push_str(&format!("  - {}{}\n", lang.pick("預期：", "Expected: ", "期待結果："), c.expected));
+    }
+    md
+}
+
+// ====== 截圖比對 ======
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScreenshotComparison {
+    // 0-100，與設計 QA 相同的像素門檻
+    pub similarity: f64,
+    pub identical: bool,
+    pub dimensions_a: (u32, u32),
+    pub dimensions_b: (u32, u32),
+    pub diff_path: String,
+}
+
+/// 逐像素比對兩張截圖，輸出差異圖（output/diffs/）與相似度，用於確認新匯出的設計稿是否真的有變動
+/// 尺寸不同時會先將 path_b 縮放至 path_a 的尺寸
+#[tauri::command]
+pub async fn compare_screenshots(path_a: String, path_b: String) -> Result<ScreenshotComparison, String> {
+    let a = PathBuf::from(&path_a);
+    let b = PathBuf::from(&path_b);
+    for p in [&a, &b] {
+        if !p.is_file() {
+            return Err(format!("檔案不存在: {}", p.display()));
+        }
+    }
+    let dimensions_a = image::image_dimensions(&a).map_err(|e| format!("讀取圖片失敗: {}", e))?;
+    let dimensions_b = image::image_dimensions(&b).map_err(|e| format!("讀取圖片失敗: {}", e))?;
+    let stem = |p: &PathBuf| sanitize_id(&p.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default());
+    let out_dir = output_root().join("diffs");
+    std::fs::create_dir_all(&out_dir).map_err(|e| format!("建立差異圖目錄失敗: {}", e))?;
+    let diff = out_dir.join(format!("{}-{}-vs-{}.png", chrono::Local::now().format("%Y%m%d-%H%M%S"), stem(&a), stem(&b)));
+    let similarity = pixel_diff_score(&a, &b, &diff)?;
+    let identical = dimensions_a == dimensions_b && std::fs::read(&a).ok() == std::fs::read(&b).ok();
+    Ok(ScreenshotComparison {
+        similarity: if identical { 100.0 } else { similarity },
+        identical,
+        dimensions_a,
+        dimensions_b,
+        diff_path: diff.to_string_lossy().to_string(),
+    })
+}
+
+// ====== 工作區鎖定 ======
+
+const WORKSPACE_LOCK_FILE: &str = ".erslice.lock";
+// 心跳超過此秒數未更新即視為失效（前一個實例當機或未正常關閉）
+const WORKSPACE_LOCK_STALE_SECS: i64 = 120;
+pub(crate) const WORKSPACE_LOCK_HEARTBEAT_SECS: u64 = 30;
+
+lazy_static::lazy_static! {
+    // 本次啟動的識別碼，用來判斷鎖定檔是否屬於自己
+    static ref WORKSPACE_SESSION_ID: String = uuid::Uuid::new_v4().to_string();
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkspaceLock {
+    pub session_id: String,
+    pub pid: u32,
+    pub hostname: String,
+    pub started_at: String,
+    pub heartbeat_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkspaceLockStatus {
+    // 本實例是否持有鎖定
+    pub acquired: bool,
+    pub holder: Option<WorkspaceLock>,
+    pub stale: bool,
+}
+
+fn local_hostname() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn read_workspace_lock() -> Option<WorkspaceLock> {
+    std::fs::read_to_string(WORKSPACE_LOCK_FILE).ok().and_then(|s| serde_json::from_str(&s).ok())
+}
+
+fn is_own_lock(lock: &WorkspaceLock) -> bool {
+    lock.session_id == *WORKSPACE_SESSION_ID
+}
+
+// 心跳過期，或同一台機器上的行程已不存在（僅能在有 /proc 的系統判斷）
+fn is_stale_lock(lock: &WorkspaceLock) -> bool {
+    let expired = chrono::DateTime::parse_from_rfc3339(&lock.heartbeat_at)
+        .map(|t| (chrono::Utc::now() - t.with_timezone(&chrono::Utc)).num_seconds() > WORKSPACE_LOCK_STALE_SECS)
+        .unwrap_or(true);
+    let dead_local = lock.hostname == local_hostname()
+        && std::path::Path::new("/proc").is_dir()
+        && !std::path::Path::new("/proc").join(lock.pid.to_string()).exists();
+    expired || dead_local
+}
+
+fn write_own_workspace_lock(started_at: Option<String>) -> Result<WorkspaceLock, String> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let lock = WorkspaceLock {
+        session_id: WORKSPACE_SESSION_ID.clone(),
+        pid: std::process::id(),
+        hostname: local_hostname(),
+        started_at: started_at.unwrap_or_else(|| now.clone()),
+        heartbeat_at: now,
+    };
+    std::fs::write(WORKSPACE_LOCK_FILE, serde_json::to_string_pretty(&lock).unwrap())
+        .map_err(|e| format!("寫入工作區鎖定檔失敗: {}", e))?;
+    Ok(lock)
+}
+
+fn workspace_lock_status() -> WorkspaceLockStatus {
+    match read_workspace_lock() {
+        Some(lock) => WorkspaceLockStatus { acquired: is_own_lock(&lock), stale: is_stale_lock(&lock), holder: Some(lock) },
+        None => WorkspaceLockStatus { acquired: false, holder: None, stale: false },
+    }
+}
+
+// 啟動時取得鎖定：無鎖定、已失效或屬於自己時寫入；否則回傳目前持有者供警告
+pub(crate) fn acquire_workspace_lock() -> WorkspaceLockStatus {
+    let status = workspace_lock_status();
+    if status.acquired || status.stale || status.holder.is_none() {
+        return match write_own_workspace_lock(None) {
+            Ok(lock) => WorkspaceLockStatus { acquired: true, holder: Some(lock), stale: false },
+            Err(e) => {
+                log::warn!("{}", e);
+                status
+            }
+        };
+    }
+    status
+}
+
+// 更新心跳；鎖定已被其他實例接管時回傳 false
+pub(crate) fn refresh_workspace_lock() -> bool {
+    match read_workspace_lock() {
+        Some(lock) if is_own_lock(&lock) => write_own_workspace_lock(Some(lock.started_at)).is_ok(),
+        _ => false,
+    }
+}
+
+pub(crate) fn release_workspace_lock() {
+    if read_workspace_lock().is_some_and(|l| is_own_lock(&l)) {
+        let _ = std::fs::remove_file(WORKSPACE_LOCK_FILE);
+    }
+}
+
+/// 查詢工作區鎖定狀態；acquired 為 false 且 holder 存在時表示另一個 ErSlice 實例（可能在其他電腦透過同步硬碟）正在使用
+#[tauri::command]
+pub async fn get_workspace_lock_status() -> Result<WorkspaceLockStatus, String> {
+    Ok(workspace_lock_status())
+}
+
+/// 強制接管工作區鎖定（確認另一個實例已關閉或願意承擔同時編輯的風險時使用）
+#[tauri::command]
+pub async fn force_takeover_workspace_lock() -> Result<WorkspaceLockStatus, String> {
+    let previous = read_workspace_lock();
+    let lock = write_own_workspace_lock(None)?;
+    if let Some(prev) = previous.filter(|p| !is_own_lock(p)) {
+        log::warn!("已強制接管工作區鎖定（原持有者 {} pid {}）", prev.hostname, prev.pid);
+    }
+    Ok(WorkspaceLockStatus { acquired: true, holder: Some(lock), stale: false })
+}
+
+// ====== 損壞 JSON 的隔離與復原 ======
+
+const QUARANTINE_DIR: &str = ".quarantine";
+const DATA_INCIDENTS_FILE: &str = "incidents.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DataIncident {
+    pub id: String,
+    pub file: String,
+    pub error: String,
+    pub quarantined_to: Option<String>,
+    // "backup"（<檔名>.bak）或 "git"（HEAD 版本）；None 表示無法復原，已改用預設值
+    pub restored_from: Option<String>,
+    pub detected_at: String,
+}
+
+// 寫入前保留上一份可解析的內容為 <檔名>.bak，作為損壞時的復原來源
+fn backup_json_file(path: &std::path::Path) {
+    let Ok(raw) = std::fs::read_to_string(path) else { return };
+    if serde_json::from_str::<serde_json::Value>(&raw).is_ok() {
+        let _ = std::fs::write(json_backup_path(path), raw);
+    }
+}
+
+fn json_backup_path(path: &std::path::Path) -> PathBuf {
+    let mut name = path.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+// 讀取 JSON；檔案不存在回傳 None，解析失敗時隔離並嘗試從備份或 git 復原
+fn read_json_with_recovery<T: serde::de::DeserializeOwned>(path: &std::path::Path) -> Option<T> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    match serde_json::from_str::<T>(&raw) {
+        Ok(v) => Some(v),
+        Err(e) => recover_corrupted_json(path, &e.to_string()),
+    }
+}
+
+fn git_head_version(path: &std::path::Path) -> Option<String> {
+    let dir = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(std::path::Path::new("."));
+    let name = path.file_name()?.to_str()?;
+    let out = std::process::Command::new("git")
+        .arg("-C").arg(dir)
+        .args(["show", &format!("HEAD:./{}", name)])
+        .output()
+        .ok()?;
+    if out.status.success() { String::from_utf8(out.stdout).ok() } else { None }
+}
+
+fn recover_corrupted_json<T: serde::de::DeserializeOwned>(path: &std::path::Path, error: &str) -> Option<T> {
+    let now = chrono::Utc::now();
+    let file = path.to_string_lossy().replace('\\', "/");
+    let quarantine = PathBuf::from(QUARANTINE_DIR).join(format!("{}-{}", now.format("%Y%m%d%H%M%S%3f"), file.replace('/', "__")));
+    let quarantined_to = std::fs::create_dir_all(QUARANTINE_DIR)
+        .and_then(|_| std::fs::rename(path, &quarantine))
+        .ok()
+        .map(|_| quarantine.to_string_lossy().to_string());
+
+    let candidates = [("backup", std::fs::read_to_string(json_backup_path(path)).ok()), ("git", git_head_version(path))];
+    let mut restored: Option<(&str, T)> = None;
+    for (source, raw) in candidates {
+        let Some(raw) = raw else { continue };
+        if let Ok(v) = serde_json::from_str::<T>(&raw) {
+            if std::fs::write(path, &raw).is_ok() {
+                restored = Some((source, v));
+                break;
+            }
+        }
+    }
+    let incident = DataIncident {
+        id: uuid::Uuid::new_v4().to_string(),
+        file,
+        error: error.to_string(),
+        quarantined_to,
+        restored_from: restored.as_ref().map(|(s, _)| s.to_string()),
+        detected_at: now.to_rfc3339(),
+    };
+    log::warn!("偵測到損壞的 JSON: {}（{}），復原來源: {:?}", incident.file, incident.error, incident.restored_from);
+    let mut incidents = read_data_incidents();
+    incidents.push(incident);
+    let _ = std::fs::write(PathBuf::from(QUARANTINE_DIR).join(DATA_INCIDENTS_FILE), serde_json::to_string_pretty(&incidents).unwrap());
+    restored.map(|(_, v)| v)
+}
+
+fn read_data_incidents() -> Vec<DataIncident> {
+    std::fs::read_to_string(PathBuf::from(QUARANTINE_DIR).join(DATA_INCIDENTS_FILE))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+/// 列出偵測到的資料損壞事件（新到舊），包含隔離位置與是否已自動復原
+#[tauri::command]
+pub async fn get_data_incidents() -> Result<Vec<DataIncident>, String> {
+    let mut incidents = read_data_incidents();
+    incidents.reverse();
+    Ok(incidents)
+}
+
+// ====== 資產標註 ======
+
+// 與 asset-tags.json 相同，存於資產所在層級，key 為 "<類型>/<檔名>"
+const ASSET_ANNOTATIONS_FILE: &str = "annotations.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AssetAnnotation {
+    pub id: String,
+    // 以圖片像素為單位的標註區域
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub text: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AnnotationInput {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub text: String,
+}
+
+type AnnotationMap = std::collections::BTreeMap<String, Vec<AssetAnnotation>>;
+
+fn read_asset_annotations(base_dir: &std::path::Path) -> AnnotationMap {
+    std::fs::read_to_string(base_dir.join(ASSET_ANNOTATIONS_FILE))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_asset_annotations(base_dir: &std::path::Path, all: &AnnotationMap) -> Result<(), String> {
+    let path = base_dir.join(ASSET_ANNOTATIONS_FILE);
+    if all.is_empty() {
+        if path.exists() { std::fs::remove_file(&path).map_err(|e| e.to_string())?; }
+        return Ok(());
+    }
+    let json = serde_json::to_string_pretty(all).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("寫入資產標註失敗: {}", e))
+}
+
+fn move_asset_annotations(from_base: &std::path::Path, from_key: &str, to: Option<(&std::path::Path, String)>) {
+    let mut from_all = read_asset_annotations(from_base);
+    let Some(notes) = from_all.remove(from_key) else { return };
+    let _ = write_asset_annotations(from_base, &from_all);
+    if let Some((to_base, to_key)) = to {
+        let mut to_all = read_asset_annotations(to_base);
+        to_all.insert(to_key, notes);
+        let _ = write_asset_annotations(to_base, &to_all);
+    }
+}
+
+fn annotated_asset_base(asset_path: &str, asset_type: &str, file_name: &str) -> Result<PathBuf, String> {
+    let base_dir = PathBuf::from("design-assets").join(asset_path);
+    if !asset_type_dir(&base_dir, asset_type)?.join(file_name).is_file() {
+        return Err("檔案不存在".to_string());
+    }
+    Ok(base_dir)
+}
+
+/// 列出單一資產的標註
+#[tauri::command]
+pub async fn list_asset_annotations(asset_path: String, asset_type: String, file_name: String) -> Result<Vec<AssetAnnotation>, String> {
+    let base_dir = annotated_asset_base(&asset_path, &asset_type, &file_name)?;
+    Ok(read_asset_annotations(&base_dir).remove(&format!("{}/{}", asset_type, file_name)).unwrap_or_default())
+}
+
+/// 在資產上新增區域標註（座標 + 說明文字），會列入生成的 ai-spec.md
+#[tauri::command]
+pub async fn add_asset_annotation(asset_path: String, asset_type: String, file_name: String, annotation: AnnotationInput) -> Result<AssetAnnotation, String> {
+    let base_dir = annotated_asset_base(&asset_path, &asset_type, &file_name)?;
+    let text = annotation.text.trim().to_string();
+    if text.is_empty() {
+        return Err("標註內容不可為空".to_string());
+    }
+    if [annotation.x, annotation.y, annotation.width, annotation.height].iter().any(|v| !v.is_finite() || *v < 0.0) {
+        return Err("標註座標無效".to_string());
+    }
+    let note = AssetAnnotation {
+        id: uuid::Uuid::new_v4().to_string(),
+        x: annotation.x,
+        y: annotation.y,
+        width: annotation.width,
+        height: annotation.height,
+        text,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let mut all = read_asset_annotations(&base_dir);
+    all.entry(format!("{}/{}", asset_type, file_name)).or_default().push(note.clone());
+    write_asset_annotations(&base_dir, &all)?;
+    Ok(note)
+}
+
+/// 刪除指定標註，回傳該資產剩餘的標註
+#[tauri::command]
+pub async fn delete_asset_annotation(asset_path: String, asset_type: String, file_name: String, id: String) -> Result<Vec<AssetAnnotation>, String> {
+    let base_dir = PathBuf::from("design-assets").join(&asset_path);
+    let key = format!("{}/{}", asset_type, file_name);
+    let mut all = read_asset_annotations(&base_dir);
+    let notes = all.get_mut(&key).ok_or_else(|| "標註不存在".to_string())?;
+    let before = notes.len();
+    notes.retain(|n| n.id != id);
+    if notes.len() == before {
+        return Err("標註不存在".to_string());
+    }
+    let remaining = notes.clone();
+    if remaining.is_empty() {
+        all.remove(&key);
+    }
+    write_asset_annotations(&base_dir, &all)?;
+    Ok(remaining)
+}
+
+fn annotations_spec_section(module_name: &str) -> String {
+    let module_dir = PathBuf::from("design-assets").join(module_name);
+    let mut rows: Vec<String> = Vec::new();
+    for level in module_asset_levels(&module_dir) {
+        let rel = level.strip_prefix(&module_dir).unwrap_or(&level).to_string_lossy().replace('\\', "/");
+        for (key, notes) in read_asset_annotations(&level) {
+            let asset = if rel.is_empty() { key } else { format!("{}/{}", rel, key) };
+            for n in notes {
+                rows.push(format!("| `{}` | ({}, {}) {}×{} | {} |", asset, n.x, n.y, n.width, n.height, n.text.replace('|', "\\|").replace('\n', " ")));
+            }
+        }
+    }
+    if rows.is_empty() {
+        return String::new();
+    }
+    let head = project_spec_language().pick(
+        "\n## 設計稿標註\n\n座標以圖片像素為單位（左上角為原點）。\n\n| 資產 | 區域 (x, y) 寬×高 | 說明 |\n|---|---|---|\n",
+        "\n## Design Annotations\n\nCoordinates are in image pixels (origin at the top-left corner).\n\n| Asset | Region (x, y) width×height | Note |\n|---|---|---|\n",
+        "\n## デザイン注釈\n\n座標は画像のピクセル単位です（左上が原点）。\n\n| アセット | 領域 (x, y) 幅×高さ | 説明 |\n|---|---|---|\n",
+    );
+    format!("{}{}\n", head, rows.join("\n"))
+}
+
+// ====== 顯示名稱 ======
+
+const MODULE_META_FILE: &str = "module.json";
+
+// 模組層級的 meta（design-assets/<module>/module.json）
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct ModuleMeta {
+    display_name: Option<String>,
+    description: Option<String>,
+}
+
+fn read_module_meta(module_dir: &std::path::Path) -> ModuleMeta {
+    read_json_with_recovery::<ModuleMeta>(&module_dir.join(MODULE_META_FILE)).unwrap_or_default()
+}
+
+fn write_module_meta(module_name: &str, meta: &ModuleMeta) -> Result<(), String> {
+    let dir = PathBuf::from("design-assets").join(module_name);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("創建模組目錄失敗: {}", e))?;
+    let path = dir.join(MODULE_META_FILE);
+    backup_json_file(&path);
+    std::fs::write(&path, serde_json::to_string_pretty(meta).map_err(|e| e.to_string())?)
+        .map_err(|e| format!("寫入 module.json 失敗: {}", e))
+}
+
+fn module_display_name(module_name: &str) -> String {
+    read_module_meta(&PathBuf::from("design-assets").join(module_name))
+        .display_name
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or_else(|| module_name.to_string())
+}
+
+fn page_display_name(meta: &PageMeta, slug: &str) -> String {
+    meta.display_name.clone().filter(|n| !n.trim().is_empty()).unwrap_or_else(|| slug.to_string())
+}
+
+// 由顯示名稱產生資料夾代稱：已是安全的 ASCII 名稱時原樣使用；
+// 含中文等字元時保留其中的英數字詞，並加上名稱雜湊以避免不同名稱撞在同一代稱
+fn slugify_display_name(name: &str, fallback_prefix: &str) -> String {
+    let name = name.trim();
+    if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return name.to_string();
+    }
+    let words: Vec<String> = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_ascii_lowercase())
+        .collect();
+    if name.is_ascii() && !words.is_empty() {
+        return words.join("-");
+    }
+    use sha2::{Digest, Sha256};
+    let hash: String = Sha256::digest(name.as_bytes()).iter().take(4).map(|b| format!("{:02x}", b)).collect();
+    let base = if words.is_empty() { fallback_prefix.to_string() } else { words.join("-") };
+    format!("{}-{}", base, hash)
+}
+
+// Mermaid 節點文字中的雙引號需轉義
+fn mermaid_text(s: &str) -> String {
+    s.replace('"', "#quot;")
+}
+
+// 顯示名稱與代稱不同時，在 Mermaid 標籤第一行顯示名稱
+fn with_display_name(meta: &PageMeta, slug: &str, label: String) -> String {
+    let display = page_display_name(meta, slug);
+    if display == slug { label } else { format!("{}\\n{}", mermaid_text(&display), label) }
+}
+
+/// 設定模組的顯示名稱（任何語言），資料夾代稱不變
+#[tauri::command]
+pub async fn set_module_display_name(module_name: String, display_name: String) -> Result<String, String> {
+    let module_dir = PathBuf::from("design-assets").join(&module_name);
+    if !module_dir.exists() {
+        return Err("設計模組不存在".to_string());
+    }
+    let mut meta = read_module_meta(&module_dir);
+    let display_name = display_name.trim().to_string();
+    meta.display_name = if display_name.is_empty() { None } else { Some(display_name) };
+    write_module_meta(&module_name, &meta)?;
+    invalidate_sitemap_cache(Some(&module_name));
+    Ok(module_display_name(&module_name))
+}
+
+/// 設定頁面/子頁的顯示名稱，資料夾代稱不變；空字串即清除
+#[tauri::command]
+pub async fn set_page_display_name(module_name: String, parent_slug: Option<String>, slug: String, display_name: String) -> Result<String, String> {
+    let dir = resolve_page_dir(&module_name, parent_slug.as_deref(), &slug);
+    if !dir.exists() {
+        return Err("頁面不存在".to_string());
+    }
+    let mut meta = read_page_meta(&dir);
+    let display_name = display_name.trim().to_string();
+    meta.display_name = if display_name.is_empty() { None } else { Some(display_name) };
+    write_page_meta(&dir, &meta)?;
+    {
+        let mut cache = SITEMAP_CACHE.lock().unwrap();
+        cache.invalidate_module(&module_name);
+    }
+    Ok(page_display_name(&meta, &slug))
+}
+
+// ====== 生成 hooks ======
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HookStage {
+    Pre,
+    Post,
+}
+
+impl HookStage {
+    fn as_str(&self) -> &'static str {
+        match self {
+            HookStage::Pre => "pre",
+            HookStage::Post => "post",
+        }
+    }
+}
+
+// 依專案設定執行生成前/後的自訂指令；未設定時直接略過，指令以非 0 結束時中止該次生成
+fn run_generation_hook(stage: HookStage, package_dir: &std::path::Path, module: Option<&str>, zip_path: Option<&str>) -> Result<(), String> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+    let Some(config) = read_active_project_config() else { return Ok(()) };
+    let hook = match stage {
+        HookStage::Pre => config.pre_generate_hook.clone(),
+        HookStage::Post => config.post_generate_hook.clone(),
+    };
+    let Some(hook) = hook.filter(|h| !h.trim().is_empty()) else { return Ok(()) };
+    let package_path = std::fs::canonicalize(package_dir).unwrap_or_else(|_| package_dir.to_path_buf());
+    let context = serde_json::json!({
+        "stage": stage.as_str(),
+        "package_path": package_path.to_string_lossy(),
+        "module": module,
+        "zip_path": zip_path,
+        "project": config.slug,
+        "generated_at": chrono::Utc::now().to_rfc3339(),
+    });
+
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut c = Command::new("cmd");
+        c.arg("/C").arg(format!("{} \"{}\"", hook, package_path.display()));
+        c
+    } else {
+        // "$@" 讓輸出包路徑以單一參數附加在指令之後
+        let mut c = Command::new("sh");
+        c.arg("-c").arg(format!("{} \"$@\"", hook)).arg("erslice-hook").arg(&package_path);
+        c
+    };
+    let mut child = cmd
+        .env("ERSLICE_HOOK_STAGE", stage.as_str())
+        .env("ERSLICE_PACKAGE_PATH", &package_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("執行 {} hook 失敗: {}", stage.as_str(), e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // hook 不讀 stdin 時寫入可能失敗（broken pipe），不視為錯誤
+        let _ = stdin.write_all(context.to_string().as_bytes());
+    }
+    let output = child.wait_with_output().map_err(|e| format!("等待 {} hook 失敗: {}", stage.as_str(), e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !stdout.trim().is_empty() {
+        log::info!("{} hook 輸出: {}", stage.as_str(), stdout.trim());
+    }
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{} hook 失敗（{}）: {}", stage.as_str(), output.status, stderr.trim()));
+    }
+    Ok(())
+}
+
+// ====== HTML 結構分析 ======
+
+// 與 asset-tags.json 相同存於資產層級，key 為 html/ 內的檔名
+const HTML_STRUCTURE_FILE: &str = "html-structure.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HtmlHeading {
+    pub level: u8,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HtmlField {
+    pub tag: String,
+    pub name: Option<String>,
+    pub field_type: Option<String>,
+    pub label: Option<String>,
+    pub required: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HtmlForm {
+    // 不在 <form> 內的欄位歸在 action/method 皆為 None 的群組
+    pub action: Option<String>,
+    pub method: Option<String>,
+    pub fields: Vec<HtmlField>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HtmlBlock {
+    // component（data-component）/ landmark（header、nav 等）/ repeated（重複出現的 class，如卡片、列表項）
+    pub kind: String,
+    pub selector: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HtmlStructure {
+    pub file: String,
+    pub title: Option<String>,
+    pub headings: Vec<HtmlHeading>,
+    pub forms: Vec<HtmlForm>,
+    pub blocks: Vec<HtmlBlock>,
+    pub analyzed_at: String,
+}
+
+const HTML_LANDMARK_TAGS: [&str; 8] = ["header", "nav", "main", "aside", "footer", "dialog", "table", "section"];
+// 同一 class 出現至少這麼多次才視為重複區塊
+const REPEATED_BLOCK_MIN: usize = 3;
+
+fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn parse_html_structure(file: &str, raw: &str) -> HtmlStructure {
+    use scraper::{ElementRef, Html, Selector};
+    let doc = Html::parse_document(raw);
+    let sel = |s: &str| Selector::parse(s).unwrap();
+    let text_of = |el: &ElementRef| collapse_whitespace(&el.text().collect::<String>());
+
+    let title = doc.select(&sel("title")).next().map(|t| text_of(&t)).filter(|t| !t.is_empty());
+    let headings = doc
+        .select(&sel("h1, h2, h3, h4, h5, h6"))
+        .map(|h| HtmlHeading { level: h.value().name()[1..].parse().unwrap_or(1), text: text_of(&h) })
+        .filter(|h| !h.text.is_empty())
+        .collect();
+
+    let labels: HashMap<String, String> = doc
+        .select(&sel("label[for]"))
+        .filter_map(|l| l.value().attr("for").map(|f| (f.to_string(), text_of(&l))))
+        .collect();
+    let field_of = |el: ElementRef| {
+        let v = el.value();
+        let label = v.attr("id").and_then(|id| labels.get(id).cloned())
+            .or_else(|| el.ancestors().filter_map(ElementRef::wrap).find(|a| a.value().name() == "label").map(|l| text_of(&l)))
+            .or_else(|| v.attr("aria-label").map(|s| s.to_string()))
+            .or_else(|| v.attr("placeholder").map(|s| s.to_string()))
+            .filter(|l| !l.is_empty());
+        HtmlField {
+            tag: v.name().to_string(),
+            name: v.attr("name").or(v.attr("id")).map(|s| s.to_string()),
+            field_type: if v.name() == "input" { Some(v.attr("type").unwrap_or("text").to_lowercase()) } else { None },
+            label,
+            required: v.attr("required").is_some(),
+        }
+    };
+    let field_sel = sel("input:not([type=hidden]), select, textarea");
+    let mut forms: Vec<HtmlForm> = doc
+        .select(&sel("form"))
+        .map(|f| HtmlForm {
+            action: f.value().attr("action").map(|s| s.to_string()),
+            method: f.value().attr("method").map(|s| s.to_lowercase()),
+            fields: f.select(&field_sel).map(field_of).collect(),
+        })
+        .collect();
+    let loose: Vec<HtmlField> = doc
+        .select(&field_sel)
+        .filter(|el| !el.ancestors().filter_map(ElementRef::wrap).any(|a| a.value().name() == "form"))
+        .map(field_of)
+        .collect();
+    if !loose.is_empty() {
+        forms.push(HtmlForm { action: None, method: None, fields: loose });
+    }
+
+    let mut counts: std::collections::BTreeMap<(String, String), usize> = std::collections::BTreeMap::new();
+    for el in doc.select(&sel("body *")) {
+        let v = el.value();
+        if let Some(c) = v.attr("data-component") {
+            *counts.entry(("component".into(), format!("[data-component=\"{}\"]", c))).or_default() += 1;
+        }
+        if HTML_LANDMARK_TAGS.contains(&v.name()) {
+            *counts.entry(("landmark".into(), v.name().to_string())).or_default() += 1;
+        }
+        if matches!(v.name(), "div" | "section" | "article" | "li" | "a") {
+            if let Some(class) = v.classes().next() {
+                *counts.entry(("repeated".into(), format!("{}.{}", v.name(), class))).or_default() += 1;
+            }
+        }
+    }
+    let blocks = counts
+        .into_iter()
+        .filter(|((kind, _), n)| kind != "repeated" || *n >= REPEATED_BLOCK_MIN)
+        .map(|((kind, selector), count)| HtmlBlock { kind, selector, count })
+        .collect();
+
+    HtmlStructure { file: file.to_string(), title, headings, forms, blocks, analyzed_at: chrono::Utc::now().to_rfc3339() }
+}
+
+fn read_html_structures(base_dir: &std::path::Path) -> std::collections::BTreeMap<String, HtmlStructure> {
+    std::fs::read_to_string(base_dir.join(HTML_STRUCTURE_FILE))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// 解析 html/ 內的檔案，擷取標題大綱、表單欄位與元件區塊，結果存於 html-structure.json 供 ai-spec.md 引用
+#[tauri::command]
+pub async fn analyze_html_asset(asset_path: String, file_name: String) -> Result<HtmlStructure, String> {
+    let base_dir = PathBuf::from("design-assets").join(&asset_path);
+    let path = asset_type_dir(&base_dir, "html")?.join(&file_name);
+    if !path.is_file() {
+        return Err("檔案不存在".to_string());
+    }
+    let raw = std::fs::read_to_string(&path).map_err(|e| format!("讀取 HTML 失敗: {}", e))?;
+    let structure = parse_html_structure(&file_name, &raw);
+    let mut all = read_html_structures(&base_dir);
+    all.insert(file_name, structure.clone());
+    std::fs::write(base_dir.join(HTML_STRUCTURE_FILE), serde_json::to_string_pretty(&all).map_err(|e| e.to_string())?)
+        .map_err(|e| format!("寫入 HTML 結構摘要失敗: {}", e))?;
+    Ok(structure)
+}
+
+fn html_structure_spec_section(module_name: &str) -> String {
+    let module_dir = PathBuf::from("design-assets").join(module_name);
+    let lang = project_spec_language();
+    let mut out = String::new();
+    for level in module_asset_levels(&module_dir) {
+        let rel = level.strip_prefix(&module_dir).unwrap_or(&level).to_string_lossy().replace('\\', "/");
+        // 已分析的 HTML 使用快取結果，尚未分析者即時解析；已刪除的 HTML 不再列出
+        let mut structures = read_html_structures(&level);
+        structures.retain(|file, _| level.join("html").join(file).is_file());
+        for file in get_files_in_dir(&level.join("html")) {
+            if structures.contains_key(&file) || !matches!(file.rsplit('.').next().map(|e| e.to_lowercase()).as_deref(), Some("html" | "htm")) { continue; }
+            if let Ok(raw) = std::fs::read_to_string(level.join("html").join(&file)) {
+                let st = parse_html_structure(&file, &raw);
+                structures.insert(file, st);
+            }
+        }
+        for (file, st) in structures {
+            let path = if rel.is_empty() { format!("html/{}", file) } else { format!("{}/html/{}", rel, file) };
+            out.push_str(&format!("\n### `{}`{}\n", path, st.title.as_ref().map(|t| fill_spec_text(lang.pick("（{}）", " ({})", "（{}）"), std::slice::from_ref(t))).unwrap_or_default()));
+            if !st.headings.is_empty() {
+                out.push_str(lang.pick("\n標題大綱：\n", "\nHeading outline:\n", "\n見出し構成：\n"));
+                for h in st.headings.iter() {
+                    out.push_str(&format!("{}- h{} {}\n", "  ".repeat(h.level.saturating_sub(1) as usize), h.level, h.text));
+                }
+            }
+            for f in st.forms.iter() {
+                let head = match (&f.method, &f.action) {
+                    (None, None) => lang.pick("表單外欄位", "Fields outside a form", "フォーム外のフィールド").to_string(),
+                    (m, a) => format!("{} {} {}", lang.pick("表單", "Form", "フォーム"), m.as_deref().unwrap_or("get").to_uppercase(), a.as_deref().unwrap_or("")),
+                };
+                out.push_str(&format!("\n{}{}\n", head.trim_end(), lang.pick("：", ":", "：")));
+                for field in f.fields.iter() {
+                    out.push_str(&format!("- `{}`{}{}{}\n",
+                        field.name.as_deref().unwrap_or(&field.tag),
+                        field.field_type.as_ref().map(|t| format!(" ({})", t)).unwrap_or_default(),
+                        field.label.as_ref().map(|l| format!(" — {}", l)).unwrap_or_default(),
+                        if field.required { lang.pick("（必填）", " (required)", "（必須）") } else { "" }));
+                }
+            }
+            if !st.blocks.is_empty() {
+                let list: Vec<String> = st.blocks.iter().map(|b| format!("`{}`×{}", b.selector, b.count)).collect();
+                out.push_str(&format!("\n{}{}\n", lang.pick("區塊：", "Blocks: ", "ブロック："), list.join(lang.list_separator())));
+            }
+        }
+    }
+    if out.is_empty() {
+        return String::new();
+    }
+    let head = lang.pick(
+        "\n## HTML 結構摘要\n以下為上傳 HTML 的結構分析，實作時請對齊標題層級、表單欄位與重複區塊。\n",
+        "\n## HTML Structure Summary\nStructural analysis of the uploaded HTML; match its heading levels, form fields and repeated blocks when implementing.\n",
+        "\n## HTML 構造サマリー\nアップロードされた HTML の構造分析です。実装時は見出しレベル、フォームフィールド、繰り返しブロックを合わせてください。\n",
+    );
+    format!("{}{}", head, out)
+}
+
+// ====== CSS token 擷取 ======
+
+// 存於模組根目錄，生成 styles.css 時以 :root 自訂屬性輸出
+const CSS_TOKENS_FILE: &str = "css-tokens.json";
+const SPACING_PROPERTY_PREFIXES: [&str; 5] = ["margin", "padding", "gap", "row-gap", "column-gap"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CssTokenValue {
+    pub value: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CssTokenInventory {
+    pub module: String,
+    pub files_scanned: usize,
+    pub custom_properties: std::collections::BTreeMap<String, String>,
+    // 依出現次數由多到少
+    pub colors: Vec<CssTokenValue>,
+    pub font_families: Vec<CssTokenValue>,
+    // 依數值由小到大
+    pub spacing: Vec<CssTokenValue>,
+}
+
+// 去除註解後切出 (屬性, 值) 宣告
+fn css_declarations(css: &str) -> Vec<(String, String)> {
+    let mut clean = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(i) = rest.find("/*") {
+        clean.push_str(&rest[..i]);
+        rest = rest[i + 2..].find("*/").map(|j| &rest[i + 2 + j + 2..]).unwrap_or("");
+    }
+    clean.push_str(rest);
+    clean
+        .split(['{', '}', ';'])
+        .filter_map(|decl| {
+            let (prop, value) = decl.split_once(':')?;
+            let prop = prop.trim().to_lowercase();
+            let value = collapse_whitespace(value.trim().trim_end_matches("!important").trim());
+            // 選擇器中的 :hover 等會被誤判為宣告，屬性名稱只接受字母、數字與 '-'
+            if prop.is_empty() || value.is_empty() || !prop.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+                return None;
+            }
+            Some((prop, value))
+        })
+        .collect()
+}
+
+fn css_color_values(value: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let lower = value.to_lowercase();
+    let bytes = lower.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'#' {
+            let hex: String = lower[i + 1..].chars().take_while(|c| c.is_ascii_hexdigit()).collect();
+            if [3, 4, 6, 8].contains(&hex.len()) {
+                out.push(format!("#{}", hex));
+            }
+            i += 1 + hex.len();
+            continue;
+        }
+        let func = ["rgba(", "rgb(", "hsla(", "hsl("].iter().find(|f| lower[i..].starts_with(**f));
+        if let Some(f) = func {
+            let is_word_start = i == 0 || !(bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'-');
+            if let Some(end) = lower[i..].find(')').filter(|_| is_word_start) {
+                out.push(collapse_whitespace(&lower[i..i + end + 1]));
+                i += end + 1;
+                continue;
+            }
+            i += f.len();
+            continue;
+        }
+        i += 1;
+    }
+    out
+}
+
+// 非 0 的長度值（px / rem / em）
+fn css_spacing_values(value: &str) -> Vec<String> {
+    if value.contains("var(") || value.contains("calc(") {
+        return Vec::new();
+    }
+    value
+        .split_whitespace()
+        .filter(|v| {
+            let num = v.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+            let unit = &v[num.len()..];
+            matches!(unit, "px" | "rem" | "em") && num.parse::<f64>().is_ok_and(|n| n > 0.0)
+        })
+        .map(|v| v.to_string())
+        .collect()
+}
+
+fn spacing_sort_key(v: &str) -> f64 {
+    let num = v.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let n: f64 = num.parse().unwrap_or(0.0);
+    // rem/em 以 16px 換算後排序
+    if v.ends_with("px") { n } else { n * 16.0 }
+}
+
+fn counted_values(counts: HashMap<String, usize>) -> Vec<CssTokenValue> {
+    let mut v: Vec<CssTokenValue> = counts.into_iter().map(|(value, count)| CssTokenValue { value, count }).collect();
+    v.sort_by(|a, b| b.count.cmp(&a.count).then(a.value.cmp(&b.value)));
+    v
+}
+
+fn read_css_tokens(module_name: &str) -> Option<CssTokenInventory> {
+    let raw = std::fs::read_to_string(PathBuf::from("design-assets").join(module_name).join(CSS_TOKENS_FILE)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+// token 名稱（不含 -- 前綴）與值：既有自訂屬性，其餘依序為 color-N / font-N / space-N
+fn css_token_entries(tokens: &CssTokenInventory) -> Vec<(String, String)> {
+    let mut entries: Vec<(String, String)> = tokens.custom_properties.iter().map(|(k, v)| (k.trim_start_matches('-').to_string(), v.clone())).collect();
+    let defined: Vec<&String> = tokens.custom_properties.values().collect();
+    for (i, c) in tokens.colors.iter().filter(|c| !defined.contains(&&c.value)).enumerate() {
+        entries.push((format!("color-{}", i + 1), c.value.clone()));
+    }
+    for (i, f) in tokens.font_families.iter().enumerate() {
+        entries.push((format!("font-{}", i + 1), f.value.clone()));
+    }
+    for (i, s) in tokens.spacing.iter().enumerate() {
+        entries.push((format!("space-{}", i + 1), s.value.clone()));
+    }
+    entries
+}
+
+// styles.css 以 var() 引用的主題色與未設定 token 時的預設值
+const THEME_COLOR_DEFAULTS: [(&str, &str); 5] = [
+    ("color-text", "#333"),
+    ("color-surface", "#f8f9fa"),
+    ("color-border", "#e9ecef"),
+    ("color-heading", "#495057"),
+    ("color-muted", "#6c757d"),
+];
+
+// 主題色別名：--color-primary 對應最常用的顏色，其餘使用預設值；設計稿已定義同名自訂屬性時不覆寫
+fn theme_color_aliases(tokens: &CssTokenInventory) -> Vec<(String, String)> {
+    let entries = css_token_entries(tokens);
+    let defined = |name: &str| entries.iter().any(|(k, _)| k == name);
+    let mut aliases: Vec<(String, String)> = Vec::new();
+    if !defined("color-primary") {
+        if let Some((first, _)) = entries.iter().find(|(k, v)| k.starts_with("color-") && css_color_values(v).len() == 1) {
+            aliases.push(("color-primary".into(), format!("var(--{})", first)));
+        }
+    }
+    for (name, value) in THEME_COLOR_DEFAULTS.iter() {
+        if !defined(name) {
+            aliases.push((name.to_string(), value.to_string()));
+        }
+    }
+    aliases
+}
+
+fn css_tokens_root_rule(tokens: &CssTokenInventory) -> String {
+    let lines: Vec<String> = css_token_entries(tokens).iter().map(|(k, v)| format!("    --{}: {};", k, v)).collect();
+    if lines.is_empty() {
+        return String::new();
+    }
+    let aliases: Vec<String> = theme_color_aliases(tokens).iter().map(|(k, v)| format!("    --{}: {};", k, v)).collect();
+    format!(":root {{\n{}\n\n    /* 主題色（styles.css 以 var() 引用） */\n{}\n}}", lines.join("\n"), aliases.join("\n"))
+}
+
+fn css_tokens_root_block(tokens: &CssTokenInventory) -> String {
+    let rule = css_tokens_root_rule(tokens);
+    if rule.is_empty() {
+        return String::new();
+    }
+    format!("\n\n/* 設計稿 token（由上傳的 CSS 擷取） */\n{}", rule)
+}
+
+// tokens.css：設計稿 token 與主題色的 :root 自訂屬性，修改此檔即可替換主題；尚未有 token 時回傳 None
+fn tokens_css_content(module_name: &str) -> Option<String> {
+    let rule = css_tokens_root_rule(&read_css_tokens(module_name)?);
+    if rule.is_empty() {
+        return None;
+    }
+    Some(format!("/* {} 模組設計 token（由上傳的 CSS 擷取）— 由 ErSlice 生成 */\n{}\n", module_name, rule))
+}
+
+/// 解析模組（含頁面/子頁）css/ 內的檔案，整理自訂屬性、顏色、字型與間距值並去重，
+/// 結果存於 css-tokens.json，之後生成的 tokens.css 會以 :root 自訂屬性提供這些值，styles.css 以 var() 引用
+#[tauri::command]
+pub async fn extract_css_tokens(module: String) -> Result<CssTokenInventory, String> {
+    let module_dir = PathBuf::from("design-assets").join(&module);
+    if !module_dir.exists() {
+        return Err("設計模組不存在".to_string());
+    }
+    let inv = scan_css_tokens(&module);
+    std::fs::write(module_dir.join(CSS_TOKENS_FILE), serde_json::to_string_pretty(&inv).map_err(|e| e.to_string())?)
+        .map_err(|e| format!("寫入 CSS token 失敗: {}", e))?;
+    Ok(inv)
+}
+
+fn scan_css_tokens(module: &str) -> CssTokenInventory {
+    let module_dir = PathBuf::from("design-assets").join(module);
+    let mut inv = CssTokenInventory { module: module.to_string(), ..Default::default() };
+    let mut colors: HashMap<String, usize> = HashMap::new();
+    let mut fonts: HashMap<String, usize> = HashMap::new();
+    let mut spacing: HashMap<String, usize> = HashMap::new();
+    for level in module_asset_levels(&module_dir) {
+        let css_dir = level.join("css");
+        for name in get_files_in_dir(&css_dir) {
+            if ![".css", ".scss", ".less"].iter().any(|e| name.to_lowercase().ends_with(e)) { continue; }
+            let Ok(css) = std::fs::read_to_string(css_dir.join(&name)) else { continue };
+            inv.files_scanned += 1;
+            for (prop, value) in css_declarations(&css) {
+                if prop.starts_with("--") {
+                    inv.custom_properties.entry(prop.clone()).or_insert_with(|| value.clone());
+                }
+                for c in css_color_values(&value) {
+                    *colors.entry(c).or_default() += 1;
+                }
+                if prop == "font-family" {
+                    *fonts.entry(value.replace('"', "'")).or_default() += 1;
+                }
+                if SPACING_PROPERTY_PREFIXES.iter().any(|p| prop == *p || prop.starts_with(&format!("{}-", p))) {
+                    for v in css_spacing_values(&value) {
+                        *spacing.entry(v).or_default() += 1;
+                    }
+                }
+            }
+        }
+    }
+    inv.colors = counted_values(colors);
+    inv.font_families = counted_values(fonts);
+    let mut spacing = counted_values(spacing);
+    spacing.sort_by(|a, b| spacing_sort_key(&a.value).total_cmp(&spacing_sort_key(&b.value)));
+    inv.spacing = spacing;
+    inv
+}
+
+// ====== 生成器外掛 ======
+
+const DEFAULT_WASM_RUNTIME: &str = "wasmtime";
+// 外掛未設定 timeout_secs 時的執行時間上限
+const DEFAULT_PLUGIN_TIMEOUT_SECS: u64 = 60;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GeneratorPlugin {
+    pub name: String,
+    // 可執行檔路徑或 .wasm 檔路徑
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    // "executable"（預設）或 "wasm"
+    pub kind: Option<String>,
+    // 執行 WASM 的 runtime（預設 wasmtime），需支援 WASI stdin/stdout
+    pub wasm_runtime: Option<String>,
+    pub description: Option<String>,
+    // 超過此秒數即終止外掛（預設 60）
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct PluginOutputFile {
+    path: String,
+    content: String,
+    // "utf8"（預設）或 "base64"
+    encoding: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct PluginOutput {
+    files: Vec<PluginOutputFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PluginRunResult {
+    pub plugin: String,
+    pub module: String,
+    pub output_dir: String,
+    pub files_written: Vec<String>,
+    pub stderr: String,
+}
+
+fn is_valid_plugin_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// 外掛回傳的相對路徑：不可為絕對路徑或跳出輸出目錄
+fn sandboxed_plugin_path(root: &std::path::Path, rel: &str) -> Option<PathBuf> {
+    use std::path::Component;
+    let rel = std::path::Path::new(rel);
+    if rel.as_os_str().is_empty() || !rel.components().all(|c| matches!(c, Component::Normal(_))) {
+        return None;
+    }
+    Some(root.join(rel))
+}
+
+// 傳給外掛的模組結構化資料：頁面樹、各頁 meta 與資產清單
+fn plugin_module_context(module_name: &str) -> Result<serde_json::Value, String> {
+    let module_dir = PathBuf::from("design-assets").join(module_name);
+    let tree = build_module_tree_uncached(module_name)?;
+    let pages: Vec<serde_json::Value> = collect_module_page_entries(module_name)
+        .iter()
+        .map(|e| serde_json::json!({
+            "page": e.page,
+            "subpage": e.subpage,
+            "route": e.meta.route.clone().unwrap_or_else(|| e.route_path()),
+            "meta": e.meta,
+        }))
+        .collect();
+    let mut files = Vec::new();
+    collect_files_recursive(&module_dir, &mut files);
+    let mut assets: Vec<String> = files
+        .iter()
+        .map(|f| f.strip_prefix(&module_dir).unwrap_or(f).to_string_lossy().replace('\\', "/"))
+        .collect();
+    assets.sort();
+    Ok(serde_json::json!({
+        "module": module_name,
+        "display_name": module_display_name(module_name),
+        "tree": tree,
+        "pages": pages,
+        "assets": assets,
+        "css_tokens": read_css_tokens(module_name),
+        "generated_at": chrono::Utc::now().to_rfc3339(),
+    }))
+}
+
+// 寫入 stdin 並收集輸出；stdin/stdout/stderr 各自以執行緒處理，避免管線塞滿時互相等待。
+// 超過 timeout 時終止外掛行程並回傳錯誤
+fn wait_plugin_output(mut child: std::process::Child, input: Vec<u8>, timeout: Duration) -> Result<std::process::Output, String> {
+    use std::io::{Read, Write};
+    if let Some(mut stdin) = child.stdin.take() {
+        // 外掛不讀 stdin 時寫入可能失敗（broken pipe），不視為錯誤
+        std::thread::spawn(move || {
+            let _ = stdin.write_all(&input);
+        });
+    }
+    fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> std::thread::JoinHandle<Vec<u8>> {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut p) = pipe {
+                let _ = p.read_to_end(&mut buf);
+            }
+            buf
+        })
+    }
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+    let deadline = std::time::Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait().map_err(|e| format!("等待外掛失敗: {}", e))? {
+            Some(status) => break status,
+            None if std::time::Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("外掛執行逾時（{} 秒），已終止", timeout.as_secs()));
+            }
+            None => std::thread::sleep(Duration::from_millis(50)),
+        }
+    };
+    Ok(std::process::Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+fn configured_plugins() -> Vec<GeneratorPlugin> {
+    read_active_project_config().and_then(|c| c.plugins).unwrap_or_default()
+}
+
+/// 列出目前專案註冊的生成器外掛
+#[tauri::command]
+pub async fn list_plugins() -> Result<Vec<GeneratorPlugin>, String> {
+    Ok(configured_plugins())
+}
+
+/// 執行生成器外掛：stdin 傳入模組結構化資料（JSON），外掛於 stdout 回傳 {"files":[{"path","content","encoding"}]}，
+/// 檔案一律寫入 output/<module>/plugins/<外掛名稱>/ 之下
+#[tauri::command]
+pub async fn run_plugin(plugin_name: String, module_name: String) -> Result<PluginRunResult, String> {
+    use std::process::{Command, Stdio};
+    let plugin = configured_plugins()
+        .into_iter()
+        .find(|p| p.name == plugin_name)
+        .ok_or_else(|| format!("外掛不存在: {}", plugin_name))?;
+    if !is_valid_plugin_name(&plugin.name) {
+        return Err(format!("外掛名稱無效: {}", plugin.name));
+    }
+    if !PathBuf::from("design-assets").join(&module_name).exists() {
+        return Err("設計模組不存在".to_string());
+    }
+    let context = plugin_module_context(&module_name)?;
+
+    let mut cmd = match plugin.kind.as_deref().unwrap_or("executable") {
+        "executable" => {
+            let mut c = Command::new(&plugin.command);
+            c.args(&plugin.args);
+            c
+        }
+        "wasm" => {
+            let mut c = Command::new(plugin.wasm_runtime.as_deref().unwrap_or(DEFAULT_WASM_RUNTIME));
+            c.arg("run").arg(&plugin.command).args(&plugin.args);
+            c
+        }
+        other => return Err(format!("不支援的外掛類型: {}", other)),
+    };
+    let child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("執行外掛失敗: {}", e))?;
+    let timeout = Duration::from_secs(plugin.timeout_secs.filter(|s| *s > 0).unwrap_or(DEFAULT_PLUGIN_TIMEOUT_SECS));
+    let output = wait_plugin_output(child, context.to_string().into_bytes(), timeout)?;
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if !output.status.success() {
+        return Err(format!("外掛執行失敗（{}）: {}", output.status, stderr));
+    }
+    let parsed: PluginOutput = serde_json::from_slice(&output.stdout).map_err(|e| format!("外掛輸出格式錯誤: {}", e))?;
+
+    let out_dir = output_root().join(&module_name).join("plugins").join(&plugin.name);
+    let mut files_written = Vec::new();
+    for f in parsed.files.iter() {
+        let target = sandboxed_plugin_path(&out_dir, &f.path).ok_or_else(|| format!("外掛輸出路徑不允許: {}", f.path))?;
+        let bytes = match f.encoding.as_deref().unwrap_or("utf8") {
+            "utf8" => f.content.clone().into_bytes(),
+            "base64" => {
+                use base64::Engine;
+                base64::engine::general_purpose::STANDARD.decode(&f.content).map_err(|e| format!("{}: base64 解碼失敗: {}", f.path, e))?
+            }
+            other => return Err(format!("{}: 不支援的編碼 {}", f.path, other)),
+        };
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("建立外掛輸出目錄失敗: {}", e))?;
+        }
+        std::fs::write(&target, bytes).map_err(|e| format!("寫入外掛輸出失敗: {}", e))?;
+        files_written.push(f.path.clone());
+    }
+    Ok(PluginRunResult {
+        plugin: plugin.name,
+        module: module_name,
+        output_dir: out_dir.to_string_lossy().to_string(),
+        files_written,
+        stderr,
+    })
+}
+
+// ====== 資產排序 ======
+
+// 與 pages/_order.json 相同的概念，存於資產所在層級：資產類型 → 檔名順序
+const ASSET_ORDER_FILE: &str = "_asset_order.json";
+
+fn read_asset_order(base_dir: &std::path::Path) -> std::collections::BTreeMap<String, Vec<String>> {
+    std::fs::read_to_string(base_dir.join(ASSET_ORDER_FILE))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+// 已排序的檔案在前（依設定順序），其餘依檔名排序
+fn apply_asset_order(files: &mut [String], order: Option<&Vec<String>>) {
+    files.sort_by(|a, b| {
+        let pos = |f: &String| order.and_then(|o| o.iter().position(|x| x == f)).unwrap_or(usize::MAX);
+        pos(a).cmp(&pos(b)).then_with(|| a.to_lowercase().cmp(&b.to_lowercase()))
+    });
+}
+
+/// 設定模組/頁面/子頁層級某資產類型的顯示順序（例如截圖的操作流程），list_assets 與 ai-spec.md 會依此排序
+/// page 為 None 時為模組層級；不存在的檔名會被忽略，未列出的檔案排在最後
+#[tauri::command]
+pub async fn set_asset_order(module: String, page: Option<String>, asset_type: String, order: Vec<String>, subpage: Option<String>) -> Result<Vec<String>, String> {
+    let asset_path = match page.as_deref() {
+        Some(p) => page_asset_path(&module, p, subpage.as_deref())?,
+        None => module.clone(),
+    };
+    let base_dir = PathBuf::from("design-assets").join(&asset_path);
+    if !base_dir.exists() {
+        return Err("設計模組不存在".to_string());
+    }
+    let type_dir = asset_type_dir(&base_dir, &asset_type)?;
+    let existing = get_files_in_dir(&type_dir);
+    let mut cleaned: Vec<String> = Vec::new();
+    for f in order {
+        if existing.contains(&f) && !cleaned.contains(&f) {
+            cleaned.push(f);
+        }
+    }
+    let mut all = read_asset_order(&base_dir);
+    if cleaned.is_empty() {
+        all.remove(&asset_type);
+    } else {
+        all.insert(asset_type, cleaned.clone());
+    }
+    let path = base_dir.join(ASSET_ORDER_FILE);
+    if all.is_empty() {
+        if path.exists() { std::fs::remove_file(&path).map_err(|e| e.to_string())?; }
+    } else {
+        std::fs::write(&path, serde_json::to_string_pretty(&all).map_err(|e| e.to_string())?)
+            .map_err(|e| format!("寫入資產排序失敗: {}", e))?;
+    }
+    invalidate_sitemap_cache(Some(&module));
+    let mut files = existing;
+    apply_asset_order(&mut files, Some(&cleaned));
+    Ok(files)
+}
+
+// 有設定截圖順序的層級，依序列出截圖作為畫面流程
+fn screenshot_flow_spec_section(module_name: &str) -> String {
+    let module_dir = PathBuf::from("design-assets").join(module_name);
+    let mut out = String::new();
+    for level in module_asset_levels(&module_dir) {
+        let order = read_asset_order(&level);
+        let Some(shots) = order.get("screenshots") else { continue };
+        let mut files: Vec<String> = get_files_in_dir(&level.join("screenshots")).into_iter().filter(|f| is_image_file(&level.join("screenshots").join(f))).collect();
+        apply_asset_order(&mut files, Some(shots));
+        if files.is_empty() { continue; }
+        let rel = level.strip_prefix(&module_dir).unwrap_or(&level).to_string_lossy().replace('\\', "/");
+        out.push_str(&format!("\n### {}\n\n", if rel.is_empty() { module_name.to_string() } else { rel.clone() }));
+        for (i, f) in files.iter().enumerate() {
+            let path = if rel.is_empty() { format!("screenshots/{}", f) } else { format!("{}/screenshots/{}", rel, f) };
+            out.push_str(&format!("{}. `{}`\n", i + 1, path));
+        }
+    }
+    if out.is_empty() {
+        return String::new();
+    }
+    let head = project_spec_language().pick(
+        "\n## 畫面流程\n截圖依操作流程排序，實作時請依序對照。\n",
+        "\n## Screen Flow\nScreenshots are ordered by user flow; follow them in sequence when implementing.\n",
+        "\n## 画面フロー\nスクリーンショットは操作フロー順に並んでいます。実装時は順番に照合してください。\n",
+    );
+    format!("{}{}", head, out)
+}
+
+// ====== 框架元件骨架 ======
+
+// 可輸出的框架骨架；"html" 或未指定時僅輸出靜態 index.html/styles.css
+const SCAFFOLD_FRAMEWORKS: [&str; 3] = ["react", "vue", "svelte"];
+
+fn resolve_scaffold_framework(framework: Option<&str>) -> Result<Option<&'static str>, String> {
+    let Some(f) = framework.map(|f| f.trim().to_lowercase()).filter(|f| !f.is_empty() && f != "html") else {
+        return Ok(None);
+    };
+    SCAFFOLD_FRAMEWORKS
+        .iter()
+        .find(|x| **x == f)
+        .copied()
+        .map(Some)
+        .ok_or_else(|| format!("不支援的框架: {}（可用：html, {}）", f, SCAFFOLD_FRAMEWORKS.join(", ")))
+}
+
+// 頁面元件名稱；同名（例如不同頁面下的同名子頁）時以父頁名稱為前綴
+fn scaffold_component_names(entries: &[PageEntry]) -> Vec<String> {
+    let base: Vec<String> = entries.iter().map(component_name_for).collect();
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let duplicated = base.iter().filter(|n| **n == base[i]).count() > 1;
+            match (&e.subpage, duplicated) {
+                (Some(_), true) => format!("{}{}", pascal_case_identifier(&e.page), base[i]),
+                _ => base[i].clone(),
+            }
+        })
+        .collect()
+}
+
+fn page_entry_dir(e: &PageEntry) -> PathBuf {
+    let pages = e.module_dir.join("pages");
+    match &e.subpage {
+        Some(sub) => pages.join(&e.page).join("subpages").join(sub),
+        None => pages.join(&e.page),
+    }
+}
+
+// 頁面的截圖（相對模組根目錄），作為骨架註解中的對照依據
+fn page_screenshot_refs(e: &PageEntry) -> Vec<String> {
+    let dir = page_entry_dir(e);
+    let rel = match &e.subpage {
+        Some(sub) => format!("pages/{}/subpages/{}", e.page, sub),
+        None => format!("pages/{}", e.page),
+    };
+    let mut files: Vec<String> = get_files_in_dir(&dir.join("screenshots")).into_iter().filter(|f| is_image_file(&dir.join("screenshots").join(f))).collect();
+    apply_asset_order(&mut files, read_asset_order(&dir).get("screenshots"));
+    files.into_iter().map(|f| format!("{}/screenshots/{}", rel, f)).collect()
+}
+
+// 骨架樣式沿用 styles.css 的生成選項
+struct ScaffoldStyles {
+    responsive: bool,
+    print: bool,
+}
+
+fn generate_framework_scaffold(module_name: &str, output_dir: &std::path::Path, framework: &str, styles: &ScaffoldStyles, strategy: &str) -> Result<(), String> {
+    match framework {
+        "react" => generate_react_scaffold(module_name, output_dir, strategy),
+        "vue" => generate_vue_scaffold(module_name, output_dir, styles, strategy),
+        "svelte" => generate_svelte_scaffold(module_name, output_dir, styles, strategy),
+        other => Err(format!("不支援的框架: {}", other)),
+    }
+}
+
+// 元件骨架共用的 CSS：沿用 styles.css 的版面與 css-tokens 擷取的字型
+fn scaffold_component_css(root_class: &str) -> String {
+    format!(
+        ".{} {{\n    font-family: var(--font-1, 'Inter', system-ui, sans-serif);\n    line-height: 1.6;\n    color: #333;\n}}\n\n.header {{\n    padding: 2rem;\n    border-bottom: 1px solid #e9ecef;\n}}\n\n.title {{\n    margin: 0;\n    font-size: 2rem;\n    font-weight: 600;\n}}\n\n.content {{\n    padding: 2rem;\n    max-width: 1200px;\n    margin: 0 auto;\n}}\n\n.field {{\n    display: flex;\n    flex-direction: column;\n    gap: 0.25rem;\n    margin-bottom: 1rem;\n}}\n",
+        root_class
+    )
+}
+
+// 表單欄位骨架（依頁面 HTML 中的 input/select/textarea）；jsx 為 true 時輸出 React 的 className/htmlFor
+fn scaffold_form_markup(fields: &[FormField], indent: &str, jsx: bool) -> String {
+    if fields.is_empty() {
+        return String::new();
+    }
+    let mut out = format!("{}<form>\n", indent);
+    for f in fields.iter() {
+        let id = sanitize_id(&f.name);
+        let required = if f.required { " required" } else { "" };
+        let control = match f.field_type.as_str() {
+            "select" if jsx => format!("<select id=\"{}\" name=\"{}\"{} />", id, id, required),
+            "select" => format!("<select id=\"{}\" name=\"{}\"{}></select>", id, id, required),
+            "textarea" if jsx => format!("<textarea id=\"{}\" name=\"{}\"{} />", id, id, required),
+            "textarea" => format!("<textarea id=\"{}\" name=\"{}\"{}></textarea>", id, id, required),
+            t => format!("<input id=\"{}\" name=\"{}\" type=\"{}\"{} />", id, id, t, required),
+        };
+        let (wrapper, label) = if jsx {
+            ("<div className={styles.field}>".to_string(), format!("<label htmlFor=\"{}\">{{{}}}</label>", id, serde_json::to_string(&f.name).unwrap_or_default()))
+        } else {
+            ("<div class=\"field\">".to_string(), format!("<label for=\"{}\">{}</label>", id, escape_html(&f.name)))
+        };
+        out.push_str(&format!("{i}  {w}\n{i}    {l}\n{i}    {c}\n{i}  </div>\n", i = indent, w = wrapper, l = label, c = control));
+    }
+    out.push_str(&format!("{}  <button type=\"submit\">送出</button>\n{}</form>\n", indent, indent));
+    out
+}
+
+fn react_page_component(e: &PageEntry, component: &str) -> String {
+    let props = e.meta.props.clone().unwrap_or_default();
+    let title = serde_json::to_string(&page_display_name(&e.meta, e.subpage.as_deref().unwrap_or(&e.page))).unwrap_or_default();
+    let route = e.meta.route.clone().unwrap_or_else(|| e.route_path());
+    let mut out = format!("// {} — 由 ErSlice 生成的頁面骨架，請依設計稿完善結構\n", route);
+    for shot in page_screenshot_refs(e) {
+        out.push_str(&format!("// 設計稿: {}\n", shot));
+    }
+    out.push_str(&format!("import styles from './{}.module.css';\n\n", component));
+    let signature = if props.is_empty() {
+        String::new()
+    } else {
+        out.push_str(&props_interface(component, &props));
+        out.push('\n');
+        let names: Vec<String> = props
+            .iter()
+            .map(|p| match p.default.as_ref() {
+                Some(d) => format!("{} = {}", p.name, d),
+                None => p.name.clone(),
+            })
+            .collect();
+        format!("{{ {} }}: {}Props", names.join(", "), component)
+    };
+    out.push_str(&format!("export default function {}({}) {{\n  return (\n", component, signature));
+    out.push_str(&format!("    <section className={{styles.{}}}>\n", component_css_class(component)));
+    out.push_str(&format!("      <header className={{styles.header}}>\n        <h1 className={{styles.title}}>{{{}}}</h1>\n      </header>\n", title));
+    out.push_str("      <div className={styles.content}>\n");
+    if !props.is_empty() {
+        // 以清單暫時呈現 props 值，實作時替換為實際內容
+        out.push_str("        <dl>\n");
+        for p in props.iter() {
+            out.push_str(&format!("          <dt>{0}</dt>\n          <dd>{{String({0})}}</dd>\n", p.name));
+        }
+        out.push_str("        </dl>\n");
+    }
+    out.push_str(&scaffold_form_markup(&page_form_fields(&page_entry_dir(e)), "        ", true));
+    out.push_str("      </div>\n    </section>\n  );\n}\n");
+    out
+}
+
+// CSS module 的根 class（camelCase，避免需以字串索引）
+fn component_css_class(component: &str) -> String {
+    let mut cs = component.chars();
+    cs.next().map(|f| f.to_ascii_lowercase().to_string() + cs.as_str()).unwrap_or_else(|| "root".into())
+}
+
+// React：每個模組/頁面輸出 function component（TSX）與 CSS module，置於輸出包的 react/
+fn generate_react_scaffold(module_name: &str, output_dir: &std::path::Path, strategy: &str) -> Result<(), String> {
+    let react_dir = output_dir.join("react");
+    std::fs::create_dir_all(react_dir.join("pages")).map_err(|e| format!("建立 react 目錄失敗: {}", e))?;
+    let write = |path: PathBuf, content: String| -> Result<(), String> {
+        write_text_with_strategy(&path, &content, strategy).map_err(|e| format!("寫入 {} 失敗: {}", path.display(), e))
+    };
+
+    let entries = collect_module_page_entries(module_name);
+    let names = scaffold_component_names(&entries);
+    let mut exports: Vec<String> = Vec::new();
+    for (e, component) in entries.iter().zip(names.iter()) {
+        write(react_dir.join("pages").join(format!("{}.tsx", component)), react_page_component(e, component))?;
+        write(react_dir.join("pages").join(format!("{}.module.css", component)), scaffold_component_css(&component_css_class(component)))?;
+        exports.push(format!("export {{ default as {} }} from './pages/{}';\n", component, component));
+    }
+
+    // 模組外框元件：標題 + children（頁面元件）
+    let module_component = format!("{}Module", pascal_case_identifier(module_name));
+    let module_title = serde_json::to_string(&module_display_name(module_name)).unwrap_or_default();
+    let module_tsx = format!(
+        "// {m} 模組外框 — 由 ErSlice 生成\nimport type {{ ReactNode }} from 'react';\nimport styles from './{c}.module.css';\n\nexport interface {c}Props {{\n  children?: ReactNode;\n}}\n\nexport default function {c}({{ children }}: {c}Props) {{\n  return (\n    <div className={{styles.{cls}}}>\n      <header className={{styles.header}}>\n        <h1 className={{styles.title}}>{{{t}}}</h1>\n      </header>\n      <main className={{styles.content}}>{{children}}</main>\n    </div>\n  );\n}}\n",
+        m = module_name, c = module_component, cls = component_css_class(&module_component), t = module_title
+    );
+    write(react_dir.join(format!("{}.tsx", module_component)), module_tsx)?;
+    let mut module_css = scaffold_component_css(&component_css_class(&module_component));
+    if let Some(tokens) = read_css_tokens(module_name) {
+        module_css.push_str(&css_tokens_root_block(&tokens));
+        module_css.push('\n');
+    }
+    write(react_dir.join(format!("{}.module.css", module_component)), module_css)?;
+    exports.insert(0, format!("export {{ default as {} }} from './{}';\n", module_component, module_component));
+
+    write(react_dir.join("index.ts"), exports.concat())?;
+    // 讓 TypeScript 在未設定 CSS modules 型別的專案中也能編譯
+    write(
+        react_dir.join("css-modules.d.ts"),
+        "declare module '*.module.css' {\n  const classes: { readonly [key: string]: string };\n  export default classes;\n}\n".to_string(),
+    )?;
+    Ok(())
+}
+
+// Vue withDefaults 中物件/陣列預設值需以 factory 函式提供
+fn vue_prop_default(default: &str) -> String {
+    let d = default.trim();
+    if d.starts_with('[') || d.starts_with('{') { format!("() => ({})", d) } else { d.to_string() }
+}
+
+fn vue_page_component(e: &PageEntry, component: &str, styles: &ScaffoldStyles) -> String {
+    let props = e.meta.props.clone().unwrap_or_default();
+    let title = escape_html(&page_display_name(&e.meta, e.subpage.as_deref().unwrap_or(&e.page)));
+    let route = e.meta.route.clone().unwrap_or_else(|| e.route_path());
+    let mut out = format!("<!-- {} — 由 ErSlice 生成的頁面骨架，請依設計稿完善結構 -->\n", route);
+    for shot in page_screenshot_refs(e) {
+        out.push_str(&format!("<!-- 設計稿: {} -->\n", shot));
+    }
+    if !props.is_empty() {
+        out.push_str("<script setup lang=\"ts\">\n");
+        out.push_str(&props_interface(component, &props));
+        let defaults: Vec<String> = props
+            .iter()
+            .filter_map(|p| p.default.as_ref().map(|d| format!("  {}: {},", p.name, vue_prop_default(d))))
+            .collect();
+        if defaults.is_empty() {
+            out.push_str(&format!("\nconst props = defineProps<{}Props>();\n", component));
+        } else {
+            out.push_str(&format!("\nconst props = withDefaults(defineProps<{}Props>(), {{\n{}\n}});\n", component, defaults.join("\n")));
+        }
+        out.push_str("</script>\n\n");
+    }
+    out.push_str(&format!("<template>\n  <section class=\"{}\">\n", kebab_case(component)));
+    out.push_str(&format!("    <header class=\"header\">\n      <h1>{}</h1>\n    </header>\n", title));
+    out.push_str("    <main class=\"main-content\">\n");
+    if !props.is_empty() {
+        // 以清單暫時呈現 props 值，實作時替換為實際內容
+        out.push_str("      <dl>\n");
+        for p in props.iter() {
+            out.push_str(&format!("        <dt>{0}</dt>\n        <dd>{{{{ props.{0} }}}}</dd>\n", p.name));
+        }
+        out.push_str("      </dl>\n");
+    }
+    out.push_str(&scaffold_form_markup(&page_form_fields(&page_entry_dir(e)), "      ", false));
+    out.push_str("    </main>\n  </section>\n</template>\n\n");
+    out.push_str(&format!("<style scoped>\n{}\n</style>\n", scoped_page_css(e, component, styles)));
+    out
+}
+
+// 頁面元件的 scoped 樣式：以 styles.css 為基礎，根 class 改為頁面 class
+fn scoped_page_css(e: &PageEntry, component: &str, styles: &ScaffoldStyles) -> String {
+    let css = module_styles_css(&e.module, styles.responsive, styles.print)
+        .replacen(&format!(".{} {{", e.module.to_lowercase().replace(' ', "-")), &format!(".{} {{", kebab_case(component)), 1);
+    format!("{}\n\n.field {{\n    display: flex;\n    flex-direction: column;\n    gap: 0.25rem;\n    margin-bottom: 1rem;\n}}", css)
+}
+
+// 頁面路由路徑（相對模組）；有 page.json route 時使用該絕對路徑
+fn scaffold_route_path(e: &PageEntry) -> String {
+    match (&e.meta.route, &e.subpage) {
+        (Some(r), _) if !r.trim().is_empty() => r.trim().to_string(),
+        (_, Some(sub)) => format!("{}/{}", e.page, sub),
+        (_, None) => e.page.clone(),
+    }
+}
+
+fn scaffold_route_name(e: &PageEntry) -> String {
+    format!("{}-{}{}", e.module, e.page, e.subpage.as_ref().map(|s| format!("-{}", s)).unwrap_or_default())
+}
+
+// vue-router 路由：模組外框為父路由，頁面/子頁為 children（有 page.json route 時使用絕對路徑）
+fn vue_router_file(module_name: &str, module_component: &str, entries: &[PageEntry], names: &[String]) -> String {
+    let mut out = format!(
+        "// {m} 模組路由 — 由 ErSlice 依頁面樹生成\nimport type {{ RouteRecordRaw }} from 'vue-router';\nimport {c} from './{c}.vue';\n\nexport const routes: RouteRecordRaw[] = [\n  {{\n    path: '/{m}',\n    component: {c},\n    children: [\n",
+        m = module_name, c = module_component
+    );
+    for (e, component) in entries.iter().zip(names.iter()) {
+        let path = scaffold_route_path(e);
+        let name = scaffold_route_name(e);
+        out.push_str(&format!(
+            "      {{ path: {}, name: {}, component: () => import('./pages/{}.vue') }},\n",
+            serde_json::to_string(&path).unwrap_or_default().replace('"', "'"),
+            serde_json::to_string(&name).unwrap_or_default().replace('"', "'"),
+            component
+        ));
+    }
+    out.push_str("    ],\n  },\n];\n\nexport default routes;\n");
+    out
+}
+
+// Vue 3：每個頁面/子頁輸出 SFC（template 骨架 + 以 styles.css 為基礎的 scoped style），並依頁面樹產生 router.ts，置於輸出包的 vue/
+fn generate_vue_scaffold(module_name: &str, output_dir: &std::path::Path, styles: &ScaffoldStyles, strategy: &str) -> Result<(), String> {
+    let vue_dir = output_dir.join("vue");
+    std::fs::create_dir_all(vue_dir.join("pages")).map_err(|e| format!("建立 vue 目錄失敗: {}", e))?;
+    let write = |path: PathBuf, content: String| -> Result<(), String> {
+        write_text_with_strategy(&path, &content, strategy).map_err(|e| format!("寫入 {} 失敗: {}", path.display(), e))
+    };
+
+    let entries = collect_module_page_entries(module_name);
+    let names = scaffold_component_names(&entries);
+    for (e, component) in entries.iter().zip(names.iter()) {
+        write(vue_dir.join("pages").join(format!("{}.vue", component)), vue_page_component(e, component, styles))?;
+    }
+
+    // 模組外框：標題 + router-view；token 以非 scoped 的 :root 提供給所有頁面
+    let module_component = format!("{}Module", pascal_case_identifier(module_name));
+    let mut module_vue = format!(
+        "<!-- {} 模組外框 — 由 ErSlice 生成 -->\n<template>\n  <div class=\"{}\">\n    <header class=\"header\">\n      <h1>{}</h1>\n    </header>\n    <router-view />\n  </div>\n</template>\n\n<style scoped>\n{}\n</style>\n",
+        module_name,
+        module_name.to_lowercase().replace(' ', "-"),
+        escape_html(&module_display_name(module_name)),
+        module_styles_css(module_name, styles.responsive, styles.print)
+    );
+    if let Some(tokens) = read_css_tokens(module_name) {
+        let root = css_tokens_root_block(&tokens);
+        if !root.is_empty() {
+            module_vue.push_str(&format!("\n<style>{}\n</style>\n", root));
+        }
+    }
+    write(vue_dir.join(format!("{}.vue", module_component)), module_vue)?;
+    write(vue_dir.join("router.ts"), vue_router_file(module_name, &module_component, &entries, &names))?;
+    Ok(())
+}
+
+fn svelte_page_component(e: &PageEntry, component: &str, styles: &ScaffoldStyles) -> String {
+    let props = e.meta.props.clone().unwrap_or_default();
+    let title = escape_html(&page_display_name(&e.meta, e.subpage.as_deref().unwrap_or(&e.page)));
+    let route = e.meta.route.clone().unwrap_or_else(|| e.route_path());
+    let mut out = format!("<!-- {} — 由 ErSlice 生成的頁面骨架，請依設計稿完善結構 -->\n", route);
+    for shot in page_screenshot_refs(e) {
+        out.push_str(&format!("<!-- 設計稿: {} -->\n", shot));
+    }
+    if !props.is_empty() {
+        out.push_str("<script lang=\"ts\">\n");
+        for p in props.iter() {
+            if let Some(d) = p.description.as_ref().filter(|d| !d.trim().is_empty()) {
+                out.push_str(&format!("  /** {} */\n", d.trim()));
+            }
+            let ty = if p.required { p.prop_type.clone() } else { format!("{} | undefined", p.prop_type) };
+            match p.default.as_ref() {
+                Some(d) => out.push_str(&format!("  export let {}: {} = {};\n", p.name, ty, d.trim())),
+                None if p.required => out.push_str(&format!("  export let {}: {};\n", p.name, ty)),
+                None => out.push_str(&format!("  export let {}: {} = undefined;\n", p.name, ty)),
+            }
+        }
+        out.push_str("</script>\n\n");
+    }
+    out.push_str(&format!("<section class=\"{}\">\n", kebab_case(component)));
+    out.push_str(&format!("  <header class=\"header\">\n    <h1>{}</h1>\n  </header>\n", title));
+    out.push_str("  <main class=\"main-content\">\n");
+    if !props.is_empty() {
+        // 以清單暫時呈現 props 值，實作時替換為實際內容
+        out.push_str("    <dl>\n");
+        for p in props.iter() {
+            out.push_str(&format!("      <dt>{0}</dt>\n      <dd>{{{0}}}</dd>\n", p.name));
+        }
+        out.push_str("    </dl>\n");
+    }
+    out.push_str(&scaffold_form_markup(&page_form_fields(&page_entry_dir(e)), "    ", false));
+    out.push_str("  </main>\n</section>\n\n");
+    out.push_str(&format!("<style>\n{}\n</style>\n", scoped_page_css(e, component, styles)));
+    out
+}
+
+#[derive(Debug, Serialize)]
+struct ScaffoldRoute {
+    path: String,
+    name: String,
+    title: String,
+    component: String,
+    parent: Option<String>,
+}
+
+// Svelte：每個頁面/子頁輸出 .svelte 元件與 routes.json 路由清單，置於輸出包的 svelte/
+fn generate_svelte_scaffold(module_name: &str, output_dir: &std::path::Path, styles: &ScaffoldStyles, strategy: &str) -> Result<(), String> {
+    let svelte_dir = output_dir.join("svelte");
+    std::fs::create_dir_all(svelte_dir.join("pages")).map_err(|e| format!("建立 svelte 目錄失敗: {}", e))?;
+    let write = |path: PathBuf, content: String| -> Result<(), String> {
+        write_text_with_strategy(&path, &content, strategy).map_err(|e| format!("寫入 {} 失敗: {}", path.display(), e))
+    };
+
+    let entries = collect_module_page_entries(module_name);
+    let names = scaffold_component_names(&entries);
+    let mut routes: Vec<ScaffoldRoute> = Vec::new();
+    for (e, component) in entries.iter().zip(names.iter()) {
+        write(svelte_dir.join("pages").join(format!("{}.svelte", component)), svelte_page_component(e, component, styles))?;
+        let path = scaffold_route_path(e);
+        routes.push(ScaffoldRoute {
+            path: if path.starts_with('/') { path } else { format!("/{}/{}", module_name, path) },
+            name: scaffold_route_name(e),
+            title: page_display_name(&e.meta, e.subpage.as_deref().unwrap_or(&e.page)),
+            component: format!("pages/{}.svelte", component),
+            parent: e.subpage.as_ref().map(|_| format!("{}-{}", e.module, e.page)),
+        });
+    }
+
+    // 模組外框：標題 + slot；token 以 :global(:root) 提供給所有頁面
+    let module_component = format!("{}Module", pascal_case_identifier(module_name));
+    let mut module_css = module_styles_css(module_name, styles.responsive, styles.print);
+    if let Some(tokens) = read_css_tokens(module_name) {
+        module_css.push_str(&css_tokens_root_block(&tokens).replacen(":root {", ":global(:root) {", 1));
+    }
+    let module_svelte = format!(
+        "<!-- {} 模組外框 — 由 ErSlice 生成 -->\n<div class=\"{}\">\n  <header class=\"header\">\n    <h1>{}</h1>\n  </header>\n  <slot />\n</div>\n\n<style>\n{}\n</style>\n",
+        module_name,
+        module_name.to_lowercase().replace(' ', "-"),
+        escape_html(&module_display_name(module_name)),
+        module_css
+    );
+    write(svelte_dir.join(format!("{}.svelte", module_component)), module_svelte)?;
+    let manifest = serde_json::json!({
+        "module": module_name,
+        "layout": format!("{}.svelte", module_component),
+        "routes": routes,
+    });
+    write(svelte_dir.join("routes.json"), serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?)?;
+    Ok(())
+}
+
+// ====== Tailwind 輸出 ======
+
+// 以獨立的 index.tailwind.html.hbs 模板輸出帶 utility class 的模組 HTML，並連結 Tailwind 建置結果
+fn tailwind_html(module_name: &str, include_responsive: bool) -> String {
+    let (padding, heading_size) = if include_responsive { ("p-4 md:p-8", "text-xl sm:text-2xl md:text-3xl") } else { ("p-8", "text-3xl") };
+    let mut context = module_html_context(module_name);
+    context["padding"] = serde_json::json!(padding);
+    context["heading_size"] = serde_json::json!(heading_size);
+    render_generation_template("index.tailwind.html.hbs", Some(module_name), &context)
+}
+
+// tailwind.config.js 的 theme.extend：顏色/間距/字型取自 css-tokens.json（token-N 與 styles.css 的 --color-N 等順序一致）
+fn tailwind_theme_extend(module_name: &str) -> serde_json::Value {
+    let mut colors = serde_json::Map::new();
+    let mut spacing = serde_json::Map::new();
+    let mut font_family = serde_json::Map::new();
+    if let Some(tokens) = read_css_tokens(module_name) {
+        for (name, value) in tokens.custom_properties.iter() {
+            if css_color_values(value).len() == 1 {
+                colors.insert(name.trim_start_matches('-').to_string(), serde_json::Value::String(value.clone()));
+            }
+        }
+        for (i, c) in tokens.colors.iter().enumerate() {
+            colors.insert(format!("token-{}", i + 1), serde_json::Value::String(c.value.clone()));
+        }
+        for (i, sp) in tokens.spacing.iter().enumerate() {
+            spacing.insert(format!("token-{}", i + 1), serde_json::Value::String(sp.value.clone()));
+        }
+        if let Some(font) = tokens.font_families.first() {
+            let stack: Vec<serde_json::Value> = font
+                .value
+                .split(',')
+                .map(|f| f.trim().trim_matches(|c| c == '\'' || c == '"').to_string())
+                .filter(|f| !f.is_empty())
+                .map(serde_json::Value::String)
+                .collect();
+            font_family.insert("sans".into(), serde_json::Value::Array(stack));
+        }
+    }
+    serde_json::json!({ "colors": colors, "spacing": spacing, "fontFamily": font_family })
+}
+
+fn generate_tailwind_assets(module_name: &str, output_dir: &std::path::Path, include_html: bool, include_responsive: bool, strategy: &str) -> Result<(), String> {
+    if include_html {
+        write_module_html(output_dir, &tailwind_html(module_name, include_responsive), strategy).map_err(|e| format!("生成 HTML 模板失敗: {}", e))?;
+    }
+    let extend = serde_json::to_string_pretty(&tailwind_theme_extend(module_name)).unwrap_or_else(|_| "{}".into());
+    let config = format!(
+        "// {} 模組 Tailwind 設定 — 由 ErSlice 依 css-tokens.json 生成\n/** @type {{import('tailwindcss').Config}} */\nmodule.exports = {{\n  content: ['./**/*.html'],\n  theme: {{\n    extend: {},\n  }},\n  plugins: [],\n}};\n",
+        module_name,
+        extend.replace('\n', "\n    ")
+    );
+    let config_path = output_dir.join("tailwind.config.js");
+    write_text_with_strategy(&config_path, &config, strategy).map_err(|e| format!("寫入 tailwind.config.js 失敗: {}", e))?;
+    // 動態效果與載入骨架沒有對應的 utility，沿用生成的 CSS
+    let mut input = String::from("@tailwind base;\n@tailwind components;\n@tailwind utilities;\n");
+    let extra = format!("{}{}", motion_css(module_name), loading_skeleton_css(module_name));
+    if !extra.trim().is_empty() {
+        input.push_str(&format!("\n@layer components {{{}\n}}\n", extra));
+    }
+    let input_path = output_dir.join("tailwind.css");
+    write_text_with_strategy(&input_path, &input, strategy).map_err(|e| format!("寫入 tailwind.css 失敗: {}", e))?;
+    Ok(())
+}
+
+// ====== SCSS / LESS 樣式表 ======
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StylesheetDialect {
+    Css,
+    Scss,
+    Less,
+}
+
+// 解析樣式表語法；未指定時使用專案設定的 stylesheet_dialect
+fn resolve_stylesheet_dialect(dialect: Option<&str>) -> Result<StylesheetDialect, String> {
+    let value = dialect
+        .map(|d| d.to_string())
+        .or_else(|| read_active_project_config().and_then(|c| c.stylesheet_dialect))
+        .map(|d| d.trim().to_lowercase())
+        .unwrap_or_default();
+    match value.as_str() {
+        "" | "css" => Ok(StylesheetDialect::Css),
+        "scss" | "sass" => Ok(StylesheetDialect::Scss),
+        "less" => Ok(StylesheetDialect::Less),
+        other => Err(format!("不支援的樣式表語法: {}（可用：css, scss, less）", other)),
+    }
+}
+
+enum CssChunk {
+    // 註解、@media 等原樣保留的區塊
+    Raw(String),
+    Rule { selector: String, declarations: Vec<String>, children: Vec<(String, Vec<String>)> },
+}
+
+// 切出最上層的註解、規則與 at-rule
+fn parse_css_chunks(css: &str) -> Vec<CssChunk> {
+    let mut chunks: Vec<CssChunk> = Vec::new();
+    let bytes = css.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        if css[i..].starts_with("/*") {
+            let end = css[i + 2..].find("*/").map(|e| i + 2 + e + 2).unwrap_or(css.len());
+            chunks.push(CssChunk::Raw(css[i..end].to_string()));
+            i = end;
+            continue;
+        }
+        let Some(open) = css[i..].find('{').map(|o| i + o) else {
+            chunks.push(CssChunk::Raw(css[i..].trim().to_string()));
+            break;
+        };
+        let mut depth = 0;
+        let mut close = css.len();
+        for (j, b) in bytes.iter().enumerate().skip(open) {
+            match b {
+                b'{' => depth += 1,
+                b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        close = j;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let selector = css[i..open].trim().to_string();
+        let body = &css[open + 1..close.min(css.len())];
+        if selector.starts_with('@') || body.contains('{') {
+            chunks.push(CssChunk::Raw(css[i..(close + 1).min(css.len())].to_string()));
+        } else {
+            let declarations = body.split(';').map(|d| d.trim().to_string()).filter(|d| !d.is_empty()).collect();
+            chunks.push(CssChunk::Rule { selector, declarations, children: Vec::new() });
+        }
+        i = close + 1;
+    }
+    chunks
+}
+
+// 將 ".parent child" 形式的規則巢狀收進先前出現的 ".parent" 規則
+fn nest_css_chunks(chunks: Vec<CssChunk>) -> Vec<CssChunk> {
+    let mut out: Vec<CssChunk> = Vec::new();
+    for chunk in chunks {
+        if let CssChunk::Rule { selector, declarations, .. } = &chunk {
+            if !selector.contains(',') {
+                if let Some((parent, child)) = selector.split_once(' ') {
+                    let target = out.iter_mut().find(|c| matches!(c, CssChunk::Rule { selector: s, .. } if s == parent));
+                    if let Some(CssChunk::Rule { children, .. }) = target {
+                        children.push((child.trim().to_string(), declarations.clone()));
+                        continue;
+                    }
+                }
+            }
+        }
+        out.push(chunk);
+    }
+    out
+}
+
+// 將生成的 CSS 轉為 SCSS/LESS：token 改為變數（$name / @name）、子選擇器巢狀
+fn css_to_preprocessor(module_name: &str, css: &str, dialect: StylesheetDialect) -> String {
+    let (sigil, ext, var_ref): (&str, &str, fn(&str) -> String) = match dialect {
+        StylesheetDialect::Less => ("@", "less", |n| format!("@{}", n)),
+        _ => ("$", "scss", |n| format!("#{{${}}}", n)),
+    };
+    let mut css = css.to_string();
+    let mut out = format!("// 編譯後輸出為 styles.css（例如 `npx {} styles.{} styles.css`）\n", if ext == "less" { "lessc" } else { "sass" }, ext);
+    let entries = read_css_tokens(module_name).map(|t| css_token_entries(&t)).unwrap_or_default();
+    if !entries.is_empty() {
+        out.push_str("\n// 設計稿 token\n");
+        for (name, value) in entries.iter() {
+            out.push_str(&format!("{}{}: {};\n", sigil, name, value));
+            css = css.replace(&format!("var(--{}, {})", name, value), &format!("{}{}", sigil, name));
+        }
+        out.push_str("\n:root {\n");
+        for (name, _) in entries.iter() {
+            out.push_str(&format!("    --{}: {};\n", name, var_ref(name)));
+        }
+        if let Some(tokens) = read_css_tokens(module_name) {
+            for (name, value) in theme_color_aliases(&tokens) {
+                out.push_str(&format!("    --{}: {};\n", name, value));
+            }
+        }
+        out.push_str("}\n");
+    }
+    for chunk in nest_css_chunks(parse_css_chunks(&css)) {
+        out.push('\n');
+        match chunk {
+            CssChunk::Raw(raw) => out.push_str(&raw),
+            CssChunk::Rule { selector, declarations, children } => {
+                out.push_str(&format!("{} {{\n", selector));
+                for d in declarations.iter() {
+                    out.push_str(&format!("    {};\n", d));
+                }
+                for (child, decls) in children.iter() {
+                    out.push_str(&format!("\n    {} {{\n", child));
+                    for d in decls.iter() {
+                        out.push_str(&format!("        {};\n", d));
+                    }
+                    out.push_str("    }\n");
+                }
+                out.push('}');
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+// ====== 設計 token（W3C Design Tokens 格式） ======
+
+const DESIGN_TOKENS_FILE: &str = "design-tokens.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DesignTokensSummary {
+    pub path: String,
+    pub modules: Vec<String>,
+    pub colors: usize,
+    pub font_families: usize,
+    pub spacing: usize,
+}
+
+// 模組的 token：優先使用 css-tokens.json，未擷取過時即時掃描
+fn module_css_tokens(module: &str) -> CssTokenInventory {
+    read_css_tokens(module).unwrap_or_else(|| scan_css_tokens(module))
+}
+
+// 合併多個模組的 token，出現次數相加後重新排序
+fn merge_css_tokens(inventories: &[CssTokenInventory]) -> CssTokenInventory {
+    if let [only] = inventories {
+        return only.clone();
+    }
+    let mut merged = CssTokenInventory { module: "*".into(), ..Default::default() };
+    let mut colors: HashMap<String, usize> = HashMap::new();
+    let mut fonts: HashMap<String, usize> = HashMap::new();
+    let mut spacing: HashMap<String, usize> = HashMap::new();
+    for inv in inventories.iter() {
+        merged.files_scanned += inv.files_scanned;
+        for (k, v) in inv.custom_properties.iter() {
+            merged.custom_properties.entry(k.clone()).or_insert_with(|| v.clone());
+        }
+        for c in inv.colors.iter() { *colors.entry(c.value.clone()).or_default() += c.count; }
+        for f in inv.font_families.iter() { *fonts.entry(f.value.clone()).or_default() += f.count; }
+        for sp in inv.spacing.iter() { *spacing.entry(sp.value.clone()).or_default() += sp.count; }
+    }
+    merged.colors = counted_values(colors);
+    merged.font_families = counted_values(fonts);
+    let mut spacing = counted_values(spacing);
+    spacing.sort_by(|a, b| spacing_sort_key(&a.value).total_cmp(&spacing_sort_key(&b.value)));
+    merged.spacing = spacing;
+    merged
+}
+
+fn font_stack(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|f| f.trim().trim_matches(|c| c == '\'' || c == '"').to_string())
+        .filter(|f| !f.is_empty())
+        .collect()
+}
+
+// W3C Design Tokens（$type/$value）；token 名稱與 styles.css 的 --color-N / --font-N / --space-N 一致
+fn design_tokens_document(tokens: &CssTokenInventory) -> serde_json::Value {
+    let mut color = serde_json::Map::new();
+    let mut font_family = serde_json::Map::new();
+    let mut spacing = serde_json::Map::new();
+    let mut custom = serde_json::Map::new();
+    let token = |t: &str, v: serde_json::Value| serde_json::json!({ "$type": t, "$value": v });
+    for (name, value) in css_token_entries(tokens) {
+        if name.starts_with("font-") && tokens.font_families.iter().any(|f| f.value == value) {
+            font_family.insert(name, token("fontFamily", serde_json::json!(font_stack(&value))));
+        } else if css_color_values(&value).len() == 1 && !value.contains(' ') {
+            color.insert(name, token("color", serde_json::Value::String(value)));
+        } else if css_spacing_values(&value).len() == 1 && !value.contains(' ') {
+            spacing.insert(name, token("dimension", serde_json::Value::String(value)));
+        } else {
+            custom.insert(name, serde_json::json!({ "$value": value }));
+        }
+    }
+    let mut doc = serde_json::Map::new();
+    doc.insert("$description".into(), serde_json::Value::String(format!("由 ErSlice 自設計稿 CSS 擷取（{}）", tokens.module)));
+    for (group, map) in [("color", color), ("fontFamily", font_family), ("spacing", spacing), ("custom", custom)] {
+        if !map.is_empty() {
+            doc.insert(group.into(), serde_json::Value::Object(map));
+        }
+    }
+    serde_json::Value::Object(doc)
+}
+
+fn write_design_tokens_file(modules: &[String], path: &std::path::Path, strategy: &str) -> Result<CssTokenInventory, String> {
+    let inventories: Vec<CssTokenInventory> = modules.iter().map(|m| module_css_tokens(m)).collect();
+    let merged = merge_css_tokens(&inventories);
+    let json = serde_json::to_string_pretty(&design_tokens_document(&merged)).map_err(|e| e.to_string())?;
+    write_text_with_strategy(&path.to_path_buf(), &json, strategy).map_err(|e| format!("寫入設計 token 失敗: {}", e))?;
+    Ok(merged)
+}
+
+/// 將擷取的顏色、字型與間距整理為 W3C Design Tokens JSON（可供 Style Dictionary、Figma 外掛使用）；
+/// 指定模組時寫入 design-assets/<module>/design-tokens.json，否則合併所有模組寫入 design-assets/design-tokens.json
+#[tauri::command]
+pub async fn generate_design_tokens(module: Option<String>) -> Result<DesignTokensSummary, String> {
+    let root = PathBuf::from("design-assets");
+    let (modules, path) = match module.filter(|m| !m.trim().is_empty()) {
+        Some(m) => {
+            if !root.join(&m).exists() {
+                return Err("設計模組不存在".to_string());
+            }
+            let path = root.join(&m).join(DESIGN_TOKENS_FILE);
+            (vec![m], path)
+        }
+        None => (list_dir_names(&root), root.join(DESIGN_TOKENS_FILE)),
+    };
+    let merged = write_design_tokens_file(&modules, &path, "overwrite")?;
+    Ok(DesignTokensSummary {
+        path: path.to_string_lossy().to_string(),
+        modules,
+        colors: merged.colors.len(),
+        font_families: merged.font_families.len(),
+        spacing: merged.spacing.len(),
+    })
+}
+
+// ====== 生成模板 ======
+
+// 專案（projects/<slug>/）或工作區根目錄下的模板資料夾；未提供的檔案使用內建模板
+const GENERATION_TEMPLATES_DIR: &str = "templates/generation";
+
+const BUILTIN_GENERATION_TEMPLATES: [(&str, &str); 6] = [
+    ("index.html.hbs", include_str!("../templates/generation/index.html.hbs")),
+    ("index.tailwind.html.hbs", include_str!("../templates/generation/index.tailwind.html.hbs")),
+    ("styles.css.hbs", include_str!("../templates/generation/styles.css.hbs")),
+    ("ai-spec.md.hbs", include_str!("../templates/generation/ai-spec.md.hbs")),
+    ("ai-spec.en.md.hbs", include_str!("../templates/generation/ai-spec.en.md.hbs")),
+    ("ai-spec.ja.md.hbs", include_str!("../templates/generation/ai-spec.ja.md.hbs")),
+];
+
+fn builtin_generation_template(name: &str) -> &'static str {
+    BUILTIN_GENERATION_TEMPLATES.iter().find(|(n, _)| *n == name).map(|(_, t)| *t).unwrap_or("")
+}
+
+// 模組自帶的模板資料夾（design-assets/<module>/templates/），優先於專案與工作區模板
+const MODULE_TEMPLATES_DIR: &str = "templates";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResolvedTemplate {
+    pub name: String,
+    // "module" | "project" | "workspace" | "builtin"
+    pub source: String,
+    pub path: Option<String>,
+}
+
+// 依優先序：模組 → 目前專案 → 工作區
+fn generation_template_dirs(module_name: Option<&str>) -> Vec<(&'static str, PathBuf)> {
+    let mut dirs = Vec::new();
+    if let Some(m) = module_name {
+        dirs.push(("module", PathBuf::from("design-assets").join(m).join(MODULE_TEMPLATES_DIR)));
+    }
+    dirs.push(("project", active_project_dir().join(GENERATION_TEMPLATES_DIR)));
+    dirs.push(("workspace", PathBuf::from(GENERATION_TEMPLATES_DIR)));
+    dirs
+}
+
+// 模組 templates/ 內含 .hbs 時視為生成模板，不複製進輸出包
+fn is_generation_templates_dir(path: &std::path::Path) -> bool {
+    path.is_dir() && get_files_in_dir(path).iter().any(|f| f.ends_with(".hbs"))
+}
+
+fn resolve_generation_template(name: &str, module_name: Option<&str>) -> ResolvedTemplate {
+    for (source, dir) in generation_template_dirs(module_name) {
+        let path = dir.join(name);
+        if path.is_file() {
+            return ResolvedTemplate { name: name.to_string(), source: source.to_string(), path: Some(path.to_string_lossy().to_string()) };
+        }
+    }
+    ResolvedTemplate { name: name.to_string(), source: "builtin".to_string(), path: None }
+}
+
+// 回傳模板內容與來源檔案（內建模板為 None）
+fn load_generation_template(name: &str, module_name: Option<&str>) -> (String, Option<PathBuf>) {
+    if let Some(path) = resolve_generation_template(name, module_name).path.map(PathBuf::from) {
+        if let Ok(source) = std::fs::read_to_string(&path) {
+            return (source, Some(path));
+        }
+    }
+    (builtin_generation_template(name).to_string(), None)
+}
+
+// 以 Handlebars 渲染；.html 模板會跳脫 {{ }} 內容（{{{ }}} 不跳脫），自訂模板有誤時改用內建模板並記錄警告
+fn render_generation_template(name: &str, module_name: Option<&str>, context: &serde_json::Value) -> String {
+    let mut hb = handlebars::Handlebars::new();
+    if !name.ends_with(".html.hbs") {
+        hb.register_escape_fn(handlebars::no_escape);
+    }
+    let (source, path) = load_generation_template(name, module_name);
+    match hb.render_template(&source, context) {
+        Ok(out) => out,
+        Err(e) => {
+            let origin = path.map(|p| p.display().to_string()).unwrap_or_else(|| name.to_string());
+            log::warn!("模板 {} 渲染失敗，改用內建模板: {}", origin, e);
+            hb.render_template(builtin_generation_template(name), context).unwrap_or_default()
+        }
+    }
+}
+
+/// 顯示模組生成時各模板實際使用的檔案（模組 templates/ → 專案 → 工作區 → 內建）
+#[tauri::command]
+pub async fn resolve_generation_templates(module_name: String) -> Result<Vec<ResolvedTemplate>, String> {
+    if !PathBuf::from("design-assets").join(&module_name).exists() {
+        return Err("設計模組不存在".to_string());
+    }
+    Ok(BUILTIN_GENERATION_TEMPLATES.iter().map(|(name, _)| resolve_generation_template(name, Some(&module_name))).collect())
+}
+
+// 模板可用的頁面清單
+fn generation_template_pages(module_name: &str) -> Vec<serde_json::Value> {
+    collect_module_page_entries(module_name)
+        .iter()
+        .map(|e| {
+            serde_json::json!({
+                "slug": e.subpage.clone().unwrap_or_else(|| e.page.clone()),
+                "parent": e.subpage.as_ref().map(|_| e.page.clone()),
+                "route": e.meta.route.clone().unwrap_or_else(|| e.route_path()),
+                "title": page_display_name(&e.meta, e.subpage.as_deref().unwrap_or(&e.page)),
+                "component": component_name_for(e),
+            })
+        })
+        .collect()
+}
+
+// ====== 頁面層級切版包 ======
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct PageSliceOptions {
+    pub subpage: Option<String>,
+    // 未設定時依 profile，再依預設（HTML/CSS 開啟、RWD 關閉）
+    pub include_html: Option<bool>,
+    pub include_css: Option<bool>,
+    pub include_responsive: Option<bool>,
+    pub overwrite_strategy: Option<String>,
+    // "zip" | "tar.gz" | "none"（預設）
+    pub archive_format: Option<String>,
+    // "default" | "minify" | "pretty"；未設定時使用專案設定
+    pub output_formatting: Option<String>,
+    // "css" | "scss" | "less"；未設定時使用專案設定
+    pub stylesheet_dialect: Option<String>,
+    // 套用已儲存的生成選項組合（只補上此處未設定的選項）
+    pub profile: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PageSlicePackageResult {
+    pub output_dir: String,
+    pub archive_path: Option<String>,
+    pub assets_copied: usize,
+    pub archive_parts: Vec<String>,
+    pub archive_excluded: Vec<String>,
+}
+
+// 單一頁面的 ai-spec 段落：路由、截圖、表單欄位、props 與測試案例
+// assets_base：截圖所在的資產目錄（相對輸出包根目錄，對應模組根目錄）；None 表示頁面資產直接複製於 design-assets/ 下
+fn page_spec_sections(e: &PageEntry, component: &str, lang: SpecLanguage, assets_base: Option<&str>) -> String {
+    let mut out = fill_spec_text(
+        lang.pick(
+            "\n## 頁面資訊\n- 模組：{}\n- 路由：`{}`\n- 元件名稱：`{}`\n",
+            "\n## Page Info\n- Module: {}\n- Route: `{}`\n- Component: `{}`\n",
+            "\n## ページ情報\n- モジュール：{}\n- ルート：`{}`\n- コンポーネント名：`{}`\n",
+        ),
+        &[e.module.clone(), e.meta.route.clone().unwrap_or_else(|| e.route_path()), component.to_string()],
+    );
+    if let Some(notes) = e.meta.notes.as_ref().filter(|n| !n.trim().is_empty()) {
+        out.push_str(&format!("{}{}\n", lang.pick("- 備註：", "- Notes: ", "- 備考："), notes.trim()));
+    }
+    let shots = page_screenshot_refs(e);
+    if !shots.is_empty() {
+        out.push_str(lang.pick("\n## 設計稿\n", "\n## Designs\n", "\n## デザイン\n"));
+        for (i, shot) in shots.iter().enumerate() {
+            let path = match assets_base {
+                Some(base) => format!("{}/{}", base, shot),
+                None => format!("design-assets/{}", shot.rsplit_once("/screenshots/").map(|(_, f)| format!("screenshots/{}", f)).unwrap_or_else(|| shot.clone())),
+            };
+            out.push_str(&format!("{}. `{}`\n", i + 1, path));
+        }
+    }
+    let fields = page_form_fields(&page_entry_dir(e));
+    if !fields.is_empty() {
+        out.push_str(lang.pick(
+            "\n## 表單欄位\n\n| 欄位 | 類型 | 必填 |\n|---|---|---|\n",
+            "\n## Form Fields\n\n| Field | Type | Required |\n|---|---|---|\n",
+            "\n## フォームフィールド\n\n| フィールド | 種類 | 必須 |\n|---|---|---|\n",
+        ));
+        for f in fields.iter() {
+            out.push_str(&format!("| `{}` | {} | {} |\n", f.name, f.field_type, if f.required { "✅" } else { "" }));
+        }
+    }
+    if let Some(props) = e.meta.props.as_ref().filter(|p| !p.is_empty()) {
+        out.push_str(&format!("\n## {}\n\n```ts\n{}```\n", lang.pick("元件 Props", "Component Props", "コンポーネント Props"), props_interface(component, props)));
+    }
+    let checklist = page_accessibility_checklist(e, lang);
+    if !checklist.is_empty() {
+        out.push_str(lang.pick("\n## 無障礙檢查清單\n", "\n## Accessibility Checklist\n", "\n## アクセシビリティチェックリスト\n"));
+        for item in checklist.iter() {
+            out.push_str(&format!("- [ ] {}\n", item));
+        }
+    }
+    let cases = page_test_cases(e, lang);
+    if !cases.is_empty() {
+        out.push_str(lang.pick(
+            "\n## 測試案例\n完整清單請見 `test-cases.md`。\n",
+            "\n## Test Cases\nSee `test-cases.md` for the full list.\n",
+            "\n## テストケース\n全項目は `test-cases.md` を参照してください。\n",
+        ));
+    }
+    out
+}
+
+// 單頁骨架的輸出選項；styles.css 沿用模組樣式
+struct PageSkeletonOptions<'a> {
+    include_html: bool,
+    include_css: bool,
+    include_responsive: bool,
+    include_spec: bool,
+    dialect: StylesheetDialect,
+    strategy: &'a str,
+    // 見 page_spec_sections 的 assets_base
+    assets_base: Option<String>,
+}
+
+// 為單一頁面（或子頁）寫入 index.html / styles.css / ai-spec.md（與 test-cases.md）；spec_label 為說明中的包名稱
+fn write_page_skeleton(entry: &PageEntry, component: &str, out_dir: &std::path::Path, spec_label: &str, options: &PageSkeletonOptions) -> Result<(), String> {
+    let module = &entry.module;
+    let page_dir = page_entry_dir(entry);
+    std::fs::create_dir_all(out_dir).map_err(|e| format!("創建輸出目錄失敗: {}", e))?;
+    let display_name = page_display_name(&entry.meta, entry.subpage.as_deref().unwrap_or(&entry.page));
+    let write = |name: &str, content: String| -> Result<(), String> {
+        write_text_with_strategy(&out_dir.join(name), &content, options.strategy).map_err(|e| format!("寫入 {} 失敗: {}", name, e))
+    };
+    if options.include_html {
+        let title = entry.meta.seo.as_ref().and_then(|s| s.meta_title.clone()).filter(|t| !t.trim().is_empty()).unwrap_or_else(|| display_name.clone());
+        let context = serde_json::json!({
+            "module": module,
+            "module_class": module.to_lowercase().replace(' ', "-"),
+            "display_name": display_name,
+            "title": title,
+            "head_tags": entry.meta.seo.as_ref().map(|s| seo_tags(s, "    ")).unwrap_or_default(),
+            "placeholders": scaffold_form_markup(&page_form_fields(&page_dir), "            ", false),
+            "pages": [],
+        });
+        let html = render_generation_template("index.html.hbs", Some(module), &context);
+        write_module_html(out_dir, &html, options.strategy).map_err(|e| format!("生成 HTML 模板失敗: {}", e))?;
+    }
+    if options.include_css {
+        generate_css_styles_with_strategy(module, &out_dir.to_path_buf(), options.include_responsive, false, options.dialect, options.strategy).map_err(|e| format!("生成 CSS 樣式失敗: {}", e))?;
+    }
+    if !options.include_spec {
+        return Ok(());
+    }
+    let lang = project_spec_language();
+    let cases = page_test_cases(entry, lang);
+    if !cases.is_empty() {
+        write("test-cases.md", test_cases_markdown(&display_name, &cases, lang))?;
+    }
+    let context = serde_json::json!({
+        "module": spec_label,
+        "display_name": display_name,
+        "pages": [],
+        "sections": page_spec_sections(entry, component, lang, options.assets_base.as_deref()),
+    });
+    write("ai-spec.md", render_generation_template(&lang.template_name("ai-spec.md.hbs"), Some(module), &context))
+}
+
+/// 只為單一頁面（或子頁）產生切版包：複製該頁資產、生成骨架與說明，可選擇封存；輸出至 output/pages/<module>-<page>[-<subpage>]
+#[tauri::command]
+pub async fn generate_page_slice_package(module: String, page: String, options: Option<PageSliceOptions>) -> Result<PageSlicePackageResult, String> {
+    let mut options = options.unwrap_or_default();
+    if let Some(profile) = load_generation_profile(options.profile.as_deref())? {
+        profile.apply_to_page_options(&mut options);
+    }
+    let started_at = chrono::Local::now();
+    let strategy = options.overwrite_strategy.clone().unwrap_or_else(|| "overwrite".to_string());
+    let archive_format = resolve_archive_format(options.archive_format.as_deref(), false)?;
+    let formatting = resolve_output_formatting(options.output_formatting.as_deref())?;
+    let subpage = options.subpage.clone().filter(|s| !s.trim().is_empty());
+    let entries = collect_module_page_entries(&module);
+    let names = scaffold_component_names(&entries);
+    let Some((entry, component)) = entries.iter().zip(names.iter()).find(|(e, _)| e.page == page && e.subpage == subpage) else {
+        return Err(if subpage.is_some() { "子頁不存在".into() } else { "頁面不存在".into() });
+    };
+    let page_dir = page_entry_dir(entry);
+    let slug = format!("{}-{}{}", module, page, subpage.as_ref().map(|s| format!("-{}", s)).unwrap_or_default());
+    let base_output = output_root().join("pages");
+    let out_dir = base_output.join(&slug);
+    std::fs::create_dir_all(&out_dir).map_err(|e| format!("創建輸出目錄失敗: {}", e))?;
+    run_generation_hook(HookStage::Pre, &out_dir, Some(&module), None)?;
+
+    // 該頁資產（頁面包含其子頁）
+    let assets_dir = out_dir.join("design-assets");
+    std::fs::create_dir_all(&assets_dir).map_err(|e| format!("創建輸出目錄失敗: {}", e))?;
+    let mut stats = CopyStats::default();
+    copy_assets_with_stats(&page_dir, &assets_dir, &strategy, &mut stats).map_err(|e| format!("複製資產失敗: {}", e))?;
+
+    let skeleton = PageSkeletonOptions {
+        include_html: options.include_html.unwrap_or(true),
+        include_css: options.include_css.unwrap_or(true),
+        include_responsive: options.include_responsive.unwrap_or(false),
+        include_spec: true,
+        dialect: resolve_stylesheet_dialect(options.stylesheet_dialect.as_deref())?,
+        strategy: &strategy,
+        assets_base: None,
+    };
+    write_page_skeleton(entry, component, &out_dir, &slug, &skeleton)?;
+    apply_asset_path_rewrite(&out_dir)?;
+    apply_branding(&out_dir)?;
+    apply_output_formatting(&out_dir, formatting, started_at.into())?;
+    write_package_checksums(&out_dir)?;
+
+    let archive = write_package_archive(&out_dir, &base_output, archive_format)?.unwrap_or_default();
+    let archive_path = archive.primary();
+    run_generation_hook(HookStage::Post, &out_dir, Some(&module), archive_path.as_deref())?;
+    let manifest_options = serde_json::to_value(&options).unwrap_or_default();
+    let mut manifest_module = ManifestModule::completed(&module, &out_dir, started_at, false);
+    manifest_module.page = Some(page.clone());
+    record_generation_manifest("page", started_at, manifest_options, vec![manifest_module], Some(&out_dir), archive_path.as_deref());
+    Ok(PageSlicePackageResult {
+        output_dir: out_dir.to_string_lossy().to_string(),
+        archive_path,
+        assets_copied: stats.copied,
+        archive_parts: archive.part_paths(),
+        archive_excluded: archive.excluded,
+    })
+}
+
+// ====== 增量生成 ======
+
+// 模組內容指紋：所有檔案（含 page.json 等 meta 與模組模板）的相對路徑、大小與修改時間，
+// 加上目前專案設定與生成選項；任一變更都會產生不同的指紋
+fn module_content_fingerprint(module_dir: &std::path::Path, options_key: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let mut files = Vec::new();
+    collect_files_recursive(module_dir, &mut files);
+    files.sort();
+    let mut hasher = Sha256::new();
+    hasher.update(options_key.as_bytes());
+    for f in files.iter() {
+        let rel = f.strip_prefix(module_dir).unwrap_or(f).to_string_lossy().replace('\\', "/");
+        let Ok(meta) = std::fs::metadata(f) else { continue };
+        let mtime = meta.modified().ok().and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok()).map(|d| d.as_nanos()).unwrap_or(0);
+        hasher.update(format!("{}\0{}\0{}\n", rel, meta.len(), mtime).as_bytes());
+    }
+    if let Ok(cfg) = std::fs::read(active_project_dir().join("project.json")) {
+        hasher.update(&cfg);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+// ====== 生成工作進度與取消 ======
+
+lazy_static::lazy_static! {
+    // 進行中的生成工作：job_id → 取消旗標
+    static ref GENERATION_JOBS: Mutex<HashMap<String, Arc<std::sync::atomic::AtomicBool>>> = Mutex::new(HashMap::new());
+}
+
+// generation-progress 事件內容；module 為剛完成的模組，開始時為 None
+#[derive(Debug, Clone, Serialize)]
+pub struct GenerationProgress {
+    pub job_id: String,
+    pub module: Option<String>,
+    pub completed: usize,
+    pub total: usize,
+    pub percent: u32,
+}
+
+// 批量/整包生成工作：每完成一個模組發送 generation-progress 事件，並可由 cancel_generation 取消
+struct GenerationJob {
+    id: String,
+    app: tauri::AppHandle,
+    total: usize,
+    completed: std::sync::atomic::AtomicUsize,
+    cancelled: Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl GenerationJob {
+    // 登記工作並發送 0% 事件；未指定 job_id 時自動產生，前端可由首個事件取得
+    fn start(app: tauri::AppHandle, job_id: Option<String>, total: usize) -> Self {
+        let id = job_id.filter(|s| !s.trim().is_empty()).unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let cancelled = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        GENERATION_JOBS.lock().unwrap_or_else(|e| e.into_inner()).insert(id.clone(), cancelled.clone());
+        let job = GenerationJob { id, app, total, completed: std::sync::atomic::AtomicUsize::new(0), cancelled };
+        job.emit(None, 0);
+        job
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    // 模組完成後累計進度並發送事件（可由多個執行緒呼叫）
+    fn advance(&self, module: &str) {
+        let completed = self.completed.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+        self.emit(Some(module), completed);
+    }
+
+    fn emit(&self, module: Option<&str>, completed: usize) {
+        use tauri::Emitter;
+        let percent = (completed * 100).checked_div(self.total).map_or(100, |p| p as u32);
+        let _ = self.app.emit("generation-progress", GenerationProgress {
+            job_id: self.id.clone(),
+            module: module.map(|m| m.to_string()),
+            completed,
+            total: self.total,
+            percent,
+        });
+    }
+
+    // 結束工作並回傳 job_id（登記於 Drop 時移除）
+    fn finish(self) -> String {
+        self.id.clone()
+    }
+}
+
+impl Drop for GenerationJob {
+    fn drop(&mut self) {
+        GENERATION_JOBS.lock().unwrap_or_else(|e| e.into_inner()).remove(&self.id);
+    }
+}
+
+/// 取消進行中的批量/整包生成；正在處理的模組會完成，其餘模組不再開始。回傳是否找到該工作
+#[tauri::command]
+pub async fn cancel_generation(job_id: String) -> Result<bool, String> {
+    let jobs = GENERATION_JOBS.lock().map_err(|e| format!("取得生成工作失敗: {}", e))?;
+    match jobs.get(&job_id) {
+        Some(flag) => {
+            flag.store(true, std::sync::atomic::Ordering::SeqCst);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+// ====== 生成紀錄 ======
+
+const GENERATION_HISTORY_DIR: &str = ".history";
+
+fn generation_history_dir() -> PathBuf {
+    output_root().join(GENERATION_HISTORY_DIR)
+}
+
+// 生成紀錄中的單一模組；files 為相對 output_dir 的檔案（僅在批量生成時逐模組記錄，整包生成見 GenerationManifest.files）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestModule {
+    pub name: String,
+    #[serde(default)]
+    pub page: Option<String>,
+    // "success" | "failed" | "skipped"
+    pub status: String,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub output_dir: Option<String>,
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub files: Vec<ManifestFile>,
+}
+
+// 生成紀錄中的產出檔案；sha256 供 diff_outputs 比對歷史紀錄
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestFile {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+impl ManifestModule {
+    // 成功生成的模組；list_files 為 false 時檔案改記錄於整包層級
+    fn completed(name: &str, output_dir: &std::path::Path, started_at: chrono::DateTime<chrono::Local>, list_files: bool) -> Self {
+        ManifestModule {
+            name: name.to_string(),
+            page: None,
+            status: "success".into(),
+            error: None,
+            output_dir: Some(output_dir.to_string_lossy().to_string()),
+            duration_ms: elapsed_ms(started_at),
+            files: if list_files { manifest_files(output_dir) } else { Vec::new() },
+        }
+    }
+
+    fn failed(name: &str, error: &str, started_at: chrono::DateTime<chrono::Local>) -> Self {
+        ManifestModule { name: name.to_string(), page: None, status: "failed".into(), error: Some(error.to_string()), output_dir: None, duration_ms: elapsed_ms(started_at), files: Vec::new() }
+    }
+
+    fn skipped(name: &str) -> Self {
+        ManifestModule { name: name.to_string(), page: None, status: "skipped".into(), error: None, output_dir: None, duration_ms: 0, files: Vec::new() }
+    }
+
+    fn from_bulk(name: &str, result: &BulkModuleResult, duration_ms: u64) -> Self {
+        let output_dir = output_root().join(name);
+        match result {
+            Ok(_) => ManifestModule {
+                name: name.to_string(),
+                page: None,
+                status: "success".into(),
+                error: None,
+                output_dir: Some(output_dir.to_string_lossy().to_string()),
+                duration_ms,
+                files: manifest_files(&output_dir),
+            },
+            Err(e) => ManifestModule { name: name.to_string(), page: None, status: "failed".into(), error: Some(e.clone()), output_dir: None, duration_ms, files: Vec::new() },
+        }
+    }
+}
+
+// 一次生成的完整紀錄（output/.history/<id>.json）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerationManifest {
+    pub id: String,
+    // "module" | "page" | "bulk" | "selected" | "unified"
+    pub kind: String,
+    pub started_at: String,
+    pub finished_at: String,
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub project: Option<String>,
+    pub host: String,
+    pub options: serde_json::Value,
+    #[serde(default)]
+    pub output_dir: Option<String>,
+    #[serde(default)]
+    pub archive_path: Option<String>,
+    // 相對 output_dir 的產出檔案（單一輸出目錄的生成）
+    #[serde(default)]
+    pub files: Vec<ManifestFile>,
+    pub modules: Vec<ManifestModule>,
+}
+
+// list_generation_history 的摘要項目
+#[derive(Debug, Clone, Serialize)]
+pub struct GenerationHistoryEntry {
+    pub id: String,
+    pub kind: String,
+    pub started_at: String,
+    pub duration_ms: u64,
+    pub project: Option<String>,
+    pub modules: Vec<String>,
+    pub failed_count: usize,
+    pub output_dir: Option<String>,
+    pub archive_path: Option<String>,
+}
+
+fn elapsed_ms(started_at: chrono::DateTime<chrono::Local>) -> u64 {
+    (chrono::Local::now() - started_at).num_milliseconds().max(0) as u64
+}
+
+// 輸出目錄內的檔案（相對路徑排序，附大小與 sha256）；略過生成紀錄目錄本身
+fn manifest_files(dir: &std::path::Path) -> Vec<ManifestFile> {
+    let mut paths = Vec::new();
+    collect_output_files(dir, &mut paths);
+    let mut files: Vec<ManifestFile> = paths
+        .iter()
+        .filter_map(|p| {
+            let rel = p.strip_prefix(dir).ok()?.to_string_lossy().replace('\\', "/");
+            if rel.split('/').next() == Some(GENERATION_HISTORY_DIR) { return None; }
+            Some(ManifestFile {
+                path: rel,
+                size: std::fs::metadata(p).map(|m| m.len()).unwrap_or(0),
+                sha256: sha256_file(p).unwrap_or_default(),
+            })
+        })
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
+// 記錄生成紀錄；失敗只記 log，不影響生成結果
+fn record_generation_manifest(
+    kind: &str,
+    started_at: chrono::DateTime<chrono::Local>,
+    options: serde_json::Value,
+    modules: Vec<ManifestModule>,
+    output_dir: Option<&std::path::Path>,
+    archive_path: Option<&str>,
+) {
+    let id = format!("{}-{}", started_at.format("%Y%m%d-%H%M%S"), &uuid::Uuid::new_v4().simple().to_string()[..8]);
+    let manifest = GenerationManifest {
+        id: id.clone(),
+        kind: kind.to_string(),
+        started_at: started_at.to_rfc3339(),
+        finished_at: chrono::Local::now().to_rfc3339(),
+        duration_ms: elapsed_ms(started_at),
+        project: read_active_slug(),
+        host: local_hostname(),
+        options,
+        output_dir: output_dir.map(|d| d.to_string_lossy().to_string()),
+        archive_path: archive_path.map(|p| p.to_string()),
+        files: output_dir.map(manifest_files).unwrap_or_default(),
+        modules,
+    };
+    let dir = generation_history_dir();
+    let result = std::fs::create_dir_all(&dir)
+        .map_err(|e| e.to_string())
+        .and_then(|_| serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string()))
+        .and_then(|json| std::fs::write(dir.join(format!("{}.json", id)), json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        log::warn!("寫入生成紀錄失敗: {}", e);
+    }
+}
+
+fn read_generation_manifest(path: &std::path::Path) -> Option<GenerationManifest> {
+    let text = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+/// 列出生成紀錄（新到舊）；limit 未指定時回傳全部
+#[tauri::command]
+pub async fn list_generation_history(limit: Option<usize>) -> Result<Vec<GenerationHistoryEntry>, String> {
+    let mut paths: Vec<PathBuf> = match std::fs::read_dir(generation_history_dir()) {
+        Ok(rd) => rd.flatten().map(|e| e.path()).filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json")).collect(),
+        Err(_) => return Ok(Vec::new()),
+    };
+    // 檔名以時間開頭，依檔名倒序即新到舊
+    paths.sort();
+    paths.reverse();
+    let entries = paths
+        .iter()
+        .filter_map(|p| read_generation_manifest(p))
+        .take(limit.unwrap_or(usize::MAX))
+        .map(|m| GenerationHistoryEntry {
+            failed_count: m.modules.iter().filter(|x| x.status == "failed").count(),
+            modules: m.modules.iter().filter(|x| x.status == "success").map(|x| x.name.clone()).collect(),
+            id: m.id,
+            kind: m.kind,
+            started_at: m.started_at,
+            duration_ms: m.duration_ms,
+            project: m.project,
+            output_dir: m.output_dir,
+            archive_path: m.archive_path,
+        })
+        .collect();
+    Ok(entries)
+}
+
+/// 取得單次生成的完整紀錄（選項、模組、產出檔案與耗時）
+#[tauri::command]
+pub async fn get_generation_manifest(id: String) -> Result<GenerationManifest, String> {
+    get_generation_manifest_by_id(&id)
+}
+
+fn get_generation_manifest_by_id(id: &str) -> Result<GenerationManifest, String> {
+    if id.is_empty() || id.contains(['/', '\\']) || id.contains("..") {
+        return Err("無效的生成紀錄 ID".into());
+    }
+    let path = generation_history_dir().join(format!("{}.json", id));
+    if !path.exists() {
+        return Err("生成紀錄不存在".into());
+    }
+    read_generation_manifest(&path).ok_or_else(|| "讀取生成紀錄失敗".to_string())
+}
+
+// ====== 輸出包差異 ======
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct OutputDiff {
+    pub a: String,
+    pub b: String,
+    // 只存在於 b
+    pub added: Vec<String>,
+    // 只存在於 a
+    pub removed: Vec<String>,
+    // 兩邊皆有但內容不同
+    pub changed: Vec<String>,
+    pub unchanged_count: usize,
+}
+
+// 差異比對的來源：輸出資料夾，或生成紀錄 ID（使用紀錄中的檔案雜湊）
+// 批量紀錄的檔案以 <module>/ 為前綴，與 output/ 資料夾的相對路徑一致
+fn output_file_hashes(source: &str) -> Result<std::collections::BTreeMap<String, String>, String> {
+    let dir = PathBuf::from(source);
+    let files = if dir.is_dir() {
+        manifest_files(&dir)
+    } else {
+        let manifest = get_generation_manifest_by_id(source).map_err(|_| format!("找不到輸出資料夾或生成紀錄: {}", source))?;
+        let mut files = manifest.files;
+        if files.is_empty() {
+            for module in manifest.modules {
+                files.extend(module.files.into_iter().map(|f| ManifestFile { path: format!("{}/{}", module.name, f.path), ..f }));
+            }
+        }
+        files
+    };
+    Ok(files.into_iter().map(|f| (f.path, f.sha256)).collect())
+}
+
+/// 逐檔比對兩個輸出資料夾（或兩筆生成紀錄 ID），回傳新增/刪除/變更的檔案，
+/// 用來告訴工程師自上次交付後改了什麼
+#[tauri::command]
+pub async fn diff_outputs(dir_a: String, dir_b: String) -> Result<OutputDiff, String> {
+    let a = output_file_hashes(&dir_a)?;
+    let b = output_file_hashes(&dir_b)?;
+    let mut diff = OutputDiff { a: dir_a, b: dir_b, ..Default::default() };
+    for (path, hash) in a.iter() {
+        match b.get(path) {
+            None => diff.removed.push(path.clone()),
+            Some(other) if other != hash => diff.changed.push(path.clone()),
+            Some(_) => diff.unchanged_count += 1,
+        }
+    }
+    diff.added = b.keys().filter(|p| !a.contains_key(*p)).cloned().collect();
+    Ok(diff)
+}
+
+// ====== 生成後驗證 ======
+
+// 檢查輸出包根目錄的 HTML 能否以 HTML5 解析、CSS 有無語法錯誤，以及 HTML/CSS 引用的本地檔案是否存在；
+// 回傳 "<檔案>: <訊息>" 形式的警告（複製進來的設計資產不檢查）
+fn validate_generated_output(output_dir: &std::path::Path) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let mut files: Vec<PathBuf> = std::fs::read_dir(output_dir)
+        .map(|rd| rd.flatten().map(|e| e.path()).filter(|p| p.is_file()).collect())
+        .unwrap_or_default();
+    files.sort();
+    for file in files.iter() {
+        let name = file.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let Ok(text) = std::fs::read_to_string(file) else { continue };
+        match file.extension().and_then(|e| e.to_str()) {
+            Some("html") => {
+                if !text.trim_start().to_lowercase().starts_with("<!doctype html") {
+                    warnings.push(format!("{}: 缺少 <!DOCTYPE html>", name));
+                }
+                let document = scraper::Html::parse_document(&text);
+                for error in document.errors.iter().take(10) {
+                    warnings.push(format!("{}: HTML 解析錯誤: {}", name, error));
+                }
+            }
+            Some("css") => {
+                for error in css_syntax_errors(&text) {
+                    warnings.push(format!("{}: {}", name, error));
+                }
+                for url in css_url_refs(&text) {
+                    let clean = url.split(['?', '#']).next().unwrap_or(&url);
+                    if !output_dir.join(clean).is_file() {
+                        warnings.push(format!("{}: 引用的檔案不存在: {}", name, url));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Ok(report) = rewrite_package_asset_refs(output_dir, None) {
+        for missing in report.missing {
+            warnings.push(format!("引用的檔案不存在: {}", missing));
+        }
+    }
+    warnings
+}
+
+// CSS 語法的基本檢查：未閉合的註解/字串/區塊、多餘的 }、缺少冒號的宣告
+fn css_syntax_errors(css: &str) -> Vec<String> {
+    let mut errors = Vec::new();
+    let chars: Vec<char> = css.chars().collect();
+    let mut line = 1usize;
+    let mut depth = 0usize;
+    let mut segment = String::new();
+    let mut segment_line = 1usize;
+    let mut parens = 0usize;
+    let mut i = 0usize;
+    let check_declaration = |segment: &str, line: usize, depth: usize, errors: &mut Vec<String>| {
+        let decl = segment.trim();
+        if decl.is_empty() { return; }
+        if depth == 0 {
+            if !decl.starts_with('@') {
+                errors.push(format!("第 {} 行: 區塊外的內容: {}", line, decl));
+            }
+        } else if !decl.contains(':') && !decl.starts_with('@') {
+            errors.push(format!("第 {} 行: 宣告缺少冒號: {}", line, decl));
+        }
+    };
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '/' && chars.get(i + 1) == Some(&'*') {
+            let start_line = line;
+            i += 2;
+            loop {
+                if i >= chars.len() {
+                    errors.push(format!("第 {} 行: 註解未閉合", start_line));
+                    return errors;
+                }
+                if chars[i] == '\n' { line += 1; }
+                if chars[i] == '*' && chars.get(i + 1) == Some(&'/') { i += 2; break; }
+                i += 1;
+            }
+            continue;
+        }
+        if segment.trim().is_empty() { segment_line = line; }
+        match c {
+            '"' | '\'' => {
+                segment.push(c);
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None | Some('\n') => {
+                            errors.push(format!("第 {} 行: 字串未閉合", line));
+                            break;
+                        }
+                        Some('\\') => {
+                            segment.push('\\');
+                            if let Some(next) = chars.get(i + 1) { segment.push(*next); }
+                            i += 2;
+                            continue;
+                        }
+                        Some(q) if *q == c => { segment.push(c); break; }
+                        Some(other) => segment.push(*other),
+                    }
+                    i += 1;
+                }
+            }
+            '{' => {
+                depth += 1;
+                segment.clear();
+            }
+            '}' => {
+                if depth == 0 {
+                    errors.push(format!("第 {} 行: 多餘的 }}", line));
+                } else {
+                    check_declaration(&segment, segment_line, depth, &mut errors);
+                    depth -= 1;
+                }
+                segment.clear();
+            }
+            // url(data:...;base64,...) 內的分號不是宣告結尾
+            '(' => {
+                parens += 1;
+                segment.push(c);
+            }
+            ')' => {
+                parens = parens.saturating_sub(1);
+                segment.push(c);
+            }
+            ';' if parens > 0 => segment.push(c),
+            ';' => {
+                check_declaration(&segment, segment_line, depth, &mut errors);
+                segment.clear();
+            }
+            '\n' => {
+                line += 1;
+                segment.push(c);
+            }
+            _ => segment.push(c),
+        }
+        i += 1;
+    }
+    if depth > 0 {
+        errors.push(format!("檔案結尾仍有 {} 個未閉合的區塊", depth));
+    }
+    if !segment.trim().is_empty() {
+        errors.push(format!("第 {} 行: 檔案結尾有未結束的內容", segment_line));
+    }
+    errors
+}
+
+// CSS 中 url(...) 與 @import "..." 的本地引用
+fn css_url_refs(css: &str) -> Vec<String> {
+    let mut refs = Vec::new();
+    let mut rest = css;
+    while let Some(idx) = rest.find("url(") {
+        let body = &rest[idx + 4..];
+        let Some(end) = body.find(')') else { break };
+        refs.push(body[..end].trim().trim_matches(['"', '\'']).to_string());
+        rest = &body[end + 1..];
+    }
+    for line in css.lines() {
+        let t = line.trim();
+        if let Some(rest) = t.strip_prefix("@import") {
+            let rest = rest.trim();
+            if rest.starts_with('"') || rest.starts_with('\'') {
+                let q = rest.chars().next().unwrap_or('"');
+                if let Some(end) = rest[1..].find(q) {
+                    refs.push(rest[1..1 + end].to_string());
+                }
+            }
+        }
+    }
+    refs.retain(|r| !is_external_ref(r));
+    refs
+}
+
+// ====== 無障礙檢查清單 ======
+
+// 頁面 HTML 原稿（小寫），用於偵測表格、圖片等結構
+fn page_html_source(dir: &std::path::Path) -> String {
+    get_files_in_dir(&dir.join("html"))
+        .iter()
+        .filter_map(|name| std::fs::read_to_string(dir.join("html").join(name)).ok())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .to_ascii_lowercase()
+}
+
+// 依偵測到的頁面類型、表單欄位、表格、浮層與狀態產出逐頁的無障礙檢查項目
+fn page_accessibility_checklist(e: &PageEntry, lang: SpecLanguage) -> Vec<String> {
+    let dir = page_entry_dir(e);
+    let slug = e.subpage.as_deref().unwrap_or(&e.page);
+    let page_type = detect_page_type(slug, &e.meta);
+    let html = page_html_source(&dir);
+    let fields = page_form_fields(&dir);
+    let t = |zh: &str, en: &str, ja: &str| lang.pick(zh, en, ja).to_string();
+    let mut items: Vec<String> = vec![t(
+        "頁面只有一個 `<h1>`，標題層級不跳級，`<title>` 與頁面名稱一致",
+        "The page has exactly one `<h1>`, heading levels never skip, and `<title>` matches the page name",
+        "ページの `<h1>` は一つだけで、見出しレベルを飛ばさず、`<title>` はページ名と一致している",
+    )];
+
+    if !fields.is_empty() || matches!(page_type.as_str(), "create" | "edit" | "search" | "settings") {
+        let names: Vec<String> = fields.iter().map(|f| format!("`{}`", f.name)).collect();
+        if names.is_empty() {
+            items.push(t(
+                "每個表單欄位都有對應的 `<label for>`（不可只用 placeholder）",
+                "Every form field has a matching `<label for>` (a placeholder alone is not enough)",
+                "すべてのフォームフィールドに対応する `<label for>` がある（placeholder だけにしない）",
+            ));
+        } else {
+            items.push(fill_spec_text(lang.pick(
+                "表單欄位 {} 皆有對應的 `<label for>`（不可只用 placeholder）",
+                "Form fields {} all have a matching `<label for>` (a placeholder alone is not enough)",
+                "フォームフィールド {} すべてに対応する `<label for>` がある（placeholder だけにしない）",
+            ), &[names.join(lang.list_separator())]));
+        }
+        let required: Vec<String> = fields.iter().filter(|f| f.required).map(|f| format!("`{}`", f.name)).collect();
+        if !required.is_empty() {
+            items.push(fill_spec_text(lang.pick(
+                "必填欄位 {} 標示 `required` / `aria-required=\"true\"`，且必填提示不只靠顏色",
+                "Required fields {} are marked with `required` / `aria-required=\"true\"`, and the required hint does not rely on colour alone",
+                "必須フィールド {} に `required` / `aria-required=\"true\"` を付け、必須の表示を色だけに頼らない",
+            ), &[required.join(lang.list_separator())]));
+        }
+        items.push(t(
+            "驗證錯誤以 `aria-invalid` 標記，錯誤訊息用 `aria-describedby` 連結欄位，送出失敗時焦點移至第一個錯誤欄位",
+            "Validation errors are flagged with `aria-invalid`, messages are linked to their field with `aria-describedby`, and a failed submit moves focus to the first invalid field",
+            "検証エラーは `aria-invalid` で示し、エラーメッセージは `aria-describedby` でフィールドに関連付け、送信失敗時は最初のエラーフィールドにフォーカスを移す",
+        ));
+        if fields.iter().any(|f| matches!(f.field_type.as_str(), "radio" | "checkbox")) {
+            items.push(t(
+                "radio / checkbox 群組以 `<fieldset>` + `<legend>` 包覆",
+                "radio / checkbox groups are wrapped in `<fieldset>` + `<legend>`",
+                "radio / checkbox のグループは `<fieldset>` + `<legend>` で囲む",
+            ));
+        }
+        if fields.iter().any(|f| matches!(f.field_type.as_str(), "email" | "tel" | "password")) || fields.iter().any(|f| f.name.contains("name") || f.name.contains("address")) {
+            items.push(t(
+                "個人資料欄位設定適當的 `autocomplete` 屬性",
+                "Personal data fields set an appropriate `autocomplete` attribute",
+                "個人情報のフィールドには適切な `autocomplete` 属性を設定する",
+            ));
+        }
+    }
+    if page_type == "list" || html.contains("<table") {
+        items.push(t(
+            "資料表格使用 `<table>` 並提供 `<caption>`（可視覺隱藏），欄標題為 `<th scope=\"col\">`",
+            "Data tables use `<table>` with a `<caption>` (may be visually hidden) and `<th scope=\"col\">` column headers",
+            "データテーブルは `<table>` を使い `<caption>`（視覚的に非表示でも可）を付け、列見出しは `<th scope=\"col\">` にする",
+        ));
+        items.push(t(
+            "可排序欄位以 `aria-sort` 標示目前排序狀態，排序按鈕可用鍵盤操作",
+            "Sortable columns expose the current order with `aria-sort`, and sort buttons are keyboard operable",
+            "並べ替え可能な列は `aria-sort` で現在の並び順を示し、並べ替えボタンはキーボードで操作できる",
+        ));
+        items.push(t(
+            "分頁元件置於 `<nav aria-label>` 內，目前頁碼以 `aria-current=\"page\"` 標示",
+            "Pagination sits inside `<nav aria-label>` and the current page is marked with `aria-current=\"page\"`",
+            "ページネーションは `<nav aria-label>` 内に置き、現在のページは `aria-current=\"page\"` で示す",
+        ));
+    }
+    if page_type == "search" {
+        items.push(t(
+            "搜尋區以 `role=\"search\"` 標示，結果數量變化透過 `aria-live=\"polite\"` 告知",
+            "The search area is marked with `role=\"search\"` and changes in the result count are announced via `aria-live=\"polite\"`",
+            "検索エリアは `role=\"search\"` で示し、結果件数の変化は `aria-live=\"polite\"` で通知する",
+        ));
+    }
+    if page_type == "detail" {
+        items.push(t(
+            "欄位名稱與值使用 `<dl>` / `<dt>` / `<dd>` 呈現",
+            "Field names and values are presented with `<dl>` / `<dt>` / `<dd>`",
+            "項目名と値は `<dl>` / `<dt>` / `<dd>` で表示する",
+        ));
+    }
+    if page_type == "delete" {
+        items.push(t(
+            "刪除確認使用 `role=\"alertdialog\"`，預設焦點在「取消」，說明文字以 `aria-describedby` 連結",
+            "The delete confirmation uses `role=\"alertdialog\"`, focuses \"Cancel\" by default, and links its description with `aria-describedby`",
+            "削除確認は `role=\"alertdialog\"` を使い、初期フォーカスは「キャンセル」に置き、説明文は `aria-describedby` で関連付ける",
+        ));
+    }
+    if page_type == "dashboard" {
+        items.push(t(
+            "圖表提供文字摘要或對應的資料表格，不只以顏色區分數列",
+            "Charts provide a text summary or an equivalent data table, and series are not distinguished by colour alone",
+            "グラフにはテキストの要約または対応するデータテーブルを用意し、系列を色だけで区別しない",
+        ));
+        items.push(t(
+            "各區塊使用 landmark（`<main>`、`<section aria-labelledby>`）方便螢幕閱讀器跳轉",
+            "Each block uses landmarks (`<main>`, `<section aria-labelledby>`) so screen reader users can jump between them",
+            "各ブロックはランドマーク（`<main>`、`<section aria-labelledby>`）を使い、スクリーンリーダーで移動しやすくする",
+        ));
+    }
+    if page_type == "settings" {
+        items.push(t(
+            "開關元件使用 `role=\"switch\"` 與 `aria-checked`，儲存結果以 `role=\"status\"` 告知",
+            "Toggles use `role=\"switch\"` with `aria-checked`, and the save result is announced with `role=\"status\"`",
+            "トグルは `role=\"switch\"` と `aria-checked` を使い、保存結果は `role=\"status\"` で通知する",
+        ));
+    }
+    for ov in e.meta.overlays.iter().flatten() {
+        match ov.kind.as_str() {
+            "modal" | "drawer" => items.push(fill_spec_text(lang.pick(
+                "{}「{}」：`role=\"dialog\"` + `aria-modal=\"true\"` + `aria-labelledby`，開啟時焦點移入並鎖定（focus trap），`Esc` 關閉後焦點還原至觸發元件",
+                "{} \"{}\": `role=\"dialog\"` + `aria-modal=\"true\"` + `aria-labelledby`; focus moves in and is trapped while open, and `Esc` closes it and returns focus to the trigger",
+                "{}「{}」：`role=\"dialog\"` + `aria-modal=\"true\"` + `aria-labelledby`。開いたらフォーカスを移して閉じ込め（focus trap）、`Esc` で閉じた後はトリガー要素にフォーカスを戻す",
+            ), &[ov.kind.clone(), ov.name.clone()])),
+            "toast" => items.push(fill_spec_text(lang.pick(
+                "toast「{}」：以 `role=\"status\"`（錯誤用 `role=\"alert\"`）播報，停留時間足夠閱讀且可手動關閉",
+                "toast \"{}\": announced with `role=\"status\"` (`role=\"alert\"` for errors), stays long enough to read and can be dismissed manually",
+                "toast「{}」：`role=\"status\"`（エラーは `role=\"alert\"`）で読み上げ、読むのに十分な時間表示し、手動で閉じられる",
+            ), std::slice::from_ref(&ov.name))),
+            _ => {}
+        }
+    }
+    if e.meta.loading_skeleton == Some(true) {
+        items.push(t(
+            "載入中容器設定 `aria-busy=\"true\"`，骨架元素對螢幕閱讀器隱藏（`aria-hidden`）",
+            "The loading container sets `aria-busy=\"true\"` and skeleton elements are hidden from screen readers (`aria-hidden`)",
+            "読み込み中のコンテナに `aria-busy=\"true\"` を設定し、スケルトン要素はスクリーンリーダーから隠す（`aria-hidden`）",
+        ));
+    }
+    if e.meta.error_state.is_some() {
+        items.push(t(
+            "錯誤狀態訊息以 `role=\"alert\"` 呈現並提供重試操作",
+            "Error state messages use `role=\"alert\"` and offer a retry action",
+            "エラー状態のメッセージは `role=\"alert\"` で表示し、再試行の操作を用意する",
+        ));
+    }
+    if e.meta.empty_state.is_some() {
+        items.push(t(
+            "空狀態說明文字可被讀取，並提供下一步操作（例如新增）",
+            "The empty state text is readable and offers a next step (for example, create)",
+            "空状態の説明文は読み上げ可能で、次の操作（例：新規作成）を用意する",
+        ));
+    }
+    if html.contains("<img") || !page_screenshot_refs(e).is_empty() {
+        items.push(t(
+            "內容圖片提供描述性 `alt`，裝飾性圖片使用 `alt=\"\"`",
+            "Content images have a descriptive `alt`; decorative images use `alt=\"\"`",
+            "コンテンツ画像には説明的な `alt` を付け、装飾画像は `alt=\"\"` にする",
+        ));
+    }
+    if e.meta.focus_order.as_ref().is_some_and(|o| !o.is_empty()) {
+        items.push(t(
+            "DOM 順序與「鍵盤操作」一節的焦點順序一致（避免正值 `tabindex`）",
+            "DOM order matches the focus order in the Keyboard Interaction section (avoid positive `tabindex`)",
+            "DOM の順序は「キーボード操作」節のフォーカス順序と一致させる（正の `tabindex` は避ける）",
+        ));
+    }
+    items
+}
+
+fn accessibility_spec_section(module_name: &str) -> String {
+    let entries = collect_module_page_entries(module_name);
+    if entries.is_empty() { return String::new(); }
+    let lang = project_spec_language();
+    let mut out = String::from(lang.pick(
+        "\n## 無障礙檢查清單（Accessibility）\n依頁面類型與結構產出的逐頁檢查項目，完成後請以鍵盤與螢幕閱讀器實際操作確認：\n",
+        "\n## Accessibility Checklist\nPer-page checks derived from each page's type and structure; once done, verify them with a keyboard and a screen reader:\n",
+        "\n## アクセシビリティチェックリスト（Accessibility）\nページ種別と構造から生成したページごとのチェック項目です。完了後はキーボードとスクリーンリーダーで実際に操作して確認してください：\n",
+    ));
+    for e in entries.iter() {
+        let slug = e.subpage.as_deref().unwrap_or(&e.page);
+        out.push_str(&fill_spec_text(lang.pick("\n### `{}`（{}）\n", "\n### `{}` ({})\n", "\n### `{}`（{}）\n"), &[e.route_path(), detect_page_type(slug, &e.meta)]));
+        for item in page_accessibility_checklist(e, lang) {
+            out.push_str(&format!("- [ ] {}\n", item));
+        }
+    }
+    out
+}
+
+// ====== Storybook stories ======
+
+// Storybook 各框架的 framework 套件
+fn storybook_framework_package(framework: &str) -> &'static str {
+    match framework {
+        "vue" => "@storybook/vue3-vite",
+        "svelte" => "@storybook/svelte-vite",
+        _ => "@storybook/react-vite",
+    }
+}
+
+// story 的 args：使用 props 的預設值，必填且無預設值者依型別給佔位值
+fn story_args(props: &[ComponentProp]) -> String {
+    let args: Vec<String> = props
+        .iter()
+        .filter_map(|p| {
+            let value = match p.default.as_ref() {
+                Some(d) => d.trim().to_string(),
+                None if p.required => match p.prop_type.trim() {
+                    "string" => serde_json::to_string(&p.name).unwrap_or_default(),
+                    "number" => "0".to_string(),
+                    "boolean" => "false".to_string(),
+                    t if t.ends_with("[]") => "[]".to_string(),
+                    _ => return None,
+                },
+                None => return None,
+            };
+            Some(format!("    {}: {},\n", p.name, value))
+        })
+        .collect();
+    if args.is_empty() { String::new() } else { format!("  args: {{\n{}  }},\n", args.concat()) }
+}
+
+// 單一元件的 story 檔（CSF3）；React 為 .stories.tsx，Vue/Svelte 為 .stories.js
+fn story_file(framework: &str, title: &str, component: &str, import_path: &str, props: &[ComponentProp]) -> String {
+    let title = serde_json::to_string(title).unwrap_or_default();
+    let args = story_args(props);
+    if framework == "react" {
+        format!(
+            "// 由 ErSlice 生成\nimport type {{ Meta, StoryObj }} from '@storybook/react';\nimport {c} from '{p}';\n\nconst meta: Meta<typeof {c}> = {{\n  title: {t},\n  component: {c},\n{a}}};\nexport default meta;\n\ntype Story = StoryObj<typeof {c}>;\n\nexport const Default: Story = {{}};\n",
+            c = component, p = import_path, t = title, a = args
+        )
+    } else {
+        format!(
+            "// 由 ErSlice 生成\nimport {c} from '{p}';\n\nexport default {{\n  title: {t},\n  component: {c},\n{a}}};\n\nexport const Default = {{}};\n",
+            c = component, p = import_path, t = title, a = args
+        )
+    }
+}
+
+// 在框架骨架旁輸出每頁與模組外框的 stories，並在 <framework>/.storybook/ 放入設定檔
+fn generate_storybook_stories(module_name: &str, output_dir: &std::path::Path, framework: &str, strategy: &str) -> Result<(), String> {
+    let root = output_dir.join(framework);
+    let (story_ext, component_ext) = match framework {
+        "react" => ("stories.tsx", ""),
+        "vue" => ("stories.js", ".vue"),
+        "svelte" => ("stories.js", ".svelte"),
+        other => return Err(format!("不支援的框架: {}", other)),
+    };
+    let write = |path: PathBuf, content: String| -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("建立 {} 失敗: {}", parent.display(), e))?;
+        }
+        write_text_with_strategy(&path, &content, strategy).map_err(|e| format!("寫入 {} 失敗: {}", path.display(), e))
+    };
+
+    let module_title = module_display_name(module_name);
+    let entries = collect_module_page_entries(module_name);
+    let names = scaffold_component_names(&entries);
+    for (e, component) in entries.iter().zip(names.iter()) {
+        let page_title = page_display_name(&e.meta, e.subpage.as_deref().unwrap_or(&e.page));
+        let title = format!("{}/{}", module_title, page_title);
+        let props = e.meta.props.clone().unwrap_or_default();
+        let content = story_file(framework, &title, component, &format!("./{}{}", component, component_ext), &props);
+        write(root.join("pages").join(format!("{}.{}", component, story_ext)), content)?;
+    }
+    let module_component = format!("{}Module", pascal_case_identifier(module_name));
+    let content = story_file(framework, &format!("{}/模組外框", module_title), &module_component, &format!("./{}{}", module_component, component_ext), &[]);
+    write(root.join(format!("{}.{}", module_component, story_ext)), content)?;
+
+    let main = format!(
+        "// 由 ErSlice 生成的 Storybook 設定，可合併至既有的 .storybook/main\n/** @type {{ import('{pkg}').StorybookConfig }} */\nconst config = {{\n  stories: ['../**/*.stories.@(js|jsx|ts|tsx)'],\n  addons: ['@storybook/addon-essentials'],\n  framework: {{\n    name: '{pkg}',\n    options: {{}},\n  }},\n}};\n\nexport default config;\n",
+        pkg = storybook_framework_package(framework)
+    );
+    write(root.join(".storybook").join("main.js"), main)?;
+    // 全域樣式：design tokens 與模組樣式（存在時）
+    let imports: String = ["tokens.css", "styles.css", "fonts.css"]
+        .iter()
+        .filter(|f| output_dir.join(f).exists())
+        .map(|f| format!("import '../../{}';\n", f))
+        .collect();
+    let preview = format!(
+        "// 由 ErSlice 生成\n{}\n/** @type {{ import('{}').Preview }} */\nconst preview = {{\n  parameters: {{\n    layout: 'fullscreen',\n  }},\n}};\n\nexport default preview;\n",
+        imports,
+        storybook_framework_package(framework)
+    );
+    write(root.join(".storybook").join("preview.js"), preview)?;
+    Ok(())
+}
+
+// ====== 路由骨架 ======
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteScaffoldResult {
+    pub framework: String,
+    pub output_dir: String,
+    // 相對 output_dir 的檔案
+    pub files: Vec<String>,
+    pub routes: usize,
+}
+
+// 路由樹節點：模組 → 頁面 → 子頁；path 為完整路徑
+struct RouteNode {
+    path: String,
+    title: String,
+    module: String,
+    component: String,
+    // 佔位元件相對路由根目錄的位置（不含副檔名）
+    file: String,
+    // 模組外框（含子路由出口與頁面連結）
+    layout: bool,
+    children: Vec<RouteNode>,
+}
+
+impl RouteNode {
+    fn count(&self) -> usize {
+        1 + self.children.iter().map(|c| c.count()).sum::<usize>()
+    }
+}
+
+// 頁面完整路徑：page.json 的 route，否則為 /<module>/<page>[/<subpage>]
+fn route_full_path(e: &PageEntry) -> String {
+    match e.meta.route.as_ref().map(|r| r.trim()).filter(|r| !r.is_empty()) {
+        Some(r) if r.starts_with('/') => r.trim_end_matches('/').to_string(),
+        Some(r) => format!("/{}/{}", e.module, r.trim_end_matches('/')),
+        None => format!("/{}", e.route_path().trim_start_matches('/')),
+    }
+}
+
+// 子路由相對父路由的路徑；不在父路由之下時回傳 None
+fn nested_route_path(parent: &str, full: &str) -> Option<String> {
+    full.strip_prefix(parent).and_then(|rest| rest.strip_prefix('/')).filter(|rest| !rest.is_empty()).map(|rest| rest.to_string())
+}
+
+// 依頁面樹建立路由樹；自訂 route 不在上層路徑之下的頁面提升為上一層（或最上層）路由
+fn build_route_tree() -> Vec<RouteNode> {
+    let mut roots: Vec<RouteNode> = Vec::new();
+    for module in list_dir_names(&PathBuf::from("design-assets")) {
+        let entries = collect_module_page_entries(&module);
+        let names = scaffold_component_names(&entries);
+        let module_component = format!("{}Layout", pascal_case_identifier(&module));
+        let mut module_node = RouteNode {
+            path: format!("/{}", module),
+            title: module_display_name(&module),
+            module: module.clone(),
+            component: module_component.clone(),
+            file: format!("{}/{}", module, module_component),
+            layout: true,
+            children: Vec::new(),
+        };
+        let mut hoisted: Vec<RouteNode> = Vec::new();
+        // 頁面代稱 → 元件名稱，供子頁找到其頁面節點
+        let page_components: HashMap<&str, &str> = entries
+            .iter()
+            .zip(names.iter())
+            .filter(|(e, _)| e.subpage.is_none())
+            .map(|(e, c)| (e.page.as_str(), c.as_str()))
+            .collect();
+        for (e, component) in entries.iter().zip(names.iter()) {
+            let node = RouteNode {
+                path: route_full_path(e),
+                title: page_display_name(&e.meta, e.subpage.as_deref().unwrap_or(&e.page)),
+                module: module.clone(),
+                component: component.clone(),
+                file: format!("{}/{}", module, component),
+                layout: false,
+                children: Vec::new(),
+            };
+            if e.subpage.is_some() {
+                let page_component = page_components.get(e.page.as_str()).copied();
+                let parent_page = module_node.children.iter_mut().chain(hoisted.iter_mut()).find(|p| Some(p.component.as_str()) == page_component);
+                if let Some(page) = parent_page.filter(|p| nested_route_path(&p.path, &node.path).is_some()) {
+                    page.children.push(node);
+                    continue;
+                }
+            }
+            if nested_route_path(&module_node.path, &node.path).is_some() {
+                module_node.children.push(node);
+            } else {
+                hoisted.push(node);
+            }
+        }
+        roots.push(module_node);
+        roots.extend(hoisted);
+    }
+    roots
+}
+
+// 路由路徑轉為 JS 字串常值（單引號）
+fn js_string(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+// 路由佔位元件的匯入名稱（頁面加上模組前綴，避免跨模組同名）
+fn route_import_name(node: &RouteNode) -> String {
+    if node.layout {
+        node.component.clone()
+    } else {
+        format!("{}{}", pascal_case_identifier(&node.module), node.component)
+    }
+}
+
+fn collect_route_nodes<'a>(nodes: &'a [RouteNode], out: &mut Vec<&'a RouteNode>) {
+    for n in nodes {
+        out.push(n);
+        collect_route_nodes(&n.children, out);
+    }
+}
+
+// 可直接連結的路徑（不含 :param 或 *）
+fn is_static_route(path: &str) -> bool {
+    !path.contains(':') && !path.contains('*')
+}
+
+// React Router：routes.tsx 的 RouteObject 巢狀結構
+fn react_route_object(node: &RouteNode, parent: Option<&str>, indent: &str, out: &mut String) {
+    let path = parent.and_then(|p| nested_route_path(p, &node.path)).unwrap_or_else(|| node.path.clone());
+    let element = format!("<{} />", route_import_name(node));
+    if node.children.is_empty() && !node.layout {
+        out.push_str(&format!("{}{{ path: {}, element: {} }},\n", indent, js_string(&path), element));
+        return;
+    }
+    let inner = format!("{}    ", indent);
+    if node.layout {
+        out.push_str(&format!("{}{{\n{}  path: {},\n{}  element: {},\n{}  children: [\n", indent, indent, js_string(&path), indent, element, indent));
+    } else {
+        out.push_str(&format!("{}{{\n{}  path: {},\n{}  children: [\n{}{{ index: true, element: {} }},\n", indent, indent, js_string(&path), indent, inner, element));
+    }
+    for child in node.children.iter() {
+        react_route_object(child, Some(&node.path), &inner, out);
+    }
+    out.push_str(&format!("{}  ],\n{}}},\n", indent, indent));
+}
+
+fn react_route_scaffold(tree: &[RouteNode]) -> Vec<(String, String)> {
+    let mut files: Vec<(String, String)> = Vec::new();
+    let mut nodes = Vec::new();
+    collect_route_nodes(tree, &mut nodes);
+    let mut routes = String::from("// 由 ErSlice 依頁面樹生成的 React Router 路由\nimport type { RouteObject } from 'react-router-dom';\n");
+    for n in nodes.iter() {
+        routes.push_str(&format!("import {} from './modules/{}';\n", route_import_name(n), n.file));
+    }
+    routes.push_str("\nexport const routes: RouteObject[] = [\n");
+    for n in tree.iter() {
+        react_route_object(n, None, "  ", &mut routes);
+    }
+    routes.push_str("];\n\nexport default routes;\n");
+    files.push(("routes.tsx".to_string(), routes));
+
+    for n in nodes.iter() {
+        let title = serde_json::to_string(&n.title).unwrap_or_default();
+        let content = if n.layout {
+            let links: String = n.children.iter().filter(|c| is_static_route(&c.path)).map(|c| {
+                format!("          <li><Link to={}>{{{}}}</Link></li>\n", js_string(&c.path), serde_json::to_string(&c.title).unwrap_or_default())
+            }).collect();
+            format!(
+                "// {p} — 由 ErSlice 生成的模組外框\nimport {{ Link, Outlet }} from 'react-router-dom';\n\nexport default function {c}() {{\n  return (\n    <div>\n      <header>\n        <h1>{{{t}}}</h1>\n        <nav>\n          <ul>\n{l}          </ul>\n        </nav>\n      </header>\n      <main>\n        <Outlet />\n      </main>\n    </div>\n  );\n}}\n",
+                p = n.path, c = n.component, t = title, l = links
+            )
+        } else {
+            format!(
+                "// {p} — 由 ErSlice 生成的路由佔位元件\nexport default function {c}() {{\n  return (\n    <section>\n      <h2>{{{t}}}</h2>\n    </section>\n  );\n}}\n",
+                p = n.path, c = n.component, t = title
+            )
+        };
+        files.push((format!("modules/{}.tsx", n.file), content));
+    }
+    files
+}
+
+// Vue Router：router.ts 的 RouteRecordRaw 巢狀結構
+fn vue_route_record(node: &RouteNode, parent: Option<&str>, indent: &str, out: &mut String) {
+    let path = parent.and_then(|p| nested_route_path(p, &node.path)).unwrap_or_else(|| node.path.clone());
+    let component = format!("() => import('./views/{}.vue')", node.file);
+    if node.children.is_empty() {
+        out.push_str(&format!("{}{{ path: {}, component: {} }},\n", indent, js_string(&path), component));
+        return;
+    }
+    let inner = format!("{}    ", indent);
+    if node.layout {
+        out.push_str(&format!("{}{{\n{}  path: {},\n{}  component: {},\n{}  children: [\n", indent, indent, js_string(&path), indent, component, indent));
+    } else {
+        out.push_str(&format!("{}{{\n{}  path: {},\n{}  children: [\n{}{{ path: '', component: {} }},\n", indent, indent, js_string(&path), indent, inner, component));
+    }
+    for child in node.children.iter() {
+        vue_route_record(child, Some(&node.path), &inner, out);
+    }
+    out.push_str(&format!("{}  ],\n{}}},\n", indent, indent));
+}
+
+fn vue_route_scaffold(tree: &[RouteNode]) -> Vec<(String, String)> {
+    let mut files: Vec<(String, String)> = Vec::new();
+    let mut router = String::from("// 由 ErSlice 依頁面樹生成的 Vue Router 路由\nimport type { RouteRecordRaw } from 'vue-router';\n\nexport const routes: RouteRecordRaw[] = [\n");
+    for n in tree.iter() {
+        vue_route_record(n, None, "  ", &mut router);
+    }
+    router.push_str("];\n\nexport default routes;\n");
+    files.push(("router.ts".to_string(), router));
+
+    let mut nodes = Vec::new();
+    collect_route_nodes(tree, &mut nodes);
+    for n in nodes.iter() {
+        let title = escape_html(&n.title);
+        let content = if n.layout {
+            let links: String = n.children.iter().filter(|c| is_static_route(&c.path)).map(|c| {
+                format!("        <li><router-link to=\"{}\">{}</router-link></li>\n", c.path, escape_html(&c.title))
+            }).collect();
+            format!("<!-- {} — 由 ErSlice 生成的模組外框 -->\n<template>\n  <div>\n    <header>\n      <h1>{}</h1>\n      <nav>\n        <ul>\n{}        </ul>\n      </nav>\n    </header>\n    <main>\n      <router-view />\n    </main>\n  </div>\n</template>\n", n.path, title, links)
+        } else {
+            format!("<!-- {} — 由 ErSlice 生成的路由佔位元件 -->\n<template>\n  <section>\n    <h2>{}</h2>\n  </section>\n</template>\n", n.path, title)
+        };
+        files.push((format!("views/{}.vue", n.file), content));
+    }
+    files
+}
+
+// Next.js app 目錄區段：:param → [param]，* → [...slug]
+fn next_route_dir(path: &str) -> String {
+    path.trim_start_matches('/')
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|seg| {
+            if let Some(param) = seg.strip_prefix(':') {
+                format!("[{}]", param.trim_end_matches('?'))
+            } else if seg == "*" {
+                "[...slug]".to_string()
+            } else {
+                seg.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn next_route_scaffold(tree: &[RouteNode]) -> Vec<(String, String)> {
+    let mut files: Vec<(String, String)> = Vec::new();
+    files.push((
+        "app/layout.tsx".to_string(),
+        "// 由 ErSlice 生成的根 layout\nimport type { ReactNode } from 'react';\n\nexport default function RootLayout({ children }: { children: ReactNode }) {\n  return (\n    <html lang=\"zh-Hant\">\n      <body>{children}</body>\n    </html>\n  );\n}\n".to_string(),
+    ));
+    let module_links: String = tree.iter().filter(|n| is_static_route(&n.path)).map(|n| {
+        format!("        <li><Link href={}>{{{}}}</Link></li>\n", js_string(&n.path), serde_json::to_string(&n.title).unwrap_or_default())
+    }).collect();
+    files.push((
+        "app/page.tsx".to_string(),
+        format!("// 由 ErSlice 生成的模組索引\nimport Link from 'next/link';\n\nexport default function Home() {{\n  return (\n    <main>\n      <ul>\n{}      </ul>\n    </main>\n  );\n}}\n", module_links),
+    ));
+
+    let mut nodes = Vec::new();
+    collect_route_nodes(tree, &mut nodes);
+    for n in nodes.iter() {
+        let dir = next_route_dir(&n.path);
+        let title = serde_json::to_string(&n.title).unwrap_or_default();
+        if n.layout {
+            let links: String = n.children.iter().filter(|c| is_static_route(&c.path)).map(|c| {
+                format!("          <li><Link href={}>{{{}}}</Link></li>\n", js_string(&c.path), serde_json::to_string(&c.title).unwrap_or_default())
+            }).collect();
+            files.push((
+                format!("app/{}/layout.tsx", dir),
+                format!(
+                    "// {p} — 由 ErSlice 生成的模組外框\nimport Link from 'next/link';\nimport type {{ ReactNode }} from 'react';\n\nexport default function {c}({{ children }}: {{ children: ReactNode }}) {{\n  return (\n    <div>\n      <header>\n        <h1>{{{t}}}</h1>\n        <nav>\n          <ul>\n{l}          </ul>\n        </nav>\n      </header>\n      <main>{{children}}</main>\n    </div>\n  );\n}}\n",
+                    p = n.path, c = n.component, t = title, l = links
+                ),
+            ));
+            files.push((
+                format!("app/{}/page.tsx", dir),
+                format!("// {} — 由 ErSlice 生成\nexport default function {}Index() {{\n  return null;\n}}\n", n.path, pascal_case_identifier(&n.module)),
+            ));
+        } else {
+            files.push((
+                format!("app/{}/page.tsx", dir),
+                format!(
+                    "// {p} — 由 ErSlice 生成的路由佔位元件\nexport default function {c}() {{\n  return (\n    <section>\n      <h2>{{{t}}}</h2>\n    </section>\n  );\n}}\n",
+                    p = n.path, c = n.component, t = title
+                ),
+            ));
+        }
+    }
+    files
+}
+
+/// 依頁面樹（模組 → 頁面 → 子頁）產生路由骨架：react（React Router）、vue（Vue Router）、next（Next.js app 目錄），
+/// 輸出至 output/routes/<framework>/，各路由皆接上佔位元件
+#[tauri::command]
+pub async fn generate_route_scaffold(framework: String, overwrite_strategy: Option<String>) -> Result<RouteScaffoldResult, String> {
+    let framework = match framework.trim().to_lowercase().as_str() {
+        "react" | "react-router" => "react",
+        "vue" | "vue-router" => "vue",
+        "next" | "nextjs" | "next.js" => "next",
+        other => return Err(format!("不支援的路由框架: {}（可用：react, vue, next）", other)),
+    };
+    let strategy = overwrite_strategy.unwrap_or_else(|| "overwrite".to_string());
+    let tree = build_route_tree();
+    if tree.is_empty() {
+        return Err("尚無任何模組".to_string());
+    }
+    let files = match framework {
+        "react" => react_route_scaffold(&tree),
+        "vue" => vue_route_scaffold(&tree),
+        _ => next_route_scaffold(&tree),
+    };
+    let out_dir = output_root().join("routes").join(framework);
+    for (rel, content) in files.iter() {
+        let path = out_dir.join(rel);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("建立 {} 失敗: {}", parent.display(), e))?;
+        }
+        write_text_with_strategy(&path, content, &strategy).map_err(|e| format!("寫入 {} 失敗: {}", path.display(), e))?;
+    }
+    Ok(RouteScaffoldResult {
+        framework: framework.to_string(),
+        output_dir: out_dir.to_string_lossy().to_string(),
+        files: files.into_iter().map(|(rel, _)| rel).collect(),
+        routes: tree.iter().map(|n| n.count()).sum(),
+    })
+}
+
+// ====== 整包 README 索引 ======
+
+// README 模組索引的一列
+struct UnifiedReadmeRow {
+    name: String,
+    display_name: String,
+    pages: usize,
+    subpages: usize,
+    assets: usize,
+    // analyze_sitemap 的頁面資產完成度（%）
+    completion: Option<f64>,
+    // page.json 狀態分佈，例如「done×2、wip×1」
+    statuses: String,
+    spec: bool,
+    sitemap: bool,
+}
+
+// 由模組資料夾統計頁面、子頁、資產（不含 ErSlice 的 JSON meta）與頁面狀態
+fn unified_readme_row(module_dir: &std::path::Path, name: &str, completion: Option<f64>, spec: bool, sitemap: bool) -> UnifiedReadmeRow {
+    let pages_dir = module_dir.join("pages");
+    let mut pages = 0usize;
+    let mut subpages = 0usize;
+    let mut statuses: std::collections::BTreeMap<String, usize> = std::collections::BTreeMap::new();
+    for page in list_dir_names(&pages_dir) {
+        pages += 1;
+        let page_dir = pages_dir.join(&page);
+        let mut metas = vec![read_page_meta(&page_dir)];
+        for sub in list_dir_names(&page_dir.join("subpages")) {
+            subpages += 1;
+            metas.push(read_page_meta(&page_dir.join("subpages").join(sub)));
+        }
+        for meta in metas {
+            *statuses.entry(meta.status.filter(|s| !s.trim().is_empty()).unwrap_or_else(|| "未設定".to_string())).or_default() += 1;
+        }
+    }
+    let mut files = Vec::new();
+    collect_files_recursive(module_dir, &mut files);
+    let assets = files.iter().filter(|f| f.extension().and_then(|e| e.to_str()) != Some("json")).count();
+    let display_name = read_module_meta(module_dir).display_name.filter(|n| !n.trim().is_empty()).unwrap_or_else(|| name.to_string());
+    UnifiedReadmeRow {
+        name: name.to_string(),
+        display_name,
+        pages,
+        subpages,
+        assets,
+        completion,
+        statuses: statuses.iter().map(|(s, n)| format!("{}×{}", s, n)).collect::<Vec<_>>().join("、"),
+        spec,
+        sitemap,
+    }
+}
+
+// Markdown 表格儲存格：跳脫直線並移除換行
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+fn unified_readme(generated_at: &str, doc_names: &[String], rows: &[UnifiedReadmeRow]) -> String {
+    let mut out = format!("# ErSlice 切版說明包\n\n生成時間：{}　模組數：{}\n\n## 內容\n- 設計資產: [./design-assets](./design-assets/)\n", generated_at, rows.len());
+    if !doc_names.is_empty() {
+        let docs: Vec<String> = doc_names.iter().map(|d| format!("[{}](./ai-docs/{})", d, d)).collect();
+        out.push_str(&format!("- AI 說明文件: {}\n", docs.join(", ")));
+    }
+    out.push_str("- 模組骨架（每模組）: ./modules/<module>/\n");
+    out.push_str("- 頁面骨架（每頁面/子頁）: ./modules/<module>/<page>/[<subpage>/]\n");
+    if !rows.is_empty() {
+        let total_pages: usize = rows.iter().map(|r| r.pages + r.subpages).sum();
+        let total_assets: usize = rows.iter().map(|r| r.assets).sum();
+        out.push_str(&format!("\n## 模組索引\n共 {} 個頁面/子頁、{} 個資產檔案。\n\n| 模組 | 頁面 | 子頁 | 資產 | 完成度 | 頁面狀態 | 連結 |\n|---|---:|---:|---:|---:|---|---|\n", total_pages, total_assets));
+        for r in rows.iter() {
+            let folder = format!("./modules/{}/", r.name);
+            let mut links = vec![format!("[資料夾]({})", folder)];
+            if r.spec {
+                links.push(format!("[AI 說明]({}ai-spec.md)", folder));
+            }
+            if r.sitemap {
+                links.push(format!("[Sitemap]({}sitemap.html)", folder));
+            }
+            out.push_str(&format!(
+                "| [{}]({}) | {} | {} | {} | {} | {} | {} |\n",
+                markdown_cell(&r.display_name),
+                folder,
+                r.pages,
+                r.subpages,
+                r.assets,
+                r.completion.map(|c| format!("{:.0}%", c)).unwrap_or_else(|| "-".to_string()),
+                if r.statuses.is_empty() { "-".to_string() } else { markdown_cell(&r.statuses) },
+                links.join(" · ")
+            ));
+        }
+    }
+    out.push_str("\n此包可直接提供給工程師或 AI 進行切版實作。\n");
+    out
+}
+
+// ====== 依模組實際內容產生的說明章節 ======
+
+// 模組概況：頁面/子頁數、資產數與頁面狀態分佈
+fn module_overview_spec_section(module_name: &str) -> String {
+    let module_dir = PathBuf::from("design-assets").join(module_name);
+    let entries = collect_module_page_entries(module_name);
+    let mut counts: std::collections::BTreeMap<&str, usize> = std::collections::BTreeMap::new();
+    for level in module_asset_levels(&module_dir) {
+        for category in ["screenshots", "html", "css", "fonts"] {
+            *counts.entry(category).or_default() += get_files_in_dir(&level.join(category)).len();
+        }
+    }
+    if entries.is_empty() && counts.values().all(|n| *n == 0) {
+        return String::new();
+    }
+    let lang = project_spec_language();
+    let subpages = entries.iter().filter(|e| e.subpage.is_some()).count();
+    let mut out = fill_spec_text(
+        lang.pick(
+            "\n## 模組概況\n- 頁面：{} 個（子頁 {} 個）\n- 設計稿截圖：{} 張\n- HTML：{} 個、CSS：{} 個、字型：{} 個\n",
+            "\n## Module Overview\n- Pages: {} ({} subpages)\n- Design screenshots: {}\n- HTML: {}, CSS: {}, fonts: {}\n",
+            "\n## モジュール概要\n- ページ：{} 件（サブページ {} 件）\n- デザインスクリーンショット：{} 枚\n- HTML：{} 件、CSS：{} 件、フォント：{} 件\n",
+        ),
+        &[
+            (entries.len() - subpages).to_string(),
+            subpages.to_string(),
+            counts["screenshots"].to_string(),
+            counts["html"].to_string(),
+            counts["css"].to_string(),
+            counts["fonts"].to_string(),
+        ],
+    );
+    let mut statuses: std::collections::BTreeMap<String, usize> = std::collections::BTreeMap::new();
+    for e in entries.iter() {
+        *statuses.entry(e.meta.status.clone().filter(|s| !s.trim().is_empty()).unwrap_or_else(|| lang.pick("未設定", "unset", "未設定").to_string())).or_default() += 1;
+    }
+    if !statuses.is_empty() {
+        let list: Vec<String> = statuses.iter().map(|(s, n)| format!("{} {}", s, n)).collect();
+        out.push_str(&format!("{}{}\n", lang.pick("- 頁面狀態：", "- Page status: ", "- ページステータス："), list.join(lang.list_separator())));
+    }
+    out
+}
+
+// 頁面樹：頁面 → 子頁，附路由、狀態、備註與 page.json 中的連結
+fn page_tree_spec_section(module_name: &str) -> String {
+    let entries = collect_module_page_entries(module_name);
+    if entries.is_empty() {
+        return String::new();
+    }
+    let lang = project_spec_language();
+    let mut out = String::from(lang.pick("\n## 頁面樹\n", "\n## Page Tree\n", "\n## ページツリー\n"));
+    for e in entries.iter() {
+        let indent = if e.subpage.is_some() { "  " } else { "" };
+        let slug = e.subpage.as_deref().unwrap_or(&e.page);
+        let mut line = indent.to_string();
+        line.push_str(&fill_spec_text(
+            lang.pick("- **{}**（`{}`）— 路由 `{}`", "- **{}** (`{}`) — route `{}`", "- **{}**（`{}`）— ルート `{}`"),
+            &[page_display_name(&e.meta, slug), slug.to_string(), e.meta.route.clone().filter(|r| !r.trim().is_empty()).unwrap_or_else(|| e.route_path())],
+        ));
+        if let Some(status) = e.meta.status.as_ref().filter(|s| !s.trim().is_empty()) {
+            line.push_str(&format!("{}{}", lang.pick("，狀態：", ", status: ", "、ステータス："), status));
+        }
+        if let Some(action) = e.meta.action.as_ref().filter(|a| !a.trim().is_empty()) {
+            line.push_str(&format!("{}{}", lang.pick("，動作：", ", action: ", "、アクション："), action));
+        }
+        out.push_str(&line);
+        out.push('\n');
+        if let Some(notes) = e.meta.notes.as_ref().filter(|n| !n.trim().is_empty()) {
+            out.push_str(&format!("{}  {}{}\n", indent, lang.pick("- 備註：", "- Notes: ", "- 備考："), notes.trim().replace('\n', " ")));
+        }
+        let links: Vec<String> = e.meta.links.iter().flatten().map(|l| match &l.label {
+            Some(label) if !label.trim().is_empty() => format!("{} → `{}`", label, l.to),
+            _ => format!("`{}`", l.to),
+        }).collect();
+        if !links.is_empty() {
+            out.push_str(&format!("{}  {}{}\n", indent, lang.pick("- 連結：", "- Links: ", "- リンク："), links.join(lang.list_separator())));
+        }
+    }
+    out
+}
+
+// 設計稿截圖清單（已在「畫面流程」排序的位置除外）
+fn screenshots_spec_section(module_name: &str) -> String {
+    let module_dir = PathBuf::from("design-assets").join(module_name);
+    let mut out = String::new();
+    for level in module_asset_levels(&module_dir) {
+        if read_asset_order(&level).contains_key("screenshots") { continue; }
+        let shots_dir = level.join("screenshots");
+        let mut files: Vec<String> = get_files_in_dir(&shots_dir).into_iter().filter(|f| is_image_file(&shots_dir.join(f))).collect();
+        if files.is_empty() { continue; }
+        files.sort();
+        let rel = level.strip_prefix(&module_dir).unwrap_or(&level).to_string_lossy().replace('\\', "/");
+        for f in files.iter() {
+            let path = if rel.is_empty() { format!("screenshots/{}", f) } else { format!("{}/screenshots/{}", rel, f) };
+            out.push_str(&format!("- `{}`\n", path));
+        }
+    }
+    if out.is_empty() {
+        return String::new();
+    }
+    let head = project_spec_language().pick(
+        "\n## 設計稿截圖\n實作時請逐一對照下列設計稿：\n\n",
+        "\n## Design Screenshots\nCompare the implementation against each of the following designs:\n\n",
+        "\n## デザインスクリーンショット\n実装時は以下のデザインと一つずつ照合してください：\n\n",
+    );
+    format!("{}{}", head, out)
+}
+
+// ====== 機器可讀規格（spec.json） ======
+
+const SPEC_JSON_FILE: &str = "spec.json";
+// spec.json 結構有不相容變更時遞增
+const SPEC_SCHEMA_VERSION: u32 = 1;
+
+// 不含生成時間，內容只隨設計變動（生成時間記錄在生成紀錄 manifest）
+#[derive(Debug, Serialize)]
+struct ModuleSpec {
+    schema_version: u32,
+    module: String,
+    display_name: String,
+    // 頁面狀態 → 頁面數
+    statuses: std::collections::BTreeMap<String, usize>,
+    assets: SpecAssets,
+    pages: Vec<SpecPage>,
+}
+
+// 相對模組根目錄的資產路徑
+#[derive(Debug, Serialize, Default)]
+struct SpecAssets {
+    screenshots: Vec<String>,
+    html: Vec<String>,
+    css: Vec<String>,
+    fonts: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct SpecFormField {
+    name: String,
+    #[serde(rename = "type")]
+    field_type: String,
+    required: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct SpecPage {
+    slug: String,
+    parent: Option<String>,
+    title: String,
+    route: String,
+    status: Option<String>,
+    page_type: String,
+    // 框架骨架使用的元件名稱
+    component: String,
+    // 頁面 HTML 中的互動元件與 page.json 的 component
+    required_components: Vec<String>,
+    form_fields: Vec<SpecFormField>,
+    props: Vec<ComponentProp>,
+    links: Vec<LinkMeta>,
+    screenshots: Vec<String>,
+    feature_flag: Option<String>,
+}
+
+fn module_spec(module_name: &str) -> ModuleSpec {
+    let module_dir = PathBuf::from("design-assets").join(module_name);
+    let mut assets = SpecAssets::default();
+    for level in module_asset_levels(&module_dir) {
+        let rel = level.strip_prefix(&module_dir).unwrap_or(&level).to_string_lossy().replace('\\', "/");
+        for (category, list) in [("screenshots", &mut assets.screenshots), ("html", &mut assets.html), ("css", &mut assets.css), ("fonts", &mut assets.fonts)] {
+            let mut files = get_files_in_dir(&level.join(category));
+            files.sort();
+            list.extend(files.into_iter().map(|f| if rel.is_empty() { format!("{}/{}", category, f) } else { format!("{}/{}/{}", rel, category, f) }));
+        }
+    }
+
+    let entries = collect_module_page_entries(module_name);
+    let names = scaffold_component_names(&entries);
+    let mut statuses: std::collections::BTreeMap<String, usize> = std::collections::BTreeMap::new();
+    let pages: Vec<SpecPage> = entries
+        .iter()
+        .zip(names.iter())
+        .map(|(e, component)| {
+            let slug = e.subpage.clone().unwrap_or_else(|| e.page.clone());
+            let dir = page_entry_dir(e);
+            let status = e.meta.status.clone().filter(|s| !s.trim().is_empty());
+            *statuses.entry(status.clone().unwrap_or_else(|| "unset".to_string())).or_default() += 1;
+            let mut required_components = page_component_inventory(&dir, &e.meta);
+            let mut seen = std::collections::HashSet::new();
+            required_components.retain(|c| seen.insert(c.clone()));
+            SpecPage {
+                title: page_display_name(&e.meta, &slug),
+                route: e.meta.route.clone().filter(|r| !r.trim().is_empty()).unwrap_or_else(|| e.route_path()),
+                status,
+                page_type: detect_page_type(&slug, &e.meta),
+                component: component.clone(),
+                required_components,
+                form_fields: page_form_fields(&dir)
+                    .into_iter()
+                    .map(|f| SpecFormField { name: f.name, field_type: f.field_type, required: f.required })
+                    .collect(),
+                props: e.meta.props.clone().unwrap_or_default(),
+                links: e.meta.links.clone().unwrap_or_default(),
+                screenshots: page_screenshot_refs(e),
+                feature_flag: e.meta.feature_flag.clone(),
+                parent: e.subpage.as_ref().map(|_| e.page.clone()),
+                slug,
+            }
+        })
+        .collect();
+
+    ModuleSpec {
+        schema_version: SPEC_SCHEMA_VERSION,
+        module: module_name.to_string(),
+        display_name: module_display_name(module_name),
+        statuses,
+        assets,
+        pages,
+    }
+}
+
+// ====== 說明文件語言 ======
+
+// project.json 的 spec_language 可用值；未設定時為 zh-TW
+const SPEC_LANGUAGES: [&str; 3] = ["zh-TW", "en", "ja"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpecLanguage {
+    ZhTw,
+    En,
+    Ja,
+}
+
+impl SpecLanguage {
+    fn parse(code: &str) -> Option<Self> {
+        match code.trim().to_ascii_lowercase().as_str() {
+            "zh-tw" | "zh" => Some(Self::ZhTw),
+            "en" => Some(Self::En),
+            "ja" => Some(Self::Ja),
+            _ => None,
+        }
+    }
+
+    fn pick<'a>(self, zh: &'a str, en: &'a str, ja: &'a str) -> &'a str {
+        match self {
+            Self::ZhTw => zh,
+            Self::En => en,
+            Self::Ja => ja,
+        }
+    }
+
+    fn list_separator(self) -> &'static str {
+        self.pick("、", ", ", "、")
+    }
+
+    // 非預設語言改用 <name>.<lang>.md.hbs（例如 ai-spec.en.md.hbs），可同樣由模組/專案/工作區覆寫
+    fn template_name(self, name: &str) -> String {
+        let suffix = self.pick("", "en", "ja");
+        match name.split_once('.') {
+            Some((stem, ext)) if !suffix.is_empty() => format!("{}.{}.{}", stem, suffix, ext),
+            _ => name.to_string(),
+        }
+    }
+}
+
+fn project_spec_language() -> SpecLanguage {
+    read_active_project_config()
+        .and_then(|c| c.spec_language)
+        .and_then(|l| SpecLanguage::parse(&l))
+        .unwrap_or(SpecLanguage::ZhTw)
+}
+
+// 依序以 args 替換範本中的 {}（各語言的語序不同，不能直接拼接）
+fn fill_spec_text(template: &str, args: &[String]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    let mut args = args.iter();
+    while let Some(pos) = rest.find("{}") {
+        out.push_str(&rest[..pos]);
+        out.push_str(args.next().map(|a| a.as_str()).unwrap_or(""));
+        rest = &rest[pos + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+// ====== 框架專案骨架（package.json / Vite 入口） ======
+
+// 各框架可直接 npm i && npm run dev 的最小依賴
+fn framework_project_dependencies(framework: &str) -> (serde_json::Value, serde_json::Value) {
+    match framework {
+        "vue" => (
+            serde_json::json!({ "vue": "^3.5.12", "vue-router": "^4.4.5" }),
+            serde_json::json!({ "@vitejs/plugin-vue": "^5.1.4", "typescript": "^5.6.3", "vite": "^5.4.10" }),
+        ),
+        "svelte" => (
+            serde_json::json!({}),
+            serde_json::json!({ "@sveltejs/vite-plugin-svelte": "^3.1.2", "svelte": "^4.2.19", "typescript": "^5.6.3", "vite": "^5.4.10" }),
+        ),
+        _ => (
+            serde_json::json!({ "react": "^18.3.1", "react-dom": "^18.3.1", "react-router-dom": "^6.27.0" }),
+            serde_json::json!({
+                "@types/react": "^18.3.12",
+                "@types/react-dom": "^18.3.1",
+                "@vitejs/plugin-react": "^4.3.3",
+                "typescript": "^5.6.3",
+                "vite": "^5.4.10"
+            }),
+        ),
+    }
+}
+
+fn framework_package_json(module_name: &str, framework: &str, storybook: bool) -> String {
+    let (dependencies, mut dev_dependencies) = framework_project_dependencies(framework);
+    let mut scripts = serde_json::json!({ "dev": "vite", "build": "vite build", "preview": "vite preview" });
+    if storybook {
+        scripts["storybook"] = "storybook dev -p 6006".into();
+        scripts["build-storybook"] = "storybook build".into();
+        for pkg in ["storybook", "@storybook/addon-essentials", storybook_framework_package(framework)] {
+            dev_dependencies[pkg] = "^8.3.6".into();
+        }
+        if framework == "react" {
+            dev_dependencies["@storybook/react"] = "^8.3.6".into();
+        }
+    }
+    let value = serde_json::json!({
+        "name": npm_package_name(&format!("{}-{}", module_name, framework)),
+        "version": "0.1.0",
+        "private": true,
+        "type": "module",
+        "scripts": scripts,
+        "dependencies": dependencies,
+        "devDependencies": dev_dependencies,
+    });
+    serde_json::to_string_pretty(&value).unwrap_or_default() + "\n"
+}
+
+fn framework_tsconfig(framework: &str) -> String {
+    let mut options = serde_json::json!({
+        "target": "ES2020",
+        "lib": ["ES2020", "DOM", "DOM.Iterable"],
+        "module": "ESNext",
+        "moduleResolution": "bundler",
+        "strict": true,
+        "skipLibCheck": true,
+        "isolatedModules": true,
+        "noEmit": true
+    });
+    let include = match framework {
+        "react" => {
+            options["jsx"] = "react-jsx".into();
+            serde_json::json!(["**/*.ts", "**/*.tsx"])
+        }
+        "vue" => serde_json::json!(["**/*.ts", "**/*.vue"]),
+        _ => serde_json::json!(["**/*.ts", "**/*.js", "**/*.svelte"]),
+    };
+    let value = serde_json::json!({ "compilerOptions": options, "include": include, "exclude": ["node_modules", "dist"] });
+    serde_json::to_string_pretty(&value).unwrap_or_default() + "\n"
+}
+
+// Vite 入口頁：導覽列樣式供開發入口列出所有頁面
+fn framework_index_html(module_name: &str, mount_id: &str, entry: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"zh-TW\">\n<head>\n    <meta charset=\"UTF-8\">\n    <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\n    <title>{}</title>\n    <style>\n        .dev-nav {{ display: flex; flex-wrap: wrap; gap: 0.5rem 1rem; padding: 0.75rem 1rem; border-bottom: 1px solid #e5e7eb; font-family: system-ui, sans-serif; font-size: 0.875rem; }}\n        .dev-nav a[aria-current=\"page\"] {{ font-weight: 600; }}\n    </style>\n</head>\n<body>\n    <div id=\"{}\"></div>\n    <script type=\"module\" src=\"/{}\"></script>\n</body>\n</html>\n",
+        escape_html(&module_display_name(module_name)),
+        mount_id,
+        entry
+    )
+}
+
+// 開發入口列出的頁面：(完整路由, 顯示名稱, 元件名稱)
+fn framework_project_pages(module_name: &str) -> Vec<(String, String, String)> {
+    let entries = collect_module_page_entries(module_name);
+    let names = scaffold_component_names(&entries);
+    entries
+        .iter()
+        .zip(names)
+        .map(|(e, component)| (route_full_path(e), page_display_name(&e.meta, e.subpage.as_deref().unwrap_or(&e.page)), component))
+        .collect()
+}
+
+fn react_project_main(module_name: &str, module_component: &str, pages: &[(String, String, String)]) -> String {
+    let mut imports = vec![module_component.to_string()];
+    imports.extend(pages.iter().map(|(_, _, c)| c.clone()));
+    let list: Vec<String> = pages.iter().map(|(path, title, _)| format!("  {{ path: {}, title: {} }},\n", js_string(path), js_string(title))).collect();
+    let mut routes = match pages.first() {
+        Some((first, _, _)) => format!("        <Route path=\"/\" element={{<Navigate to={} replace />}} />\n", js_string(first).replace('\'', "\"")),
+        None => format!("        <Route path=\"/\" element={{<{} />}} />\n", module_component),
+    };
+    for (path, _, component) in pages.iter() {
+        routes.push_str(&format!("        <Route path={} element={{<{m}><{c} /></{m}>}} />\n", js_string(path).replace('\'', "\""), m = module_component, c = component));
+    }
+    format!(
+        "// {m} 開發入口 — 由 ErSlice 生成，npm run dev 後可由導覽列切換所有頁面\nimport {{ StrictMode }} from 'react';\nimport {{ createRoot }} from 'react-dom/client';\nimport {{ BrowserRouter, NavLink, Navigate, Route, Routes }} from 'react-router-dom';\nimport {{ {i} }} from './index';\n\nconst pages = [\n{l}];\n\nfunction App() {{\n  return (\n    <>\n      <nav className=\"dev-nav\">\n        {{pages.map((p) => (\n          <NavLink key={{p.path}} to={{p.path}}>\n            {{p.title}}\n          </NavLink>\n        ))}}\n      </nav>\n      <Routes>\n{r}      </Routes>\n    </>\n  );\n}}\n\ncreateRoot(document.getElementById('root')!).render(\n  <StrictMode>\n    <BrowserRouter>\n      <App />\n    </BrowserRouter>\n  </StrictMode>,\n);\n",
+        m = module_name, i = imports.join(", "), l = list.concat(), r = routes
+    )
+}
+
+fn vue_project_files(module_name: &str, pages: &[(String, String, String)]) -> (String, String) {
+    let home = pages.first().map(|(p, _, _)| p.clone()).unwrap_or_else(|| format!("/{}", module_name));
+    let main = format!(
+        "// {} 開發入口 — 由 ErSlice 生成\nimport {{ createApp }} from 'vue';\nimport {{ createRouter, createWebHistory }} from 'vue-router';\nimport App from './App.vue';\nimport {{ routes }} from './router';\n\nconst router = createRouter({{\n  history: createWebHistory(),\n  routes: [{{ path: '/', redirect: {} }}, ...routes],\n}});\n\ncreateApp(App).use(router).mount('#app');\n",
+        module_name, js_string(&home)
+    );
+    let list: Vec<String> = pages.iter().map(|(path, title, _)| format!("  {{ path: {}, title: {} }},\n", js_string(path), js_string(title))).collect();
+    let app = format!(
+        "<!-- {} 開發入口 — 由 ErSlice 生成，導覽列列出所有頁面 -->\n<script setup lang=\"ts\">\nconst pages = [\n{}];\n</script>\n\n<template>\n  <nav class=\"dev-nav\">\n    <router-link v-for=\"p in pages\" :key=\"p.path\" :to=\"p.path\">{{{{ p.title }}}}</router-link>\n  </nav>\n  <router-view />\n</template>\n",
+        module_name, list.concat()
+    );
+    (main, app)
+}
+
+// Svelte 骨架沒有路由套件，開發入口以 hash 切換頁面
+fn svelte_project_app(module_name: &str, module_component: &str, pages: &[(String, String, String)]) -> String {
+    let mut imports = format!("  import {c} from './{c}.svelte';\n", c = module_component);
+    let mut list = String::new();
+    for (path, title, component) in pages.iter() {
+        imports.push_str(&format!("  import {c} from './pages/{c}.svelte';\n", c = component));
+        list.push_str(&format!("    {{ path: {}, title: {}, component: {} }},\n", js_string(path), js_string(title), component));
+    }
+    format!(
+        "<!-- {m} 開發入口 — 由 ErSlice 生成，以 #/路由 切換頁面 -->\n<script>\n{i}\n  const pages = [\n{l}  ];\n  const current = () => location.hash.slice(1) || (pages[0] ? pages[0].path : '/');\n  let path = current();\n  $: page = pages.find((p) => p.path === path);\n</script>\n\n<svelte:window on:hashchange={{() => (path = current())}} />\n\n<nav class=\"dev-nav\">\n  {{#each pages as p}}\n    <a href={{'#' + p.path}} aria-current={{p.path === path ? 'page' : undefined}}>{{p.title}}</a>\n  {{/each}}\n</nav>\n<{c}>\n  {{#if page}}\n    <svelte:component this={{page.component}} />\n  {{/if}}\n</{c}>\n",
+        m = module_name, i = imports, l = list, c = module_component
+    )
+}
+
+// 在 <framework>/ 輸出可直接執行的 Vite 專案：package.json、vite 設定、tsconfig、index.html 與列出所有頁面的開發入口
+fn generate_framework_project(module_name: &str, output_dir: &std::path::Path, framework: &str, storybook: bool, strategy: &str) -> Result<(), String> {
+    let root = output_dir.join(framework);
+    std::fs::create_dir_all(&root).map_err(|e| format!("建立 {} 目錄失敗: {}", framework, e))?;
+    let write = |name: &str, content: String| -> Result<(), String> {
+        let path = root.join(name);
+        write_text_with_strategy(&path, &content, strategy).map_err(|e| format!("寫入 {} 失敗: {}", path.display(), e))
+    };
+    let module_component = format!("{}Module", pascal_case_identifier(module_name));
+    let pages = framework_project_pages(module_name);
+    write("package.json", framework_package_json(module_name, framework, storybook))?;
+    write("tsconfig.json", framework_tsconfig(framework))?;
+    match framework {
+        "react" => {
+            write("vite.config.ts", "import { defineConfig } from 'vite';\nimport react from '@vitejs/plugin-react';\n\nexport default defineConfig({\n  plugins: [react()],\n});\n".to_string())?;
+            write("index.html", framework_index_html(module_name, "root", "main.tsx"))?;
+            write("main.tsx", react_project_main(module_name, &module_component, &pages))?;
+        }
+        "vue" => {
+            write("vite.config.ts", "import { defineConfig } from 'vite';\nimport vue from '@vitejs/plugin-vue';\n\nexport default defineConfig({\n  plugins: [vue()],\n});\n".to_string())?;
+            write("index.html", framework_index_html(module_name, "app", "main.ts"))?;
+            let (main, app) = vue_project_files(module_name, &pages);
+            write("main.ts", main)?;
+            write("App.vue", app)?;
+            write(
+                "env.d.ts",
+                "/// <reference types=\"vite/client\" />\n\ndeclare module '*.vue' {\n  import type { DefineComponent } from 'vue';\n  const component: DefineComponent;\n  export default component;\n}\n".to_string(),
+            )?;
+        }
+        "svelte" => {
+            write("vite.config.js", "import { defineConfig } from 'vite';\nimport { svelte } from '@sveltejs/vite-plugin-svelte';\n\nexport default defineConfig({\n  plugins: [svelte()],\n});\n".to_string())?;
+            write("svelte.config.js", "import { vitePreprocess } from '@sveltejs/vite-plugin-svelte';\n\nexport default {\n  preprocess: vitePreprocess(),\n};\n".to_string())?;
+            write("index.html", framework_index_html(module_name, "app", "main.js"))?;
+            write("main.js", "import App from './App.svelte';\n\nexport default new App({ target: document.getElementById('app') });\n".to_string())?;
+            write("App.svelte", svelte_project_app(module_name, &module_component, &pages))?;
+        }
+        other => return Err(format!("不支援的框架: {}", other)),
+    }
+    Ok(())
+}
+
+// ====== Markdown 合併策略 ======
+
+// "merge" 策略：生成內容置於 managed 標記之間，重新生成時只替換標記內的內容。
+// 標記外的內容，以及標記內以 <!-- user --> … <!-- /user --> 包住的段落（跟隨其前一個標題）會保留
+const MERGE_MANAGED_START: &str = "<!-- erslice:managed-start -->";
+const MERGE_MANAGED_END: &str = "<!-- erslice:managed-end -->";
+const MERGE_USER_START: &str = "<!-- user -->";
+const MERGE_USER_END: &str = "<!-- /user -->";
+
+fn is_mergeable_markdown(path: &std::path::Path) -> bool {
+    matches!(path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()).as_deref(), Some("md" | "markdown"))
+}
+
+fn wrap_managed_markdown(content: &str) -> String {
+    let newline = if content.ends_with('\n') { "" } else { "\n" };
+    format!("{}\n{}{}{}\n", MERGE_MANAGED_START, content, newline, MERGE_MANAGED_END)
+}
+
+// 逐行走訪 Markdown，回傳 (行, 是否為標題)；程式碼區塊內以 # 開頭的行不視為標題
+fn markdown_lines_with_headings(content: &str) -> Vec<(&str, bool)> {
+    let mut in_fence = false;
+    content
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+                return (line, false);
+            }
+            (line, !in_fence && trimmed.starts_with('#'))
+        })
+        .collect()
+}
+
+// 取出使用者段落與其前一個標題（在第一個標題之前者為 None）
+fn markdown_user_blocks(managed: &str) -> Vec<(Option<String>, String)> {
+    let mut blocks: Vec<(Option<String>, String)> = Vec::new();
+    let mut heading: Option<String> = None;
+    let mut current: Option<String> = None;
+    for (line, is_heading) in markdown_lines_with_headings(managed) {
+        if let Some(block) = current.as_mut() {
+            block.push_str(line);
+            block.push('\n');
+            if line.trim() == MERGE_USER_END {
+                blocks.push((heading.clone(), current.take().unwrap_or_default()));
+            }
+            continue;
+        }
+        if line.trim() == MERGE_USER_START {
+            current = Some(format!("{}\n", line));
+        } else if is_heading {
+            heading = Some(line.trim().to_string());
+        }
+    }
+    // 缺少結束標記的段落仍保留，避免內容遺失
+    if let Some(block) = current {
+        blocks.push((heading, format!("{}{}\n", block, MERGE_USER_END)));
+    }
+    blocks
+}
+
+// 以新生成的內容取代既有檔案的 managed 區段；既有檔案沒有標記時回傳 None
+fn merge_markdown(existing: &str, generated: &str) -> Option<String> {
+    let start = existing.find(MERGE_MANAGED_START)?;
+    let end = existing[start..].find(MERGE_MANAGED_END).map(|e| start + e)?;
+    let before = &existing[..start];
+    let after = &existing[end + MERGE_MANAGED_END.len()..];
+    let mut blocks = markdown_user_blocks(&existing[start + MERGE_MANAGED_START.len()..end]);
+
+    // 將屬於 heading 的使用者段落放回該章節末尾（下一個標題之前）
+    let mut flush = |heading: &Option<String>, out: &mut String| {
+        let (matched, rest): (Vec<_>, Vec<_>) = std::mem::take(&mut blocks).into_iter().partition(|(h, _)| h == heading);
+        blocks = rest;
+        for (_, block) in matched {
+            out.push_str(&block);
+            out.push('\n');
+        }
+    };
+    let mut managed = String::new();
+    let mut heading: Option<String> = None;
+    for (line, is_heading) in markdown_lines_with_headings(generated) {
+        if is_heading {
+            flush(&heading, &mut managed);
+            heading = Some(line.trim().to_string());
+        }
+        managed.push_str(line);
+        managed.push('\n');
+    }
+    flush(&heading, &mut managed);
+    // 原章節已不存在的段落附加於最後
+    for (_, block) in std::mem::take(&mut blocks) {
+        managed.push_str(&block);
+    }
+    Some(format!("{}{}{}", before, wrap_managed_markdown(&managed).trim_end_matches('\n'), after))
+}
+
+// ====== 輸出包校驗碼 ======
+
+// 與 `sha256sum -c` 相容的校驗碼清單，位於輸出包根目錄
+const PACKAGE_CHECKSUMS_FILE: &str = "checksums.txt";
+
+// 列出輸出包內所有檔案的 SHA-256（相對路徑排序），清單本身除外
+fn write_package_checksums(package_dir: &std::path::Path) -> Result<(), String> {
+    let mut files = Vec::new();
+    collect_output_files(package_dir, &mut files);
+    files.sort();
+    let content = package_checksums_listing(package_dir, &files)?;
+    std::fs::write(package_dir.join(PACKAGE_CHECKSUMS_FILE), content).map_err(|e| format!("寫入校驗碼清單失敗: {}", e))
+}
+
+// 指定檔案（已排序）的校驗碼清單內容，路徑相對於 package_dir
+fn package_checksums_listing(package_dir: &std::path::Path, files: &[PathBuf]) -> Result<String, String> {
+    let mut lines: Vec<String> = Vec::new();
+    for f in files.iter() {
+        let rel = f.strip_prefix(package_dir).unwrap_or(f).to_string_lossy().replace('\\', "/");
+        if rel == PACKAGE_CHECKSUMS_FILE { continue; }
+        lines.push(format!("{}  {}", sha256_file(f)?, rel));
+    }
+    let mut content = lines.join("\n");
+    content.push('\n');
+    Ok(content)
+}
+
+#[derive(Debug, Serialize)]
+pub struct PackageVerification {
+    pub ok: bool,
+    pub package_dir: String,
+    pub verified: usize,
+    // 內容與校驗碼不符
+    pub mismatched: Vec<String>,
+    // 清單中有但包內沒有
+    pub missing: Vec<String>,
+    // 包內有但不在清單中
+    pub unexpected: Vec<String>,
+}
+
+// 將 zip / tar.gz 解壓至暫存目錄，回傳解壓目錄
+fn extract_package_archive(archive: &std::path::Path) -> Result<PathBuf, String> {
+    let name = archive.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+    let dest = std::env::temp_dir().join(format!("erslice-verify-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dest).map_err(|e| format!("建立暫存目錄失敗: {}", e))?;
+    let file = std::fs::File::open(archive).map_err(|e| format!("開啟封存檔失敗: {}", e))?;
+    if name.ends_with(".zip") {
+        let mut zip = zip::ZipArchive::new(file).map_err(|e| format!("讀取 zip 失敗: {}", e))?;
+        zip.extract(&dest).map_err(|e| format!("解壓 zip 失敗: {}", e))?;
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        tar::Archive::new(flate2::read::GzDecoder::new(file)).unpack(&dest).map_err(|e| format!("解壓 tar.gz 失敗: {}", e))?;
+    } else {
+        return Err(format!("不支援的封存格式: {}", archive.display()));
+    }
+    Ok(dest)
+}
+
+// 找出含校驗碼清單的輸出包目錄：本身或唯一的子目錄（封存檔以資料夾名稱為根目錄）
+fn find_checksummed_package(dir: &std::path::Path) -> Option<PathBuf> {
+    if dir.join(PACKAGE_CHECKSUMS_FILE).is_file() {
+        return Some(dir.to_path_buf());
+    }
+    let subdirs: Vec<PathBuf> = std::fs::read_dir(dir).ok()?.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect();
+    match subdirs.as_slice() {
+        [only] if only.join(PACKAGE_CHECKSUMS_FILE).is_file() => Some(only.clone()),
+        _ => None,
+    }
+}
+
+fn verify_checksummed_package(package_dir: &std::path::Path) -> Result<PackageVerification, String> {
+    let listing = std::fs::read_to_string(package_dir.join(PACKAGE_CHECKSUMS_FILE)).map_err(|e| format!("讀取校驗碼清單失敗: {}", e))?;
+    let mut expected: std::collections::BTreeMap<String, String> = std::collections::BTreeMap::new();
+    for line in listing.lines().filter(|l| !l.trim().is_empty()) {
+        // sha256sum 的二進位模式以 " *" 分隔
+        let Some((hash, rel)) = line.split_once("  ").or_else(|| line.split_once(" *")) else {
+            return Err(format!("校驗碼清單格式錯誤: {}", line));
+        };
+        let rel_path = std::path::Path::new(rel);
+        if rel_path.is_absolute() || rel.starts_with(['/', '\\']) || rel.split(['/', '\\']).any(|seg| seg == "..") {
+            return Err(format!("校驗碼清單含不安全的路徑: {}", rel));
+        }
+        expected.insert(rel.to_string(), hash.trim().to_lowercase());
+    }
+    let mut result = PackageVerification {
+        ok: false,
+        package_dir: package_dir.to_string_lossy().to_string(),
+        verified: 0,
+        mismatched: Vec::new(),
+        missing: Vec::new(),
+        unexpected: Vec::new(),
+    };
+    for (rel, hash) in expected.iter() {
+        match sha256_file(&package_dir.join(rel)) {
+            Ok(actual) if &actual == hash => result.verified += 1,
+            Ok(_) => result.mismatched.push(rel.clone()),
+            Err(_) => result.missing.push(rel.clone()),
+        }
+    }
+    // 不套用任何忽略規則，隱藏檔或 node_modules 等被加入的檔案也要回報
+    let mut files = Vec::new();
+    collect_all_files(package_dir, &mut files);
+    result.unexpected = files
+        .iter()
+        .map(|f| f.strip_prefix(package_dir).unwrap_or(f).to_string_lossy().replace('\\', "/"))
+        .filter(|rel| rel != PACKAGE_CHECKSUMS_FILE && !expected.contains_key(rel))
+        .collect();
+    result.unexpected.sort();
+    result.ok = result.mismatched.is_empty() && result.missing.is_empty() && result.unexpected.is_empty();
+    Ok(result)
+}
+
+/// 依輸出包內的 checksums.txt 驗證檔案完整性；path 可為輸出包資料夾或其 zip / tar.gz 封存檔
+#[tauri::command]
+pub async fn verify_package(path: String) -> Result<PackageVerification, String> {
+    let target = PathBuf::from(&path);
+    if target.is_dir() {
+        let package_dir = find_checksummed_package(&target).ok_or_else(|| format!("找不到校驗碼清單: {}", path))?;
+        return verify_checksummed_package(&package_dir);
+    }
+    if !target.is_file() {
+        return Err(format!("路徑不存在: {}", path));
+    }
+    let extracted = extract_package_archive(&target)?;
+    let result = find_checksummed_package(&extracted)
+        .ok_or_else(|| format!("封存檔內找不到校驗碼清單: {}", path))
+        .and_then(|dir| verify_checksummed_package(&dir))
+        .map(|mut r| {
+            r.package_dir = path.clone();
+            r
+        });
+    let _ = std::fs::remove_dir_all(&extracted);
+    result
+}
+
+// ====== 輸出格式（壓縮 / 排版） ======
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormatting {
+    // 模板原樣輸出
+    Default,
+    Minify,
+    Pretty,
+}
+
+// 解析輸出格式；未指定時使用專案設定的 output_formatting
+fn resolve_output_formatting(value: Option<&str>) -> Result<OutputFormatting, String> {
+    let value = value
+        .map(|v| v.to_string())
+        .or_else(|| read_active_project_config().and_then(|c| c.output_formatting))
+        .map(|v| v.trim().to_lowercase())
+        .unwrap_or_default();
+    match value.as_str() {
+        "" | "default" => Ok(OutputFormatting::Default),
+        "minify" | "minified" => Ok(OutputFormatting::Minify),
+        "pretty" => Ok(OutputFormatting::Pretty),
+        other => Err(format!("不支援的輸出格式: {}（可用：default, minify, pretty）", other)),
+    }
+}
+
+// 依輸出格式重新排版輸出包內生成的 HTML/CSS；SCSS/Less 有 // 註解等語法，維持原樣。
+// 只處理 written_since 之後寫入的檔案，skip/merge 策略保留的既有檔案不會被改動
+fn apply_output_formatting(package_root: &std::path::Path, formatting: OutputFormatting, written_since: SystemTime) -> Result<(), String> {
+    if formatting == OutputFormatting::Default { return Ok(()); }
+    let mut files = Vec::new();
+    collect_generated_text_files(package_root, &asset_categories(), &mut files);
+    for f in files.iter() {
+        let ext = f.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if !matches!(ext, "html" | "css") { continue; }
+        let written = std::fs::metadata(f).and_then(|m| m.modified()).map(|t| t >= written_since).unwrap_or(true);
+        if !written { continue; }
+        let original = std::fs::read_to_string(f).map_err(|e| format!("讀取 {} 失敗: {}", f.display(), e))?;
+        let formatted = match (ext, formatting) {
+            ("html", OutputFormatting::Minify) => minify_html(&original),
+            ("html", _) => pretty_html(&original),
+            (_, OutputFormatting::Minify) => minify_css(&original),
+            _ => pretty_css(&original),
+        };
+        if formatted != original {
+            std::fs::write(f, formatted).map_err(|e| format!("寫入 {} 失敗: {}", f.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+const FORMAT_INDENT: &str = "    ";
+
+// 合併 CSS 的連續空白（引號內保留原樣）
+fn collapse_css_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut quote: Option<char> = None;
+    let mut pending_space = false;
+    for c in text.trim().chars() {
+        if quote.is_none() && c.is_whitespace() {
+            pending_space = true;
+            continue;
+        }
+        if pending_space {
+            out.push(' ');
+            pending_space = false;
+        }
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            _ => {}
+        }
+        out.push(c);
+    }
+    out
+}
+
+// 去除區塊的共同縮排後以 indent 重新縮排，前後空行略過
+fn reindent_block(block: &str, indent: &str) -> String {
+    let lines: Vec<&str> = block.lines().map(str::trim_end).collect();
+    let start = lines.iter().position(|l| !l.is_empty()).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|l| !l.is_empty()).map(|i| i + 1).unwrap_or(start);
+    let lines = &lines[start..end];
+    let common = lines.iter().filter(|l| !l.is_empty()).map(|l| l.len() - l.trim_start().len()).min().unwrap_or(0);
+    lines
+        .iter()
+        .map(|l| if l.is_empty() { String::new() } else { format!("{}{}", indent, &l[common.min(l.len() - l.trim_start().len())..]) })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+enum CssToken {
+    Comment(String),
+    // 選擇器或 at-rule 前綴（{ 之前）
+    Open(String),
+    // 宣告或 @import 等敘述（不含 ;）
+    Statement(String),
+    Close,
+}
+
+// 切出註解、區塊與敘述；引號與括號（如 url(data:...;...)）內的 ; { } 不視為分隔
+fn css_tokens(css: &str) -> Vec<CssToken> {
+    let mut tokens = Vec::new();
+    let mut buf = String::new();
+    let mut quote: Option<char> = None;
+    let mut parens = 0usize;
+    let mut chars = css.chars().peekable();
+    let flush = |buf: &mut String, tokens: &mut Vec<CssToken>| {
+        let text = collapse_css_whitespace(buf);
+        if !text.is_empty() { tokens.push(CssToken::Statement(text)); }
+        buf.clear();
+    };
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            buf.push(c);
+            if c == '\\' {
+                if let Some(n) = chars.next() { buf.push(n); }
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => {
+                quote = Some(c);
+                buf.push(c);
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut comment = String::from("/*");
+                let mut prev = '\0';
+                for n in chars.by_ref() {
+                    comment.push(n);
+                    if prev == '*' && n == '/' { break; }
+                    prev = n;
+                }
+                if buf.trim().is_empty() { tokens.push(CssToken::Comment(comment)); } else { buf.push_str(&comment); }
+            }
+            '(' => {
+                parens += 1;
+                buf.push(c);
+            }
+            ')' => {
+                parens = parens.saturating_sub(1);
+                buf.push(c);
+            }
+            '{' if parens == 0 => {
+                tokens.push(CssToken::Open(collapse_css_whitespace(&buf).replace(" ,", ",")));
+                buf.clear();
+            }
+            ';' if parens == 0 => flush(&mut buf, &mut tokens),
+            '}' if parens == 0 => {
+                flush(&mut buf, &mut tokens);
+                tokens.push(CssToken::Close);
+            }
+            _ => buf.push(c),
+        }
+    }
+    flush(&mut buf, &mut tokens);
+    tokens
+}
+
+// 宣告統一為 "prop: value"（at-rule 敘述不處理）
+fn css_declaration(statement: &str, separator: &str) -> String {
+    match statement.split_once(':') {
+        Some((prop, value)) if !statement.starts_with('@') && !prop.trim().contains(' ') => format!("{}:{}{}", prop.trim(), separator, value.trim()),
+        _ => statement.to_string(),
+    }
+}
+
+// 壓縮 CSS：移除空白與註解，保留檔案開頭的表頭註解
+fn minify_css(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut needs_semicolon = false;
+    for (i, token) in css_tokens(css).iter().enumerate() {
+        match token {
+            CssToken::Comment(c) => {
+                if i == 0 {
+                    out.push_str(c);
+                    out.push('\n');
+                }
+            }
+            CssToken::Open(selector) => {
+                if needs_semicolon { out.push(';'); }
+                out.push_str(&selector.replace(", ", ",").replace(" > ", ">"));
+                out.push('{');
+                needs_semicolon = false;
+            }
+            CssToken::Statement(statement) => {
+                if needs_semicolon { out.push(';'); }
+                out.push_str(&css_declaration(statement, ""));
+                needs_semicolon = true;
+            }
+            CssToken::Close => {
+                out.push('}');
+                needs_semicolon = false;
+            }
+        }
+    }
+    if needs_semicolon { out.push(';'); }
+    out.push('\n');
+    out
+}
+
+// 排版 CSS：每個宣告一行、巢狀區塊縮排，最上層規則之間空一行
+fn pretty_css(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut depth = 0usize;
+    for token in css_tokens(css).iter() {
+        let indent = FORMAT_INDENT.repeat(depth);
+        match token {
+            CssToken::Comment(c) => out.push_str(&format!("{}{}\n", indent, c.trim())),
+            CssToken::Open(selector) => {
+                out.push_str(&format!("{}{} {{\n", indent, selector));
+                depth += 1;
+            }
+            CssToken::Statement(statement) => out.push_str(&format!("{}{};\n", indent, css_declaration(statement, " "))),
+            CssToken::Close => {
+                depth = depth.saturating_sub(1);
+                out.push_str(&format!("{}}}\n", FORMAT_INDENT.repeat(depth)));
+                if depth == 0 { out.push('\n'); }
+            }
+        }
+    }
+    format!("{}\n", out.trim_end())
+}
+
+enum HtmlToken<'a> {
+    // <!DOCTYPE ...> 與註解
+    Markup(&'a str),
+    Open { name: String, raw: &'a str, void: bool },
+    Close { raw: &'a str },
+    Text(&'a str),
+    // script / style / pre / textarea 的內容
+    RawText(&'a str),
+}
+
+const HTML_VOID_ELEMENTS: &[&str] = &["area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track", "wbr"];
+const HTML_RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "pre", "textarea"];
+
+fn html_tag_name(tag: &str) -> String {
+    tag.trim_start_matches('<').trim_start_matches('/').chars().take_while(|c| c.is_ascii_alphanumeric() || *c == '-').collect::<String>().to_lowercase()
+}
+
+// 標籤結尾（引號內的 > 不算）
+fn html_tag_end(rest: &str) -> usize {
+    let mut quote: Option<char> = None;
+    for (i, c) in rest.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return i + 1,
+            _ => {}
+        }
+    }
+    rest.len()
+}
+
+fn html_tokens(html: &str) -> Vec<HtmlToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map(|i| i + 3).unwrap_or(rest.len());
+            tokens.push(HtmlToken::Markup(&rest[..end]));
+            rest = &rest[end..];
+            continue;
+        }
+        if rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!') {
+            let end = html_tag_end(rest);
+            let tag = &rest[..end];
+            rest = &rest[end..];
+            if tag.starts_with("<!") {
+                tokens.push(HtmlToken::Markup(tag));
+            } else if tag.starts_with("</") {
+                tokens.push(HtmlToken::Close { raw: tag });
+            } else {
+                let name = html_tag_name(tag);
+                let void = tag.ends_with("/>") || HTML_VOID_ELEMENTS.contains(&name.as_str());
+                let raw_text = !void && HTML_RAW_TEXT_ELEMENTS.contains(&name.as_str());
+                tokens.push(HtmlToken::Open { name: name.clone(), raw: tag, void });
+                if raw_text {
+                    let end = rest.to_ascii_lowercase().find(&format!("</{}", name)).unwrap_or(rest.len());
+                    tokens.push(HtmlToken::RawText(&rest[..end]));
+                    rest = &rest[end..];
+                }
+            }
+            continue;
+        }
+        let end = match rest.find('<') {
+            Some(0) => rest[1..].find('<').map(|i| i + 1).unwrap_or(rest.len()),
+            Some(i) => i,
+            None => rest.len(),
+        };
+        tokens.push(HtmlToken::Text(&rest[..end]));
+        rest = &rest[end..];
+    }
+    tokens
+}
+
+// 壓縮 HTML：合併空白、移除註解（保留檔案開頭的表頭與 erslice 標記），內嵌 CSS 一併壓縮；pre/textarea 保持原樣
+fn minify_html(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut raw_owner = String::new();
+    let mut in_preamble = true;
+    for token in html_tokens(html) {
+        match token {
+            HtmlToken::Markup(m) => {
+                if !m.starts_with("<!--") || in_preamble || m.contains("erslice:") { out.push_str(m.trim()); }
+            }
+            HtmlToken::Open { name, raw, .. } => {
+                in_preamble = false;
+                out.push_str(raw);
+                raw_owner = name;
+            }
+            HtmlToken::Close { raw } => out.push_str(raw),
+            HtmlToken::RawText(text) => match raw_owner.as_str() {
+                "style" => out.push_str(minify_css(text).trim()),
+                "script" => out.push_str(text.trim()),
+                _ => out.push_str(text),
+            },
+            HtmlToken::Text(text) => {
+                let collapsed = collapse_whitespace(text);
+                if collapsed.is_empty() {
+                    // 同一行的行內元素之間保留一個空白
+                    if !text.is_empty() && !text.contains('\n') { out.push(' '); }
+                    continue;
+                }
+                if text.starts_with(char::is_whitespace) { out.push(' '); }
+                out.push_str(&collapsed);
+                if text.ends_with(char::is_whitespace) { out.push(' '); }
+            }
+        }
+    }
+    out.push('\n');
+    out
+}
+
+// 排版 HTML：每個元素一行並依巢狀縮排；只含文字的元素維持單行，script/style 內容重新縮排，pre/textarea 保持原樣
+fn pretty_html(html: &str) -> String {
+    let tokens = html_tokens(html);
+    let mut lines: Vec<String> = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < tokens.len() {
+        let indent = FORMAT_INDENT.repeat(depth);
+        match &tokens[i] {
+            HtmlToken::Markup(m) => lines.push(format!("{}{}", indent, m.trim())),
+            HtmlToken::Text(text) => {
+                let collapsed = collapse_whitespace(text);
+                if !collapsed.is_empty() { lines.push(format!("{}{}", indent, collapsed)); }
+            }
+            HtmlToken::RawText(text) => lines.push(format!("{}{}", indent, text.trim())),
+            HtmlToken::Close { raw } => {
+                depth = depth.saturating_sub(1);
+                lines.push(format!("{}{}", FORMAT_INDENT.repeat(depth), raw));
+            }
+            HtmlToken::Open { raw, void: true, .. } => lines.push(format!("{}{}", indent, raw)),
+            HtmlToken::Open { name, raw, .. } if HTML_RAW_TEXT_ELEMENTS.contains(&name.as_str()) => {
+                let body = match tokens.get(i + 1) {
+                    Some(HtmlToken::RawText(text)) => {
+                        i += 1;
+                        *text
+                    }
+                    _ => "",
+                };
+                let close = match tokens.get(i + 1) {
+                    Some(HtmlToken::Close { raw }) => {
+                        i += 1;
+                        *raw
+                    }
+                    _ => "",
+                };
+                if body.trim().is_empty() || matches!(name.as_str(), "pre" | "textarea") {
+                    lines.push(format!("{}{}{}{}", indent, raw, body, close));
+                } else {
+                    let inner = format!("{}{}", indent, FORMAT_INDENT);
+                    let body = if name == "style" { pretty_css(body) } else { body.to_string() };
+                    lines.push(format!("{}{}", indent, raw));
+                    lines.push(reindent_block(&body, &inner));
+                    lines.push(format!("{}{}", indent, close));
+                }
+            }
+            HtmlToken::Open { raw, .. } => match (tokens.get(i + 1), tokens.get(i + 2)) {
+                (Some(HtmlToken::Close { raw: close }), _) => {
+                    lines.push(format!("{}{}{}", indent, raw, close));
+                    i += 1;
+                }
+                (Some(HtmlToken::Text(text)), Some(HtmlToken::Close { raw: close })) => {
+                    lines.push(format!("{}{}{}{}", indent, raw, collapse_whitespace(text), close));
+                    i += 2;
+                }
+                _ => {
+                    lines.push(format!("{}{}", indent, raw));
+                    depth += 1;
+                }
+            },
+        }
+        i += 1;
+    }
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+// ====== 生成選項組合（profile） ======
+
+// 已命名的生成選項組合；只補上呼叫端未明確傳入的選項，呼叫端有傳者優先
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GenerationProfile {
+    pub name: String,
+    pub include_html: Option<bool>,
+    pub include_css: Option<bool>,
+    pub include_responsive: Option<bool>,
+    pub include_comparison: Option<bool>,
+    pub include_print_css: Option<bool>,
+    // 僅整包生成使用
+    pub include_specs: Option<bool>,
+    pub overwrite_strategy: Option<String>,
+    pub archive_format: Option<String>,
+    pub stylesheet_dialect: Option<String>,
+    pub output_formatting: Option<String>,
+    pub validate: Option<bool>,
+}
+
+impl GenerationProfile {
+    fn apply_to_page_options(&self, options: &mut PageSliceOptions) {
+        options.include_html = options.include_html.or(self.include_html);
+        options.include_css = options.include_css.or(self.include_css);
+        options.include_responsive = options.include_responsive.or(self.include_responsive);
+        options.overwrite_strategy = options.overwrite_strategy.take().or_else(|| self.overwrite_strategy.clone());
+        options.archive_format = options.archive_format.take().or_else(|| self.archive_format.clone());
+        options.output_formatting = options.output_formatting.take().or_else(|| self.output_formatting.clone());
+        options.stylesheet_dialect = options.stylesheet_dialect.take().or_else(|| self.stylesheet_dialect.clone());
+    }
+}
+
+// 依名稱讀取目前專案的生成選項組合；name 為 None 或空字串時回傳 None
+fn load_generation_profile(name: Option<&str>) -> Result<Option<GenerationProfile>, String> {
+    let Some(name) = name.map(str::trim).filter(|n| !n.is_empty()) else { return Ok(None) };
+    read_active_project_config()
+        .and_then(|c| c.generation_profiles)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|p| p.name == name)
+        .map(Some)
+        .ok_or_else(|| format!("找不到生成選項組合: {}", name))
+}
+
+/// 列出目前專案的生成選項組合（依名稱排序）
+#[tauri::command]
+pub async fn list_generation_profiles() -> Result<Vec<GenerationProfile>, String> {
+    let mut profiles = read_active_project_config().and_then(|c| c.generation_profiles).unwrap_or_default();
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(profiles)
+}
+
+/// 儲存生成選項組合；同名者覆寫。選項值會先驗證，避免生成時才發現設定錯誤
+#[tauri::command]
+pub async fn save_generation_profile(profile: GenerationProfile) -> Result<Vec<GenerationProfile>, String> {
+    let mut profile = profile;
+    profile.name = profile.name.trim().to_string();
+    if profile.name.is_empty() {
+        return Err("生成選項組合名稱不可為空".to_string());
+    }
+    if let Some(strategy) = profile.overwrite_strategy.as_deref() {
+        if !matches!(strategy, "overwrite" | "skip" | "rename" | "merge") {
+            return Err(format!("不支援的覆寫策略: {}（可用：overwrite, skip, rename, merge）", strategy));
+        }
+    }
+    if let Some(format) = profile.archive_format.as_deref() {
+        resolve_archive_format(Some(format), false)?;
+    }
+    if let Some(dialect) = profile.stylesheet_dialect.as_deref() {
+        resolve_stylesheet_dialect(Some(dialect))?;
+    }
+    if let Some(formatting) = profile.output_formatting.as_deref() {
+        resolve_output_formatting(Some(formatting))?;
+    }
+    let mut cfg = get_or_init_default_project().await?;
+    let mut profiles = cfg.generation_profiles.take().unwrap_or_default();
+    profiles.retain(|p| p.name != profile.name);
+    profiles.push(profile);
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+    cfg.generation_profiles = Some(profiles.clone());
+    update_default_project(cfg).await?;
+    Ok(profiles)
+}
+
+/// 刪除生成選項組合，回傳剩餘的組合
+#[tauri::command]
+pub async fn delete_generation_profile(name: String) -> Result<Vec<GenerationProfile>, String> {
+    let mut cfg = get_or_init_default_project().await?;
+    let mut profiles = cfg.generation_profiles.take().unwrap_or_default();
+    let before = profiles.len();
+    profiles.retain(|p| p.name != name);
+    if profiles.len() == before {
+        return Err(format!("找不到生成選項組合: {}", name));
+    }
+    cfg.generation_profiles = Some(profiles.clone());
+    update_default_project(cfg).await?;
+    Ok(profiles)
+}
+
+// ====== PDF 匯出 ======
+
+const PRINT_STYLE: &str = "body{font:14px/1.6 -apple-system,BlinkMacSystemFont,'Segoe UI',Roboto,'Noto Sans TC','Noto Sans JP',sans-serif;color:#111827;max-width:960px;margin:0 auto;padding:24px}h1,h2,h3{page-break-after:avoid}h1{border-bottom:2px solid #e5e7eb;padding-bottom:8px}h2{border-bottom:1px solid #e5e7eb;padding-bottom:4px;margin-top:32px}pre{background:#f3f4f6;padding:12px;border-radius:6px;white-space:pre-wrap;word-break:break-word}code{font-family:ui-monospace,SFMono-Regular,Menlo,monospace;font-size:12px}table{border-collapse:collapse;width:100%;margin:12px 0}th,td{border:1px solid #d1d5db;padding:6px 8px;text-align:left;vertical-align:top}th{background:#f9fafb}img{max-width:100%}blockquote{border-left:4px solid #d1d5db;margin:0;padding-left:12px;color:#4b5563}tr,img,pre,.mermaid{page-break-inside:avoid}";
+const PRINT_MERMAID_SCRIPT: &str = "<script type=\"module\">import mermaid from 'https://cdn.jsdelivr.net/npm/mermaid@10/dist/mermaid.esm.min.mjs'; mermaid.initialize({ startOnLoad: true });</script>";
+
+// Markdown 行內語法：`code`、![alt](src)、[text](url)、**粗體**、*斜體* / _斜體_
+fn markdown_inline(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let find = |from: usize, pat: &[char]| (from..chars.len().saturating_sub(pat.len() - 1)).find(|&j| chars[j..j + pat.len()] == *pat);
+    let slice = |a: usize, b: usize| chars[a..b].iter().collect::<String>();
+    // [label](target) 的結尾位置與兩段內容
+    let bracket = |from: usize| -> Option<(String, String, usize)> {
+        let close = find(from + 1, &[']'])?;
+        if chars.get(close + 1) != Some(&'(') { return None; }
+        let end = find(close + 2, &[')'])?;
+        Some((slice(from + 1, close), slice(close + 2, end), end + 1))
+    };
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '`' => {
+                if let Some(end) = find(i + 1, &['`']) {
+                    out.push_str(&format!("<code>{}</code>", escape_html(&slice(i + 1, end))));
+                    i = end + 1;
+                    continue;
+                }
+            }
+            '!' if chars.get(i + 1) == Some(&'[') => {
+                if let Some((alt, src, next)) = bracket(i + 1) {
+                    out.push_str(&format!("<img src=\"{}\" alt=\"{}\">", escape_html(src.trim()), escape_html(&alt)));
+                    i = next;
+                    continue;
+                }
+            }
+            '[' => {
+                if let Some((label, href, next)) = bracket(i) {
+                    out.push_str(&format!("<a href=\"{}\">{}</a>", escape_html(href.trim()), markdown_inline(&label)));
+                    i = next;
+                    continue;
+                }
+            }
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                if let Some(end) = find(i + 2, &['*', '*']).filter(|e| *e > i + 2) {
+                    out.push_str(&format!("<strong>{}</strong>", markdown_inline(&slice(i + 2, end))));
+                    i = end + 2;
+                    continue;
+                }
+            }
+            '*' | '_' if chars.get(i + 1).map(|n| !n.is_whitespace()).unwrap_or(false) => {
+                // 單字內的底線（如 snake_case）不視為斜體
+                let word_inner = c == '_' && i > 0 && chars[i - 1].is_alphanumeric();
+                if let Some(end) = find(i + 1, &[c]).filter(|_| !word_inner) {
+                    out.push_str(&format!("<em>{}</em>", markdown_inline(&slice(i + 1, end))));
+                    i = end + 1;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        out.push_str(&escape_html(&c.to_string()));
+        i += 1;
+    }
+    out
+}
+
+fn markdown_list_marker(line: &str) -> Option<(bool, &str)> {
+    let t = line.trim_start();
+    for m in ["- ", "* ", "+ "] {
+        if let Some(rest) = t.strip_prefix(m) { return Some((false, rest)); }
+    }
+    let digits = t.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 { t[digits..].strip_prefix(". ").map(|rest| (true, rest)) } else { None }
+}
+
+fn markdown_table_cells(line: &str) -> Vec<String> {
+    line.trim().trim_start_matches('|').trim_end_matches('|').split('|').map(|c| c.trim().to_string()).collect()
+}
+
+fn is_markdown_table_separator(line: &str) -> bool {
+    let t = line.trim();
+    t.contains('-') && t.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+fn is_markdown_block_start(line: &str) -> bool {
+    let t = line.trim_start();
+    t.starts_with('#') || t.starts_with("```") || t.starts_with('>') || t.starts_with('|') || t.starts_with('<') || markdown_list_marker(line).is_some()
+}
+
+// 去除至多 indent 位元組的前導空白；全形空白（U+3000）等多位元組字元不會從中切開
+fn strip_leading_indent(line: &str, indent: usize) -> &str {
+    let cut = line
+        .char_indices()
+        .take_while(|(_, c)| c.is_whitespace())
+        .map(|(i, c)| i + c.len_utf8())
+        .take_while(|end| *end <= indent)
+        .last()
+        .unwrap_or(0);
+    &line[cut..]
+}
+
+// 將 ai-spec.md 等生成的 Markdown 轉為 HTML（標題、段落、清單、表格、程式碼區塊、引言、分隔線）；
+// mermaid 程式碼區塊輸出為 <div class="mermaid"> 供瀏覽器繪製
+fn markdown_to_html(md: &str) -> String {
+    let lines: Vec<&str> = md.lines().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let t = line.trim();
+        if t.is_empty() {
+            i += 1;
+            continue;
+        }
+        if let Some(lang) = t.strip_prefix("```") {
+            let end = (i + 1..lines.len()).find(|&j| lines[j].trim().starts_with("```")).unwrap_or(lines.len());
+            let body = lines[i + 1..end].join("\n");
+            if lang.trim() == "mermaid" {
+                out.push_str(&format!("<div class=\"mermaid\">\n{}\n</div>\n", escape_html(&body)));
+            } else {
+                out.push_str(&format!("<pre><code>{}</code></pre>\n", escape_html(&body)));
+            }
+            i = end + 1;
+            continue;
+        }
+        let level = t.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&level) && t[level..].starts_with(' ') {
+            out.push_str(&format!("<h{0}>{1}</h{0}>\n", level, markdown_inline(t[level..].trim())));
+            i += 1;
+            continue;
+        }
+        if t.len() >= 3 && (t.chars().all(|c| c == '-') || t.chars().all(|c| c == '*') || t.chars().all(|c| c == '_')) {
+            out.push_str("<hr>\n");
+            i += 1;
+            continue;
+        }
+        if t.starts_with('<') {
+            out.push_str(line);
+            out.push('\n');
+            i += 1;
+            continue;
+        }
+        if t.starts_with('|') && lines.get(i + 1).map(|l| is_markdown_table_separator(l)).unwrap_or(false) {
+            out.push_str("<table>\n<thead><tr>");
+            for cell in markdown_table_cells(t) {
+                out.push_str(&format!("<th>{}</th>", markdown_inline(&cell)));
+            }
+            out.push_str("</tr></thead>\n<tbody>\n");
+            i += 2;
+            while i < lines.len() && lines[i].trim().starts_with('|') {
+                out.push_str("<tr>");
+                for cell in markdown_table_cells(lines[i]) {
+                    out.push_str(&format!("<td>{}</td>", markdown_inline(&cell)));
+                }
+                out.push_str("</tr>\n");
+                i += 1;
+            }
+            out.push_str("</tbody>\n</table>\n");
+            continue;
+        }
+        if t.starts_with('>') {
+            let mut quoted = Vec::new();
+            while i < lines.len() && lines[i].trim_start().starts_with('>') {
+                let q = lines[i].trim_start().trim_start_matches('>');
+                quoted.push(q.strip_prefix(' ').unwrap_or(q));
+                i += 1;
+            }
+            out.push_str(&format!("<blockquote>\n{}</blockquote>\n", markdown_to_html(&quoted.join("\n"))));
+            continue;
+        }
+        if let Some((ordered, _)) = markdown_list_marker(line) {
+            let indent = line.len() - line.trim_start().len();
+            let tag = if ordered { "ol" } else { "ul" };
+            out.push_str(&format!("<{}>\n", tag));
+            while i < lines.len() {
+                let Some((_, first)) = markdown_list_marker(lines[i]).filter(|_| lines[i].len() - lines[i].trim_start().len() == indent) else { break };
+                // 縮排較深的後續行（含子清單）屬於此項目
+                let mut nested: Vec<&str> = Vec::new();
+                i += 1;
+                while i < lines.len() && !lines[i].trim().is_empty() && lines[i].len() - lines[i].trim_start().len() > indent {
+                    nested.push(strip_leading_indent(lines[i], indent));
+                    i += 1;
+                }
+                let item = match first.strip_prefix("[ ] ").map(|r| (false, r)).or_else(|| first.strip_prefix("[x] ").map(|r| (true, r))) {
+                    Some((checked, rest)) => format!("<input type=\"checkbox\" disabled{}> {}", if checked { " checked" } else { "" }, markdown_inline(rest)),
+                    None => markdown_inline(first),
+                };
+                let nested_html = if nested.is_empty() { String::new() } else { format!("\n{}", markdown_to_html(&nested.join("\n"))) };
+                out.push_str(&format!("<li>{}{}</li>\n", item, nested_html));
+            }
+            out.push_str(&format!("</{}>\n", tag));
+            continue;
+        }
+        let mut para = vec![t];
+        i += 1;
+        while i < lines.len() && !lines[i].trim().is_empty() && !is_markdown_block_start(lines[i]) {
+            para.push(lines[i].trim());
+            i += 1;
+        }
+        out.push_str(&format!("<p>{}</p>\n", markdown_inline(&para.join("\n"))));
+    }
+    out
+}
+
+fn markdown_print_page(title: &str, md: &str) -> String {
+    // 能在本機渲染時以內嵌 SVG 取代 Mermaid 區塊，不再依賴 CDN
+    let mut renderer = MermaidPrerenderer::new(std::env::temp_dir().join(format!("erslice-mermaid-{}", uuid::Uuid::new_v4())), "svg");
+    let md = prerender_mermaid_blocks(md, |source| {
+        use base64::Engine;
+        let svg = std::fs::read(renderer.render(source)?).ok()?;
+        Some(format!("data:image/svg+xml;base64,{}", base64::engine::general_purpose::STANDARD.encode(svg)))
+    });
+    renderer.cleanup();
+    let body = markdown_to_html(&md);
+    let mermaid = if body.contains("class=\"mermaid\"") { PRINT_MERMAID_SCRIPT } else { "" };
+    let lang = project_spec_language().pick("zh-TW", "en", "ja");
+    brand_preview_html(&format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n{}\n</head>\n<body>\n{}</body>\n</html>\n",
+        lang,
+        escape_html(title),
+        PRINT_STYLE,
+        mermaid,
+        body
+    ))
+}
+
+// 以無頭瀏覽器將本機 HTML 列印為 PDF；沿用截圖的 screenshot_backend 設定
+fn print_html_to_pdf(html_path: &std::path::Path, dest: &std::path::Path) -> Result<(), String> {
+    use std::process::Command;
+    let abs = std::fs::canonicalize(html_path).map_err(|e| format!("讀取 {} 失敗: {}", html_path.display(), e))?;
+    let url = format!("file://{}", abs.to_string_lossy().replace('\\', "/").replace(' ', "%20"));
+    let cfg = read_active_project_config();
+    let backend = cfg.as_ref().and_then(|c| c.screenshot_backend.clone()).unwrap_or_else(|| "playwright".into());
+    let _ = std::fs::remove_file(dest);
+    let output = match backend.as_str() {
+        "chrome" => {
+            let chrome = cfg.as_ref().and_then(|c| c.chrome_path.clone())
+                .or_else(|| std::env::var("CHROME_PATH").ok())
+                .unwrap_or_else(|| "google-chrome".into());
+            // 預留時間讓 Mermaid 等腳本完成繪製
+            Command::new(chrome)
+                .args(["--headless=new", "--disable-gpu", "--no-pdf-header-footer", "--virtual-time-budget=10000"])
+                .arg(format!("--print-to-pdf={}", dest.display()))
+                .arg(&url)
+                .output()
+        }
+        "playwright" => {
+            let npx = if cfg!(target_os = "windows") { "npx.cmd" } else { "npx" };
+            Command::new(npx)
+                .args(["--yes", "playwright", "pdf", "--wait-for-timeout=3000"])
+                .arg(&url)
+                .arg(dest)
+                .output()
+        }
+        other => return Err(format!("不支援的截圖工具: {}", other)),
+    };
+    match output {
+        Ok(o) if o.status.success() && dest.exists() => Ok(()),
+        Ok(o) => Err(format!("PDF 匯出失敗（{}）: {}", backend, String::from_utf8_lossy(&o.stderr).trim())),
+        Err(e) => Err(format!("無法執行 {}: {}", backend, e)),
+    }
+}
+
+/// 將生成的 Markdown（如 ai-spec.md）或 HTML（如 Mermaid sitemap 預覽）匯出為 PDF；
+/// output 未指定時輸出至來源旁的同名 .pdf，回傳 PDF 路徑
+#[tauri::command]
+pub async fn export_pdf(target: String, output: Option<String>) -> Result<String, String> {
+    let source = PathBuf::from(&target);
+    if !source.is_file() {
+        return Err(format!("檔案不存在: {}", target));
+    }
+    let ext = source.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    let dest = output.map(PathBuf::from).unwrap_or_else(|| source.with_extension("pdf"));
+    if let Some(parent) = dest.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| format!("建立輸出目錄失敗: {}", e))?;
+    }
+    match ext.as_str() {
+        "html" | "htm" => print_html_to_pdf(&source, &dest)?,
+        "md" | "markdown" => {
+            let md = std::fs::read_to_string(&source).map_err(|e| format!("讀取 {} 失敗: {}", target, e))?;
+            let title = source.file_stem().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            // 暫存頁放在來源旁，Markdown 內的相對圖片路徑才能正確載入
+            let page = source.with_file_name(format!(".{}.print.html", title));
+            std::fs::write(&page, markdown_print_page(&title, &md)).map_err(|e| format!("寫入暫存頁失敗: {}", e))?;
+            let result = print_html_to_pdf(&page, &dest);
+            let _ = std::fs::remove_file(&page);
+            result?;
+        }
+        _ => return Err(format!("不支援匯出 PDF 的檔案類型: {}（可用：md, html）", target)),
+    }
+    Ok(dest.to_string_lossy().to_string())
+}
+
+// ====== Confluence / Notion 文件包匯出 ======
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocsBundleTarget {
+    Confluence,
+    Notion,
+}
+
+impl DocsBundleTarget {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "confluence" => Ok(Self::Confluence),
+            "notion" => Ok(Self::Notion),
+            other => Err(format!("不支援的匯出目標: {}（可用：confluence, notion）", other)),
+        }
+    }
+
+    // Notion 只有 H1–H3，更深的標題一律視為 H3
+    fn max_heading(self) -> usize {
+        match self {
+            Self::Confluence => 6,
+            Self::Notion => 3,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DocsBundleResult {
+    pub output_dir: String,
+    pub archive_path: String,
+    pub pages: usize,
+    pub attachments: usize,
+    // 找不到的圖片等無法轉換的引用
+    pub warnings: Vec<String>,
+}
+
+// 文件包中的一頁：原始相對路徑 → 攤平後的檔名與標題
+struct DocsBundlePage {
+    source: PathBuf,
+    rel: String,
+    file_name: String,
+    title: String,
+}
+
+// modules/user/login/ai-spec.md → user-login-ai-spec.md
+fn docs_bundle_file_name(rel: &str) -> String {
+    let stem = rel.strip_suffix(".md").or_else(|| rel.strip_suffix(".markdown")).unwrap_or(rel);
+    let stem = stem.strip_prefix("modules/").unwrap_or(stem);
+    let name: String = stem.chars().map(|c| if c == '/' || c == '\\' || c.is_whitespace() { '-' } else { c }).collect();
+    format!("{}.md", name)
+}
+
+fn docs_bundle_title(rel: &str) -> String {
+    let stem = rel.strip_suffix(".md").or_else(|| rel.strip_suffix(".markdown")).unwrap_or(rel);
+    stem.strip_prefix("modules/").unwrap_or(stem).split('/').collect::<Vec<_>>().join(" / ")
+}
+
+// 替換一行中 [text](target) 與 ![alt](target) 的 target；f 回傳 None 時保留原值
+fn rewrite_markdown_line_targets(line: &str, f: &mut impl FnMut(bool, &str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(open) = rest.find('[') {
+        let Some(close) = rest[open..].find("](").map(|i| open + i) else { break };
+        let Some(end) = rest[close + 2..].find(')').map(|i| close + 2 + i) else { break };
+        let is_image = rest[..open].ends_with('!');
+        let target = &rest[close + 2..end];
+        out.push_str(&rest[..close + 2]);
+        match f(is_image, target.trim()) {
+            Some(new_target) => out.push_str(&new_target),
+            None => out.push_str(target),
+        }
+        out.push(')');
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+// 轉換單頁：移除 erslice 標記註解、改寫圖片與頁面連結、整理標題層級並轉換 mermaid 區塊
+fn convert_docs_bundle_page(md: &str, page: &DocsBundlePage, target: DocsBundleTarget, rewrite: &mut impl FnMut(bool, &str) -> Option<String>) -> String {
+    let mut body: Vec<String> = Vec::new();
+    let mut headings: Vec<(usize, usize)> = Vec::new();
+    let mut in_fence = false;
+    let mut in_mermaid = false;
+    for line in md.lines() {
+        let t = line.trim();
+        if t.starts_with("```") {
+            if !in_fence && t.trim_start_matches('`').trim() == "mermaid" && target == DocsBundleTarget::Confluence {
+                // Confluence 的 Markdown 匯入不會繪製 Mermaid，保留原始碼供貼入 Mermaid 巨集
+                body.push("> Mermaid 圖（可貼入 Confluence 的 Mermaid 巨集顯示）".to_string());
+                body.push(String::new());
+                body.push("```text".to_string());
+                in_fence = true;
+                in_mermaid = true;
+                continue;
+            }
+            in_fence = !in_fence;
+            in_mermaid &= in_fence;
+            body.push(line.to_string());
+            continue;
+        }
+        if in_fence || in_mermaid {
+            body.push(line.to_string());
+            continue;
+        }
+        if t.starts_with("<!--") && t.ends_with("-->") && (t.contains("erslice:") || t == MERGE_USER_START || t == MERGE_USER_END) {
+            continue;
+        }
+        let level = t.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&level) && t[level..].starts_with(' ') {
+            headings.push((body.len(), level));
+        }
+        body.push(rewrite_markdown_line_targets(line, rewrite));
+    }
+
+    // 每頁只保留一個 H1 作為頁面標題：原本恰好以單一 H1 開頭者沿用，否則加上標題並將其餘標題降一級
+    let h1_count = headings.iter().filter(|(_, l)| *l == 1).count();
+    let starts_with_h1 = headings.first().map(|(_, l)| *l == 1).unwrap_or(false);
+    let keep_title = h1_count == 1 && starts_with_h1;
+    let shift = if keep_title || h1_count == 0 { 0 } else { 1 };
+    for (idx, level) in headings.iter() {
+        let new_level = if keep_title && *level == 1 { 1 } else { (level + shift).clamp(2, target.max_heading()) };
+        let text = body[*idx].trim_start()[*level..].to_string();
+        body[*idx] = format!("{}{}", "#".repeat(new_level), text);
+    }
+    let mut out = String::new();
+    if !keep_title {
+        out.push_str(&format!("# {}\n\n", page.title));
+    }
+    out.push_str(body.join("\n").trim());
+    out.push('\n');
+    out
+}
+
+/// 將輸出包內生成的 Markdown 轉為可直接匯入 Confluence 或 Notion 的文件包（攤平的頁面、附件與 index.md），
+/// 輸出至 output/exports/<包名稱>-<target>/ 並另存 zip
+#[tauri::command]
+pub async fn export_docs_bundle(package_dir: String, target: String) -> Result<DocsBundleResult, String> {
+    let target = DocsBundleTarget::parse(&target)?;
+    let root = PathBuf::from(&package_dir);
+    if !root.is_dir() {
+        return Err(format!("輸出包不存在: {}", package_dir));
+    }
+    let package_name = root.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| "package".to_string());
+    let target_name = match target {
+        DocsBundleTarget::Confluence => "confluence",
+        DocsBundleTarget::Notion => "notion",
+    };
+    let bundle_name = format!("{}-{}", package_name, target_name);
+    let export_dir = output_root().join("exports");
+    let bundle_dir = export_dir.join(&bundle_name);
+    if bundle_dir.exists() {
+        std::fs::remove_dir_all(&bundle_dir).map_err(|e| format!("清除舊的文件包失敗: {}", e))?;
+    }
+    // Confluence 以檔名對應頁面附件，圖片與頁面放在同一層；Notion 保留 attachments/ 子資料夾
+    let attachments_rel = match target {
+        DocsBundleTarget::Confluence => PathBuf::new(),
+        DocsBundleTarget::Notion => PathBuf::from("attachments"),
+    };
+    std::fs::create_dir_all(bundle_dir.join(&attachments_rel)).map_err(|e| format!("建立文件包資料夾失敗: {}", e))?;
+
+    let mut files = Vec::new();
+    collect_output_files(&root, &mut files);
+    files.sort();
+    let pages: Vec<DocsBundlePage> = files
+        .into_iter()
+        .filter(|f| matches!(f.extension().and_then(|e| e.to_str()), Some("md" | "markdown")))
+        .filter_map(|f| {
+            let rel = f.strip_prefix(&root).ok()?.to_string_lossy().replace('\\', "/");
+            if rel.split('/').any(|c| c == "node_modules") { return None; }
+            Some(DocsBundlePage { file_name: docs_bundle_file_name(&rel), title: docs_bundle_title(&rel), rel, source: f })
+        })
+        .collect();
+    if pages.is_empty() {
+        return Err("輸出包內沒有 Markdown 文件".to_string());
+    }
+    // 以正規化路徑比對頁面間的相對連結
+    let page_names: HashMap<PathBuf, String> = pages
+        .iter()
+        .filter_map(|p| std::fs::canonicalize(&p.source).ok().map(|abs| (abs, p.file_name.clone())))
+        .collect();
+
+    let mut attachments: HashMap<PathBuf, String> = HashMap::new();
+    let mut warnings: Vec<String> = Vec::new();
+    // Confluence 不會繪製 Mermaid：能在本機渲染時改為 PNG 附件，否則保留原始碼
+    let mut renderer = MermaidPrerenderer::new(std::env::temp_dir().join(format!("erslice-mermaid-{}", uuid::Uuid::new_v4())), "png");
+    for page in pages.iter() {
+        let md = std::fs::read_to_string(&page.source).map_err(|e| format!("讀取 {} 失敗: {}", page.rel, e))?;
+        let md = if target == DocsBundleTarget::Confluence {
+            prerender_mermaid_blocks(&md, |source| renderer.render(source).map(|p| p.to_string_lossy().to_string()))
+        } else {
+            md
+        };
+        let page_dir = page.source.parent().map(|p| p.to_path_buf()).unwrap_or_else(|| root.clone());
+        let mut rewrite = |is_image: bool, link: &str| -> Option<String> {
+            if link.is_empty() || link.starts_with('#') || link.contains("://") || link.starts_with("data:") || link.starts_with("mailto:") {
+                return None;
+            }
+            let (path_part, anchor) = match link.split_once('#') {
+                Some((p, a)) => (p, format!("#{}", a)),
+                None => (link, String::new()),
+            };
+            let resolved = page_dir.join(path_part);
+            if let Some(name) = std::fs::canonicalize(&resolved).ok().and_then(|abs| page_names.get(&abs)) {
+                return Some(format!("{}{}", name, anchor));
+            }
+            if !is_image && !is_image_file(&resolved) {
+                return None;
+            }
+            if !resolved.is_file() {
+                warnings.push(format!("{}: 找不到圖片 {}", page.rel, link));
+                return None;
+            }
+            let name = match attachments.get(&resolved) {
+                Some(n) => n.clone(),
+                None => {
+                    // 以包內路徑命名，避免不同頁面的同名截圖互相覆蓋
+                    // 包外的檔案（如渲染的 Mermaid 圖）以檔名命名
+                    let rel = match resolved.strip_prefix(&root) {
+                        Ok(r) => r.to_string_lossy().replace('\\', "/"),
+                        Err(_) => resolved.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| path_part.to_string()),
+                    };
+                    let name: String = rel.trim_start_matches("../").chars().map(|c| if c == '/' || c.is_whitespace() { '-' } else { c }).collect();
+                    if let Err(e) = std::fs::copy(&resolved, bundle_dir.join(&attachments_rel).join(&name)) {
+                        warnings.push(format!("{}: 複製圖片 {} 失敗: {}", page.rel, link, e));
+                        return None;
+                    }
+                    attachments.insert(resolved.clone(), name.clone());
+                    name
+                }
+            };
+            Some(attachments_rel.join(&name).to_string_lossy().replace('\\', "/").replace(' ', "%20"))
+        };
+        let converted = convert_docs_bundle_page(&md, page, target, &mut rewrite);
+        std::fs::write(bundle_dir.join(&page.file_name), converted).map_err(|e| format!("寫入 {} 失敗: {}", page.file_name, e))?;
+    }
+    warnings.extend(renderer.error.take());
+    renderer.cleanup();
+
+    // index.md：依原始路徑列出所有頁面
+    let mut index = format!("# {}\n\n", package_name);
+    for page in pages.iter() {
+        index.push_str(&format!("- [{}]({})\n", page.title, page.file_name));
+    }
+    std::fs::write(bundle_dir.join("index.md"), index).map_err(|e| format!("寫入 index.md 失敗: {}", e))?;
+
+    let mut bundle_files = Vec::new();
+    collect_output_files(&bundle_dir, &mut bundle_files);
+    bundle_files.sort();
+    let zip_path = export_dir.join(format!("{}.zip", bundle_name));
+    write_zip_archive(&bundle_dir, &bundle_files, &zip_path, &bundle_name, None)?;
+    Ok(DocsBundleResult {
+        output_dir: bundle_dir.to_string_lossy().to_string(),
+        archive_path: zip_path.to_string_lossy().to_string(),
+        pages: pages.len(),
+        attachments: attachments.len(),
+        warnings,
+    })
+}
+
+// ====== 生成完成通知（webhook） ======
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GenerationWebhookPayload {
+    // 固定為 "generation.completed"（測試通知為 "generation.test"）
+    pub event: String,
+    // Slack incoming webhook 顯示的摘要
+    pub text: String,
+    // "bulk" | "selected" | "unified"
+    pub kind: String,
+    pub job_id: String,
+    pub project: Option<String>,
+    pub host: String,
+    pub modules: Vec<String>,
+    pub success_count: usize,
+    pub failed_count: usize,
+    pub skipped_count: usize,
+    // 失敗模組的錯誤訊息
+    pub failures: Vec<String>,
+    pub cancelled: bool,
+    pub output_dir: Option<String>,
+    // 第一個封存檔（向下相容單一 zip 的接收端）與全部封存檔
+    pub archive_path: Option<String>,
+    pub archives: Vec<String>,
+    pub duration_ms: u64,
+    pub finished_at: String,
+}
+
+impl GenerationWebhookPayload {
+    fn new(
+        kind: &str,
+        job_id: &str,
+        started_at: chrono::DateTime<chrono::Local>,
+        modules: &[ManifestModule],
+        output_dir: Option<&std::path::Path>,
+        archives: Vec<String>,
+        cancelled: bool,
+    ) -> Self {
+        let count = |status: &str| modules.iter().filter(|m| m.status == status).count();
+        let (success_count, failed_count, skipped_count) = (count("success"), count("failed"), count("skipped"));
+        let project = read_active_slug();
+        let mut text = format!(
+            "ErSlice {} 生成{}：成功 {}、失敗 {}、略過 {}（{:.1} 秒）",
+            kind,
+            if cancelled { "已取消" } else { "完成" },
+            success_count,
+            failed_count,
+            skipped_count,
+            elapsed_ms(started_at) as f64 / 1000.0
+        );
+        if let Some(p) = project.as_deref() {
+            text = format!("[{}] {}", p, text);
+        }
+        if let Some(a) = archives.first() {
+            text.push_str(&format!("\n封存檔: {}", a));
+        }
+        GenerationWebhookPayload {
+            event: "generation.completed".to_string(),
+            text,
+            kind: kind.to_string(),
+            job_id: job_id.to_string(),
+            project,
+            host: local_hostname(),
+            modules: modules.iter().map(|m| m.name.clone()).collect(),
+            success_count,
+            failed_count,
+            skipped_count,
+            failures: modules.iter().filter(|m| m.status == "failed").filter_map(|m| m.error.clone()).collect(),
+            cancelled,
+            output_dir: output_dir.map(|d| d.to_string_lossy().to_string()),
+            archive_path: archives.first().cloned(),
+            archives,
+            duration_ms: elapsed_ms(started_at),
+            finished_at: chrono::Local::now().to_rfc3339(),
+        }
+    }
+
+    // 模組以外的失敗（例如封存或 post hook），計入失敗數並附於摘要
+    fn with_failure(mut self, error: String) -> Self {
+        self.failed_count += 1;
+        self.text.push_str(&format!("\n錯誤: {}", error));
+        self.failures.push(error);
+        self
+    }
+}
+
+fn configured_webhook_url() -> Option<String> {
+    read_active_project_config()
+        .and_then(|c| c.webhook_url)
+        .map(|u| u.trim().to_string())
+        .filter(|u| u.starts_with("http://") || u.starts_with("https://"))
+}
+
+fn post_generation_webhook(url: &str, payload: &GenerationWebhookPayload) -> Result<(), String> {
+    let body = serde_json::to_string(payload).map_err(|e| e.to_string())?;
+    ureq::post(url)
+        .timeout(Duration::from_secs(10))
+        .set("Content-Type", "application/json")
+        .send_string(&body)
+        .map(|_| ())
+        .map_err(|e| format!("webhook 通知失敗: {}", e))
+}
+
+// 背景送出通知，不阻塞生成結果；失敗只記 log
+fn notify_generation_webhook(payload: GenerationWebhookPayload) {
+    let Some(url) = configured_webhook_url() else { return };
+    std::thread::spawn(move || {
+        if let Err(e) = post_generation_webhook(&url, &payload) {
+            log::warn!("{}", e);
+        }
+    });
+}
+
+/// 對專案設定的 webhook 送出測試通知，回傳錯誤訊息以便設定時確認網址可用
+#[tauri::command]
+pub async fn send_test_webhook() -> Result<(), String> {
+    let url = configured_webhook_url().ok_or("尚未設定 webhook 網址（需以 http:// 或 https:// 開頭）")?;
+    let mut payload = GenerationWebhookPayload::new("test", "test", chrono::Local::now(), &[], None, Vec::new(), false);
+    payload.event = "generation.test".to_string();
+    payload.text = "ErSlice webhook 測試通知".to_string();
+    post_generation_webhook(&url, &payload)
+}
+
+// ====== Mermaid 本機渲染（SVG / PNG） ======
+
+// 以 mermaid-cli（mmdc）將 .mmd 渲染為 output 副檔名對應的格式（svg / png / pdf）；
+// 有設定 chrome_path 時交給 puppeteer 使用，避免另外下載 Chromium
+fn render_mermaid_file(input: &std::path::Path, output: &std::path::Path) -> Result<(), String> {
+    use std::process::Command;
+    let cfg = read_active_project_config();
+    let cli = cfg.as_ref().and_then(|c| c.mermaid_cli_path.clone()).filter(|p| !p.trim().is_empty());
+    let mut cmd = match cli.as_ref() {
+        Some(path) => Command::new(path),
+        None => {
+            let npx = if cfg!(target_os = "windows") { "npx.cmd" } else { "npx" };
+            let mut c = Command::new(npx);
+            c.args(["--yes", "-p", "@mermaid-js/mermaid-cli", "mmdc"]);
+            c
+        }
+    };
+    let settings = get_mermaid_settings();
+    cmd.arg("-i").arg(input).arg("-o").arg(output).args(["-t", &settings.theme, "-b", "white"]);
+    let chrome = cfg.as_ref().and_then(|c| c.chrome_path.clone()).or_else(|| std::env::var("CHROME_PATH").ok());
+    let puppeteer_config = output.with_extension("puppeteer.json");
+    if let Some(chrome) = chrome {
+        let json = serde_json::json!({ "executablePath": chrome, "args": ["--no-sandbox"] });
+        std::fs::write(&puppeteer_config, json.to_string()).map_err(|e| format!("寫入 puppeteer 設定失敗: {}", e))?;
+        cmd.arg("-p").arg(&puppeteer_config);
+    }
+    let _ = std::fs::remove_file(output);
+    let result = cmd.output();
+    let _ = std::fs::remove_file(&puppeteer_config);
+    let tool = cli.unwrap_or_else(|| "mmdc".to_string());
+    match result {
+        Ok(o) if o.status.success() && output.exists() => Ok(()),
+        Ok(o) => Err(format!("Mermaid 渲染失敗（{}）: {}", tool, String::from_utf8_lossy(&o.stderr).trim())),
+        Err(e) => Err(format!("無法執行 {}: {}", tool, e)),
+    }
+}
+
+// 匯出時逐一渲染 Markdown 內的 Mermaid 區塊；第一次失敗後不再嘗試（通常是未安裝 mmdc），錯誤保留於 error
+struct MermaidPrerenderer {
+    dir: PathBuf,
+    ext: &'static str,
+    count: usize,
+    error: Option<String>,
+}
+
+impl MermaidPrerenderer {
+    fn new(dir: PathBuf, ext: &'static str) -> Self {
+        MermaidPrerenderer { dir, ext, count: 0, error: None }
+    }
+
+    fn render(&mut self, source: &str) -> Option<PathBuf> {
+        if self.error.is_some() { return None; }
+        self.count += 1;
+        let input = self.dir.join(format!("mermaid-{}.mmd", self.count));
+        let output = input.with_extension(self.ext);
+        let result = std::fs::create_dir_all(&self.dir)
+            .and_then(|_| std::fs::write(&input, source))
+            .map_err(|e| format!("寫入暫存 Mermaid 檔失敗: {}", e))
+            .and_then(|_| render_mermaid_file(&input, &output));
+        match result {
+            Ok(()) => Some(output),
+            Err(e) => {
+                self.error = Some(e);
+                None
+            }
+        }
+    }
+
+    fn cleanup(&self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+// 將 Markdown 中的 ```mermaid 區塊替換為 render 回傳的圖片連結；render 回傳 None 時保留原區塊
+fn prerender_mermaid_blocks(md: &str, mut render: impl FnMut(&str) -> Option<String>) -> String {
+    let lines: Vec<&str> = md.lines().collect();
+    let mut out: Vec<String> = Vec::with_capacity(lines.len());
+    let mut i = 0;
+    while i < lines.len() {
+        let t = lines[i].trim();
+        if !t.starts_with("```") {
+            out.push(lines[i].to_string());
+            i += 1;
+            continue;
+        }
+        let end = (i + 1..lines.len()).find(|&j| lines[j].trim().starts_with("```")).unwrap_or(lines.len());
+        let block_end = (end + 1).min(lines.len());
+        let rendered = if t.trim_start_matches('`').trim() == "mermaid" { render(&lines[i + 1..end].join("\n")) } else { None };
+        match rendered {
+            Some(link) => out.push(format!("![Mermaid]({})", link)),
+            None => out.extend(lines[i..block_end].iter().map(|l| l.to_string())),
+        }
+        i = block_end;
+    }
+    let mut result = out.join("\n");
+    if md.ends_with('\n') { result.push('\n'); }
+    result
+}
+
+/// 在本機將 .mmd 渲染為 SVG 或 PNG（不需瀏覽器預覽頁與 CDN），輸出至 .mmd 旁的同名檔案並回傳路徑；
+/// 需可執行 mermaid-cli（mermaid_cli_path 或 npx @mermaid-js/mermaid-cli）
+#[tauri::command]
+pub async fn render_mermaid_svg(mmd_path: String, format: Option<String>) -> Result<String, String> {
+    let input = PathBuf::from(&mmd_path);
+    if !input.is_file() {
+        return Err(format!("檔案不存在: {}", mmd_path));
+    }
+    let ext = match format.as_deref().map(|f| f.trim().to_lowercase()).as_deref() {
+        None | Some("") | Some("svg") => "svg",
+        Some("png") => "png",
+        Some(other) => return Err(format!("不支援的輸出格式: {}（可用：svg, png）", other)),
+    };
+    let output = input.with_extension(ext);
+    render_mermaid_file(&input, &output)?;
+    Ok(output.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod merge_markdown_tests {
+    use super::*;
+
+    #[test]
+    fn merge_replaces_managed_section_and_keeps_outside_text() {
+        let existing = format!("intro\n{}outro\n", wrap_managed_markdown("# A\nold\n"));
+        let merged = merge_markdown(&existing, "# A\nnew\n").unwrap();
+        assert_eq!(merged, format!("intro\n{}\n# A\nnew\n{}\noutro\n", MERGE_MANAGED_START, MERGE_MANAGED_END));
+        // 再次合併結果不變
+        assert_eq!(merge_markdown(&merged, "# A\nnew\n").unwrap(), merged);
+    }
+
+    #[test]
+    fn merge_keeps_user_block_under_its_heading() {
+        let user = format!("{}\nmine\n{}\n", MERGE_USER_START, MERGE_USER_END);
+        let existing = wrap_managed_markdown(&format!("# A\nold a\n{}# B\nold b\n", user));
+        let merged = merge_markdown(&existing, "# A\nnew a\n# B\nnew b\n").unwrap();
+        assert!(!merged.contains("old a"));
+        let block = merged.find(&user).unwrap();
+        assert!(merged.find("new a").unwrap() < block);
+        assert!(block < merged.find("# B").unwrap());
+    }
+
+    #[test]
+    fn merge_appends_user_block_when_heading_is_renamed() {
+        let user = format!("{}\nkeep me\n{}\n", MERGE_USER_START, MERGE_USER_END);
+        let existing = wrap_managed_markdown(&format!("# Old\ntext\n{}", user));
+        let merged = merge_markdown(&existing, "# New\ntext\n").unwrap();
+        assert_eq!(merged, format!("{}\n# New\ntext\n{}{}", MERGE_MANAGED_START, user, MERGE_MANAGED_END));
+    }
+
+    #[test]
+    fn user_block_without_end_marker_is_closed() {
+        let managed = format!("# A\n{}\nunterminated\n", MERGE_USER_START);
+        assert_eq!(
+            markdown_user_blocks(&managed),
+            vec![(Some("# A".to_string()), format!("{}\nunterminated\n{}\n", MERGE_USER_START, MERGE_USER_END))]
+        );
+        let merged = merge_markdown(&wrap_managed_markdown(&managed), "# A\nnew\n").unwrap();
+        assert!(merged.contains(&format!("unterminated\n{}", MERGE_USER_END)));
+    }
+
+    #[test]
+    fn merge_without_markers_returns_none() {
+        assert_eq!(merge_markdown("# A\nplain\n", "# A\nnew\n"), None);
+        assert_eq!(merge_markdown(&format!("{}\n# A\n", MERGE_MANAGED_START), "# A\n"), None);
+    }
+}
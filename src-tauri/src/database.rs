@@ -162,10 +162,39 @@ fn create_tables(conn: &Connection) -> Result<()> {
         [],
     )?;
 
+    // 模組生成指紋表（增量生成時比對資產是否變更）
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS generation_fingerprints (
+            module_name TEXT PRIMARY KEY,
+            fingerprint TEXT NOT NULL,
+            generated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+
+    // 舊版數據庫補上下載管理需要的欄位
+    add_column_if_missing(conn, "figma_exports", "target_module", "TEXT")?;
+    add_column_if_missing(conn, "figma_exports", "checksum", "TEXT")?;
+    add_column_if_missing(conn, "figma_exports", "downloaded_bytes", "INTEGER DEFAULT 0")?;
+    add_column_if_missing(conn, "figma_exports", "local_path", "TEXT")?;
+
     info!("數據庫表創建完成");
     Ok(())
 }
 
+/// 欄位不存在時以 ALTER TABLE 補上
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, decl: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .any(|name| name == column);
+    if !exists {
+        conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, decl), [])?;
+    }
+    Ok(())
+}
+
 // ==================== 設計模組 CRUD ====================
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -475,6 +504,168 @@ impl AISpec {
     }
 }
 
+// ==================== Figma 導出記錄 ====================
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FigmaExport {
+    pub id: String,
+    pub name: String,
+    pub export_format: String,
+    pub status: Option<String>,
+    pub file_size: Option<String>,
+    pub download_url: Option<String>,
+    pub error_message: Option<String>,
+    pub target_module: Option<String>,
+    pub checksum: Option<String>,
+    pub downloaded_bytes: Option<i64>,
+    pub local_path: Option<String>,
+}
+
+impl FigmaExport {
+    pub fn read(id: &str) -> Result<Option<Self>> {
+        let conn = get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, export_format, status, file_size, download_url, error_message, target_module, checksum, downloaded_bytes, local_path
+             FROM figma_exports WHERE id = ?"
+        )?;
+
+        let mut rows = stmt.query(params![id])?;
+        if let Some(row) = rows.next()? {
+            Ok(Some(Self::from_row(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn list_all() -> Result<Vec<Self>> {
+        let conn = get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, export_format, status, file_size, download_url, error_message, target_module, checksum, downloaded_bytes, local_path
+             FROM figma_exports ORDER BY created_at DESC"
+        )?;
+
+        let rows = stmt.query_map([], Self::from_row)?;
+        let mut exports = Vec::new();
+        for row in rows {
+            exports.push(row?);
+        }
+        Ok(exports)
+    }
+
+    /// 更新下載狀態相關欄位
+    pub fn update_download_state(&self) -> Result<()> {
+        let conn = get_connection()?;
+        conn.execute(
+            "UPDATE figma_exports
+             SET status = ?2, file_size = ?3, error_message = ?4, checksum = ?5, downloaded_bytes = ?6, local_path = ?7
+             WHERE id = ?1",
+            params![
+                self.id, self.status, self.file_size, self.error_message,
+                self.checksum, self.downloaded_bytes, self.local_path
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn from_row(row: &Row) -> Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            export_format: row.get(2)?,
+            status: row.get(3)?,
+            file_size: row.get(4)?,
+            download_url: row.get(5)?,
+            error_message: row.get(6)?,
+            target_module: row.get(7)?,
+            checksum: row.get(8)?,
+            downloaded_bytes: row.get(9)?,
+            local_path: row.get(10)?,
+        })
+    }
+}
+
+// ==================== 資產記錄 ====================
+
+/// 資產檔案被移動或複製後同步 assets 表；回傳受影響筆數（沒有對應記錄時為 0）
+pub fn relocate_asset_rows(
+    old_path: &str,
+    new_path: &str,
+    file_type: &str,
+    module_name: &str,
+    page_slug: Option<&str>,
+    subpage_slug: Option<&str>,
+    copy: bool,
+) -> Result<usize> {
+    let conn = get_connection()?;
+    let module_id: Option<String> = conn
+        .query_row("SELECT id FROM design_modules WHERE name = ?", params![module_name], |row| row.get(0))
+        .ok();
+    let page_id: Option<String> = match (&module_id, page_slug) {
+        (Some(mid), Some(slug)) => conn
+            .query_row("SELECT id FROM pages WHERE module_id = ?1 AND slug = ?2", params![mid, slug], |row| row.get(0))
+            .ok(),
+        _ => None,
+    };
+    let subpage_id: Option<String> = match (&page_id, subpage_slug) {
+        (Some(pid), Some(slug)) => conn
+            .query_row("SELECT id FROM subpages WHERE page_id = ?1 AND slug = ?2", params![pid, slug], |row| row.get(0))
+            .ok(),
+        _ => None,
+    };
+
+    let affected = if copy {
+        conn.execute(
+            "INSERT INTO assets (id, module_id, page_id, subpage_id, file_path, file_type, file_size, mime_type, metadata)
+             SELECT ?1, COALESCE(?2, module_id), ?3, ?4, ?5, ?6, file_size, mime_type, metadata
+             FROM assets WHERE file_path = ?7 LIMIT 1",
+            params![uuid::Uuid::new_v4().to_string(), module_id, page_id, subpage_id, new_path, file_type, old_path],
+        )?
+    } else {
+        conn.execute(
+            "UPDATE assets
+             SET file_path = ?1, file_type = ?2, module_id = COALESCE(?3, module_id), page_id = ?4, subpage_id = ?5
+             WHERE file_path = ?6",
+            params![new_path, file_type, module_id, page_id, subpage_id, old_path],
+        )?
+    };
+    Ok(affected)
+}
+
+/// 列出所有資產記錄的 (id, file_path)，供完整性檢查比對實際檔案
+pub fn list_asset_paths() -> Result<Vec<(String, String)>> {
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare("SELECT id, file_path FROM assets ORDER BY file_path")?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect()
+}
+
+// ==================== 生成指紋 ====================
+
+/// 取得模組上次成功生成時的內容指紋
+pub fn get_generation_fingerprint(module_name: &str) -> Result<Option<String>> {
+    let conn = get_connection()?;
+    match conn.query_row(
+        "SELECT fingerprint FROM generation_fingerprints WHERE module_name = ?",
+        params![module_name],
+        |row| row.get(0),
+    ) {
+        Ok(fp) => Ok(Some(fp)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// 記錄模組成功生成後的內容指紋
+pub fn set_generation_fingerprint(module_name: &str, fingerprint: &str) -> Result<()> {
+    let conn = get_connection()?;
+    conn.execute(
+        "INSERT INTO generation_fingerprints (module_name, fingerprint, generated_at) VALUES (?1, ?2, CURRENT_TIMESTAMP)
+         ON CONFLICT(module_name) DO UPDATE SET fingerprint = excluded.fingerprint, generated_at = CURRENT_TIMESTAMP",
+        params![module_name, fingerprint],
+    )?;
+    Ok(())
+}
+
 // ==================== 數據庫管理工具 ====================
 
 /// 初始化數據庫
@@ -10,14 +10,81 @@ pub fn run() {
     .plugin(tauri_plugin_shell::init())
     .invoke_handler(tauri::generate_handler![
       commands::create_design_module,
+      commands::get_workspace_lock_status,
+      commands::force_takeover_workspace_lock,
+      commands::get_data_incidents,
       commands::get_design_modules,
       commands::get_archived_design_modules,
       commands::upload_design_asset,
+      commands::upload_design_assets_bulk,
+      commands::download_design_asset,
+      commands::list_page_assets,
+      commands::check_asset_integrity,
+      commands::upload_page_asset,
       commands::generate_slice_package,
+      commands::generate_page_slice_package,
       commands::generate_all_slice_packages,
       commands::generate_selected_slice_packages,
+      commands::cancel_generation,
+      commands::send_test_webhook,
+      commands::list_generation_history,
+      commands::get_generation_manifest,
+      commands::diff_outputs,
+      commands::export_pdf,
+      commands::export_docs_bundle,
+      commands::render_mermaid_svg,
+      commands::generate_route_scaffold,
+      commands::set_output_root,
+      commands::list_generation_profiles,
+      commands::save_generation_profile,
+      commands::delete_generation_profile,
       commands::list_assets,
+      commands::read_asset_base64,
+      commands::analyze_html_asset,
+      commands::extract_css_tokens,
+      commands::generate_design_tokens,
+      commands::resolve_generation_templates,
+      commands::set_asset_order,
+      commands::get_asset_categories,
+      commands::get_ignore_patterns,
       commands::delete_design_asset,
+      commands::delete_assets_bulk,
+      commands::export_module_assets_zip,
+      commands::list_trash,
+      commands::restore_from_trash,
+      commands::empty_trash,
+      commands::rename_design_asset,
+      commands::move_design_asset,
+      commands::copy_design_asset,
+      commands::set_asset_tags,
+      commands::list_assets_by_tag,
+      commands::list_asset_annotations,
+      commands::add_asset_annotation,
+      commands::delete_asset_annotation,
+      commands::set_page_overlays,
+      commands::set_page_states,
+      commands::analyze_sitemap,
+      commands::compress_module_screenshots,
+      commands::set_page_focus_order,
+      commands::optimize_module_svgs,
+      commands::get_module_motion,
+      commands::set_module_motion,
+      commands::dedupe_module_assets,
+      commands::list_asset_versions,
+      commands::restore_asset_version,
+      commands::set_page_loading_skeleton,
+      commands::search_assets,
+      commands::set_page_seo,
+      commands::set_page_events,
+      commands::export_tracking_plan,
+      commands::set_page_props,
+      commands::sync_package_to_repo,
+      commands::get_language_report,
+      commands::map_implementation,
+      commands::set_module_display_name,
+      commands::set_page_display_name,
+      commands::list_plugins,
+      commands::run_plugin,
       commands::archive_design_module,
       commands::delete_design_module,
       commands::unarchive_design_module,
@@ -46,6 +113,29 @@ pub fn run() {
       commands::generate_module_mermaid_html,
       commands::generate_module_crud_mermaid_html,
       commands::generate_page_mermaid_html,
+      commands::set_page_navigation,
+      commands::export_navigation,
+      commands::set_page_roles,
+      commands::get_role_coverage,
+      commands::generate_access_matrix,
+      commands::set_page_feature_flag,
+      commands::list_feature_flags,
+      commands::export_feature_flags,
+      commands::capture_screenshots_from_urls,
+      commands::run_design_qa,
+      commands::compare_screenshots,
+      commands::freeze_sitemap,
+      commands::list_pending_changes,
+      commands::approve_changes,
+      commands::merge_workspace,
+      commands::get_staleness_report,
+      commands::schedule_stale_reminders,
+      commands::list_figma_exports,
+      commands::download_figma_export,
+      commands::import_asset_folder,
+      commands::import_from_codebase,
+      commands::validate_package_assets,
+      commands::verify_package,
       // 新增的數據庫命令
       commands::init_database,
       commands::get_database_stats,
@@ -80,12 +170,17 @@ pub fn run() {
       
       Ok(())
     })
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
+    .build(tauri::generate_context!())
+    .expect("error while running tauri application")
+    .run(|_app, event| {
+      if let tauri::RunEvent::Exit = event {
+        commands::release_workspace_lock();
+      }
+    });
 }
 
 // ErSlice 核心功能設置
-fn setup_erslice(_app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+fn setup_erslice(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
   log::info!("ErSlice 前端切版說明包生成器啟動中...");
   
   // 設置應用程式資訊 (在 Tauri 2.0 中需要通過其他方式設置)
@@ -99,6 +194,14 @@ fn setup_erslice(_app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
   
   // 初始化設計資產目錄
   init_design_assets_directory()?;
+
+  // 工作區鎖定：避免兩個實例（可能透過同步硬碟在不同電腦上）同時寫入
+  start_workspace_lock(app.handle().clone());
+
+  // 監看 design-assets 的外部變更（例如從 Finder 拖入檔案）
+  if let Err(e) = start_design_assets_watcher(app.handle().clone()) {
+    log::warn!("啟動檔案監看失敗: {}", e);
+  }
   
   log::info!("ErSlice 初始化完成");
   Ok(())
@@ -117,3 +220,98 @@ fn init_design_assets_directory() -> Result<(), Box<dyn std::error::Error>> {
   
   Ok(())
 }
+
+// 取得工作區鎖定並定期更新心跳；被他人持有或接管時對前端發送 workspace-locked / workspace-lock-lost 事件
+fn start_workspace_lock(app: tauri::AppHandle) {
+  use tauri::Emitter;
+
+  let status = commands::acquire_workspace_lock();
+  if !status.acquired {
+    if let Some(holder) = status.holder.as_ref() {
+      log::warn!("工作區已被另一個 ErSlice 實例開啟: {} (pid {})", holder.hostname, holder.pid);
+    }
+    let _ = app.emit("workspace-locked", status);
+  }
+  std::thread::spawn(move || {
+    let mut held = commands::refresh_workspace_lock();
+    loop {
+      std::thread::sleep(std::time::Duration::from_secs(commands::WORKSPACE_LOCK_HEARTBEAT_SECS));
+      let now_held = commands::refresh_workspace_lock();
+      if held && !now_held {
+        log::warn!("工作區鎖定已被其他實例接管");
+        let _ = app.emit("workspace-lock-lost", ());
+      }
+      held = now_held;
+    }
+  });
+}
+
+#[derive(Clone, serde::Serialize)]
+struct ModuleChangedEvent {
+  module: String,
+  paths: Vec<String>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct AssetAddedEvent {
+  module: String,
+  asset_type: String,
+  path: String,
+}
+
+// 監看 design-assets：變更時清除 sitemap 快取，並對前端發送 module-changed / asset-added 事件
+fn start_design_assets_watcher(app: tauri::AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+  use notify::{EventKind, RecursiveMode, Watcher};
+  use std::collections::{BTreeMap, BTreeSet};
+  use std::sync::mpsc;
+  use std::time::Duration;
+  use tauri::Emitter;
+
+  let root = std::fs::canonicalize("design-assets")?;
+  let (tx, rx) = mpsc::channel::<notify::Result<notify::Event>>();
+  let mut watcher = notify::recommended_watcher(tx)?;
+  watcher.watch(&root, RecursiveMode::Recursive)?;
+
+  std::thread::spawn(move || {
+    // watcher 需與執行緒同生命週期
+    let _watcher = watcher;
+    // 短時間內的多個事件合併後再發送（複製大量檔案時避免事件風暴）
+    let debounce = Duration::from_millis(300);
+    let mut changed: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut added: Vec<AssetAddedEvent> = Vec::new();
+    loop {
+      match rx.recv_timeout(debounce) {
+        Ok(Ok(event)) => {
+          let is_create = matches!(event.kind, EventKind::Create(_));
+          for path in event.paths.iter() {
+            let Ok(rel) = path.strip_prefix(&root) else { continue };
+            let parts: Vec<String> = rel.components().map(|c| c.as_os_str().to_string_lossy().to_string()).collect();
+            let Some(module) = parts.first().cloned() else { continue };
+            if parts.iter().any(|p| commands::is_ignored_name(p)) { continue; }
+            changed.entry(module.clone()).or_default().insert(rel.to_string_lossy().replace('\\', "/"));
+            if is_create && path.is_file() && parts.len() >= 2 {
+              // screenshots/<breakpoint>/ 內的檔案仍視為截圖
+              let categories = commands::asset_categories();
+              let asset_type = parts[..parts.len() - 1].iter().rev().take(2).find(|p| categories.contains(p));
+              if let Some(asset_type) = asset_type {
+                added.push(AssetAddedEvent { module, asset_type: asset_type.clone(), path: path.to_string_lossy().to_string() });
+              }
+            }
+          }
+        }
+        Ok(Err(e)) => log::warn!("檔案監看錯誤: {}", e),
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+          for (module, paths) in std::mem::take(&mut changed) {
+            commands::invalidate_sitemap_cache(Some(&module));
+            let _ = app.emit("module-changed", ModuleChangedEvent { module, paths: paths.into_iter().collect() });
+          }
+          for ev in added.drain(..) {
+            let _ = app.emit("asset-added", ev);
+          }
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => break,
+      }
+    }
+  });
+  Ok(())
+}
@@ -3,7 +3,7 @@ import { FolderIcon, PlusIcon, ArrowPathIcon, XMarkIcon, ArrowUpTrayIcon, PhotoI
 import { useDesignModulesStore, selectFilteredSorted, selectPaged } from '../stores/designModules'
 import PageLayout from '../components/PageLayout'
 import SearchAndFilters from '../components/SearchAndFilters'
-import { createDesignModule, archiveDesignModule, deleteDesignModule, unarchiveDesignModule, generateAllSlicePackages, generateSelectedSlicePackages, OverwriteStrategy, generateUnifiedSlicePackage, generateProjectMermaid, generateProjectMermaidHtml, generateModuleMermaidHtml, generateModuleCrudMermaidHtml, generateUserWorkflowMermaidHtml, exportSitemap, importSitemap } from '../utils/tauriCommands'
+import { createDesignModule, archiveDesignModule, deleteDesignModule, unarchiveDesignModule, generateAllSlicePackages, generateSelectedSlicePackages, OverwriteStrategy, generateUnifiedSlicePackage, generateProjectMermaid, generateProjectMermaidHtml, generateModuleMermaidHtml, generateModuleCrudMermaidHtml, generateUserWorkflowMermaidHtml, exportSitemap, importSitemap, moduleDisplayName } from '../utils/tauriCommands'
 import SitemapAnalyticsModal from '../components/SitemapAnalyticsModal'
 import FigmaExportOptions from '../components/FigmaExportOptions'
 import { loadSettings } from '@/utils/settings'
@@ -231,7 +231,7 @@ const DesignAssets: React.FC = () => {
       const selected = await open({
         multiple: true,
         filters,
-        title: `選擇要匯入到 ${module.name} 的資產文件`
+        title: `選擇要匯入到 ${moduleDisplayName(module)} 的資產文件`
       })
 
       if (!selected || (Array.isArray(selected) && selected.length === 0)) return
@@ -272,7 +272,7 @@ const DesignAssets: React.FC = () => {
       }
 
       if (successCount > 0) {
-        showSuccess(`成功匯入 ${successCount} 個文件到 ${module.name}`)
+        showSuccess(`成功匯入 ${successCount} 個文件到 ${moduleDisplayName(module)}`)
         await store.refresh()
         setOpenQuickImport(false)
         setSelectedModule('')
@@ -677,7 +677,12 @@ const DesignAssets: React.FC = () => {
                       overwriteStrategy: overwrite as OverwriteStrategy,
                       makeZip: unifiedZip,
                     })
-                    showSuccess('導出完成', `模組骨架：${rs.modulesCount}，輸出：${rs.outputDir}${rs.zipPath ? `；ZIP：${rs.zipPath}` : ''}`)
+                    if (rs.cancelled) {
+                      showInfo('導出已取消', `已完成 ${rs.modulesCount} 個模組，未完成的輸出已移除`)
+                      setOpenUnified(false)
+                      return
+                    }
+                    showSuccess('導出完成', `模組骨架：${rs.modulesCount}，輸出：${rs.outputDir}${rs.zipPath ? `；ZIP：${rs.zipPath}` : ''}${rs.failed.length ? `；失敗：${rs.failed.join('、')}` : ''}`)
                     setOpenUnified(false)
                   } catch (e) {
                     const m = e instanceof Error ? e.message : String(e)
@@ -725,13 +730,13 @@ const DesignAssets: React.FC = () => {
                   className="mt-1"
                   checked={!!selected[module.id]}
                   onChange={() => toggleSelected(module.id)}
-                  aria-label={`選取 ${module.name}`}
+                  aria-label={`選取 ${moduleDisplayName(module)}`}
                 />
                 <div className="p-2 bg-blue-100 dark:bg-blue-900 rounded-lg">
                   <FolderIcon className="h-6 w-6 text-blue-600 dark:text-blue-400" />
                 </div>
                 <div>
-                  <h3 className="text-lg font-semibold text-gray-900 dark:text-white">{module.name}</h3>
+                  <h3 className="text-lg font-semibold text-gray-900 dark:text-white">{moduleDisplayName(module)}</h3>
                   <span
                     className={`inline-flex items-center px-2 py-1 rounded-full text-xs font-medium ${
                       module.status === 'active'
@@ -831,11 +836,11 @@ const DesignAssets: React.FC = () => {
                           store.setViewArchived(false)
                           await store.refresh()
                           await refreshProjectSitemap()
-                          showSuccess('還原完成', `已還原：${module.name}`)
+                          showSuccess('還原完成', `已還原：${moduleDisplayName(module)}`)
                         } else {
                           store.updateLocalStatuses([module.id], 'active')
                           store.setViewArchived(false)
-                          showSuccess('還原（本地）', `已標記為活躍：${module.name}`)
+                          showSuccess('還原（本地）', `已標記為活躍：${moduleDisplayName(module)}`)
                         }
                       } catch (e) {
                         const m = e instanceof Error ? e.message : String(e)
@@ -850,16 +855,16 @@ const DesignAssets: React.FC = () => {
                     size="sm"
                     className="flex-1"
                     onClick={async () => {
-                      if (!confirm(`確認刪除模組「${module.name}」？此動作不可回復`)) return
+                      if (!confirm(`確認刪除模組「${moduleDisplayName(module)}」？此動作不可回復`)) return
                       try {
                         if (store.tauriAvailable) {
                           await deleteDesignModule(module.name)
                           await store.refresh()
                           await refreshProjectSitemap()
-                          showSuccess('刪除完成', module.name)
+                          showSuccess('刪除完成', moduleDisplayName(module))
                         } else {
                           store.removeLocal([module.id])
-                          showSuccess('刪除（本地）', module.name)
+                          showSuccess('刪除（本地）', moduleDisplayName(module))
                         }
                       } catch (e) {
                         const m = e instanceof Error ? e.message : String(e)
@@ -972,7 +977,7 @@ const DesignAssets: React.FC = () => {
                     showError('名稱格式不合法', '僅允許中英數、空格、-、_、()')
                     return
                   }
-                  const exists = store.modules.some(m => m.name.toLowerCase() === trimmed.toLowerCase())
+                  const exists = store.modules.some(m => [m.name, moduleDisplayName(m)].some(n => n.toLowerCase() === trimmed.toLowerCase()))
                   if (exists) {
                     showError('名稱重複', '已存在相同名稱的模組，請更換名稱')
                     return
@@ -990,14 +995,14 @@ const DesignAssets: React.FC = () => {
                       const defaultPages = ['首頁', '列表頁', '詳情頁', '編輯頁']
                       for (const pageName of defaultPages) {
                         try {
-                          await createModulePage(trimmed, pageName.toLowerCase().replace(/頁$/, ''))
+                          await createModulePage(created.name, pageName.toLowerCase().replace(/頁$/, ''))
                         } catch (e) {
                           console.warn(`創建預設頁面 ${pageName} 失敗:`, e)
                         }
                       }
-                      showSuccess('創建成功', `已建立模組：${created.name}，包含 ${defaultPages.length} 個預設頁面`)
+                      showSuccess('創建成功', `已建立模組：${moduleDisplayName(created)}，包含 ${defaultPages.length} 個預設頁面`)
                     } catch (e) {
-                      showSuccess('創建成功', `已建立模組：${created.name}（頁面創建失敗，請手動添加）`)
+                      showSuccess('創建成功', `已建立模組：${moduleDisplayName(created)}（頁面創建失敗，請手動添加）`)
                     }
                     
                     await refreshProjectSitemap()
@@ -1069,7 +1074,7 @@ const DesignAssets: React.FC = () => {
                       }`}
                     >
                       <div className="flex items-center justify-between mb-2">
-                        <h5 className="font-medium text-gray-900 dark:text-white">{module.name}</h5>
+                        <h5 className="font-medium text-gray-900 dark:text-white">{moduleDisplayName(module)}</h5>
                         <input
                           type="radio"
                           name="selectedModule"
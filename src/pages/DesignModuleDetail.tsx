@@ -2,7 +2,7 @@ import React, { useState } from 'react'
 import { useParams, Link, useNavigate } from 'react-router-dom'
 import { ArrowLeftIcon, DocumentArrowDownIcon, ArrowPathIcon, CloudArrowUpIcon, EyeIcon, TrashIcon, ChartBarIcon, MapIcon, DocumentIcon, FolderIcon, PencilIcon, CodeBracketIcon, EllipsisVerticalIcon, PlusIcon, XMarkIcon, CheckIcon, MagnifyingGlassIcon, FunnelIcon, ChevronDownIcon } from '@heroicons/react/24/outline'
 import { useDesignModulesStore } from '../stores/designModules'
-import { generateSlicePackage, uploadDesignAsset, listAssets, deleteDesignAsset, getModuleTree, createModulePage, deleteModulePage, renameModulePage, createSubpage, deleteSubpage, renameSubpage, setPageOrder, setSubpageOrder, applyCrudSubpages, updatePageMeta, updateSubpageMeta, generateModuleMermaidHtml, generateModuleCrudMermaidHtml, generatePageMermaidHtml, generateUserWorkflowMermaidHtml, type PageNode } from '../utils/tauriCommands'
+import { generateSlicePackage, uploadDesignAsset, listAssets, deleteDesignAsset, getModuleTree, createModulePage, deleteModulePage, renameModulePage, createSubpage, deleteSubpage, renameSubpage, setPageOrder, setSubpageOrder, applyCrudSubpages, updatePageMeta, updateSubpageMeta, generateModuleMermaidHtml, generateModuleCrudMermaidHtml, generatePageMermaidHtml, generateUserWorkflowMermaidHtml, moduleDisplayName, type PageNode } from '../utils/tauriCommands'
 import MetaEditorModal from '../components/MetaEditorModal'
 import PageAssetManager from '../components/PageAssetManager'
 import { useToast } from '../components/ui/Toast'
@@ -14,6 +14,7 @@ const DesignModuleDetail: React.FC = () => {
   const moduleName = decodeURIComponent(routeName || '')
   const navigate = useNavigate()
   const store = useDesignModulesStore()
+  const displayName = moduleDisplayName(store.modules.find(m => m.name === moduleName) ?? { name: moduleName })
   const { showSuccess, showError } = useToast()
   const [genOptions, setGenOptions] = useState({ html: true, css: true, responsive: true })
   const [generating, setGenerating] = useState(false)
@@ -770,7 +771,7 @@ const DesignModuleDetail: React.FC = () => {
       showError('Tauri 不可用')
       return
     }
-    if (!confirm(`確認刪除模組「${moduleName}」？此動作不可回復`)) return
+    if (!confirm(`確認刪除模組「${displayName}」？此動作不可回復`)) return
     try {
       const { deleteDesignModule } = await import('../utils/tauriCommands')
       const msg = await deleteDesignModule(moduleName)
@@ -795,7 +796,7 @@ const DesignModuleDetail: React.FC = () => {
             返回
           </Button>
           <div>
-            <h1 className="heading-1 text-gray-900 dark:text-white">{moduleName}</h1>
+            <h1 className="heading-1 text-gray-900 dark:text-white">{displayName}</h1>
             <p className="subtitle">模組詳情與資產管理</p>
           </div>
         </div>
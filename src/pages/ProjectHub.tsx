@@ -27,7 +27,7 @@ import {
   ClockIcon
 } from '@heroicons/react/24/outline'
 import { Link } from 'react-router-dom'
-import { listProjects, createProject, deleteProject, switchProject, isTauriEnvironment } from '@/utils/tauriCommands'
+import { listProjects, createProject, deleteProject, switchProject, isTauriEnvironment, moduleDisplayName } from '@/utils/tauriCommands'
 import { Button } from '@/components/ui/Button'
 
 // 專案介面
@@ -1239,7 +1239,7 @@ const ProjectHub: React.FC = () => {
                           }`}
                         >
                           <div className="flex items-center justify-between mb-2">
-                            <h5 className="font-medium text-gray-900 dark:text-white">{module.name}</h5>
+                            <h5 className="font-medium text-gray-900 dark:text-white">{moduleDisplayName(module)}</h5>
                             <input
                               type="checkbox"
                               checked={isSelected}
@@ -4,6 +4,7 @@ import {
   getDesignModules,
   getArchivedDesignModules,
   checkTauriAvailable,
+  moduleDisplayName,
 } from '../utils/tauriCommands'
 
 // Re-export DesignModule type for external use
@@ -181,7 +182,7 @@ export function selectFilteredSorted(state: DesignModulesState): DesignModule[]
   let list = modules.filter((m) => {
     const passStatus = status === 'all' ? true : m.status === status
     const passQuery = q
-      ? m.name.toLowerCase().includes(q) || m.description.toLowerCase().includes(q)
+      ? m.name.toLowerCase().includes(q) || moduleDisplayName(m).toLowerCase().includes(q) || m.description.toLowerCase().includes(q)
       : true
     const passProject = projectFilter === 'all' 
       ? true 
@@ -192,7 +193,7 @@ export function selectFilteredSorted(state: DesignModulesState): DesignModule[]
   list.sort((a, b) => {
     let cmp = 0
     if (sortBy === 'name') {
-      cmp = a.name.localeCompare(b.name)
+      cmp = moduleDisplayName(a).localeCompare(moduleDisplayName(b))
     } else if (sortBy === 'assets') {
       cmp = (a.asset_count ?? 0) - (b.asset_count ?? 0)
     } else if (sortBy === 'updated') {
@@ -30,7 +30,8 @@ export const isTauriEnvironment = () => {
 // 設計模組介面
 export interface DesignModule {
   id: string
-  name: string
+  name: string // 資料夾代稱（ASCII），用於路徑與指令參數
+  display_name?: string // 建立時輸入的名稱，顯示用
   description: string
   asset_count: number
   last_updated: string
@@ -41,6 +42,9 @@ export interface DesignModule {
   created_from?: 'manual' | 'figma-import' | 'template' // 模組來源
 }
 
+// 模組的顯示名稱；舊模組或本地模組沒有 display_name 時退回資料夾代稱
+export const moduleDisplayName = (module: Pick<DesignModule, 'name' | 'display_name'>) => module.display_name || module.name
+
 // 資產清單介面
 export interface AssetList {
   screenshots: string[]
@@ -165,17 +169,35 @@ export async function uploadDesignAsset(
 export async function generateSlicePackage(
   moduleName: string,
   options: {
-    includeHtml: boolean
-    includeCss: boolean
-    includeResponsive: boolean
+    // 未指定的選項依 profile，再依預設值（HTML/CSS 開啟、RWD 關閉）
+    includeHtml?: boolean
+    includeCss?: boolean
+    includeResponsive?: boolean
+    archiveFormat?: ArchiveFormat
+    // 指定框架時於 <framework>/ 輸出元件骨架與可直接 npm run dev 的 Vite 專案
+    framework?: ScaffoldFramework
+    cssMode?: 'css' | 'tailwind'
+    stylesheetDialect?: StylesheetDialect
+    // 在框架骨架旁輸出 .stories 與 .storybook 設定（需指定 framework）
+    storybook?: boolean
+    outputFormatting?: OutputFormatting
+    // 套用已儲存的生成選項組合（只補上此處未指定的選項）
+    profile?: string
   }
 ): Promise<string> {
   try {
     const result = await typedInvoke<string>('generate_slice_package', {
       moduleName,
-      includeHtml: options.includeHtml,
-      includeCss: options.includeCss,
-      includeResponsive: options.includeResponsive
+      includeHtml: options.includeHtml ?? null,
+      includeCss: options.includeCss ?? null,
+      includeResponsive: options.includeResponsive ?? null,
+      archiveFormat: options.archiveFormat ?? null,
+      framework: options.framework ?? null,
+      cssMode: options.cssMode ?? null,
+      stylesheetDialect: options.stylesheetDialect ?? null,
+      storybook: options.storybook ?? false,
+      outputFormatting: options.outputFormatting ?? null,
+      profile: options.profile ?? null
     })
     return result
   } catch (error) {
@@ -189,22 +211,58 @@ export interface BulkGenerationResult {
   total: number
   success: string[]
   failed: string[]
+  files_copied?: number
+  files_skipped?: number
+  skipped_modules?: string[]
+  job_id?: string
+  cancelled?: boolean
+  // validate 時的生成後檢查警告
+  warnings?: string[]
+  // 指定 archiveFormat 時各模組的封存檔
+  archives?: string[]
+}
+
+// generation-progress 事件內容
+export interface GenerationProgress {
+  job_id: string
+  module: string | null
+  completed: number
+  total: number
+  percent: number
 }
 
-export type OverwriteStrategy = 'overwrite' | 'skip' | 'rename'
+// minify：壓縮 HTML/CSS 便於直接上線；pretty：統一縮排便於手動編輯；未指定時使用專案設定
+export type OutputFormatting = 'default' | 'minify' | 'pretty'
+
+// merge：Markdown 只重新生成 <!-- erslice:managed-start/end --> 之間的內容，保留其外與 <!-- user --> 段落
+export type OverwriteStrategy = 'overwrite' | 'skip' | 'rename' | 'merge'
 
 export async function generateAllSlicePackages(options: {
-  includeHtml: boolean
-  includeCss: boolean
-  includeResponsive: boolean
+  // 未指定的選項依 profile，再依預設值
+  includeHtml?: boolean
+  includeCss?: boolean
+  includeResponsive?: boolean
   overwriteStrategy?: OverwriteStrategy
+  onlyChanged?: boolean
+  jobId?: string
+  validate?: boolean
+  // 每個模組各自封存為 output/<module>.zip 或 .tar.gz
+  archiveFormat?: ArchiveFormat
+  outputFormatting?: OutputFormatting
+  profile?: string
 }): Promise<BulkGenerationResult> {
   try {
     const result = await typedInvoke<BulkGenerationResult>('generate_all_slice_packages', {
-      includeHtml: options.includeHtml,
-      includeCss: options.includeCss,
-      includeResponsive: options.includeResponsive,
-      overwriteStrategy: options.overwriteStrategy ?? 'overwrite'
+      includeHtml: options.includeHtml ?? null,
+      includeCss: options.includeCss ?? null,
+      includeResponsive: options.includeResponsive ?? null,
+      overwriteStrategy: options.overwriteStrategy ?? null,
+      onlyChanged: options.onlyChanged ?? false,
+      jobId: options.jobId ?? null,
+      validate: options.validate ?? null,
+      archiveFormat: options.archiveFormat ?? null,
+      outputFormatting: options.outputFormatting ?? null,
+      profile: options.profile ?? null
     })
     return result
   } catch (error) {
@@ -215,18 +273,30 @@ export async function generateAllSlicePackages(options: {
 
 export async function generateSelectedSlicePackages(params: {
   modules: string[]
-  includeHtml: boolean
-  includeCss: boolean
-  includeResponsive: boolean
+  // 未指定的選項依 profile，再依預設值
+  includeHtml?: boolean
+  includeCss?: boolean
+  includeResponsive?: boolean
   overwriteStrategy?: OverwriteStrategy
+  jobId?: string
+  validate?: boolean
+  // 每個模組各自封存為 output/<module>.zip 或 .tar.gz
+  archiveFormat?: ArchiveFormat
+  outputFormatting?: OutputFormatting
+  profile?: string
 }): Promise<BulkGenerationResult> {
   try {
     const result = await typedInvoke<BulkGenerationResult>('generate_selected_slice_packages', {
       modules: params.modules,
-      includeHtml: params.includeHtml,
-      includeCss: params.includeCss,
-      includeResponsive: params.includeResponsive,
-      overwriteStrategy: params.overwriteStrategy ?? 'overwrite'
+      includeHtml: params.includeHtml ?? null,
+      includeCss: params.includeCss ?? null,
+      includeResponsive: params.includeResponsive ?? null,
+      overwriteStrategy: params.overwriteStrategy ?? null,
+      jobId: params.jobId ?? null,
+      validate: params.validate ?? null,
+      archiveFormat: params.archiveFormat ?? null,
+      outputFormatting: params.outputFormatting ?? null,
+      profile: params.profile ?? null
     })
     return result
   } catch (error) {
@@ -235,6 +305,251 @@ export async function generateSelectedSlicePackages(params: {
   }
 }
 
+// 取消進行中的批量/整包生成（jobId 可由呼叫端指定或取自 generation-progress 事件）
+export async function cancelGeneration(jobId: string): Promise<boolean> {
+  try {
+    return await typedInvoke<boolean>('cancel_generation', { jobId })
+  } catch (error) {
+    const ersliceError = handleTauriError(error)
+    throw new Error(getUserFriendlyMessage(ersliceError))
+  }
+}
+
+// 生成紀錄（output/.history）
+export interface GenerationManifestFile {
+  path: string
+  size: number
+  sha256: string
+}
+
+export interface GenerationManifestModule {
+  name: string
+  page?: string | null
+  status: 'success' | 'failed' | 'skipped'
+  error?: string | null
+  output_dir?: string | null
+  duration_ms: number
+  files: GenerationManifestFile[]
+}
+
+export interface GenerationManifest {
+  id: string
+  kind: 'module' | 'page' | 'bulk' | 'selected' | 'unified'
+  started_at: string
+  finished_at: string
+  duration_ms: number
+  project?: string | null
+  host: string
+  options: Record<string, unknown>
+  output_dir?: string | null
+  archive_path?: string | null
+  files: GenerationManifestFile[]
+  modules: GenerationManifestModule[]
+}
+
+export interface GenerationHistoryEntry {
+  id: string
+  kind: GenerationManifest['kind']
+  started_at: string
+  duration_ms: number
+  project?: string | null
+  modules: string[]
+  failed_count: number
+  output_dir?: string | null
+  archive_path?: string | null
+}
+
+export async function listGenerationHistory(limit?: number): Promise<GenerationHistoryEntry[]> {
+  try {
+    return await typedInvoke<GenerationHistoryEntry[]>('list_generation_history', { limit: limit ?? null })
+  } catch (error) {
+    const ersliceError = handleTauriError(error)
+    throw new Error(getUserFriendlyMessage(ersliceError))
+  }
+}
+
+export async function getGenerationManifest(id: string): Promise<GenerationManifest> {
+  try {
+    return await typedInvoke<GenerationManifest>('get_generation_manifest', { id })
+  } catch (error) {
+    const ersliceError = handleTauriError(error)
+    throw new Error(getUserFriendlyMessage(ersliceError))
+  }
+}
+
+export interface OutputDiff {
+  a: string
+  b: string
+  added: string[]
+  removed: string[]
+  changed: string[]
+  unchanged_count: number
+}
+
+// 比對兩個輸出資料夾或兩筆生成紀錄 ID
+export async function diffOutputs(dirA: string, dirB: string): Promise<OutputDiff> {
+  try {
+    return await typedInvoke<OutputDiff>('diff_outputs', { dirA, dirB })
+  } catch (error) {
+    const ersliceError = handleTauriError(error)
+    throw new Error(getUserFriendlyMessage(ersliceError))
+  }
+}
+
+export type RouteScaffoldFramework = 'react' | 'vue' | 'next'
+
+export interface RouteScaffoldResult {
+  framework: RouteScaffoldFramework
+  output_dir: string
+  files: string[]
+  routes: number
+}
+
+// 依頁面樹產生 React Router / Vue Router / Next.js app 目錄的路由骨架（output/routes/<framework>）
+export async function generateRouteScaffold(
+  framework: RouteScaffoldFramework,
+  overwriteStrategy?: OverwriteStrategy
+): Promise<RouteScaffoldResult> {
+  try {
+    return await typedInvoke<RouteScaffoldResult>('generate_route_scaffold', {
+      framework,
+      overwriteStrategy: overwriteStrategy ?? null
+    })
+  } catch (error) {
+    const ersliceError = handleTauriError(error)
+    throw new Error(getUserFriendlyMessage(ersliceError))
+  }
+}
+
+// 設定目前專案的輸出根目錄；傳入 null 恢復為 ./output，回傳實際使用的路徑
+export async function setOutputRoot(path: string | null): Promise<string> {
+  try {
+    return await typedInvoke<string>('set_output_root', { path })
+  } catch (error) {
+    const ersliceError = handleTauriError(error)
+    throw new Error(getUserFriendlyMessage(ersliceError))
+  }
+}
+
+// 以資料夾選擇器挑選輸出根目錄；取消時回傳 null
+export async function pickOutputRoot(): Promise<string | null> {
+  const { open } = await import('@tauri-apps/plugin-dialog')
+  const selected = await open({ directory: true, multiple: false, title: '選擇輸出資料夾' })
+  if (typeof selected !== 'string') return null
+  return setOutputRoot(selected)
+}
+
+export interface PackageVerification {
+  ok: boolean
+  package_dir: string
+  verified: number
+  mismatched: string[]
+  missing: string[]
+  unexpected: string[]
+}
+
+// 依 checksums.txt 驗證輸出包資料夾或其 zip / tar.gz 封存檔
+export async function verifyPackage(path: string): Promise<PackageVerification> {
+  try {
+    return await typedInvoke<PackageVerification>('verify_package', { path })
+  } catch (error) {
+    const ersliceError = handleTauriError(error)
+    throw new Error(getUserFriendlyMessage(ersliceError))
+  }
+}
+
+// 已命名的生成選項組合；未設定的欄位沿用呼叫時傳入的值
+export interface GenerationProfile {
+  name: string
+  include_html?: boolean | null
+  include_css?: boolean | null
+  include_responsive?: boolean | null
+  include_comparison?: boolean | null
+  include_print_css?: boolean | null
+  include_specs?: boolean | null
+  overwrite_strategy?: OverwriteStrategy | null
+  archive_format?: ArchiveFormat | null
+  stylesheet_dialect?: StylesheetDialect | null
+  output_formatting?: OutputFormatting | null
+  validate?: boolean | null
+}
+
+export async function listGenerationProfiles(): Promise<GenerationProfile[]> {
+  try {
+    return await typedInvoke<GenerationProfile[]>('list_generation_profiles')
+  } catch (error) {
+    const ersliceError = handleTauriError(error)
+    throw new Error(getUserFriendlyMessage(ersliceError))
+  }
+}
+
+// 同名者覆寫，回傳儲存後的所有組合
+export async function saveGenerationProfile(profile: GenerationProfile): Promise<GenerationProfile[]> {
+  try {
+    return await typedInvoke<GenerationProfile[]>('save_generation_profile', { profile })
+  } catch (error) {
+    const ersliceError = handleTauriError(error)
+    throw new Error(getUserFriendlyMessage(ersliceError))
+  }
+}
+
+export async function deleteGenerationProfile(name: string): Promise<GenerationProfile[]> {
+  try {
+    return await typedInvoke<GenerationProfile[]>('delete_generation_profile', { name })
+  } catch (error) {
+    const ersliceError = handleTauriError(error)
+    throw new Error(getUserFriendlyMessage(ersliceError))
+  }
+}
+
+// 將生成的 Markdown（ai-spec.md）或 Mermaid HTML 匯出為 PDF；output 未指定時輸出至來源旁的同名 .pdf
+export async function exportPdf(target: string, output?: string): Promise<string> {
+  try {
+    return await typedInvoke<string>('export_pdf', { target, output: output ?? null })
+  } catch (error) {
+    const ersliceError = handleTauriError(error)
+    throw new Error(getUserFriendlyMessage(ersliceError))
+  }
+}
+
+export interface DocsBundleResult {
+  output_dir: string
+  archive_path: string
+  pages: number
+  attachments: number
+  warnings: string[]
+}
+
+// 將輸出包內的 Markdown 轉為可匯入 Confluence / Notion 的文件包（含 index.md 與 zip）
+export async function exportDocsBundle(packageDir: string, target: 'confluence' | 'notion'): Promise<DocsBundleResult> {
+  try {
+    return await typedInvoke<DocsBundleResult>('export_docs_bundle', { packageDir, target })
+  } catch (error) {
+    const ersliceError = handleTauriError(error)
+    throw new Error(getUserFriendlyMessage(ersliceError))
+  }
+}
+
+// 對專案設定的 webhook_url 送出測試通知
+export async function sendTestWebhook(): Promise<void> {
+  try {
+    await typedInvoke<void>('send_test_webhook')
+  } catch (error) {
+    const ersliceError = handleTauriError(error)
+    throw new Error(getUserFriendlyMessage(ersliceError))
+  }
+}
+
+// 在本機將 .mmd 渲染為 SVG / PNG（輸出至同名檔案），回傳輸出路徑
+export async function renderMermaidSvg(mmdPath: string, format?: 'svg' | 'png'): Promise<string> {
+  try {
+    return await typedInvoke<string>('render_mermaid_svg', { mmdPath, format: format ?? null })
+  } catch (error) {
+    const ersliceError = handleTauriError(error)
+    throw new Error(getUserFriendlyMessage(ersliceError))
+  }
+}
+
 // 檢查 Tauri 是否可用
 let __tauriAvailableCache: boolean | null = null
 export async function checkTauriAvailable(): Promise<boolean> {
@@ -301,9 +616,27 @@ export const batchOperationManager = new BatchOperationManager()
 export interface UnifiedPackageResult {
   outputDir: string
   zipPath?: string | null
+  archivePath?: string | null
   modulesCount: number
+  // 生成失敗的模組（其餘模組仍會輸出）
+  failed: string[]
+  // 封存檔分割時的所有分段
+  archiveParts: string[]
+  // 超過截圖大小上限而未封存的檔案
+  archiveExcluded: string[]
+  // validate 時的生成後檢查警告
+  warnings: string[]
+  jobId: string
+  // 取消時未完成的輸出已移除
+  cancelled: boolean
 }
 
+export type ArchiveFormat = 'zip' | 'tar.gz' | 'none'
+
+export type ScaffoldFramework = 'html' | 'react' | 'vue' | 'svelte'
+
+export type StylesheetDialect = 'css' | 'scss' | 'less'
+
 // Mermaid sitemap
 export interface MermaidResult {
   mmd_path: string
@@ -361,29 +694,51 @@ export async function generateUnifiedSlicePackage(params: {
   externalDesignAssetsRoot: string
   aiDocFrontendInstructions: string
   aiDocUiFriendly: string
-  includeHtml: boolean
-  includeCss: boolean
-  includeResponsive: boolean
+  // 未指定的選項依 profile，再依預設值
+  includeHtml?: boolean
+  includeCss?: boolean
+  includeResponsive?: boolean
   includePageSpecs?: boolean
   overwriteStrategy?: OverwriteStrategy
   makeZip?: boolean
+  archiveFormat?: ArchiveFormat
+  jobId?: string
+  includeComparison?: boolean
+  includePrintCss?: boolean
+  validate?: boolean
+  stylesheetDialect?: StylesheetDialect
+  profile?: string
 }): Promise<UnifiedPackageResult> {
   try {
     const res = await typedInvoke<any>('generate_unified_slice_package', {
       externalDesignAssetsRoot: params.externalDesignAssetsRoot,
       aiDocFrontendInstructions: params.aiDocFrontendInstructions,
       aiDocUiFriendly: params.aiDocUiFriendly,
-      includeHtml: params.includeHtml,
-      includeCss: params.includeCss,
-      includeResponsive: params.includeResponsive,
-      includeSpecs: params.includePageSpecs ?? false,
-      overwriteStrategy: params.overwriteStrategy ?? 'overwrite',
+      includeHtml: params.includeHtml ?? null,
+      includeCss: params.includeCss ?? null,
+      includeResponsive: params.includeResponsive ?? null,
+      includeSpecs: params.includePageSpecs ?? null,
+      overwriteStrategy: params.overwriteStrategy ?? null,
       makeZip: params.makeZip ?? true,
+      archiveFormat: params.archiveFormat ?? null,
+      jobId: params.jobId ?? null,
+      includeComparison: params.includeComparison ?? null,
+      includePrintCss: params.includePrintCss ?? null,
+      validate: params.validate ?? null,
+      stylesheetDialect: params.stylesheetDialect ?? null,
+      profile: params.profile ?? null,
     })
     return {
       outputDir: res.output_dir,
       zipPath: res.zip_path ?? null,
+      archivePath: res.archive_path ?? null,
       modulesCount: res.modules_count,
+      failed: res.failed ?? [],
+      archiveParts: res.archive_parts ?? [],
+      archiveExcluded: res.archive_excluded ?? [],
+      warnings: res.warnings ?? [],
+      jobId: res.job_id,
+      cancelled: res.cancelled ?? false,
     }
   } catch (error) {
     const ersliceError = handleTauriError(error)
@@ -391,6 +746,37 @@ export async function generateUnifiedSlicePackage(params: {
   }
 }
 
+// 單一頁面（或子頁）切版包的選項；未指定的選項依 profile，再依預設值（HTML/CSS 開啟、RWD 關閉）
+export interface PageSliceOptions {
+  subpage?: string | null
+  include_html?: boolean | null
+  include_css?: boolean | null
+  include_responsive?: boolean | null
+  overwrite_strategy?: OverwriteStrategy | null
+  archive_format?: ArchiveFormat | null
+  output_formatting?: OutputFormatting | null
+  stylesheet_dialect?: StylesheetDialect | null
+  profile?: string | null
+}
+
+export interface PageSlicePackageResult {
+  output_dir: string
+  archive_path: string | null
+  assets_copied: number
+  archive_parts: string[]
+  archive_excluded: string[]
+}
+
+// 只為單一頁面（或子頁）產生切版包，輸出至 output/pages/<module>-<page>[-<subpage>]
+export async function generatePageSlicePackage(module: string, page: string, options?: PageSliceOptions): Promise<PageSlicePackageResult> {
+  try {
+    return await typedInvoke<PageSlicePackageResult>('generate_page_slice_package', { module, page, options: options ?? null })
+  } catch (error) {
+    const ersliceError = handleTauriError(error)
+    throw new Error(getUserFriendlyMessage(ersliceError))
+  }
+}
+
 // Project APIs
 export interface TauriProjectConfig {
   name: string
@@ -401,9 +787,25 @@ export interface TauriProjectConfig {
   zip_default: boolean
   include_bone_default: boolean
   include_specs_default: boolean
-  overwrite_strategy_default?: 'overwrite' | 'skip' | 'rename' | null
+  overwrite_strategy_default?: OverwriteStrategy | null
   mermaid_theme?: string | null
   mermaid_layout_direction?: string | null
+  spec_language?: 'zh-TW' | 'en' | 'ja' | null
+  output_root?: string | null
+  archive_split_mb?: number | null
+  archive_max_screenshot_kb?: number | null
+  output_formatting?: OutputFormatting | null
+  generation_profiles?: GenerationProfile[] | null
+  // 批量/整包生成完成時 POST JSON 通知（可直接使用 Slack incoming webhook）
+  webhook_url?: string | null
+  // mermaid-cli（mmdc）路徑；未設定時透過 npx 執行
+  mermaid_cli_path?: string | null
+  // 注入生成 HTML 與 Mermaid 預覽頁的品牌頁首/頁尾
+  brand_block?: {
+    logo_path?: string | null
+    project_name?: string | null
+    footer_text?: string | null
+  } | null
 }
 
 export async function getDefaultProject(): Promise<TauriProjectConfig> {